@@ -1,6 +1,16 @@
 use crate::co;
 use crate::decl::*;
 
+/// Type alias to
+/// [`LPFNPSPCALLBACK`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/nc-prsht-lpfnpspcallbackw)
+/// callback function.
+pub type LPFNPSPCALLBACK =
+	extern "system" fn(
+		hWnd: HWND,
+		uMsg: u32,
+		ppsp: *mut PROPSHEETPAGE,
+	) -> u32;
+
 /// Type alias to
 /// [`PFNLVCOMPARE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/nf-commctrl-listview_sortitems)
 /// callback function.
@@ -21,6 +31,16 @@ pub type PFNLVGROUPCOMPARE =
 		lpRefData: isize,
 	) -> i32;
 
+/// Type alias to
+/// [`PFNPROPSHEETCALLBACK`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/nc-prsht-pfnpropsheetcallback)
+/// callback function.
+pub type PFNPROPSHEETCALLBACK =
+	extern "system" fn(
+		hWnd: HWND,
+		uMsg: u32,
+		lParam: isize,
+	) -> i32;
+
 /// Type alias to
 /// [`PFNTVCOMPARE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-tvsortcb)
 /// callback function.
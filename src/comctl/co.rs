@@ -1604,6 +1604,196 @@ const_ordinary! { PBST: u32;
 	PAUSED 0x0003
 }
 
+const_bitflag! { PD: u32;
+	/// [`PRINTDLGEX`](crate::PRINTDLGEX) `Flags` (`u32`).
+	=>
+	=>
+	ALLPAGES 0x0000_0000
+	SELECTION 0x0000_0001
+	PAGENUMS 0x0000_0002
+	NOSELECTION 0x0000_0004
+	NOPAGENUMS 0x0000_0008
+	COLLATE 0x0000_0010
+	PRINTTOFILE 0x0000_0020
+	NOWARNING 0x0000_0080
+	RETURNDC 0x0000_0100
+	RETURNIC 0x0000_0200
+	RETURNDEFAULT 0x0000_0400
+	ENABLEPRINTTEMPLATE 0x0000_4000
+	ENABLEPRINTTEMPLATEHANDLE 0x0001_0000
+	USEDEVMODECOPIESANDCOLLATE 0x0004_0000
+	DISABLEPRINTTOFILE 0x0008_0000
+	HIDEPRINTTOFILE 0x0010_0000
+	CURRENTPAGE 0x0040_0000
+	NOCURRENTPAGE 0x0080_0000
+	EXCLUSIONFLAGS 0x0100_0000
+	USELARGETEMPLATE 0x1000_0000
+}
+
+const_ordinary! { PD_RESULT: u32;
+	/// [`PRINTDLGEX`](crate::PRINTDLGEX) `dwResultAction` (`u32`).
+	=>
+	=>
+	/// The user clicked the Cancel button.
+	CANCEL 0
+	/// The user clicked the Print button.
+	PRINT 1
+	/// The user clicked the Apply button and later clicked the Cancel button.
+	APPLY 2
+}
+
+const_ordinary! { PSBTN: u32;
+	/// [`psm::PressButton`](crate::msg::psm::PressButton) `button` (`u32`).
+	=>
+	=>
+	BACK 0
+	NEXT 1
+	FINISH 2
+	OK 3
+	APPLYNOW 4
+	CANCEL 5
+	HELP 6
+}
+
+const_bitflag! { PSH: u32;
+	/// [`PROPSHEETHEADER`](crate::PROPSHEETHEADER) `dwFlags` (`u32`).
+	=>
+	=>
+	DEFAULT 0x0000_0000
+	PROPTITLE 0x0000_0001
+	USEHICON 0x0000_0002
+	USEICONID 0x0000_0004
+	PROPSHEETPAGE 0x0000_0008
+	WIZARDHASFINISH 0x0000_0010
+	WIZARD 0x0000_0020
+	USEPSTARTPAGE 0x0000_0040
+	NOAPPLYNOW 0x0000_0080
+	USECALLBACK 0x0000_0100
+	HASHELP 0x0000_0200
+	MODELESS 0x0000_0400
+	RTLREADING 0x0000_0800
+	WIZARDCONTEXTHELP 0x0000_1000
+	AEROWIZARD 0x0000_4000
+	WATERMARK 0x0000_8000
+	USEHBMWATERMARK 0x0001_0000
+	USEHPLWATERMARK 0x0002_0000
+	STRETCHWATERMARK 0x0004_0000
+	HEADER 0x0008_0000
+	USEHBMHEADER 0x0010_0000
+	USEPAGELANG 0x0020_0000
+	WIZARD_LITE 0x0040_0000
+	WIZARD97 0x0100_0000
+	NOCONTEXTHELP 0x0200_0000
+	RESIZABLE 0x0400_0000
+	HEADERBITMAP 0x0800_0000
+	NOMARGIN 0x1000_0000
+}
+
+const_wm! { PSM;
+	/// Property sheet
+	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-property-sheets-reference-messages)
+	/// (`u32`).
+	=>
+	=>
+	SETCURSEL WM::USER.raw() + 101
+	REMOVEPAGE WM::USER.raw() + 102
+	ADDPAGE WM::USER.raw() + 103
+	CHANGED WM::USER.raw() + 104
+	RESTARTWINDOWS WM::USER.raw() + 105
+	REBOOTSYSTEM WM::USER.raw() + 106
+	CANCELTOCLOSE WM::USER.raw() + 107
+	QUERYSIBLINGS WM::USER.raw() + 108
+	UNCHANGED WM::USER.raw() + 109
+	APPLY WM::USER.raw() + 110
+	SETWIZBUTTONS WM::USER.raw() + 112
+	PRESSBUTTON WM::USER.raw() + 113
+	SETCURSELID WM::USER.raw() + 114
+	GETTABCONTROL WM::USER.raw() + 116
+	ISDIALOGMESSAGE WM::USER.raw() + 117
+	GETCURRENTPAGEHWND WM::USER.raw() + 118
+	INSERTPAGE WM::USER.raw() + 119
+	SETTITLE WM::USER.raw() + 120
+	SETFINISHTEXT WM::USER.raw() + 121
+	SETHEADERTITLE WM::USER.raw() + 126
+	SETHEADERSUBTITLE WM::USER.raw() + 128
+	HWNDTOINDEX WM::USER.raw() + 129
+	INDEXTOHWND WM::USER.raw() + 130
+	PAGETOINDEX WM::USER.raw() + 131
+	INDEXTOPAGE WM::USER.raw() + 132
+	IDTOINDEX WM::USER.raw() + 133
+	INDEXTOID WM::USER.raw() + 134
+	GETRESULT WM::USER.raw() + 135
+	RECALCPAGESIZES WM::USER.raw() + 136
+	SETNEXTTEXT WM::USER.raw() + 137
+	SHOWWIZBUTTONS WM::USER.raw() + 138
+	ENABLEWIZBUTTONS WM::USER.raw() + 139
+	SETBUTTONTEXT WM::USER.raw() + 140
+}
+
+const_nm! { PSN;
+	/// Property sheet page `WM_NOTIFY`
+	/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-property-sheets-reference-notifications)
+	/// (`i32`).
+	=>
+	FIRST -200
+	=>
+	SETACTIVE Self::FIRST.0
+	KILLACTIVE Self::FIRST.0 - 1
+	APPLY Self::FIRST.0 - 2
+	RESET Self::FIRST.0 - 3
+	HELP Self::FIRST.0 - 5
+	WIZBACK Self::FIRST.0 - 6
+	WIZNEXT Self::FIRST.0 - 7
+	WIZFINISH Self::FIRST.0 - 8
+	QUERYCANCEL Self::FIRST.0 - 9
+	GETOBJECT Self::FIRST.0 - 10
+	TRANSLATEACCELERATOR Self::FIRST.0 - 12
+	QUERYINITIALFOCUS Self::FIRST.0 - 13
+}
+
+const_ordinary! { PSNRET: isize;
+	/// [`PSN::APPLY`](crate::co::PSN::APPLY) return value, set with
+	/// [`DWLP_MSGRESULT`](crate::co::GWLP::DWLP_MSGRESULT) (`isize`).
+	=>
+	=>
+	NOERROR 0
+	INVALID 1
+	INVALID_NOCHANGEPAGE 2
+	MESSAGEHANDLED 3
+}
+
+const_bitflag! { PSP: u32;
+	/// [`PROPSHEETPAGE`](crate::PROPSHEETPAGE) `dwFlags` (`u32`).
+	=>
+	=>
+	DEFAULT 0x0000
+	DLGINDIRECT 0x0001
+	USEHICON 0x0002
+	USEICONID 0x0004
+	USETITLE 0x0008
+	RTLREADING 0x0010
+	HASHELP 0x0020
+	USEREFPARENT 0x0040
+	USECALLBACK 0x0080
+	PREMATURE 0x0400
+	HIDEHEADER 0x0800
+	USEHEADERTITLE 0x1000
+	USEHEADERSUBTITLE 0x2000
+	USEFUSIONCONTEXT 0x4000
+}
+
+const_bitflag! { PSWIZB: u32;
+	/// [`psm::SetWizButtons`](crate::msg::psm::SetWizButtons) `buttons`
+	/// (`u32`).
+	=>
+	=>
+	BACK 0x0000_0001
+	NEXT 0x0000_0002
+	FINISH 0x0000_0004
+	DISABLEDFINISH 0x0000_0008
+	CANCEL 0x0000_0010
+}
+
 const_wm! { RB;
 	/// Rebar control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-rebar-control-reference-messages)
@@ -2187,6 +2377,18 @@ const_bitflag! { TDCBF: i32;
 	CLOSE 0x0020
 }
 
+const_ordinary! { TDE: u32;
+	/// [`TDM_SET_ELEMENT_TEXT`](crate::msg::tdm::SetElementText) and
+	/// [`TDM_UPDATE_ELEMENT_TEXT`](crate::msg::tdm::UpdateElementText)
+	/// elements (`u32`).
+	=>
+	=>
+	CONTENT 0
+	EXPANDED_INFORMATION 1
+	FOOTER 2
+	MAIN_INSTRUCTION 3
+}
+
 const_bitflag! { TDF: i32;
 	/// [`TASKDIALOGCONFIG`](crate::TASKDIALOGCONFIG) `dwFlags` (`i32`).
 	=>
@@ -2228,6 +2430,29 @@ const_ordinary! { TDN: u32;
     EXPANDO_BUTTON_CLICKED 10   // wParam = 0 (dialog is now collapsed), wParam != 0 (dialog is now expanded)
 }
 
+const_wm! { TDM;
+	/// Task dialog
+	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-task-dialogs-reference-messages)
+	/// (`u32`).
+	=>
+	=>
+	NAVIGATE_PAGE WM::USER.raw() + 101
+	CLICK_BUTTON WM::USER.raw() + 102
+	SET_MARQUEE_PROGRESS_BAR WM::USER.raw() + 103
+	SET_PROGRESS_BAR_STATE WM::USER.raw() + 104
+	SET_PROGRESS_BAR_RANGE WM::USER.raw() + 105
+	SET_PROGRESS_BAR_POS WM::USER.raw() + 106
+	SET_PROGRESS_BAR_MARQUEE WM::USER.raw() + 107
+	SET_ELEMENT_TEXT WM::USER.raw() + 108
+	CLICK_RADIO_BUTTON WM::USER.raw() + 110
+	ENABLE_BUTTON WM::USER.raw() + 111
+	ENABLE_RADIO_BUTTON WM::USER.raw() + 112
+	CLICK_VERIFICATION WM::USER.raw() + 113
+	UPDATE_ELEMENT_TEXT WM::USER.raw() + 114
+	SET_BUTTON_ELEVATION_REQUIRED_STATE WM::USER.raw() + 115
+	UPDATE_ICON WM::USER.raw() + 116
+}
+
 const_wm! { TRBM;
 	/// Trackbar control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-trackbar-control-reference-messages)
@@ -15,13 +15,19 @@ extern_sys! { "comctl32";
 	ImageList_Remove(HANDLE, i32) -> BOOL
 	ImageList_ReplaceIcon(HANDLE, i32, HANDLE) -> i32
 	ImageList_SetImageCount(HANDLE, u32) -> BOOL
+	ImageList_SetOverlayImage(HANDLE, i32, i32) -> BOOL
 	InitCommonControls()
 	InitCommonControlsEx(PVOID) -> BOOL
 	InitializeFlatSB(HANDLE) -> HRES
 	InitMUILanguage(u16)
+	PropertySheetW(PCVOID) -> isize
 	RemoveWindowSubclass(HANDLE, PFUNC, usize) -> BOOL
 	SetWindowSubclass(HANDLE, PFUNC, usize, usize) -> BOOL
 	TaskDialog(HANDLE, HANDLE, PCSTR, PCSTR, PCSTR, i32, PCSTR, *mut i32) -> HRES
 	TaskDialogIndirect(PCVOID, *mut i32, *mut i32, *mut BOOL) -> HRES
 	UninitializeFlatSB(HANDLE) -> HRES
 }
+
+extern_sys! { "comdlg32";
+	PrintDlgExW(PVOID) -> HRES
+}
@@ -26,6 +26,55 @@ pub fn InitMUILanguage(ui_lang: LANGID) {
 	unsafe { ffi::InitMUILanguage(ui_lang.into()) }
 }
 
+/// [`PrintDlgEx`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/nf-commdlg-printdlgexw)
+/// function.
+///
+/// Returns the `dwResultAction` field, which tells whether the user clicked
+/// Print, Apply or Cancel. Note that `hwndOwner` must be a valid window.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// let mut ranges = [w::PRINTPAGERANGE::default(); 10];
+///
+/// let mut pd = w::PRINTDLGEX::default();
+/// pd.hwndOwner = unsafe { hparent.raw_copy() };
+/// pd.Flags = co::PD::RETURNDC | co::PD::NOSELECTION | co::PD::NOCURRENTPAGE;
+/// pd.nMinPage = 1;
+/// pd.nMaxPage = 20;
+/// pd.nCopies = 1;
+/// pd.set_lpPageRanges(Some(&mut ranges));
+///
+/// if w::PrintDlgEx(&mut pd)? == co::PD_RESULT::PRINT {
+///     let _hdc = unsafe { w::guard::DeleteDCGuard::new(pd.hDC.raw_copy()) };
+///     let _devmode = unsafe { w::guard::GlobalFreeGuard::new(pd.hDevMode.raw_copy()) };
+///     let _devnames = unsafe { w::guard::GlobalFreeGuard::new(pd.hDevNames.raw_copy()) };
+///     // print...
+/// }
+/// # Ok::<_, co::HRESULT>(())
+/// ```
+pub fn PrintDlgEx(pd: &mut PRINTDLGEX) -> HrResult<co::PD_RESULT> {
+	ok_to_hrresult(unsafe { ffi::PrintDlgExW(pd as *mut _ as _) })
+		.map(|_| pd.dwResultAction)
+}
+
+/// [`PropertySheet`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/nf-prsht-propertysheetw)
+/// function.
+///
+/// For modal property sheets, returns `true` if the user saved changes, and
+/// `false` otherwise.
+pub fn PropertySheet(header: &PROPSHEETHEADER) -> SysResult<bool> {
+	match unsafe { ffi::PropertySheetW(header as *const _ as _) } {
+		-1 => Err(GetLastError()),
+		ret => Ok(ret > 0),
+	}
+}
+
 /// [`TaskDialogIndirect`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/nf-commctrl-taskdialogindirect)
 /// function.
 ///
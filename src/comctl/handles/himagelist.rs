@@ -218,6 +218,18 @@ pub trait comctl_Himagelist: Handle {
 		}
 	}
 
+	/// [`ImageList_SetOverlayImage`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/nf-commctrl-imagelist_setoverlayimage)
+	/// function.
+	///
+	/// `overlay` is the one-based index of the overlay mask, from 1 to 15.
+	fn SetOverlayImage(&self, image: u32, overlay: u32) -> SysResult<()> {
+		bool_to_sysresult(
+			unsafe {
+				ffi::ImageList_SetOverlayImage(self.ptr(), image as _, overlay as _)
+			},
+		)
+	}
+
 	/// [`ImageList_SetImageCount`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/nf-commctrl-imagelist_setimagecount)
 	/// function.
 	fn SetImageCount(&self, new_count: u32) -> SysResult<()> {
@@ -6,10 +6,12 @@ pub mod hdm;
 pub mod lvm;
 pub mod mcm;
 pub mod pbm;
+pub mod psm;
 pub mod sb;
 pub mod stm;
 pub mod tbm;
 pub mod tcm;
+pub mod tdm;
 pub mod trbm;
 pub mod tvm;
 pub mod udm;
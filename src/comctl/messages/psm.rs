@@ -0,0 +1,460 @@
+use crate::co;
+use crate::decl::*;
+use crate::msg::*;
+use crate::prelude::*;
+use crate::user::privs::*;
+
+/// [`PSM_APPLY`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-apply)
+/// message, which has no parameters.
+///
+/// Return type: `bool`.
+pub struct Apply {}
+
+unsafe impl MsgSend for Apply {
+	type RetType = bool;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::APPLY.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_CANCELTOCLOSE`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-canceltoclose)
+/// message, which has no parameters.
+///
+/// Return type: `()`.
+pub struct CancelToClose {}
+
+unsafe impl MsgSend for CancelToClose {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::CANCELTOCLOSE.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_CHANGED`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-changed)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct Changed<'a> {
+	pub hwnd_page: &'a HWND,
+}
+
+unsafe impl<'a> MsgSend for Changed<'a> {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::CHANGED.into(),
+			wparam: self.hwnd_page.ptr() as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_ENABLEWIZBUTTONS`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-enablewizbuttons)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct EnableWizButtons {
+	pub enable: co::PSWIZB,
+	pub buttons: co::PSWIZB,
+}
+
+unsafe impl MsgSend for EnableWizButtons {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::ENABLEWIZBUTTONS.into(),
+			wparam: self.enable.raw() as _,
+			lparam: self.buttons.raw() as _,
+		}
+	}
+}
+
+/// [`PSM_GETCURRENTPAGEHWND`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-getcurrentpagehwnd)
+/// message, which has no parameters.
+///
+/// Return type: `Option<HWND>`.
+pub struct GetCurrentPageHwnd {}
+
+unsafe impl MsgSend for GetCurrentPageHwnd {
+	type RetType = Option<HWND>;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		zero_as_none(v).map(|p| unsafe { HWND::from_ptr(p as _) })
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::GETCURRENTPAGEHWND.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_GETRESULT`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-getresult)
+/// message, which has no parameters.
+///
+/// Return type: `isize`.
+pub struct GetResult {}
+
+unsafe impl MsgSend for GetResult {
+	type RetType = isize;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		v
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::GETRESULT.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_HWNDTOINDEX`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-hwndtoindex)
+/// message parameters.
+///
+/// Return type: `Option<u32>`.
+pub struct HwndToIndex<'a> {
+	pub hwnd_page: &'a HWND,
+}
+
+unsafe impl<'a> MsgSend for HwndToIndex<'a> {
+	type RetType = Option<u32>;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		minus1_as_none(v).map(|i| i as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::HWNDTOINDEX.into(),
+			wparam: self.hwnd_page.ptr() as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_INDEXTOHWND`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-indextohwnd)
+/// message parameters.
+///
+/// Return type: `Option<HWND>`.
+pub struct IndexToHwnd {
+	pub index: u32,
+}
+
+unsafe impl MsgSend for IndexToHwnd {
+	type RetType = Option<HWND>;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		zero_as_none(v).map(|p| unsafe { HWND::from_ptr(p as _) })
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::INDEXTOHWND.into(),
+			wparam: self.index as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_INDEXTOID`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-indextoid)
+/// message parameters.
+///
+/// Return type: `Option<isize>`.
+///
+/// The returned value is the resource ID of the page or, if it was created from
+/// an in-memory template, the pointer to that template.
+pub struct IndexToId {
+	pub index: u32,
+}
+
+unsafe impl MsgSend for IndexToId {
+	type RetType = Option<isize>;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		zero_as_none(v).map(|id| id as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::INDEXTOID.into(),
+			wparam: self.index as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_PRESSBUTTON`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-pressbutton)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct PressButton {
+	pub button: co::PSBTN,
+}
+
+unsafe impl MsgSend for PressButton {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::PRESSBUTTON.into(),
+			wparam: self.button.raw() as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_RECALCPAGESIZES`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-recalcpagesizes)
+/// message, which has no parameters.
+///
+/// Return type: `bool`.
+pub struct RecalcPageSizes {}
+
+unsafe impl MsgSend for RecalcPageSizes {
+	type RetType = bool;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::RECALCPAGESIZES.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_SETCURSEL`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-setcursel)
+/// message parameters.
+///
+/// Return type: `bool`.
+pub struct SetCurSel {
+	pub index: u32,
+}
+
+unsafe impl MsgSend for SetCurSel {
+	type RetType = bool;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETCURSEL.into(),
+			wparam: self.index as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PSM_SETFINISHTEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-setfinishtext)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetFinishText {
+	pub text: WString,
+}
+
+unsafe impl MsgSend for SetFinishText {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETFINISHTEXT.into(),
+			wparam: 0,
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
+
+/// [`PSM_SETHEADERSUBTITLE`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-setheadersubtitle)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetHeaderSubTitle {
+	pub index: u32,
+	pub text: WString,
+}
+
+unsafe impl MsgSend for SetHeaderSubTitle {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETHEADERSUBTITLE.into(),
+			wparam: self.index as _,
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
+
+/// [`PSM_SETHEADERTITLE`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-setheadertitle)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetHeaderTitle {
+	pub index: u32,
+	pub text: WString,
+}
+
+unsafe impl MsgSend for SetHeaderTitle {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETHEADERTITLE.into(),
+			wparam: self.index as _,
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
+
+/// [`PSM_SETNEXTTEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-setnexttext)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetNextText {
+	pub text: WString,
+}
+
+unsafe impl MsgSend for SetNextText {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETNEXTTEXT.into(),
+			wparam: 0,
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
+
+/// [`PSM_SETTITLE`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-settitle)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetTitle {
+	pub properties_for: bool,
+	pub text: WString,
+}
+
+unsafe impl MsgSend for SetTitle {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETTITLE.into(),
+			wparam: if self.properties_for { co::PSH::PROPTITLE.raw() as _ } else { 0 },
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
+
+/// [`PSM_SETWIZBUTTONS`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-setwizbuttons)
+/// message parameters.
+///
+/// Return type: `()`.
+#[derive(Clone, Copy)]
+pub struct SetWizButtons {
+	pub buttons: co::PSWIZB,
+}
+
+unsafe impl MsgSend for SetWizButtons {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SETWIZBUTTONS.into(),
+			wparam: 0,
+			lparam: self.buttons.raw() as _,
+		}
+	}
+}
+
+/// [`PSM_SHOWWIZBUTTONS`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-showwizbuttons)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct ShowWizButtons {
+	pub show: co::PSWIZB,
+	pub buttons: co::PSWIZB,
+}
+
+unsafe impl MsgSend for ShowWizButtons {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::SHOWWIZBUTTONS.into(),
+			wparam: self.show.raw() as _,
+			lparam: self.buttons.raw() as _,
+		}
+	}
+}
+
+/// [`PSM_UNCHANGED`](https://learn.microsoft.com/en-us/windows/win32/controls/psm-unchanged)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct Unchanged<'a> {
+	pub hwnd_page: &'a HWND,
+}
+
+unsafe impl<'a> MsgSend for Unchanged<'a> {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PSM::UNCHANGED.into(),
+			wparam: self.hwnd_page.ptr() as _,
+			lparam: 0,
+		}
+	}
+}
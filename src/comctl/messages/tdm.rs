@@ -0,0 +1,309 @@
+use crate::co;
+use crate::decl::*;
+use crate::msg::*;
+use crate::prelude::*;
+
+/// [`TDM_CLICK_BUTTON`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-click-button)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct ClickButton {
+	pub button_id: u16,
+}
+
+unsafe impl MsgSend for ClickButton {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::CLICK_BUTTON.into(),
+			wparam: self.button_id as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TDM_CLICK_RADIO_BUTTON`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-click-radio-button)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct ClickRadioButton {
+	pub button_id: u16,
+}
+
+unsafe impl MsgSend for ClickRadioButton {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::CLICK_RADIO_BUTTON.into(),
+			wparam: self.button_id as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TDM_CLICK_VERIFICATION`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-click-verification)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct ClickVerification {
+	pub checked: bool,
+	pub set_focus: bool,
+}
+
+unsafe impl MsgSend for ClickVerification {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::CLICK_VERIFICATION.into(),
+			wparam: self.checked as _,
+			lparam: self.set_focus as _,
+		}
+	}
+}
+
+/// [`TDM_ENABLE_BUTTON`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-enable-button)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct EnableButton {
+	pub button_id: u16,
+	pub enable: bool,
+}
+
+unsafe impl MsgSend for EnableButton {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::ENABLE_BUTTON.into(),
+			wparam: self.button_id as _,
+			lparam: self.enable as _,
+		}
+	}
+}
+
+/// [`TDM_ENABLE_RADIO_BUTTON`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-enable-radio-button)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct EnableRadioButton {
+	pub button_id: u16,
+	pub enable: bool,
+}
+
+unsafe impl MsgSend for EnableRadioButton {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::ENABLE_RADIO_BUTTON.into(),
+			wparam: self.button_id as _,
+			lparam: self.enable as _,
+		}
+	}
+}
+
+/// [`TDM_SET_BUTTON_ELEVATION_REQUIRED_STATE`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-button-elevation-required-state)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetButtonElevationRequiredState {
+	pub button_id: u16,
+	pub elevation_required: bool,
+}
+
+unsafe impl MsgSend for SetButtonElevationRequiredState {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_BUTTON_ELEVATION_REQUIRED_STATE.into(),
+			wparam: self.button_id as _,
+			lparam: self.elevation_required as _,
+		}
+	}
+}
+
+/// [`TDM_SET_ELEMENT_TEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-element-text)
+/// message parameters.
+///
+/// The dialog is resized to fit the new text.
+///
+/// Return type: `()`.
+pub struct SetElementText {
+	pub element: co::TDE,
+	pub text: WString,
+}
+
+unsafe impl MsgSend for SetElementText {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_ELEMENT_TEXT.into(),
+			wparam: self.element.raw() as _,
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
+
+/// [`TDM_SET_MARQUEE_PROGRESS_BAR`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-marquee-progress-bar)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetMarqueeProgressBar {
+	pub marquee: bool,
+}
+
+unsafe impl MsgSend for SetMarqueeProgressBar {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_MARQUEE_PROGRESS_BAR.into(),
+			wparam: self.marquee as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TDM_SET_PROGRESS_BAR_MARQUEE`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-progress-bar-marquee)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetProgressBarMarquee {
+	pub turn_on: bool,
+	pub speed_ms: u32,
+}
+
+unsafe impl MsgSend for SetProgressBarMarquee {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_PROGRESS_BAR_MARQUEE.into(),
+			wparam: self.turn_on as _,
+			lparam: self.speed_ms as _,
+		}
+	}
+}
+
+/// [`TDM_SET_PROGRESS_BAR_POS`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-progress-bar-pos)
+/// message parameters.
+///
+/// Return type: `u32`.
+pub struct SetProgressBarPos {
+	pub position: u32,
+}
+
+unsafe impl MsgSend for SetProgressBarPos {
+	type RetType = u32;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_PROGRESS_BAR_POS.into(),
+			wparam: self.position as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TDM_SET_PROGRESS_BAR_RANGE`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-progress-bar-range)
+/// message parameters.
+///
+/// Return type: `(u16, u16)`.
+pub struct SetProgressBarRange {
+	pub min: u16,
+	pub max: u16,
+}
+
+unsafe impl MsgSend for SetProgressBarRange {
+	type RetType = (u16, u16);
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		(LOWORD(v as _), HIWORD(v as _))
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_PROGRESS_BAR_RANGE.into(),
+			wparam: 0,
+			lparam: MAKEDWORD(self.min, self.max) as _,
+		}
+	}
+}
+
+/// [`TDM_SET_PROGRESS_BAR_STATE`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-set-progress-bar-state)
+/// message parameters.
+///
+/// Return type: `bool`.
+pub struct SetProgressBarState {
+	pub state: co::PBST,
+}
+
+unsafe impl MsgSend for SetProgressBarState {
+	type RetType = bool;
+
+	fn convert_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::SET_PROGRESS_BAR_STATE.into(),
+			wparam: self.state.raw() as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TDM_UPDATE_ELEMENT_TEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/tdm-update-element-text)
+/// message parameters.
+///
+/// The dialog is not resized, so the new text should not be larger than the
+/// current one.
+///
+/// Return type: `()`.
+pub struct UpdateElementText {
+	pub element: co::TDE,
+	pub text: WString,
+}
+
+unsafe impl MsgSend for UpdateElementText {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TDM::UPDATE_ELEMENT_TEXT.into(),
+			wparam: self.element.raw() as _,
+			lparam: self.text.as_ptr() as _,
+		}
+	}
+}
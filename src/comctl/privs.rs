@@ -5,3 +5,4 @@ pub(crate) const I_IMAGECALLBACK: isize = -1;
 pub(crate) const I_IMAGENONE: isize = -2;
 pub(crate) const L_MAX_URL_LENGTH: usize = 2048 + 32 + 4;
 pub(crate) const MAX_LINKID_TEXT: usize = 48;
+pub(crate) const START_PAGE_GENERAL: u32 = 0xffff_ffff;
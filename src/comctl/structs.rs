@@ -1003,6 +1003,14 @@ pub struct NMTVCUSTOMDRAW {
 	pub iLevel: i32,
 }
 
+/// [`NMTVDISPINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtvdispinfow)
+/// struct.
+#[repr(C)]
+pub struct NMTVDISPINFO<'a> {
+	pub hdr: NMHDR,
+	pub item: TVITEM<'a>,
+}
+
 /// [`NMTVITEMCHANGE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtvitemchange)
 /// struct.
 #[repr(C)]
@@ -1042,6 +1050,192 @@ pub struct PBRANGE {
 	pub iHigh: i32,
 }
 
+/// [`PRINTDLGEX`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/ns-commdlg-printdlgexw)
+/// struct.
+///
+/// The `hDevMode` and `hDevNames` handles returned by
+/// [`PrintDlgEx`](crate::PrintDlgEx) must be freed with
+/// [`GlobalFree`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-globalfree),
+/// and the `hDC` must be freed with
+/// [`DeleteDC`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-deletedc).
+#[repr(C)]
+pub struct PRINTDLGEX<'a> {
+	lStructSize: u32,
+	pub hwndOwner: HWND,
+	pub hDevMode: HGLOBAL,
+	pub hDevNames: HGLOBAL,
+	pub hDC: HDC,
+	pub Flags: co::PD,
+	pub Flags2: u32,
+	pub ExclusionFlags: co::DM,
+	pub nPageRanges: u32,
+	nMaxPageRanges: u32,
+	lpPageRanges: *mut PRINTPAGERANGE,
+	pub nMinPage: u32,
+	pub nMaxPage: u32,
+	pub nCopies: u32,
+	pub hInstance: HINSTANCE,
+	lpPrintTemplateName: *mut u16, // u16 resource ID
+	lpCallback: *mut std::ffi::c_void,
+	nPropertyPages: u32,
+	lphPropertyPages: *mut std::ffi::c_void,
+	pub nStartPage: u32,
+	pub dwResultAction: co::PD_RESULT,
+
+	_lpPageRanges: PhantomData<&'a mut PRINTPAGERANGE>,
+}
+
+impl<'a> Default for PRINTDLGEX<'a> {
+	fn default() -> Self {
+		let mut obj = unsafe { std::mem::zeroed::<Self>() };
+		obj.lStructSize = std::mem::size_of::<Self>() as _;
+		obj.nStartPage = START_PAGE_GENERAL;
+		obj
+	}
+}
+
+impl<'a> PRINTDLGEX<'a> {
+	/// Returns the `lpPageRanges` field, limited to the `nPageRanges` ranges
+	/// actually filled.
+	#[must_use]
+	pub fn lpPageRanges(&self) -> Option<&[PRINTPAGERANGE]> {
+		unsafe {
+			self.lpPageRanges.as_ref().map(|p| {
+				std::slice::from_raw_parts(p, self.nPageRanges as _)
+			})
+		}
+	}
+
+	/// Returns the `lpPageRanges` field, limited to the `nPageRanges` ranges
+	/// actually filled, allowing them to be modified.
+	#[must_use]
+	pub fn lpPageRanges_mut(&mut self) -> Option<&mut [PRINTPAGERANGE]> {
+		unsafe {
+			self.lpPageRanges.as_mut().map(|p| {
+				std::slice::from_raw_parts_mut(p, self.nPageRanges as _)
+			})
+		}
+	}
+
+	/// Sets the `lpPageRanges` field, and the `nMaxPageRanges` field to the
+	/// length of the buffer. The `nPageRanges` field must be set to the number
+	/// of ranges initially filled.
+	pub fn set_lpPageRanges(&mut self, val: Option<&'a mut [PRINTPAGERANGE]>) {
+		self.nMaxPageRanges = val.as_ref().map_or(0, |buf| buf.len() as _);
+		self.lpPageRanges = val.map_or(std::ptr::null_mut(), |buf| buf.as_mut_ptr());
+	}
+
+	pub_fn_resource_id_get_set!(lpPrintTemplateName, set_lpPrintTemplateName);
+}
+
+/// [`PRINTPAGERANGE`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/ns-commdlg-printpagerange)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PRINTPAGERANGE {
+	pub nFromPage: u32,
+	pub nToPage: u32,
+}
+
+/// [`PROPSHEETHEADER`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/ns-prsht-propsheetheaderw_v2)
+/// struct.
+#[repr(C)]
+pub struct PROPSHEETHEADER<'a, 'b> {
+	dwSize: u32,
+	pub dwFlags: co::PSH,
+	pub hwndParent: HWND,
+	pub hInstance: HINSTANCE,
+	pub hIcon: HICON,
+	pszCaption: *mut u16,
+	nPages: u32,
+	nStartPage: usize, // union with pStartPage
+	ppsp: *mut PROPSHEETPAGE<'b, 'b>,
+	pub pfnCallback: Option<PFNPROPSHEETCALLBACK>,
+	hbmWatermark: HANDLE,
+	hplWatermark: HANDLE,
+	hbmHeader: HANDLE,
+
+	_pszCaption: PhantomData<&'a mut u16>,
+	_ppsp: PhantomData<&'b mut PROPSHEETPAGE<'b, 'b>>,
+}
+
+impl_default_with_size!(PROPSHEETHEADER, dwSize, 'a, 'b);
+
+impl<'a, 'b> PROPSHEETHEADER<'a, 'b> {
+	pub_fn_string_ptr_get_set!('a, pszCaption, set_pszCaption);
+
+	/// Returns the `nStartPage` field.
+	#[must_use]
+	pub const fn nStartPage(&self) -> u32 {
+		self.nStartPage as _
+	}
+
+	/// Sets the `nStartPage` field.
+	pub fn set_nStartPage(&mut self, val: u32) {
+		self.nStartPage = val as _;
+	}
+
+	pub_fn_array_buf_get_set!('b, ppsp, set_ppsp, nPages, PROPSHEETPAGE<'b, 'b>);
+}
+
+/// [`PROPSHEETPAGE`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/ns-prsht-propsheetpagew_v4)
+/// struct.
+///
+/// Before passing the page to
+/// [`PropertySheet`](crate::PropertySheet), set either the dialog resource ID
+/// with [`set_pszTemplate`](crate::PROPSHEETPAGE::set_pszTemplate) or an
+/// in-memory template with
+/// [`set_pResource`](crate::PROPSHEETPAGE::set_pResource).
+#[repr(C)]
+pub struct PROPSHEETPAGE<'a, 'b> {
+	dwSize: u32,
+	pub dwFlags: co::PSP,
+	pub hInstance: HINSTANCE,
+	pszTemplate: *mut u16, // union with pResource
+	pub hIcon: HICON,
+	pszTitle: *mut u16,
+	pub pfnDlgProc: Option<DLGPROC>,
+	pub lParam: isize,
+	pub pfnCallback: Option<LPFNPSPCALLBACK>,
+	pcRefParent: *mut u32,
+	pszHeaderTitle: *mut u16,
+	pszHeaderSubTitle: *mut u16,
+	hActCtx: HANDLE,
+	hbmHeader: HANDLE,
+
+	_pszTitle: PhantomData<&'a mut u16>,
+	_pResource: PhantomData<&'b DLGTEMPLATE>,
+}
+
+impl_default_with_size!(PROPSHEETPAGE, dwSize, 'a, 'b);
+
+impl<'a, 'b> PROPSHEETPAGE<'a, 'b> {
+	pub_fn_resource_id_get_set!(pszTemplate, set_pszTemplate);
+
+	/// Sets the `pResource` field, which shares memory with `pszTemplate`.
+	///
+	/// The `DLGTEMPLATE` must be followed, in the same DWORD-aligned memory
+	/// block, by the menu, class, title and controls of the template. Also
+	/// set the [`co::PSP::DLGINDIRECT`](crate::co::PSP::DLGINDIRECT) flag.
+	pub fn set_pResource(&mut self, val: &'b DLGTEMPLATE) {
+		self.pszTemplate = val as *const _ as _;
+	}
+
+	pub_fn_string_ptr_get_set!('a, pszTitle, set_pszTitle);
+	pub_fn_string_ptr_get_set!('a, pszHeaderTitle, set_pszHeaderTitle);
+	pub_fn_string_ptr_get_set!('a, pszHeaderSubTitle, set_pszHeaderSubTitle);
+}
+
+/// [`PSHNOTIFY`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/ns-prsht-pshnotify)
+/// struct.
+#[repr(C)]
+pub struct PSHNOTIFY {
+	pub hdr: NMHDR,
+	pub lParam: isize,
+}
+
+impl_default!(PSHNOTIFY);
+
 /// [`TASKDIALOG_BUTTON`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-taskdialog_button)
 /// struct.
 #[repr(C, packed)]
@@ -1 +1,5 @@
+mod task_dialog;
+
 pub mod task_dlg;
+
+pub use task_dialog::*;
@@ -0,0 +1,697 @@
+use std::cell::RefCell;
+
+use crate::co;
+use crate::decl::*;
+use crate::msg;
+use crate::prelude::*;
+
+/// Result of a [`TaskDialog`](crate::TaskDialog) after it's closed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TaskDialogResult {
+	/// ID of the button which closed the dialog: either a common button or the
+	/// ID of a custom button.
+	pub button: co::DLGID,
+	/// ID of the selected radio button, if any.
+	pub radio_button: Option<u16>,
+	/// State of the verification checkbox.
+	pub verification_checked: bool,
+}
+
+/// Handle to a live [`TaskDialog`](crate::TaskDialog), passed to its
+/// callbacks, which sends
+/// [task dialog messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-task-dialogs-reference-messages)
+/// to update its contents.
+///
+/// Clicks are notified synchronously: a click made from within a callback
+/// won't call that same callback again while it's still running.
+pub struct TaskDialogHandle {
+	hwnd: HWND,
+}
+
+impl TaskDialogHandle {
+	/// Returns the handle of the dialog window.
+	#[must_use]
+	pub const fn hwnd(&self) -> &HWND {
+		&self.hwnd
+	}
+
+	/// Simulates the click of a button, which may close the dialog.
+	pub fn click_button(&self, button_id: u16) {
+		self.hwnd.SendMessage(msg::tdm::ClickButton { button_id });
+	}
+
+	/// Selects a radio button.
+	pub fn click_radio_button(&self, button_id: u16) {
+		self.hwnd.SendMessage(msg::tdm::ClickRadioButton { button_id });
+	}
+
+	/// Sets the state of the verification checkbox.
+	pub fn click_verification(&self, checked: bool) {
+		self.hwnd.SendMessage(msg::tdm::ClickVerification {
+			checked,
+			set_focus: false,
+		});
+	}
+
+	/// Enables or disables a button.
+	pub fn enable_button(&self, button_id: u16, enable: bool) {
+		self.hwnd.SendMessage(msg::tdm::EnableButton { button_id, enable });
+	}
+
+	/// Enables or disables a radio button.
+	pub fn enable_radio_button(&self, button_id: u16, enable: bool) {
+		self.hwnd.SendMessage(msg::tdm::EnableRadioButton { button_id, enable });
+	}
+
+	/// Displays or hides the UAC shield icon on a button.
+	pub fn set_button_elevation_required(&self, button_id: u16, required: bool) {
+		self.hwnd.SendMessage(msg::tdm::SetButtonElevationRequiredState {
+			button_id,
+			elevation_required: required,
+		});
+	}
+
+	/// Sets the content text, resizing the dialog.
+	pub fn set_content(&self, text: &str) {
+		self.set_element_text(co::TDE::CONTENT, text);
+	}
+
+	/// Sets the expanded information text, resizing the dialog.
+	pub fn set_expanded_info(&self, text: &str) {
+		self.set_element_text(co::TDE::EXPANDED_INFORMATION, text);
+	}
+
+	/// Sets the footer text, resizing the dialog.
+	pub fn set_footer(&self, text: &str) {
+		self.set_element_text(co::TDE::FOOTER, text);
+	}
+
+	/// Sets the main instruction text, resizing the dialog.
+	pub fn set_main_instruction(&self, text: &str) {
+		self.set_element_text(co::TDE::MAIN_INSTRUCTION, text);
+	}
+
+	/// Turns the progress bar into a marquee progress bar, or back into a
+	/// normal one. The marquee animation speed is in milliseconds, and zero
+	/// means the default speed.
+	pub fn set_marquee(&self, marquee: bool, speed_ms: u32) {
+		self.hwnd.SendMessage(msg::tdm::SetMarqueeProgressBar { marquee });
+		if marquee {
+			self.hwnd.SendMessage(msg::tdm::SetProgressBarMarquee {
+				turn_on: true,
+				speed_ms,
+			});
+		}
+	}
+
+	/// Sets the position of the progress bar, returning the previous one.
+	pub fn set_progress_pos(&self, pos: u32) -> u32 {
+		self.hwnd.SendMessage(msg::tdm::SetProgressBarPos { position: pos })
+	}
+
+	/// Sets the range of the progress bar, which defaults to 0-100.
+	pub fn set_progress_range(&self, min: u16, max: u16) {
+		self.hwnd.SendMessage(msg::tdm::SetProgressBarRange { min, max });
+	}
+
+	/// Sets the state of the progress bar: normal, paused or error.
+	pub fn set_progress_state(&self, state: co::PBST) {
+		self.hwnd.SendMessage(msg::tdm::SetProgressBarState { state });
+	}
+
+	fn set_element_text(&self, element: co::TDE, text: &str) {
+		self.hwnd.SendMessage(msg::tdm::SetElementText {
+			element,
+			text: WString::from_str(text),
+		});
+	}
+}
+
+//------------------------------------------------------------------------------
+
+// Each callback is borrowed only while it runs: a TaskDialogHandle method
+// called from within a callback may synchronously fire another notification,
+// which then skips any callback that is already running.
+type Callback = RefCell<Option<Box<dyn FnMut(&TaskDialogHandle)>>>;
+type CallbackArg<A, R = ()> = RefCell<Option<Box<dyn FnMut(&TaskDialogHandle, A) -> R>>>;
+type CallbackStr = RefCell<Option<Box<dyn FnMut(&TaskDialogHandle, &str)>>>;
+
+#[derive(Default)]
+struct Callbacks {
+	created: Callback,
+	destroyed: Callback,
+	button_clicked: CallbackArg<co::DLGID, bool>,
+	radio_button_clicked: CallbackArg<u16>,
+	verification_clicked: CallbackArg<bool>,
+	expando_clicked: CallbackArg<bool>,
+	hyperlink_clicked: CallbackStr,
+	timer: CallbackArg<u32, bool>,
+}
+
+/// High-level builder of a task dialog, over
+/// [`TaskDialogIndirect`](crate::TaskDialogIndirect).
+///
+/// Unlike the simple functions of the [`task_dlg`](crate::task_dlg) module,
+/// supports custom buttons and command links, radio buttons, a verification
+/// checkbox, expandable information, a footer, hyperlinks and a progress bar.
+/// The callbacks receive a [`TaskDialogHandle`](crate::TaskDialogHandle), used
+/// to update the dialog while it's displayed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// const BTN_START: u16 = 1001;
+///
+/// let res = w::TaskDialog::new()
+///     .title("Backup")
+///     .main_instruction("Back up your files now?")
+///     .content("The backup may take a few minutes.")
+///     .main_icon(w::IconIdTdicon::Tdicon(co::TD_ICON::INFORMATION))
+///     .add_button(BTN_START, "Start backup\nCopies all files to the server")
+///     .use_command_links(true)
+///     .common_buttons(co::TDCBF::CANCEL)
+///     .progress_bar(false)
+///     .footer("See <a href=\"https://example.com\">the docs</a>.")
+///     .on_hyperlink_clicked(|_, href| println!("Clicked {}", href))
+///     .on_button_clicked(|hdlg, btn| {
+///         if btn.raw() == BTN_START {
+///             hdlg.enable_button(BTN_START, false);
+///             return false; // keep the dialog open
+///         }
+///         true
+///     })
+///     .on_timer(|hdlg, ms| {
+///         hdlg.set_progress_pos((ms / 100).min(100));
+///         false
+///     })
+///     .show()?;
+///
+/// println!("Closed with {}", res.button.raw());
+/// # Ok::<_, co::HRESULT>(())
+/// ```
+#[derive(Default)]
+pub struct TaskDialog {
+	hparent: Option<HWND>,
+	flags: co::TDF,
+	common_buttons: co::TDCBF,
+	title: Option<String>,
+	main_instruction: Option<String>,
+	content: Option<String>,
+	main_icon: Option<IconIdTdicon>,
+	buttons: Vec<(u16, String)>,
+	default_button: Option<u16>,
+	radio_buttons: Vec<(u16, String)>,
+	default_radio_button: Option<u16>,
+	verification: Option<String>,
+	expanded_info: Option<(String, Option<String>, Option<String>)>,
+	footer: Option<String>,
+	footer_icon: Option<IconId>,
+	width: u32,
+	callbacks: Callbacks,
+}
+
+impl TaskDialog {
+	/// Creates a new task dialog, which can be cancelled with ESC and is
+	/// centered on its parent.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			flags: co::TDF::ALLOW_DIALOG_CANCELLATION
+				| co::TDF::POSITION_RELATIVE_TO_WINDOW,
+			..Default::default()
+		}
+	}
+
+	/// Sets the parent window.
+	#[must_use]
+	pub fn parent(mut self, hparent: &HWND) -> Self {
+		self.hparent = Some(unsafe { hparent.raw_copy() });
+		self
+	}
+
+	/// Sets the window title. If not set, the executable name is used.
+	#[must_use]
+	pub fn title(mut self, text: &str) -> Self {
+		self.title = Some(text.to_owned());
+		self
+	}
+
+	/// Sets the main instruction, displayed in a larger font.
+	#[must_use]
+	pub fn main_instruction(mut self, text: &str) -> Self {
+		self.main_instruction = Some(text.to_owned());
+		self
+	}
+
+	/// Sets the content text.
+	#[must_use]
+	pub fn content(mut self, text: &str) -> Self {
+		self.content = Some(text.to_owned());
+		self
+	}
+
+	/// Sets the main icon.
+	#[must_use]
+	pub fn main_icon(mut self, icon: IconIdTdicon) -> Self {
+		self.main_icon = Some(icon);
+		self
+	}
+
+	/// Sets the common buttons, like OK and Cancel, displayed after the custom
+	/// ones.
+	#[must_use]
+	pub fn common_buttons(mut self, buttons: co::TDCBF) -> Self {
+		self.common_buttons = buttons;
+		self
+	}
+
+	/// Adds a custom button. If command links are used, a line break separates
+	/// the main text from the note.
+	#[must_use]
+	pub fn add_button(mut self, button_id: u16, text: &str) -> Self {
+		self.buttons.push((button_id, text.to_owned()));
+		self
+	}
+
+	/// Displays the custom buttons as command links, optionally without the
+	/// arrow glyph.
+	#[must_use]
+	pub fn use_command_links(mut self, with_icon: bool) -> Self {
+		self.flags |= if with_icon {
+			co::TDF::USE_COMMAND_LINKS
+		} else {
+			co::TDF::USE_COMMAND_LINKS_NO_ICON
+		};
+		self
+	}
+
+	/// Sets the button which has the focus, either a common or a custom one.
+	#[must_use]
+	pub fn default_button(mut self, button_id: u16) -> Self {
+		self.default_button = Some(button_id);
+		self
+	}
+
+	/// Adds a radio button.
+	#[must_use]
+	pub fn add_radio_button(mut self, button_id: u16, text: &str) -> Self {
+		self.radio_buttons.push((button_id, text.to_owned()));
+		self
+	}
+
+	/// Sets the radio button which is initially selected. If not set, the
+	/// first one is selected.
+	#[must_use]
+	pub fn default_radio_button(mut self, button_id: u16) -> Self {
+		self.default_radio_button = Some(button_id);
+		self
+	}
+
+	/// Adds a verification checkbox with its initial state.
+	#[must_use]
+	pub fn verification(mut self, text: &str, checked: bool) -> Self {
+		self.verification = Some(text.to_owned());
+		if checked {
+			self.flags |= co::TDF::VERIFICATION_FLAG_CHECKED;
+		}
+		self
+	}
+
+	/// Sets the additional information, displayed when the user expands the
+	/// dialog. The labels of the expando button are optional.
+	#[must_use]
+	pub fn expanded_info(mut self,
+		text: &str,
+		expanded_label: Option<&str>,
+		collapsed_label: Option<&str>,
+	) -> Self
+	{
+		self.expanded_info = Some((
+			text.to_owned(),
+			expanded_label.map(|s| s.to_owned()),
+			collapsed_label.map(|s| s.to_owned()),
+		));
+		self
+	}
+
+	/// Displays the expanded information initially expanded, and/or at the
+	/// bottom of the dialog instead of below the content.
+	#[must_use]
+	pub fn expanded_info_options(mut self, expanded: bool, in_footer: bool) -> Self {
+		if expanded {
+			self.flags |= co::TDF::EXPANDED_BY_DEFAULT;
+		}
+		if in_footer {
+			self.flags |= co::TDF::EXPAND_FOOTER_AREA;
+		}
+		self
+	}
+
+	/// Sets the footer text.
+	#[must_use]
+	pub fn footer(mut self, text: &str) -> Self {
+		self.footer = Some(text.to_owned());
+		self
+	}
+
+	/// Sets the footer icon.
+	#[must_use]
+	pub fn footer_icon(mut self, icon: IconId) -> Self {
+		self.footer_icon = Some(icon);
+		self
+	}
+
+	/// Displays a progress bar, either a normal or a marquee one.
+	#[must_use]
+	pub fn progress_bar(mut self, marquee: bool) -> Self {
+		self.flags |= if marquee {
+			co::TDF::SHOW_MARQUEE_PROGRESS_BAR
+		} else {
+			co::TDF::SHOW_PROGRESS_BAR
+		};
+		self
+	}
+
+	/// Sets the width of the client area, in dialog units. If zero, the width
+	/// is automatically calculated.
+	#[must_use]
+	pub fn width(mut self, dialog_units: u32) -> Self {
+		self.width = dialog_units;
+		self
+	}
+
+	/// Adds arbitrary flags to the dialog.
+	#[must_use]
+	pub fn flags(mut self, flags: co::TDF) -> Self {
+		self.flags |= flags;
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_CREATED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-created).
+	#[must_use]
+	pub fn on_created<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle) + 'static,
+	{
+		*self.callbacks.created.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_DESTROYED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-destroyed).
+	#[must_use]
+	pub fn on_destroyed<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle) + 'static,
+	{
+		*self.callbacks.destroyed.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_BUTTON_CLICKED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-button-clicked).
+	///
+	/// Return `true` to close the dialog, or `false` to keep it open.
+	#[must_use]
+	pub fn on_button_clicked<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle, co::DLGID) -> bool + 'static,
+	{
+		*self.callbacks.button_clicked.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_RADIO_BUTTON_CLICKED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-radio-button-clicked).
+	#[must_use]
+	pub fn on_radio_button_clicked<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle, u16) + 'static,
+	{
+		*self.callbacks.radio_button_clicked.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_VERIFICATION_CLICKED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-verification-clicked),
+	/// which receives the new state of the checkbox.
+	#[must_use]
+	pub fn on_verification_clicked<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle, bool) + 'static,
+	{
+		*self.callbacks.verification_clicked.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_EXPANDO_BUTTON_CLICKED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-expando-button-clicked),
+	/// which receives `true` if the dialog is now expanded.
+	#[must_use]
+	pub fn on_expando_clicked<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle, bool) + 'static,
+	{
+		*self.callbacks.expando_clicked.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_HYPERLINK_CLICKED`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-hyperlink-clicked),
+	/// which receives the `href` of the clicked `<a>` tag.
+	///
+	/// Hyperlinks are rendered in the content, expanded information and footer
+	/// only when this callback is set.
+	#[must_use]
+	pub fn on_hyperlink_clicked<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle, &str) + 'static,
+	{
+		self.flags |= co::TDF::ENABLE_HYPERLINKS;
+		*self.callbacks.hyperlink_clicked.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Sets the callback of
+	/// [`TDN_TIMER`](https://learn.microsoft.com/en-us/windows/win32/controls/tdn-timer),
+	/// called about every 200 milliseconds, which receives the milliseconds
+	/// elapsed since the dialog was created or the timer was reset.
+	///
+	/// Return `true` to reset the timer.
+	#[must_use]
+	pub fn on_timer<F>(mut self, func: F) -> Self
+		where F: FnMut(&TaskDialogHandle, u32) -> bool + 'static,
+	{
+		self.flags |= co::TDF::CALLBACK_TIMER;
+		*self.callbacks.timer.get_mut() = Some(Box::new(func));
+		self
+	}
+
+	/// Displays the modal dialog, blocking until it's closed.
+	pub fn show(&mut self) -> HrResult<TaskDialogResult> {
+		let mut tdc = TASKDIALOGCONFIG::default();
+		let mut flags = self.flags;
+		if let Some(hparent) = &self.hparent {
+			tdc.hwndParent = unsafe { hparent.raw_copy() };
+		}
+		tdc.dwCommonButtons = self.common_buttons;
+		tdc.cxWidth = self.width;
+
+		let mut title_buf = WString::from_opt_str(self.title.as_deref());
+		if self.title.is_some() {
+			tdc.set_pszWindowTitle(Some(&mut title_buf));
+		}
+		let mut instruction_buf = WString::from_opt_str(self.main_instruction.as_deref());
+		if self.main_instruction.is_some() {
+			tdc.set_pszMainInstruction(Some(&mut instruction_buf));
+		}
+		let mut content_buf = WString::from_opt_str(self.content.as_deref());
+		if self.content.is_some() {
+			tdc.set_pszContent(Some(&mut content_buf));
+		}
+
+		if let Some(icon) = &self.main_icon {
+			if let IconIdTdicon::Icon(_) = icon {
+				flags |= co::TDF::USE_HICON_MAIN;
+			}
+			tdc.set_pszMainIcon(match icon {
+				IconIdTdicon::None => IconIdTdicon::None,
+				IconIdTdicon::Icon(hicon) => IconIdTdicon::Icon(unsafe { hicon.raw_copy() }),
+				IconIdTdicon::Id(id) => IconIdTdicon::Id(*id),
+				IconIdTdicon::Tdicon(tdi) => IconIdTdicon::Tdicon(*tdi),
+			});
+		}
+
+		let mut btn_texts = self.buttons.iter()
+			.map(|(_, text)| WString::from_str(text))
+			.collect::<Vec<_>>();
+		let mut btns = self.buttons.iter()
+			.zip(btn_texts.iter_mut())
+			.map(|((id, _), text)| {
+				let mut btn = TASKDIALOG_BUTTON::default();
+				btn.set_nButtonID(*id);
+				btn.set_pszButtonText(Some(text));
+				btn
+			})
+			.collect::<Vec<_>>();
+		if !btns.is_empty() {
+			tdc.set_pButtons(Some(&mut btns));
+		}
+		if let Some(default_button) = self.default_button {
+			tdc.nDefaultButton = default_button as _;
+		}
+
+		let mut radio_texts = self.radio_buttons.iter()
+			.map(|(_, text)| WString::from_str(text))
+			.collect::<Vec<_>>();
+		let mut radios = self.radio_buttons.iter()
+			.zip(radio_texts.iter_mut())
+			.map(|((id, _), text)| {
+				let mut btn = TASKDIALOG_BUTTON::default();
+				btn.set_nButtonID(*id);
+				btn.set_pszButtonText(Some(text));
+				btn
+			})
+			.collect::<Vec<_>>();
+		if !radios.is_empty() {
+			tdc.set_pRadioButtons(Some(&mut radios));
+		}
+		if let Some(default_radio) = self.default_radio_button {
+			tdc.nDefaultRadioButton = default_radio as _;
+		}
+
+		let mut verification_buf = WString::from_opt_str(self.verification.as_deref());
+		if self.verification.is_some() {
+			tdc.set_pszVerificationText(Some(&mut verification_buf));
+		}
+
+		let (mut expanded_buf, mut expanded_lbl_buf, mut collapsed_lbl_buf) =
+			match &self.expanded_info {
+				Some((text, expanded_lbl, collapsed_lbl)) => (
+					WString::from_str(text),
+					WString::from_opt_str(expanded_lbl.as_deref()),
+					WString::from_opt_str(collapsed_lbl.as_deref()),
+				),
+				None => (WString::default(), WString::default(), WString::default()),
+			};
+		if let Some((_, expanded_lbl, collapsed_lbl)) = &self.expanded_info {
+			tdc.set_pszExpandedInformation(Some(&mut expanded_buf));
+			if expanded_lbl.is_some() {
+				tdc.set_pszExpandedControlText(Some(&mut expanded_lbl_buf));
+			}
+			if collapsed_lbl.is_some() {
+				tdc.set_pszCollapsedControlText(Some(&mut collapsed_lbl_buf));
+			}
+		}
+
+		let mut footer_buf = WString::from_opt_str(self.footer.as_deref());
+		if self.footer.is_some() {
+			tdc.set_pszFooter(Some(&mut footer_buf));
+		}
+		if let Some(icon) = &self.footer_icon {
+			if let IconId::Icon(_) = icon {
+				flags |= co::TDF::USE_HICON_FOOTER;
+			}
+			tdc.set_pszFooterIcon(match icon {
+				IconId::None => IconId::None,
+				IconId::Icon(hicon) => IconId::Icon(unsafe { hicon.raw_copy() }),
+				IconId::Id(id) => IconId::Id(*id),
+			});
+		}
+
+		tdc.dwFlags = flags;
+		tdc.pfCallback = Some(Self::callback);
+		tdc.lpCallbackData = &self.callbacks as *const _ as _;
+
+		let mut verification_checked = false;
+		let (button, radio_button) = TaskDialogIndirect(
+			&tdc, Some(&mut verification_checked))?;
+
+		Ok(TaskDialogResult {
+			button,
+			radio_button: if self.radio_buttons.is_empty() {
+				None
+			} else {
+				Some(radio_button)
+			},
+			verification_checked,
+		})
+	}
+
+	extern "system" fn callback(
+		hwnd: HWND,
+		msg: co::TDN,
+		wparam: usize,
+		lparam: isize,
+		ref_data: usize,
+	) -> co::HRESULT
+	{
+		let callbacks = unsafe { &*(ref_data as *const Callbacks) };
+		let hdlg = TaskDialogHandle { hwnd };
+
+		match msg {
+			co::TDN::CREATED => {
+				Self::run(&callbacks.created, |func| func(&hdlg));
+			},
+			co::TDN::DESTROYED => {
+				Self::run(&callbacks.destroyed, |func| func(&hdlg));
+			},
+			co::TDN::BUTTON_CLICKED => {
+				let btn = unsafe { co::DLGID::from_raw(wparam as _) };
+				let close = Self::run(&callbacks.button_clicked, |func| func(&hdlg, btn));
+				if close == Some(false) {
+					return co::HRESULT::S_FALSE; // prevents the dialog from closing
+				}
+			},
+			co::TDN::RADIO_BUTTON_CLICKED => {
+				Self::run(&callbacks.radio_button_clicked, |func| func(&hdlg, wparam as _));
+			},
+			co::TDN::VERIFICATION_CLICKED => {
+				Self::run(&callbacks.verification_clicked, |func| func(&hdlg, wparam != 0));
+			},
+			co::TDN::EXPANDO_BUTTON_CLICKED => {
+				Self::run(&callbacks.expando_clicked, |func| func(&hdlg, wparam != 0));
+			},
+			co::TDN::HYPERLINK_CLICKED => {
+				let href = unsafe { WString::from_wchars_nullt(lparam as _) };
+				Self::run(&callbacks.hyperlink_clicked, |func| func(&hdlg, &href.to_string()));
+			},
+			co::TDN::TIMER => {
+				let reset = Self::run(&callbacks.timer, |func| func(&hdlg, wparam as _));
+				if reset == Some(true) {
+					return co::HRESULT::S_FALSE; // resets the timer
+				}
+			},
+			_ => {},
+		}
+		co::HRESULT::S_OK
+	}
+
+	/// Calls the callback in the slot, unless it's not set or already running.
+	fn run<F: ?Sized, R>(
+		slot: &RefCell<Option<Box<F>>>,
+		call: impl FnOnce(&mut F) -> R,
+	) -> Option<R>
+	{
+		let mut func = slot.try_borrow_mut().ok()?;
+		func.as_deref_mut().map(call)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn nested_callback_skipped() {
+		type Slot = RefCell<Option<Box<dyn FnMut(u32) -> u32>>>;
+		let slot: std::rc::Rc<Slot> = Default::default();
+		assert_eq!(TaskDialog::run(&slot, |func| func(1)), None); // not set
+
+		let inner = slot.clone();
+		*slot.borrow_mut() = Some(Box::new(move |n| {
+			// a click sent from within the callback reaches the same slot
+			assert_eq!(TaskDialog::run(&inner, |func| func(n + 1)), None);
+			n * 10
+		}));
+		assert_eq!(TaskDialog::run(&slot, |func| func(4)), Some(40));
+		assert_eq!(TaskDialog::run(&slot, |func| func(5)), Some(50)); // released afterwards
+		slot.borrow_mut().take(); // breaks the Rc cycle
+	}
+}
@@ -1,9 +1,11 @@
 #![cfg(all(feature = "comctl", feature = "gdi"))]
 
 mod structs;
+mod utilities;
 
 pub mod messages;
 
 pub mod decl {
 	pub use super::structs::*;
+	pub use super::utilities::*;
 }
@@ -0,0 +1,456 @@
+//! Provides high-level abstractions to the
+//! [`ChooseFont`](crate::ChooseFont), [`PrintDlgEx`](crate::PrintDlgEx),
+//! [`PageSetupDlg`](crate::PageSetupDlg), [`FindText`](crate::FindText) and
+//! [`ReplaceText`](crate::ReplaceText) common dialogs.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// The printer chosen in [`print`](crate::comm_dlg::print) or
+/// [`page_setup`](crate::comm_dlg::page_setup) dialogs.
+pub struct Printer {
+	/// Device context of the printer, ready to print.
+	pub hdc: DeleteDCGuard,
+	/// Printer settings. The private driver data is not kept, so `dmDriverExtra`
+	/// is zero.
+	pub devmode: DEVMODE,
+	/// Name of the printer driver.
+	pub driver: String,
+	/// Name of the printer.
+	pub device: String,
+	/// Name of the output port.
+	pub output: String,
+}
+
+/// Result of the [`print`](crate::comm_dlg::print) dialog.
+pub struct PrintChoice {
+	/// The chosen printer.
+	pub printer: Printer,
+	/// Number of copies.
+	pub copies: u32,
+	/// Whether the copies must be collated.
+	pub collate: bool,
+	/// Whether the user chose to print only the selection.
+	pub selection: bool,
+	/// Inclusive page ranges. If empty, all pages must be printed.
+	pub page_ranges: Vec<(u32, u32)>,
+}
+
+/// Result of the [`page_setup`](crate::comm_dlg::page_setup) dialog. All
+/// dimensions are in thousandths of inches.
+pub struct PageSetupChoice {
+	/// The chosen printer.
+	pub printer: Printer,
+	/// Paper size, already considering the orientation.
+	pub paper_size: SIZE,
+	/// Margins.
+	pub margins: RECT,
+}
+
+/// Displays the font dialog, with screen fonts and effects, returning the
+/// chosen font and its color, or `None` if the user cancelled.
+///
+/// High-level abstraction over [`ChooseFont`](crate::ChooseFont).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// if let Some((lf, color)) = w::comm_dlg::choose_font(&hparent, None)? {
+///     let _hfont = w::HFONT::CreateFontIndirect(&lf);
+///     println!("{}, color {}", lf.lfFaceName(), color);
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn choose_font(
+	hparent: &HWND,
+	initial: Option<(&LOGFONT, COLORREF)>,
+) -> Result<Option<(LOGFONT, COLORREF)>, co::CDERR>
+{
+	let mut lf = initial.map_or_else(LOGFONT::default, |(lf, _)| lf.clone());
+
+	let mut cf = CHOOSEFONT::default();
+	cf.hwndOwner = unsafe { hparent.raw_copy() };
+	cf.Flags = co::CF_FONT::SCREENFONTS | co::CF_FONT::EFFECTS
+		| co::CF_FONT::NOVERTFONTS;
+	if let Some((_, color)) = initial {
+		cf.Flags |= co::CF_FONT::INITTOLOGFONTSTRUCT;
+		cf.rgbColors = color;
+	}
+	cf.set_lpLogFont(Some(&mut lf));
+
+	if !ChooseFont(&mut cf)? {
+		return Ok(None);
+	}
+	let color = cf.rgbColors;
+	Ok(Some((lf, color)))
+}
+
+/// Displays the print dialog, with the given range of pages, returning the
+/// chosen printer and options, or `None` if the user cancelled.
+///
+/// High-level abstraction over [`PrintDlgEx`](crate::PrintDlgEx).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// if let Some(choice) = w::comm_dlg::print(&hparent, (1, 12))? {
+///     println!("Printing {} copies on {}",
+///         choice.copies, choice.printer.device);
+///     // use choice.printer.hdc...
+/// }
+/// # Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+pub fn print(
+	hparent: &HWND,
+	pages: (u32, u32),
+) -> AnyResult<Option<PrintChoice>>
+{
+	let mut ranges = [PRINTPAGERANGE::default(); 16];
+	ranges[0] = PRINTPAGERANGE { nFromPage: pages.0, nToPage: pages.1 };
+
+	let mut pd = PRINTDLGEX::default();
+	pd.hwndOwner = unsafe { hparent.raw_copy() };
+	pd.Flags = co::PD::RETURNDC | co::PD::NOSELECTION | co::PD::NOCURRENTPAGE
+		| co::PD::HIDEPRINTTOFILE;
+	pd.nMinPage = pages.0;
+	pd.nMaxPage = pages.1;
+	pd.nCopies = 1;
+	pd.nPageRanges = 1;
+	pd.set_lpPageRanges(Some(&mut ranges));
+
+	let res = PrintDlgEx(&mut pd);
+	let hdc = unsafe { DeleteDCGuard::new(pd.hDC.raw_copy()) }; // free whatever was returned
+	let hdevmode = unsafe { GlobalFreeGuard::new(pd.hDevMode.raw_copy()) };
+	let hdevnames = unsafe { GlobalFreeGuard::new(pd.hDevNames.raw_copy()) };
+	if res? != co::PD_RESULT::PRINT {
+		return Ok(None);
+	}
+
+	let page_ranges = if pd.Flags.has(co::PD::PAGENUMS) {
+		pd.lpPageRanges()
+			.unwrap_or_default()
+			.iter()
+			.map(|r| (r.nFromPage, r.nToPage))
+			.collect()
+	} else {
+		Vec::default()
+	};
+
+	let (driver, device, output) = read_devnames(&hdevnames)?;
+	Ok(Some(PrintChoice {
+		printer: Printer {
+			hdc,
+			devmode: read_devmode(&hdevmode)?,
+			driver,
+			device,
+			output,
+		},
+		copies: pd.nCopies,
+		collate: pd.Flags.has(co::PD::COLLATE),
+		selection: pd.Flags.has(co::PD::SELECTION),
+		page_ranges,
+	}))
+}
+
+/// Displays the page setup dialog, with the given initial margins in
+/// thousandths of inches, returning the chosen printer, paper and margins, or
+/// `None` if the user cancelled.
+///
+/// High-level abstraction over [`PageSetupDlg`](crate::PageSetupDlg).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// let margins = w::RECT { left: 1000, top: 1000, right: 1000, bottom: 1000 };
+///
+/// if let Some(choice) = w::comm_dlg::page_setup(&hparent, Some(margins))? {
+///     println!("Paper: {}x{}", choice.paper_size.cx, choice.paper_size.cy);
+/// }
+/// # Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+pub fn page_setup(
+	hparent: &HWND,
+	margins: Option<RECT>,
+) -> AnyResult<Option<PageSetupChoice>>
+{
+	let mut psd = PAGESETUPDLG::default();
+	psd.hwndOwner = unsafe { hparent.raw_copy() };
+	psd.Flags = co::PSD::INTHOUSANDTHSOFINCHES;
+	if let Some(margins) = margins {
+		psd.Flags |= co::PSD::MARGINS;
+		psd.rtMargin = margins;
+	}
+
+	let res = PageSetupDlg(&mut psd);
+	let hdevmode = unsafe { GlobalFreeGuard::new(psd.hDevMode.raw_copy()) };
+	let hdevnames = unsafe { GlobalFreeGuard::new(psd.hDevNames.raw_copy()) };
+	if !res? {
+		return Ok(None);
+	}
+
+	let (driver, device, output) = read_devnames(&hdevnames)?;
+	let (hdc, devmode) = {
+		let block = hdevmode.GlobalLock()?;
+		let pdm = unsafe { &*(block.as_ptr() as *const DEVMODE) }; // with the private driver data
+		let hdc = HDC::CreateDC(Some(&driver), Some(&device), Some(pdm))?;
+		(hdc, read_devmode(&hdevmode)?)
+	};
+
+	Ok(Some(PageSetupChoice {
+		printer: Printer { hdc, devmode, driver, device, output },
+		paper_size: SIZE::new(psd.ptPaperSize.x, psd.ptPaperSize.y),
+		margins: psd.rtMargin,
+	}))
+}
+
+/// Displays the modeless Find dialog, with the given initial text, returning
+/// its handle.
+///
+/// The dialog notifies its owner window with the registered
+/// [`FINDMSGSTRING`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/findmsgstring)
+/// message – see
+/// [`wm_find_msg_string`](crate::prelude::GuiEvents::wm_find_msg_string).
+/// The text buffer is allocated here, and freed when the dialog is destroyed,
+/// right after it sends
+/// [`co::FR::DIALOGTERM`](crate::co::FR::DIALOGTERM). Remember to call
+/// [`IsDialogMessage`](crate::prelude::user_Hwnd::IsDialogMessage) with the
+/// returned handle in the message loop, so the keyboard navigation works.
+///
+/// High-level abstraction over [`FindText`](crate::FindText).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// let hfind = w::comm_dlg::find_text(&hparent, "foo", co::FR::DOWN)?;
+/// # Ok::<_, co::CDERR>(())
+/// ```
+pub fn find_text(
+	hparent: &HWND,
+	find_what: &str,
+	flags: co::FR,
+) -> Result<HWND, co::CDERR>
+{
+	show_find_replace(hparent, find_what, None, flags)
+}
+
+/// Displays the modeless Replace dialog, with the given initial texts,
+/// returning its handle.
+///
+/// The dialog notifies its owner window with the registered
+/// [`FINDMSGSTRING`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/findmsgstring)
+/// message – see
+/// [`wm_find_msg_string`](crate::prelude::GuiEvents::wm_find_msg_string).
+/// The text buffers are allocated here, and freed when the dialog is
+/// destroyed, right after it sends
+/// [`co::FR::DIALOGTERM`](crate::co::FR::DIALOGTERM). Remember to call
+/// [`IsDialogMessage`](crate::prelude::user_Hwnd::IsDialogMessage) with the
+/// returned handle in the message loop, so the keyboard navigation works.
+///
+/// High-level abstraction over [`ReplaceText`](crate::ReplaceText).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// let hreplace = w::comm_dlg::replace_text(&hparent, "foo", "bar", co::FR::DOWN)?;
+/// # Ok::<_, co::CDERR>(())
+/// ```
+pub fn replace_text(
+	hparent: &HWND,
+	find_what: &str,
+	replace_with: &str,
+	flags: co::FR,
+) -> Result<HWND, co::CDERR>
+{
+	show_find_replace(hparent, find_what, Some(replace_with), flags)
+}
+
+/// The struct and buffers of an open Find or Replace dialog.
+struct FindReplaceBufs {
+	fr: FINDREPLACE<'static, 'static>,
+	find_what: WString,
+	replace_with: WString,
+}
+
+thread_local! {
+	// Keyed by dialog handle; each entry is freed when its dialog is destroyed.
+	static FIND_REPLACE_BUFS: RefCell<HashMap<usize, *mut FindReplaceBufs>> =
+		RefCell::new(HashMap::new());
+}
+
+fn show_find_replace(
+	hparent: &HWND,
+	find_what: &str,
+	replace_with: Option<&str>,
+	flags: co::FR,
+) -> Result<HWND, co::CDERR>
+{
+	// Owned by the dialog, which keeps pointers to the struct and the buffers.
+	let bufs = Box::into_raw(Box::new(FindReplaceBufs {
+		fr: FINDREPLACE::default(),
+		find_what: find_replace_buf(find_what)?,
+		replace_with: match replace_with {
+			Some(text) => find_replace_buf(text)?,
+			None => WString::default(), // Find dialog has no replace field
+		},
+	}));
+
+	let bufs_ref = unsafe { &mut *bufs };
+	bufs_ref.fr.hwndOwner = unsafe { hparent.raw_copy() };
+	bufs_ref.fr.Flags = (flags & !(co::FR::ENABLETEMPLATE | co::FR::ENABLETEMPLATEHANDLE))
+		| co::FR::ENABLEHOOK;
+	bufs_ref.fr.lpfnHook = Some(find_replace_hook);
+	bufs_ref.fr.set_lpstrFindWhat(Some(&mut bufs_ref.find_what));
+
+	let res = match replace_with {
+		None => unsafe { FindText(&mut bufs_ref.fr) },
+		Some(_) => {
+			bufs_ref.fr.set_lpstrReplaceWith(Some(&mut bufs_ref.replace_with));
+			unsafe { ReplaceText(&mut bufs_ref.fr) }
+		},
+	};
+	let hdlg = match res {
+		Ok(hdlg) => hdlg,
+		Err(e) => {
+			drop(unsafe { Box::from_raw(bufs) }); // no dialog was created
+			return Err(e);
+		},
+	};
+
+	FIND_REPLACE_BUFS.with(|all| all.borrow_mut().insert(hdlg.ptr() as _, bufs));
+	Ok(hdlg)
+}
+
+/// Allocates a text buffer of a Find or Replace dialog, holding the initial
+/// text; the dialog requires at least 80 chars, and the length must fit in a
+/// `u16`.
+fn find_replace_buf(text: &str) -> Result<WString, co::CDERR> {
+	let chars = text.encode_utf16().collect::<Vec<_>>();
+	let len = (chars.len() + 1).max(80); // plus terminating null
+	if len > u16::MAX as usize {
+		return Err(co::CDERR::FR_BUFFERLENGTHZERO); // invalid buffer length
+	}
+
+	let mut buf = WString::new_alloc_buf(len);
+	buf.as_mut_slice()[..chars.len()].copy_from_slice(&chars);
+	Ok(buf)
+}
+
+extern "system" fn find_replace_hook(
+	hdlg: HWND,
+	msg: u32,
+	_: usize,
+	_: isize,
+) -> usize
+{
+	match unsafe { co::WM::from_raw(msg) } {
+		co::WM::INITDIALOG => 1, // let the dialog be displayed
+		co::WM::DESTROY => {
+			let bufs = FIND_REPLACE_BUFS.with(|all| all.borrow_mut().remove(&(hdlg.ptr() as _)));
+			if let Some(bufs) = bufs {
+				drop(unsafe { Box::from_raw(bufs) }); // DIALOGTERM, if any, has already been sent
+			}
+			0
+		},
+		_ => 0, // default processing
+	}
+}
+
+/// Copies the public part of the `DEVMODE` stored in the global memory block.
+fn read_devmode(hdevmode: &HGLOBAL) -> SysResult<DEVMODE> {
+	let block = hdevmode.GlobalLock()?;
+	let mut dm = DEVMODE::default();
+	let num_bytes = block.as_slice().len().min(std::mem::size_of::<DEVMODE>());
+	unsafe {
+		std::ptr::copy_nonoverlapping(
+			block.as_ptr() as *const u8,
+			&mut dm as *mut _ as *mut u8,
+			num_bytes,
+		);
+	}
+	dm.dmDriverExtra = 0; // private driver data is not copied
+	Ok(dm)
+}
+
+/// Reads the driver, device and output names from the `DEVNAMES` stored in
+/// the global memory block; the struct contains char offsets to the strings.
+fn read_devnames(hdevnames: &HGLOBAL) -> SysResult<(String, String, String)> {
+	let block = hdevnames.GlobalLock()?;
+	let wchars = block.as_slice()
+		.chunks_exact(2)
+		.map(|pair| u16::from_ne_bytes([pair[0], pair[1]]))
+		.collect::<Vec<_>>();
+
+	let str_at = |idx: usize| -> String {
+		let off = wchars.get(idx).copied().unwrap_or_default() as usize;
+		let chars = wchars.get(off..).unwrap_or_default();
+		let len = chars.iter().position(|ch| *ch == 0).unwrap_or(chars.len());
+		String::from_utf16_lossy(&chars[..len])
+	};
+	Ok((str_at(0), str_at(1), str_at(2))) // wDriverOffset, wDeviceOffset, wOutputOffset
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn find_replace_buf_min_len() {
+		let buf = find_replace_buf("").unwrap();
+		assert_eq!(buf.buf_len(), 80);
+		assert_eq!(buf.to_string(), "");
+
+		let buf = find_replace_buf("foo").unwrap();
+		assert_eq!(buf.buf_len(), 80);
+		assert_eq!(buf.to_string(), "foo");
+	}
+
+	#[test]
+	fn find_replace_buf_long_text() {
+		let text = "x".repeat(100);
+		let buf = find_replace_buf(&text).unwrap();
+		assert_eq!(buf.buf_len(), 101); // plus terminating null
+		assert_eq!(buf.to_string(), text);
+
+		let text = "\u{1f600}".repeat(50); // surrogate pairs count twice
+		assert_eq!(find_replace_buf(&text).unwrap().buf_len(), 101);
+	}
+
+	#[test]
+	fn find_replace_buf_too_long() {
+		let text = "x".repeat(u16::MAX as usize - 1);
+		assert_eq!(find_replace_buf(&text).unwrap().buf_len(), u16::MAX as usize);
+
+		let text = "x".repeat(u16::MAX as usize);
+		assert_eq!(find_replace_buf(&text).err(), Some(co::CDERR::FR_BUFFERLENGTHZERO));
+	}
+}
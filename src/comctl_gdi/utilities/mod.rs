@@ -0,0 +1 @@
+pub mod comm_dlg;
@@ -0,0 +1,75 @@
+use crate::co;
+use crate::decl::*;
+
+/// A window attribute to be set.
+///
+/// Variable parameter for:
+///
+/// * [`HWND::DwmSetWindowAttribute`](crate::prelude::dwm_Hwnd::DwmSetWindowAttribute).
+#[derive(Clone, Copy)]
+pub enum DwmAttr {
+	/// [`co::DWMWA::NCRENDERING_POLICY`](crate::co::DWMWA::NCRENDERING_POLICY).
+	NcRenderingPolicy(co::DWMNCRP),
+	/// [`co::DWMWA::TRANSITIONS_FORCEDISABLED`](crate::co::DWMWA::TRANSITIONS_FORCEDISABLED).
+	TransitionsForceDisabled(bool),
+	/// [`co::DWMWA::ALLOW_NCPAINT`](crate::co::DWMWA::ALLOW_NCPAINT).
+	AllowNcPaint(bool),
+	/// [`co::DWMWA::NONCLIENT_RTL_LAYOUT`](crate::co::DWMWA::NONCLIENT_RTL_LAYOUT).
+	NonClientRtlLayout(bool),
+	/// [`co::DWMWA::FORCE_ICONIC_REPRESENTATION`](crate::co::DWMWA::FORCE_ICONIC_REPRESENTATION).
+	ForceIconicRepresentation(bool),
+	/// [`co::DWMWA::HAS_ICONIC_BITMAP`](crate::co::DWMWA::HAS_ICONIC_BITMAP).
+	HasIconicBitmap(bool),
+	/// [`co::DWMWA::DISALLOW_PEEK`](crate::co::DWMWA::DISALLOW_PEEK).
+	DisallowPeek(bool),
+	/// [`co::DWMWA::EXCLUDED_FROM_PEEK`](crate::co::DWMWA::EXCLUDED_FROM_PEEK).
+	ExcludedFromPeek(bool),
+	/// [`co::DWMWA::CLOAK`](crate::co::DWMWA::CLOAK).
+	Cloak(bool),
+	/// [`co::DWMWA::FREEZE_REPRESENTATION`](crate::co::DWMWA::FREEZE_REPRESENTATION).
+	FreezeRepresentation(bool),
+	/// [`co::DWMWA::USE_IMMERSIVE_DARK_MODE`](crate::co::DWMWA::USE_IMMERSIVE_DARK_MODE),
+	/// available since Windows 10 build 18985.
+	UseImmersiveDarkMode(bool),
+	/// [`co::DWMWA::WINDOW_CORNER_PREFERENCE`](crate::co::DWMWA::WINDOW_CORNER_PREFERENCE),
+	/// available since Windows 11.
+	WindowCornerPreference(co::DWMWCP),
+	/// [`co::DWMWA::BORDER_COLOR`](crate::co::DWMWA::BORDER_COLOR), available
+	/// since Windows 11.
+	BorderColor(COLORREF),
+	/// [`co::DWMWA::CAPTION_COLOR`](crate::co::DWMWA::CAPTION_COLOR), available
+	/// since Windows 11.
+	CaptionColor(COLORREF),
+	/// [`co::DWMWA::TEXT_COLOR`](crate::co::DWMWA::TEXT_COLOR), available since
+	/// Windows 11.
+	TextColor(COLORREF),
+	/// [`co::DWMWA::SYSTEMBACKDROP_TYPE`](crate::co::DWMWA::SYSTEMBACKDROP_TYPE),
+	/// available since Windows 11 build 22621.
+	SystemBackdropType(co::DWMSBT),
+}
+
+impl DwmAttr {
+	/// Returns the attribute identifier and its value. All attributes are
+	/// 32-bit values.
+	#[must_use]
+	pub fn as_raw(&self) -> (co::DWMWA, u32) {
+		match *self {
+			Self::NcRenderingPolicy(v) => (co::DWMWA::NCRENDERING_POLICY, v.raw()),
+			Self::TransitionsForceDisabled(v) => (co::DWMWA::TRANSITIONS_FORCEDISABLED, v as _),
+			Self::AllowNcPaint(v) => (co::DWMWA::ALLOW_NCPAINT, v as _),
+			Self::NonClientRtlLayout(v) => (co::DWMWA::NONCLIENT_RTL_LAYOUT, v as _),
+			Self::ForceIconicRepresentation(v) => (co::DWMWA::FORCE_ICONIC_REPRESENTATION, v as _),
+			Self::HasIconicBitmap(v) => (co::DWMWA::HAS_ICONIC_BITMAP, v as _),
+			Self::DisallowPeek(v) => (co::DWMWA::DISALLOW_PEEK, v as _),
+			Self::ExcludedFromPeek(v) => (co::DWMWA::EXCLUDED_FROM_PEEK, v as _),
+			Self::Cloak(v) => (co::DWMWA::CLOAK, v as _),
+			Self::FreezeRepresentation(v) => (co::DWMWA::FREEZE_REPRESENTATION, v as _),
+			Self::UseImmersiveDarkMode(v) => (co::DWMWA::USE_IMMERSIVE_DARK_MODE, v as _),
+			Self::WindowCornerPreference(v) => (co::DWMWA::WINDOW_CORNER_PREFERENCE, v.raw()),
+			Self::BorderColor(v) => (co::DWMWA::BORDER_COLOR, v.raw()),
+			Self::CaptionColor(v) => (co::DWMWA::CAPTION_COLOR, v.raw()),
+			Self::TextColor(v) => (co::DWMWA::TEXT_COLOR, v.raw()),
+			Self::SystemBackdropType(v) => (co::DWMWA::SYSTEMBACKDROP_TYPE, v.raw()),
+		}
+	}
+}
@@ -78,4 +78,34 @@ pub trait dwm_Hwnd: uxtheme_Hwnd {
 			},
 		)
 	}
+
+	/// [`DwmSetWindowAttribute`](https://learn.microsoft.com/en-us/windows/win32/api/dwmapi/nf-dwmapi-dwmsetwindowattribute)
+	/// function.
+	///
+	/// # Examples
+	///
+	/// Enabling the dark title bar:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hwnd: w::HWND; // initialized somewhere
+	/// # let hwnd = w::HWND::NULL;
+	///
+	/// hwnd.DwmSetWindowAttribute(w::DwmAttr::UseImmersiveDarkMode(true))?;
+	/// # w::HrResult::Ok(())
+	/// ```
+	fn DwmSetWindowAttribute(&self, attr: DwmAttr) -> HrResult<()> {
+		let (attr_id, val) = attr.as_raw();
+		ok_to_hrresult(
+			unsafe {
+				ffi::DwmSetWindowAttribute(
+					self.ptr(),
+					attr_id.raw(),
+					&val as *const _ as _,
+					std::mem::size_of::<u32>() as _,
+				)
+			},
+		)
+	}
 }
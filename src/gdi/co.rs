@@ -48,6 +48,40 @@ const_ordinary! { BSS: u32;
 	MONOPATTERN 9
 }
 
+const_bitflag! { CF_FONT: u32;
+	/// [`CHOOSEFONT`](crate::CHOOSEFONT) `Flags` (`u32`).
+	///
+	/// Originally has `CF` prefix.
+	=>
+	=>
+	SCREENFONTS 0x0000_0001
+	PRINTERFONTS 0x0000_0002
+	BOTH 0x0000_0003
+	SHOWHELP 0x0000_0004
+	ENABLEHOOK 0x0000_0008
+	ENABLETEMPLATE 0x0000_0010
+	ENABLETEMPLATEHANDLE 0x0000_0020
+	INITTOLOGFONTSTRUCT 0x0000_0040
+	USESTYLE 0x0000_0080
+	EFFECTS 0x0000_0100
+	APPLY 0x0000_0200
+	SCRIPTSONLY 0x0000_0400
+	NOVECTORFONTS 0x0000_0800
+	NOSIMULATIONS 0x0000_1000
+	LIMITSIZE 0x0000_2000
+	FIXEDPITCHONLY 0x0000_4000
+	FORCEFONTEXIST 0x0001_0000
+	SCALABLEONLY 0x0002_0000
+	TTONLY 0x0004_0000
+	NOFACESEL 0x0008_0000
+	NOSTYLESEL 0x0010_0000
+	NOSIZESEL 0x0020_0000
+	SELECTSCRIPT 0x0040_0000
+	NOSCRIPTSEL 0x0080_0000
+	NOVERTFONTS 0x0100_0000
+	INACTIVEFONTS 0x0200_0000
+}
+
 const_ordinary! { CHARSET: u8;
 	/// [`HFONT::CreateFont`](crate::prelude::gdi_Hfont::CreateFont) `char_set`
 	/// and [`LOGFONT`](crate::LOGFONT) `lfCharset` (`u8`).
@@ -91,6 +125,59 @@ const_ordinary! { CLIP: u8;
 	EMBEDDED 8 << 4
 }
 
+const_ordinary! { DC_CAP: u16;
+	/// [`DeviceCapabilities`](crate::DeviceCapabilities) `capability` (`u16`).
+	///
+	/// Originally has `DC` prefix.
+	=>
+	=>
+	FIELDS 1
+	PAPERS 2
+	PAPERSIZE 3
+	MINEXTENT 4
+	MAXEXTENT 5
+	BINS 6
+	DUPLEX 7
+	SIZE 8
+	EXTRA 9
+	VERSION 10
+	DRIVER 11
+	BINNAMES 12
+	ENUMRESOLUTIONS 13
+	FILEDEPENDENCIES 14
+	TRUETYPE 15
+	PAPERNAMES 16
+	ORIENTATION 17
+	COPIES 18
+	BINADJUST 19
+	EMF_COMPLIANT 20
+	DATATYPE_PRODUCED 21
+	COLLATE 22
+	MANUFACTURER 23
+	MODEL 24
+	PERSONALITY 25
+	PRINTRATE 26
+	PRINTRATEUNIT 27
+	PRINTERMEM 28
+	MEDIAREADY 29
+	STAPLE 30
+	PRINTRATEPPM 31
+	COLORDEVICE 32
+	NUP 33
+	MEDIATYPENAMES 34
+	MEDIATYPES 35
+}
+
+const_bitflag! { DI: u32;
+	/// [`DOCINFO`](crate::DOCINFO) `fwType` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	APPBANDING 0x0000_0001
+	ROPS_READ_DESTINATION 0x0000_0002
+}
+
 const_ordinary! { DIB: u32;
 	/// [`LOGBRUSH`](crate::LOGBRUSH) `lbColor` (`u32`).
 	=>
@@ -115,6 +202,18 @@ const_ordinary! { FF: u8;
 	DECORATIVE 5 << 4
 }
 
+const_bitflag! { FONTTYPE: u16;
+	/// [`CHOOSEFONT`](crate::CHOOSEFONT) `nFontType` (`u16`).
+	=>
+	=>
+	BOLD 0x0100
+	ITALIC 0x0200
+	REGULAR 0x0400
+	SCREEN 0x2000
+	PRINTER 0x4000
+	SIMULATED 0x8000
+}
+
 const_ordinary! { FW: u32;
 	/// [`HFONT::CreateFont`](crate::prelude::gdi_Hfont::CreateFont) `weight`
 	/// and [`LOGFONT`](crate::LOGFONT) `lfWeight` (`u32`).
@@ -237,6 +336,20 @@ const_bitflag! { LR: u32;
 	SHARED 0x0000_8000
 }
 
+const_ordinary! { MM: i32;
+	/// [`HDC::SetMapMode`](crate::prelude::gdi_Hdc::SetMapMode) `mode` (`i32`).
+	=>
+	=>
+	TEXT 1
+	LOMETRIC 2
+	HIMETRIC 3
+	LOENGLISH 4
+	HIENGLISH 5
+	TWIPS 6
+	ISOTROPIC 7
+	ANISOTROPIC 8
+}
+
 const_ordinary! { OUT_PRECIS: u8;
 	/// [`HFONT::CreateFont`](crate::prelude::gdi_Hfont::CreateFont)
 	/// `out_precision` and [`LOGFONT`](crate::LOGFONT) `lfOutPrecision` (`u8`).
@@ -298,6 +411,80 @@ const_ordinary! { PS: i32;
 	INSIDEFRAME 6
 }
 
+const_bitflag! { PRINTER_ATTRIBUTE: u32;
+	/// [`PRINTER_INFO_2`](crate::PRINTER_INFO_2) `Attributes` (`u32`).
+	=>
+	=>
+	QUEUED 0x0000_0001
+	DIRECT 0x0000_0002
+	DEFAULT 0x0000_0004
+	SHARED 0x0000_0008
+	NETWORK 0x0000_0010
+	HIDDEN 0x0000_0020
+	LOCAL 0x0000_0040
+	ENABLE_DEVQ 0x0000_0080
+	KEEPPRINTEDJOBS 0x0000_0100
+	DO_COMPLETE_FIRST 0x0000_0200
+	WORK_OFFLINE 0x0000_0400
+	ENABLE_BIDI 0x0000_0800
+	RAW_ONLY 0x0000_1000
+	PUBLISHED 0x0000_2000
+	FAX 0x0000_4000
+	TS 0x0000_8000
+	PUSHED_USER 0x0002_0000
+	PUSHED_MACHINE 0x0004_0000
+	MACHINE 0x0008_0000
+	FRIENDLY_NAME 0x0010_0000
+	TS_GENERIC_DRIVER 0x0020_0000
+}
+
+const_bitflag! { PRINTER_ENUM: u32;
+	/// [`EnumPrinters`](crate::EnumPrinters) `flags` (`u32`).
+	=>
+	=>
+	DEFAULT 0x0000_0001
+	LOCAL 0x0000_0002
+	CONNECTIONS 0x0000_0004
+	FAVORITE 0x0000_0004
+	NAME 0x0000_0008
+	REMOTE 0x0000_0010
+	SHARED 0x0000_0020
+	NETWORK 0x0000_0040
+}
+
+const_bitflag! { PRINTER_STATUS: u32;
+	/// [`PRINTER_INFO_2`](crate::PRINTER_INFO_2) `Status` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	PAUSED 0x0000_0001
+	ERROR 0x0000_0002
+	PENDING_DELETION 0x0000_0004
+	PAPER_JAM 0x0000_0008
+	PAPER_OUT 0x0000_0010
+	MANUAL_FEED 0x0000_0020
+	PAPER_PROBLEM 0x0000_0040
+	OFFLINE 0x0000_0080
+	IO_ACTIVE 0x0000_0100
+	BUSY 0x0000_0200
+	PRINTING 0x0000_0400
+	OUTPUT_BIN_FULL 0x0000_0800
+	NOT_AVAILABLE 0x0000_1000
+	WAITING 0x0000_2000
+	PROCESSING 0x0000_4000
+	INITIALIZING 0x0000_8000
+	WARMING_UP 0x0001_0000
+	TONER_LOW 0x0002_0000
+	NO_TONER 0x0004_0000
+	PAGE_PUNT 0x0008_0000
+	USER_INTERVENTION 0x0010_0000
+	OUT_OF_MEMORY 0x0020_0000
+	DOOR_OPEN 0x0040_0000
+	SERVER_UNKNOWN 0x0080_0000
+	POWER_SAVE 0x0100_0000
+}
+
 const_ordinary! { QUALITY: u8;
 	/// [`HFONT::CreateFont`](crate::prelude::gdi_Hfont::CreateFont) `quality`
 	/// and [`LOGFONT`](crate::LOGFONT) `lfQuality` (`u8`).
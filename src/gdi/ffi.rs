@@ -1,6 +1,11 @@
 use crate::kernel::ffi_types::*;
 
+extern_sys! { "comdlg32";
+	ChooseFontW(PVOID) -> BOOL
+}
+
 extern_sys! { "gdi32";
+	AbortDoc(HANDLE) -> i32
 	AbortPath(HANDLE) -> BOOL
 	AngleArc(HANDLE, i32, i32, u32, f32, f32) -> BOOL
 	Arc(HANDLE, i32, i32, i32, i32, i32, i32, i32, i32) -> BOOL
@@ -14,6 +19,7 @@ extern_sys! { "gdi32";
 	CreateBrushIndirect(PCVOID) -> HANDLE
 	CreateCompatibleBitmap(HANDLE, i32, i32) -> HANDLE
 	CreateCompatibleDC(HANDLE) -> HANDLE
+	CreateDCW(PCSTR, PCSTR, PCSTR, PCVOID) -> HANDLE
 	CreateFontIndirectW(PCVOID) -> HANDLE
 	CreateFontW(i32, i32, i32, i32, i32, u32, u32, u32, u32, u32, u32, u32, u32, PCSTR) -> HANDLE
 	CreateHalftonePalette(HANDLE) -> HANDLE
@@ -29,6 +35,8 @@ extern_sys! { "gdi32";
 	DeleteDC(HANDLE) -> BOOL
 	DeleteObject(HANDLE) -> BOOL
 	Ellipse(HANDLE, i32, i32, i32, i32) -> BOOL
+	EndDoc(HANDLE) -> i32
+	EndPage(HANDLE) -> i32
 	EndPath(HANDLE) -> BOOL
 	FillPath(HANDLE) -> BOOL
 	FillRect(HANDLE, PCVOID, HANDLE) -> i32
@@ -87,6 +95,7 @@ extern_sys! { "gdi32";
 	SetDCPenColor(HANDLE, u32) -> u32
 	SetDIBits(HANDLE, HANDLE, u32, u32, PCVOID, PCVOID, u32) -> i32
 	SetGraphicsMode(HANDLE, i32) -> i32
+	SetMapMode(HANDLE, i32) -> i32
 	SetStretchBltMode(HANDLE, i32) -> i32
 	SetTextAlign(HANDLE, u32) -> u32
 	SetTextColor(HANDLE, u32) -> u32
@@ -95,6 +104,8 @@ extern_sys! { "gdi32";
 	SetViewportOrgEx(HANDLE, i32, i32, PVOID) -> BOOL
 	SetWindowExtEx(HANDLE, i32, i32, PVOID) -> BOOL
 	SetWindowOrgEx(HANDLE, i32, i32, PVOID) -> BOOL
+	StartDocW(HANDLE, PCVOID) -> i32
+	StartPage(HANDLE) -> i32
 	StretchBlt(HANDLE, i32, i32, i32, i32, HANDLE, i32, i32, i32, i32, u32) -> BOOL
 	StrokeAndFillPath(HANDLE) -> BOOL
 	StrokePath(HANDLE) -> BOOL
@@ -111,3 +122,12 @@ extern_sys! { "msimg32";
 extern_sys! { "user32";
 	LoadImageW(HANDLE, PCSTR, u32, i32, i32, u32) -> HANDLE // returns GdiObjectGuard, so needs gdi feature
 }
+
+extern_sys! { "winspool";
+	ClosePrinter(HANDLE) -> BOOL
+	DeviceCapabilitiesW(PCSTR, PCSTR, u16, PVOID, PCVOID) -> i32
+	EnumPrintersW(u32, PCSTR, u32, PVOID, u32, *mut u32, *mut u32) -> BOOL
+	GetDefaultPrinterW(PSTR, *mut u32) -> BOOL
+	GetPrinterW(HANDLE, u32, PVOID, u32, *mut u32) -> BOOL
+	OpenPrinterW(PCSTR, *mut HANDLE, PCVOID) -> BOOL
+}
@@ -1,9 +1,153 @@
 #![allow(non_snake_case)]
 
+use crate::co;
 use crate::decl::*;
 use crate::gdi::ffi;
 use crate::kernel::privs::*;
 
+/// [`ChooseFont`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/nf-commdlg-choosefontw)
+/// function.
+///
+/// Returns `false` if the user cancelled the dialog.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// let mut lf = w::LOGFONT::default();
+/// lf.set_lfFaceName("Segoe UI");
+/// lf.lfHeight = -12;
+///
+/// let mut cf = w::CHOOSEFONT::default();
+/// cf.hwndOwner = unsafe { hparent.raw_copy() };
+/// cf.Flags = co::CF_FONT::SCREENFONTS | co::CF_FONT::EFFECTS
+///     | co::CF_FONT::INITTOLOGFONTSTRUCT;
+/// cf.set_lpLogFont(Some(&mut lf));
+///
+/// if w::ChooseFont(&mut cf)? {
+///     let pt_size = cf.iPointSize / 10;
+///     println!("Font: {}, {} pt", lf.lfFaceName(), pt_size);
+/// }
+/// # Ok::<_, co::CDERR>(())
+/// ```
+pub fn ChooseFont(cf: &mut CHOOSEFONT) -> Result<bool, co::CDERR> {
+	match unsafe { ffi::ChooseFontW(cf as *mut _ as _) } {
+		0 => match CommDlgExtendedError() {
+			co::CDERR::NoValue => Ok(false),
+			err => Err(err),
+		},
+		_ => Ok(true),
+	}
+}
+
+/// [`DeviceCapabilities`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-devicecapabilitiesw)
+/// function.
+///
+/// If `output` is `None`, returns the number of elements the capability
+/// would write, or the value of a numeric capability.
+///
+/// # Safety
+///
+/// The `output` buffer must be large enough to hold all the elements of the
+/// capability, whose size depends on the capability itself. Call this function
+/// first with `None` to retrieve the number of elements.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let max_copies = unsafe {
+///     w::DeviceCapabilities("My Printer", None, co::DC_CAP::COPIES, None, None)?
+/// };
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub unsafe fn DeviceCapabilities(
+	device: &str,
+	port: Option<&str>,
+	capability: co::DC_CAP,
+	output: Option<&mut [u8]>,
+	dev_mode: Option<&DEVMODE>,
+) -> SysResult<i32>
+{
+	match ffi::DeviceCapabilitiesW(
+		WString::from_str(device).as_ptr(),
+		WString::from_opt_str(port).as_ptr(),
+		capability.raw(),
+		output.map_or(std::ptr::null_mut(), |buf| buf.as_mut_ptr() as _),
+		dev_mode.map_or(std::ptr::null(), |dm| dm as *const _ as _),
+	) {
+		-1 => Err(GetLastError()),
+		n => Ok(n),
+	}
+}
+
+/// [`EnumPrinters`](https://learn.microsoft.com/en-us/windows/win32/printdocs/enumprinters)
+/// function, at level 2.
+///
+/// The structs point to memory owned by the function, so they're only
+/// available inside the closure. Return `false` to stop the enumeration.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// w::EnumPrinters(
+///     co::PRINTER_ENUM::LOCAL | co::PRINTER_ENUM::CONNECTIONS,
+///     None,
+///     |pi: &w::PRINTER_INFO_2| -> bool {
+///         println!("{}", pi.pPrinterName().unwrap_or_default());
+///         true
+///     },
+/// )?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub fn EnumPrinters<F>(
+	flags: co::PRINTER_ENUM,
+	name: Option<&str>,
+	mut func: F,
+) -> SysResult<()>
+	where F: FnMut(&PRINTER_INFO_2) -> bool,
+{
+	let wname = WString::from_opt_str(name);
+	let (mut needed, mut returned) = (u32::default(), u32::default());
+
+	if unsafe {
+		ffi::EnumPrintersW(flags.raw(), wname.as_ptr(), 2,
+			std::ptr::null_mut(), 0, &mut needed, &mut returned)
+	} != 0 {
+		return Ok(()); // no printers at all
+	}
+	match GetLastError() {
+		co::ERROR::INSUFFICIENT_BUFFER => {},
+		err => return Err(err),
+	}
+
+	let mut buf = HeapBlock::alloc(needed as _)?;
+	bool_to_sysresult(
+		unsafe {
+			ffi::EnumPrintersW(flags.raw(), wname.as_ptr(), 2,
+				buf.as_mut_ptr() as _, needed, &mut needed, &mut returned)
+		},
+	)?;
+
+	let infos = unsafe {
+		std::slice::from_raw_parts(
+			buf.as_ptr() as *const PRINTER_INFO_2, returned as _)
+	};
+	for pi in infos.iter() {
+		if !func(pi) {
+			break;
+		}
+	}
+	Ok(())
+}
+
 /// [`GdiFlush`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-gdiflush)
 /// function.
 pub fn GdiFlush() -> SysResult<()> {
@@ -25,3 +169,20 @@ pub fn GdiSetBatchLimit(limit: u32) -> SysResult<u32> {
 		n => Ok(n),
 	}
 }
+
+/// [`GetDefaultPrinter`](https://learn.microsoft.com/en-us/windows/win32/printdocs/getdefaultprinter)
+/// function.
+#[must_use]
+pub fn GetDefaultPrinter() -> SysResult<String> {
+	let mut len = u32::default();
+	unsafe { ffi::GetDefaultPrinterW(std::ptr::null_mut(), &mut len); } // fails with ERROR_INSUFFICIENT_BUFFER
+	match GetLastError() {
+		co::ERROR::INSUFFICIENT_BUFFER => {},
+		err => return Err(err),
+	}
+
+	let mut buf = WString::new_alloc_buf(len as _);
+	bool_to_sysresult(
+		unsafe { ffi::GetDefaultPrinterW(buf.as_mut_ptr(), &mut len) },
+	).map(|_| buf.to_string())
+}
@@ -5,6 +5,14 @@ use crate::decl::*;
 use crate::gdi::ffi;
 use crate::prelude::*;
 
+handle_guard! { ClosePrinterGuard: HPRINTER;
+	ffi::ClosePrinter;
+	/// RAII implementation for [`HPRINTER`](crate::HPRINTER) which
+	/// automatically calls
+	/// [`ClosePrinter`](https://learn.microsoft.com/en-us/windows/win32/printdocs/closeprinter)
+	/// when the object goes out of scope.
+}
+
 handle_guard! { DeleteDCGuard: HDC;
 	ffi::DeleteDC;
 	/// RAII implementation for [`HDC`](crate::HDC) which automatically calls
@@ -81,6 +89,134 @@ impl<T> DeleteObjectGuard<T>
 
 //------------------------------------------------------------------------------
 
+/// RAII implementation for [`HDC::StartDoc`](crate::prelude::gdi_Hdc::StartDoc)
+/// calls, which automatically calls
+/// [`EndDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-enddoc)
+/// when the object goes out of scope.
+///
+/// Since errors from `EndDoc` are significant when printing, prefer calling
+/// [`end`](crate::guard::EndDocGuard::end) explicitly.
+pub struct EndDocGuard<'a, H>
+	where H: gdi_Hdc,
+{
+	hdc: Option<&'a H>,
+	job_id: i32,
+}
+
+impl<'a, H> Drop for EndDocGuard<'a, H>
+	where H: gdi_Hdc,
+{
+	fn drop(&mut self) {
+		if let Some(h) = self.hdc.and_then(|h| h.as_opt()) {
+			unsafe { ffi::EndDoc(h.ptr()); } // ignore errors
+		}
+	}
+}
+
+impl<'a, H> EndDocGuard<'a, H>
+	where H: gdi_Hdc,
+{
+	/// Constructs the guard by taking ownership of the handle.
+	///
+	/// # Safety
+	///
+	/// Be sure you must call
+	/// [`EndDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-enddoc)
+	/// at the end of scope.
+	#[must_use]
+	pub const unsafe fn new(hdc: &'a H, job_id: i32) -> Self {
+		Self { hdc: Some(hdc), job_id }
+	}
+
+	/// Calls
+	/// [`EndDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-enddoc)
+	/// immediately, returning its result, and disarms the destructor.
+	pub fn end(mut self) -> SysResult<()> {
+		match self.hdc.take() {
+			Some(h) => match unsafe { ffi::EndDoc(h.ptr()) } {
+				n if n <= 0 => Err(GetLastError()),
+				_ => Ok(()),
+			},
+			None => Ok(()),
+		}
+	}
+
+	/// Calls
+	/// [`AbortDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-abortdoc)
+	/// immediately, discarding the print job, and disarms the destructor, so
+	/// `EndDoc` is not called.
+	pub fn abort(mut self) -> SysResult<()> {
+		match self.hdc.take() {
+			Some(h) => h.AbortDoc(),
+			None => Ok(()),
+		}
+	}
+
+	/// Returns the print job identifier returned by the source
+	/// [`HDC::StartDoc`](crate::prelude::gdi_Hdc::StartDoc) call.
+	#[must_use]
+	pub const fn job_id(&self) -> i32 {
+		self.job_id
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// RAII implementation for
+/// [`HDC::StartPage`](crate::prelude::gdi_Hdc::StartPage) calls, which
+/// automatically calls
+/// [`EndPage`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-endpage)
+/// when the object goes out of scope.
+///
+/// Since errors from `EndPage` are significant when printing, prefer calling
+/// [`end`](crate::guard::EndPageGuard::end) explicitly.
+pub struct EndPageGuard<'a, H>
+	where H: gdi_Hdc,
+{
+	hdc: Option<&'a H>,
+}
+
+impl<'a, H> Drop for EndPageGuard<'a, H>
+	where H: gdi_Hdc,
+{
+	fn drop(&mut self) {
+		if let Some(h) = self.hdc.and_then(|h| h.as_opt()) {
+			unsafe { ffi::EndPage(h.ptr()); } // ignore errors
+		}
+	}
+}
+
+impl<'a, H> EndPageGuard<'a, H>
+	where H: gdi_Hdc,
+{
+	/// Constructs the guard by taking ownership of the handle.
+	///
+	/// # Safety
+	///
+	/// Be sure you must call
+	/// [`EndPage`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-endpage)
+	/// at the end of scope.
+	#[must_use]
+	pub const unsafe fn new(hdc: &'a H) -> Self {
+		Self { hdc: Some(hdc) }
+	}
+
+	/// Calls
+	/// [`EndPage`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-endpage)
+	/// immediately, returning its result, and disarms the destructor.
+	pub fn end(mut self) -> SysResult<()> {
+		match self.hdc.take() {
+			Some(h) => match unsafe { ffi::EndPage(h.ptr()) } {
+				n if n <= 0 => Err(GetLastError()),
+				_ => Ok(()),
+			},
+			None => Ok(()),
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
 /// RAII implementation for [`LOGPALETTE`](crate::LOGPALETTE) which manages the
 /// allocated memory.
 pub struct LogpaletteGuard {
@@ -20,6 +20,18 @@ impl gdi_Hdc for HDC {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait gdi_Hdc: user_Hdc {
+	/// [`AbortDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-abortdoc)
+	/// function.
+	///
+	/// Stops the current print job, discarding everything drawn since the
+	/// [`HDC::StartDoc`](crate::prelude::gdi_Hdc::StartDoc) call.
+	fn AbortDoc(&self) -> SysResult<()> {
+		match unsafe { ffi::AbortDoc(self.ptr()) } {
+			n if n <= 0 => Err(GetLastError()),
+			_ => Ok(()),
+		}
+	}
+
 	/// [`AborthPath`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-abortpath)
 	/// function.
 	fn AbortPath(&self) -> SysResult<()> {
@@ -175,6 +187,41 @@ pub trait gdi_Hdc: user_Hdc {
 		}
 	}
 
+	/// [`CreateDC`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-createdcw)
+	/// function.
+	///
+	/// To create a printer device context, pass the driver and device names
+	/// retrieved from the printer, along with its [`DEVMODE`](crate::DEVMODE).
+	///
+	/// # Examples
+	///
+	/// Creating a device context for the whole screen:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hdc = w::HDC::CreateDC(Some("DISPLAY"), None, None)?;
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn CreateDC(
+		driver: Option<&str>,
+		device: Option<&str>,
+		dm: Option<&DEVMODE>,
+	) -> SysResult<DeleteDCGuard>
+	{
+		unsafe {
+			ptr_to_sysresult_handle(
+				ffi::CreateDCW(
+					WString::from_opt_str(driver).as_ptr(),
+					WString::from_opt_str(device).as_ptr(),
+					std::ptr::null(),
+					dm.map_or(std::ptr::null(), |dm| dm as *const _ as _),
+				),
+			).map(|h| DeleteDCGuard::new(h))
+		}
+	}
+
 	/// [`CreateHalftonePalette`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-createhalftonepalette)
 	/// function.
 	#[must_use]
@@ -867,6 +914,15 @@ pub trait gdi_Hdc: user_Hdc {
 		}
 	}
 
+	/// [`SetMapMode`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-setmapmode)
+	/// function.
+	fn SetMapMode(&self, mode: co::MM) -> SysResult<co::MM> {
+		match unsafe { ffi::SetMapMode(self.ptr(), mode.raw()) } {
+			0 => Err(GetLastError()),
+			v => Ok(unsafe { co::MM::from_raw(v) }),
+		}
+	}
+
 	/// [`SetStretchBltMode`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-setstretchbltmode)
 	/// function.
 	fn SetStretchBltMode(&self,
@@ -953,6 +1009,63 @@ pub trait gdi_Hdc: user_Hdc {
 		).map(|_| pt)
 	}
 
+	/// [`StartDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-startdocw)
+	/// function.
+	///
+	/// In the original C implementation, you must call
+	/// [`EndDoc`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-enddoc)
+	/// as a cleanup operation.
+	///
+	/// Here, the cleanup is performed automatically, because `StartDoc` returns
+	/// an [`EndDocGuard`](crate::guard::EndDocGuard). To cancel the job, call
+	/// [`EndDocGuard::abort`](crate::guard::EndDocGuard::abort).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hdc_printer: w::HDC; // initialized somewhere
+	/// # let hdc_printer = w::HDC::NULL;
+	///
+	/// let mut doc_name = w::WString::from_str("My document");
+	/// let mut di = w::DOCINFO::default();
+	/// di.set_lpszDocName(Some(&mut doc_name));
+	///
+	/// let _doc = hdc_printer.StartDoc(&di)?;
+	/// for page in 1..=3 {
+	///     let pg = hdc_printer.StartPage()?;
+	///     hdc_printer.TextOut(100, 100, &format!("Page {}", page))?;
+	///     pg.end()?;
+	/// }
+	/// // EndDoc automatically called
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn StartDoc(&self, di: &DOCINFO) -> SysResult<EndDocGuard<'_, Self>> {
+		match unsafe { ffi::StartDocW(self.ptr(), di as *const _ as _) } {
+			n if n <= 0 => Err(GetLastError()),
+			job_id => Ok(unsafe { EndDocGuard::new(self, job_id) }),
+		}
+	}
+
+	/// [`StartPage`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-startpage)
+	/// function.
+	///
+	/// In the original C implementation, you must call
+	/// [`EndPage`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-endpage)
+	/// as a cleanup operation.
+	///
+	/// Here, the cleanup is performed automatically, because `StartPage`
+	/// returns an [`EndPageGuard`](crate::guard::EndPageGuard).
+	#[must_use]
+	fn StartPage(&self) -> SysResult<EndPageGuard<'_, Self>> {
+		match unsafe { ffi::StartPage(self.ptr()) } {
+			n if n <= 0 => Err(GetLastError()),
+			_ => Ok(unsafe { EndPageGuard::new(self) }),
+		}
+	}
+
 	/// [`StretchBlt`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-stretchblt)
 	/// function.
 	fn StretchBlt(&self,
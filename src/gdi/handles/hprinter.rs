@@ -0,0 +1,79 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::decl::*;
+use crate::gdi::ffi;
+use crate::guard::*;
+use crate::kernel::privs::*;
+use crate::prelude::*;
+
+impl_handle! { HPRINTER;
+	/// Handle to a
+	/// [printer](https://learn.microsoft.com/en-us/windows/win32/printdocs/openprinter).
+}
+
+impl gdi_Hprinter for HPRINTER {}
+
+/// This trait is enabled with the `gdi` feature, and provides methods for
+/// [`HPRINTER`](crate::HPRINTER).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait gdi_Hprinter: Handle {
+	/// [`GetPrinter`](https://learn.microsoft.com/en-us/windows/win32/printdocs/getprinter)
+	/// function, at level 2.
+	///
+	/// The returned struct points to memory owned by the system, so it's only
+	/// available inside the closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hprinter = w::HPRINTER::OpenPrinter(Some("My Printer"))?;
+	/// hprinter.GetPrinter2(|pi| {
+	///     println!("{} jobs", pi.cJobs);
+	/// })?;
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	fn GetPrinter2<F>(&self, func: F) -> SysResult<()>
+		where F: FnOnce(&PRINTER_INFO_2),
+	{
+		let mut needed = u32::default();
+		unsafe {
+			ffi::GetPrinterW(self.ptr(), 2, std::ptr::null_mut(), 0, &mut needed); // fails with ERROR_INSUFFICIENT_BUFFER
+		}
+		if needed == 0 {
+			return Err(GetLastError());
+		}
+
+		let mut buf = HeapBlock::alloc(needed as _)?;
+		bool_to_sysresult(
+			unsafe {
+				ffi::GetPrinterW(
+					self.ptr(), 2, buf.as_mut_ptr(), needed, &mut needed)
+			},
+		).map(|_| func(unsafe { &*(buf.as_ptr() as *const PRINTER_INFO_2) }))
+	}
+
+	/// [`OpenPrinter`](https://learn.microsoft.com/en-us/windows/win32/printdocs/openprinter)
+	/// static method.
+	///
+	/// If `name` is `None`, opens the local print server.
+	#[must_use]
+	fn OpenPrinter(name: Option<&str>) -> SysResult<ClosePrinterGuard> {
+		let mut handle = HPRINTER::NULL;
+		unsafe {
+			bool_to_sysresult(
+				ffi::OpenPrinterW(
+					WString::from_opt_str(name).as_ptr(),
+					handle.as_mut(),
+					std::ptr::null(),
+				),
+			).map(|_| ClosePrinterGuard::new(handle))
+		}
+	}
+}
@@ -6,12 +6,14 @@ mod hfont;
 mod hinstance;
 mod hpalette;
 mod hpen;
+mod hprinter;
 mod hrgn;
 
 pub mod decl {
 	pub use super::hfont::HFONT;
 	pub use super::hpalette::HPALETTE;
 	pub use super::hpen::HPEN;
+	pub use super::hprinter::HPRINTER;
 }
 
 pub mod traits {
@@ -23,5 +25,6 @@ pub mod traits {
 	pub use super::hinstance::gdi_Hinstance;
 	pub use super::hpalette::gdi_Hpalette;
 	pub use super::hpen::gdi_Hpen;
+	pub use super::hprinter::gdi_Hprinter;
 	pub use super::hrgn::gdi_Hrgn;
 }
@@ -1,5 +1,7 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use std::marker::PhantomData;
+
 use crate::co;
 use crate::decl::*;
 use crate::gdi::privs::*;
@@ -86,6 +88,62 @@ impl BITMAPINFOHEADER {
 	pub_fn_serialize!();
 }
 
+/// [`CHOOSEFONT`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/ns-commdlg-choosefontw)
+/// struct.
+#[repr(C)]
+pub struct CHOOSEFONT<'a, 'b> {
+	lStructSize: u32,
+	pub hwndOwner: HWND,
+	pub hDC: HDC,
+	lpLogFont: *mut LOGFONT,
+	pub iPointSize: i32,
+	pub Flags: co::CF_FONT,
+	pub rgbColors: COLORREF,
+	pub lCustData: isize,
+	lpfnHook: *mut std::ffi::c_void,
+	lpTemplateName: *mut u16, // u16 resource ID
+	pub hInstance: HINSTANCE,
+	lpszStyle: *mut u16,
+	pub nFontType: co::FONTTYPE,
+	___MISSING_ALIGNMENT__: u16,
+	pub nSizeMin: i32,
+	pub nSizeMax: i32,
+
+	_lpLogFont: PhantomData<&'a mut LOGFONT>,
+	_lpszStyle: PhantomData<&'b mut u16>,
+}
+
+impl_default_with_size!(CHOOSEFONT, lStructSize, 'a, 'b);
+
+impl<'a, 'b> CHOOSEFONT<'a, 'b> {
+	pub_fn_ptr_get_set!('a, lpLogFont, set_lpLogFont, LOGFONT);
+	pub_fn_resource_id_get_set!(lpTemplateName, set_lpTemplateName);
+	pub_fn_string_ptr_get_set!('b, lpszStyle, set_lpszStyle);
+}
+
+/// [`DOCINFO`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-docinfow)
+/// struct.
+#[repr(C)]
+pub struct DOCINFO<'a, 'b, 'c> {
+	cbSize: i32,
+	lpszDocName: *mut u16,
+	lpszOutput: *mut u16,
+	lpszDatatype: *mut u16,
+	pub fwType: co::DI,
+
+	_lpszDocName: PhantomData<&'a mut u16>,
+	_lpszOutput: PhantomData<&'b mut u16>,
+	_lpszDatatype: PhantomData<&'c mut u16>,
+}
+
+impl_default_with_size!(DOCINFO, cbSize, 'a, 'b, 'c);
+
+impl<'a, 'b, 'c> DOCINFO<'a, 'b, 'c> {
+	pub_fn_string_ptr_get_set!('a, lpszDocName, set_lpszDocName);
+	pub_fn_string_ptr_get_set!('b, lpszOutput, set_lpszOutput);
+	pub_fn_string_ptr_get_set!('c, lpszDatatype, set_lpszDatatype);
+}
+
 /// [`LOGBRUSH`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-logbrush)
 /// struct.
 #[repr(C)]
@@ -246,6 +304,52 @@ pub struct PALETTEENTRY {
 	pub peFlags: co::PC,
 }
 
+/// [`PRINTER_INFO_2`](https://learn.microsoft.com/en-us/windows/win32/printdocs/printer-info-2)
+/// struct.
+#[repr(C)]
+pub struct PRINTER_INFO_2<'a> {
+	pServerName: *mut u16,
+	pPrinterName: *mut u16,
+	pShareName: *mut u16,
+	pPortName: *mut u16,
+	pDriverName: *mut u16,
+	pComment: *mut u16,
+	pLocation: *mut u16,
+	pDevMode: *mut DEVMODE,
+	pSepFile: *mut u16,
+	pPrintProcessor: *mut u16,
+	pDatatype: *mut u16,
+	pParameters: *mut u16,
+	pSecurityDescriptor: *mut std::ffi::c_void,
+	pub Attributes: co::PRINTER_ATTRIBUTE,
+	pub Priority: u32,
+	pub DefaultPriority: u32,
+	pub StartTime: u32,
+	pub UntilTime: u32,
+	pub Status: co::PRINTER_STATUS,
+	pub cJobs: u32,
+	pub AveragePPM: u32,
+
+	_strs: PhantomData<&'a mut u16>,
+}
+
+impl_default!(PRINTER_INFO_2, 'a);
+
+impl<'a> PRINTER_INFO_2<'a> {
+	pub_fn_string_ptr_get_set!('a, pServerName, set_pServerName);
+	pub_fn_string_ptr_get_set!('a, pPrinterName, set_pPrinterName);
+	pub_fn_string_ptr_get_set!('a, pShareName, set_pShareName);
+	pub_fn_string_ptr_get_set!('a, pPortName, set_pPortName);
+	pub_fn_string_ptr_get_set!('a, pDriverName, set_pDriverName);
+	pub_fn_string_ptr_get_set!('a, pComment, set_pComment);
+	pub_fn_string_ptr_get_set!('a, pLocation, set_pLocation);
+	pub_fn_ptr_get_set!('a, pDevMode, set_pDevMode, DEVMODE);
+	pub_fn_string_ptr_get_set!('a, pSepFile, set_pSepFile);
+	pub_fn_string_ptr_get_set!('a, pPrintProcessor, set_pPrintProcessor);
+	pub_fn_string_ptr_get_set!('a, pDatatype, set_pDatatype);
+	pub_fn_string_ptr_get_set!('a, pParameters, set_pParameters);
+}
+
 /// [`RGBQUAD`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-rgbquad)
 /// struct.
 #[repr(C)]
@@ -0,0 +1,5 @@
+mod page_metrics;
+mod printer;
+
+pub use page_metrics::PageMetrics;
+pub use printer::{print_document, PrinterBin, PrinterCaps, PrinterPaper};
@@ -0,0 +1,343 @@
+use std::ops::Range;
+
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// Dimensions of a printed page, in device pixels, used to lay out content
+/// independently of the device resolution.
+///
+/// A printer device context has its origin at the top-left corner of the
+/// printable area, not of the paper, so
+/// [`printable_offset`](crate::PageMetrics::printable_offset) must be taken
+/// into account when positioning content relative to the paper edges.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct PageMetrics {
+	/// Horizontal and vertical resolution, in pixels per inch.
+	pub dpi: SIZE,
+	/// Size of the whole paper.
+	pub paper: SIZE,
+	/// Distance from the paper top-left corner to the printable area.
+	pub printable_offset: POINT,
+	/// Size of the printable area.
+	pub printable: SIZE,
+}
+
+impl PageMetrics {
+	/// Retrieves the metrics of a device context with
+	/// [`HDC::GetDeviceCaps`](crate::prelude::gdi_Hdc::GetDeviceCaps).
+	///
+	/// Screen device contexts have no physical paper, so the whole screen is
+	/// reported as printable.
+	#[must_use]
+	pub fn from_hdc(hdc: &impl gdi_Hdc) -> Self {
+		let dpi = SIZE::new(
+			hdc.GetDeviceCaps(co::GDC::LOGPIXELSX),
+			hdc.GetDeviceCaps(co::GDC::LOGPIXELSY),
+		);
+		let printable = SIZE::new(
+			hdc.GetDeviceCaps(co::GDC::HORZRES),
+			hdc.GetDeviceCaps(co::GDC::VERTRES),
+		);
+		let paper = SIZE::new(
+			hdc.GetDeviceCaps(co::GDC::PHYSICALWIDTH),
+			hdc.GetDeviceCaps(co::GDC::PHYSICALHEIGHT),
+		);
+		let printable_offset = POINT::new(
+			hdc.GetDeviceCaps(co::GDC::PHYSICALOFFSETX),
+			hdc.GetDeviceCaps(co::GDC::PHYSICALOFFSETY),
+		);
+
+		if paper.cx == 0 || paper.cy == 0 { // not a printer
+			Self { dpi, paper: printable, printable_offset: POINT::default(), printable }
+		} else {
+			Self { dpi, paper, printable_offset, printable }
+		}
+	}
+
+	/// Builds the metrics of a paper given its size in thousandths of inches,
+	/// and a resolution in pixels per inch. The whole paper is printable.
+	///
+	/// Useful to lay out pages when no printer is available.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let letter = w::PageMetrics::from_paper(w::SIZE::new(8500, 11000), 600);
+	/// println!("{} x {} pixels", letter.paper.cx, letter.paper.cy);
+	/// ```
+	#[must_use]
+	pub fn from_paper(size: SIZE, dpi: i32) -> Self {
+		let paper = SIZE::new(
+			mul_div(size.cx, dpi, 1000),
+			mul_div(size.cy, dpi, 1000),
+		);
+		Self {
+			dpi: SIZE::new(dpi, dpi),
+			paper,
+			printable_offset: POINT::default(),
+			printable: paper,
+		}
+	}
+
+	/// Converts a horizontal distance in thousandths of inches to pixels.
+	#[must_use]
+	pub const fn to_pixels_x(&self, thousandths: i32) -> i32 {
+		mul_div(thousandths, self.dpi.cx, 1000)
+	}
+
+	/// Converts a vertical distance in thousandths of inches to pixels.
+	#[must_use]
+	pub const fn to_pixels_y(&self, thousandths: i32) -> i32 {
+		mul_div(thousandths, self.dpi.cy, 1000)
+	}
+
+	/// Converts a horizontal distance in pixels to thousandths of inches.
+	#[must_use]
+	pub const fn to_thousandths_x(&self, pixels: i32) -> i32 {
+		mul_div(pixels, 1000, self.dpi.cx)
+	}
+
+	/// Converts a vertical distance in pixels to thousandths of inches.
+	#[must_use]
+	pub const fn to_thousandths_y(&self, pixels: i32) -> i32 {
+		mul_div(pixels, 1000, self.dpi.cy)
+	}
+
+	/// Returns the rectangle available for content, given the margins in
+	/// thousandths of inches measured from the paper edges.
+	///
+	/// The rectangle is in device coordinates, whose origin is the top-left
+	/// corner of the printable area, and it's clamped to the printable area,
+	/// since the printer can't reach beyond it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let metrics = w::PageMetrics::from_paper(w::SIZE::new(8500, 11000), 100);
+	/// let margins = w::RECT { left: 1000, top: 1000, right: 1000, bottom: 1000 };
+	///
+	/// let rc = metrics.content_rect(margins);
+	/// println!("{} x {} pixels", rc.right - rc.left, rc.bottom - rc.top);
+	/// ```
+	#[must_use]
+	pub fn content_rect(&self, margins: RECT) -> RECT {
+		let off = self.printable_offset;
+		let clamp_x = |x: i32| x.clamp(0, self.printable.cx);
+		let clamp_y = |y: i32| y.clamp(0, self.printable.cy);
+
+		let left = clamp_x(self.to_pixels_x(margins.left) - off.x);
+		let top = clamp_y(self.to_pixels_y(margins.top) - off.y);
+		let right = clamp_x(self.paper.cx - self.to_pixels_x(margins.right) - off.x);
+		let bottom = clamp_y(self.paper.cy - self.to_pixels_y(margins.bottom) - off.y);
+
+		RECT {
+			left,
+			top,
+			right: right.max(left),
+			bottom: bottom.max(top),
+		}
+	}
+
+	/// Returns the rectangle where the paper must be drawn to fit inside
+	/// `area`, keeping its aspect ratio and centered.
+	///
+	/// Used to scale a page to a screen preview.
+	#[must_use]
+	pub fn fit_rect(&self, area: RECT) -> RECT {
+		let (area_cx, area_cy) = (area.right - area.left, area.bottom - area.top);
+		if self.paper.cx <= 0 || self.paper.cy <= 0 || area_cx <= 0 || area_cy <= 0 {
+			return RECT { left: area.left, top: area.top, right: area.left, bottom: area.top };
+		}
+
+		let (cx, cy) = if area_cx as i64 * self.paper.cy as i64
+			<= area_cy as i64 * self.paper.cx as i64
+		{
+			(area_cx, mul_div(area_cx, self.paper.cy, self.paper.cx)) // width-bound
+		} else {
+			(mul_div(area_cy, self.paper.cx, self.paper.cy), area_cy) // height-bound
+		};
+
+		let left = area.left + (area_cx - cx) / 2;
+		let top = area.top + (area_cy - cy) / 2;
+		RECT { left, top, right: left + cx, bottom: top + cy }
+	}
+
+	/// Splits a sequence of items with the given heights into pages which fit
+	/// `page_height`, returning the index range of the items of each page.
+	///
+	/// An item taller than a page gets a page of its own. An empty sequence
+	/// returns no pages.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let line_heights = [40, 40, 40, 150, 10];
+	///
+	/// for (num, page) in w::PageMetrics::paginate(&line_heights, 100).iter().enumerate() {
+	///     println!("Page {}: lines {} to {}", num + 1, page.start, page.end - 1);
+	/// }
+	/// ```
+	#[must_use]
+	pub fn paginate(item_heights: &[i32], page_height: i32) -> Vec<Range<usize>> {
+		let mut pages = Vec::<Range<usize>>::default();
+		let mut start = 0;
+		let mut used = 0i32;
+
+		for (idx, height) in item_heights.iter().enumerate() {
+			if idx > start && used.saturating_add(*height) > page_height {
+				pages.push(start..idx);
+				start = idx;
+				used = 0;
+			}
+			used = used.saturating_add(*height);
+		}
+
+		if start < item_heights.len() {
+			pages.push(start..item_heights.len());
+		}
+		pages
+	}
+}
+
+/// Computes `num * mul / div` with 64-bit intermediate precision, rounding
+/// half away from zero. Returns zero if `div` is zero.
+#[must_use]
+const fn mul_div(num: i32, mul: i32, div: i32) -> i32 {
+	if div == 0 {
+		return 0;
+	}
+	let prod = num as i64 * mul as i64;
+	let half = (div as i64).abs() / 2;
+	let res = if (prod < 0) == (div < 0) {
+		(prod.abs() + half) / (div as i64).abs()
+	} else {
+		-((prod.abs() + half) / (div as i64).abs())
+	};
+	res as _
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn from_paper() {
+		let letter = PageMetrics::from_paper(SIZE::new(8500, 11000), 600);
+		assert!(letter.dpi == SIZE::new(600, 600));
+		assert!(letter.paper == SIZE::new(5100, 6600));
+		assert!(letter.printable == letter.paper);
+		assert!(letter.printable_offset == POINT::default());
+	}
+
+	#[test]
+	fn conversions() {
+		let metrics = PageMetrics::from_paper(SIZE::new(8500, 11000), 300);
+		assert_eq!(metrics.to_pixels_x(1000), 300);
+		assert_eq!(metrics.to_pixels_y(-500), -150);
+		assert_eq!(metrics.to_thousandths_x(150), 500);
+		assert_eq!(metrics.to_thousandths_y(1), 3); // 3.33 rounded
+		assert_eq!(PageMetrics::default().to_thousandths_x(100), 0); // zero DPI
+	}
+
+	#[test]
+	fn content_rect() {
+		let metrics = PageMetrics::from_paper(SIZE::new(8500, 11000), 100);
+		let margins = RECT { left: 1000, top: 1000, right: 1000, bottom: 1000 };
+		assert!(metrics.content_rect(margins) == RECT { left: 100, top: 100, right: 750, bottom: 1000 });
+	}
+
+	#[test]
+	fn content_rect_printable_offset() {
+		let metrics = PageMetrics {
+			dpi: SIZE::new(100, 100),
+			paper: SIZE::new(850, 1100),
+			printable_offset: POINT::new(25, 25),
+			printable: SIZE::new(800, 1050),
+		};
+		let margins = RECT { left: 1000, top: 0, right: 0, bottom: 1000 };
+		assert!(metrics.content_rect(margins) == RECT { left: 75, top: 0, right: 800, bottom: 975 });
+
+		let huge = RECT { left: 6000, top: 6000, right: 6000, bottom: 6000 };
+		let rc = metrics.content_rect(huge);
+		assert!(rc.right == rc.left && rc.bottom == rc.top); // empty, never inverted
+	}
+
+	#[test]
+	fn fit_rect_width_bound() {
+		let metrics = PageMetrics::from_paper(SIZE::new(8500, 11000), 100);
+		let area = RECT { left: 10, top: 20, right: 95, bottom: 520 };
+		assert!(metrics.fit_rect(area) == RECT { left: 10, top: 215, right: 95, bottom: 325 });
+	}
+
+	#[test]
+	fn fit_rect_height_bound() {
+		let metrics = PageMetrics::from_paper(SIZE::new(8500, 11000), 100);
+		let area = RECT { left: 0, top: 0, right: 1000, bottom: 550 };
+		assert!(metrics.fit_rect(area) == RECT { left: 287, top: 0, right: 712, bottom: 550 });
+	}
+
+	#[test]
+	fn fit_rect_empty() {
+		let metrics = PageMetrics::from_paper(SIZE::new(8500, 11000), 100);
+		let area = RECT { left: 10, top: 20, right: 10, bottom: 500 };
+		assert!(metrics.fit_rect(area) == RECT { left: 10, top: 20, right: 10, bottom: 20 });
+
+		let inverted = RECT { left: 10, top: 20, right: 100, bottom: 0 };
+		assert!(metrics.fit_rect(inverted) == RECT { left: 10, top: 20, right: 10, bottom: 20 });
+
+		let no_paper = PageMetrics::default();
+		let area = RECT { left: 0, top: 0, right: 100, bottom: 100 };
+		assert!(no_paper.fit_rect(area) == RECT::default());
+	}
+
+	#[test]
+	fn paginate() {
+		let pages = PageMetrics::paginate(&[40, 40, 40, 150, 10], 100);
+		assert_eq!(pages, [0..2, 2..3, 3..4, 4..5]);
+
+		let pages = PageMetrics::paginate(&[50, 50, 50, 50], 100);
+		assert_eq!(pages, [0..2, 2..4]); // exact fit
+	}
+
+	#[test]
+	fn paginate_empty() {
+		assert!(PageMetrics::paginate(&[], 100).is_empty());
+	}
+
+	#[test]
+	fn paginate_taller_than_page() {
+		let pages = PageMetrics::paginate(&[300], 100);
+		assert_eq!(pages.len(), 1);
+		assert_eq!(pages[0], 0..1);
+
+		let pages = PageMetrics::paginate(&[10, 300, 10], 100);
+		assert_eq!(pages, [0..1, 1..2, 2..3]);
+	}
+
+	#[test]
+	fn paginate_zero_or_negative_page() {
+		assert_eq!(PageMetrics::paginate(&[10, 20, 30], 0), [0..1, 1..2, 2..3]);
+		assert_eq!(PageMetrics::paginate(&[10, 20, 30], -50), [0..1, 1..2, 2..3]);
+		assert_eq!(PageMetrics::paginate(&[0, 0, 10], 0), [0..2, 2..3]); // zero-height items fit
+	}
+
+	#[test]
+	fn paginate_huge_heights() {
+		let pages = PageMetrics::paginate(&[i32::MAX, i32::MAX, 10], 1000);
+		assert_eq!(pages, [0..1, 1..2, 2..3]);
+
+		let pages = PageMetrics::paginate(&[500, i32::MAX - 100, 10], i32::MAX - 1);
+		assert_eq!(pages, [0..1, 1..3]);
+
+		let pages = PageMetrics::paginate(&[i32::MIN, i32::MIN, 10], 100);
+		assert_eq!(pages.len(), 1);
+		assert_eq!(pages[0], 0..3);
+	}
+}
@@ -0,0 +1,228 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// A paper size supported by a printer, as reported by
+/// [`PrinterCaps::query`](crate::PrinterCaps::query).
+#[derive(Clone)]
+pub struct PrinterPaper {
+	/// Paper identifier, to be used in [`DEVMODE`](crate::DEVMODE).
+	pub id: co::DMPAPER,
+	/// Paper name, as displayed by the printer driver.
+	pub name: String,
+	/// Paper size, in tenths of millimeters.
+	pub size: SIZE,
+}
+
+/// A paper bin supported by a printer, as reported by
+/// [`PrinterCaps::query`](crate::PrinterCaps::query).
+#[derive(Clone)]
+pub struct PrinterBin {
+	/// Bin identifier, to be used in [`DEVMODE`](crate::DEVMODE).
+	pub id: co::DMBIN,
+	/// Bin name, as displayed by the printer driver.
+	pub name: String,
+}
+
+/// Capabilities of a printer, retrieved with
+/// [`DeviceCapabilities`](crate::DeviceCapabilities).
+#[derive(Clone)]
+pub struct PrinterCaps {
+	/// Supported paper sizes.
+	pub papers: Vec<PrinterPaper>,
+	/// Available paper bins.
+	pub bins: Vec<PrinterBin>,
+	/// Supported resolutions, in dots per inch, horizontal and vertical.
+	pub resolutions: Vec<SIZE>,
+	/// Maximum number of copies the printer can make.
+	pub max_copies: u32,
+	/// Whether the printer can collate copies.
+	pub collate: bool,
+	/// Whether the printer can print on both sides of the paper.
+	pub duplex: bool,
+	/// Whether the printer supports color.
+	pub color: bool,
+	/// Rotation, in degrees, of portrait to produce landscape; zero if the
+	/// printer doesn't support landscape.
+	pub landscape_degrees: i32,
+}
+
+impl PrinterCaps {
+	/// Queries the capabilities of the given printer, with its default
+	/// settings.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let printer = w::GetDefaultPrinter()?;
+	/// let caps = w::PrinterCaps::query(&printer, None)?;
+	///
+	/// for paper in caps.papers.iter() {
+	///     println!("{}: {}x{} mm",
+	///         paper.name, paper.size.cx / 10, paper.size.cy / 10);
+	/// }
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	pub fn query(device: &str, port: Option<&str>) -> SysResult<Self> {
+		let papers = {
+			let ids = query_array::<u16>(device, port, co::DC_CAP::PAPERS)?;
+			let names = query_names(device, port, co::DC_CAP::PAPERNAMES, 64)?;
+			let sizes = query_array::<POINT>(device, port, co::DC_CAP::PAPERSIZE)?;
+			ids.iter()
+				.zip(names)
+				.zip(sizes.iter())
+				.map(|((id, name), sz)| PrinterPaper {
+					id: unsafe { co::DMPAPER::from_raw(*id as _) },
+					name,
+					size: SIZE::new(sz.x, sz.y),
+				})
+				.collect()
+		};
+
+		let bins = {
+			let ids = query_array::<u16>(device, port, co::DC_CAP::BINS)?;
+			let names = query_names(device, port, co::DC_CAP::BINNAMES, 24)?;
+			ids.iter()
+				.zip(names)
+				.map(|(id, name)| PrinterBin {
+					id: unsafe { co::DMBIN::from_raw(*id as _) },
+					name,
+				})
+				.collect()
+		};
+
+		let resolutions = query_array::<[i32; 2]>(device, port, co::DC_CAP::ENUMRESOLUTIONS)?
+			.iter()
+			.map(|res| SIZE::new(res[0], res[1]))
+			.collect();
+
+		let num = |cap: co::DC_CAP| -> i32 {
+			unsafe { DeviceCapabilities(device, port, cap, None, None) }
+				.unwrap_or_default() // not all drivers support all capabilities
+		};
+
+		Ok(Self {
+			papers,
+			bins,
+			resolutions,
+			max_copies: num(co::DC_CAP::COPIES).max(1) as _,
+			collate: num(co::DC_CAP::COLLATE) == 1,
+			duplex: num(co::DC_CAP::DUPLEX) == 1,
+			color: num(co::DC_CAP::COLORDEVICE) == 1,
+			landscape_degrees: num(co::DC_CAP::ORIENTATION),
+		})
+	}
+}
+
+/// Prints a document with the given pages, wrapping the
+/// [`HDC::StartDoc`](crate::prelude::gdi_Hdc::StartDoc) and
+/// [`HDC::StartPage`](crate::prelude::gdi_Hdc::StartPage) calls.
+///
+/// The closure is called once for each page, receiving the printer device
+/// context, the page number and the page metrics. If it returns an error, the
+/// print job is aborted with
+/// [`HDC::AbortDoc`](crate::prelude::gdi_Hdc::AbortDoc), and the error is
+/// returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let hdc_printer: w::HDC; // initialized somewhere
+/// # let hdc_printer = w::HDC::NULL;
+///
+/// let margins = w::RECT { left: 1000, top: 1000, right: 1000, bottom: 1000 };
+///
+/// w::print_document(&hdc_printer, "Report", 1..=3, |hdc, page, metrics| {
+///     let rc = metrics.content_rect(margins);
+///     hdc.TextOut(rc.left, rc.top, &format!("Page {}", page))?;
+///     Ok(())
+/// })?;
+/// # Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+pub fn print_document<H, F>(
+	hdc: &H,
+	doc_name: &str,
+	pages: impl IntoIterator<Item = u32>,
+	mut draw_page: F,
+) -> AnyResult<()>
+	where H: gdi_Hdc,
+		F: FnMut(&H, u32, &PageMetrics) -> AnyResult<()>,
+{
+	let metrics = PageMetrics::from_hdc(hdc);
+	let mut wdoc_name = WString::from_str(doc_name);
+	let mut di = DOCINFO::default();
+	di.set_lpszDocName(Some(&mut wdoc_name));
+
+	let doc = hdc.StartDoc(&di)?;
+	for page in pages {
+		let res = hdc.StartPage()
+			.map_err(|e| e.into())
+			.and_then(|pg| {
+				draw_page(hdc, page, &metrics)?;
+				pg.end().map_err(|e| e.into())
+			});
+
+		if let Err(e) = res {
+			doc.abort().ok(); // the original error matters more
+			return Err(e);
+		}
+	}
+	doc.end().map_err(|e| e.into())
+}
+
+/// Queries an array capability, whose elements are of type `T`.
+fn query_array<T>(
+	device: &str,
+	port: Option<&str>,
+	cap: co::DC_CAP,
+) -> SysResult<Vec<T>>
+	where T: Default + Clone,
+{
+	let count = unsafe { DeviceCapabilities(device, port, cap, None, None) }?;
+	if count <= 0 {
+		return Ok(Vec::default());
+	}
+
+	let mut buf = vec![T::default(); count as _];
+	let bytes = unsafe {
+		std::slice::from_raw_parts_mut(
+			buf.as_mut_ptr() as *mut u8,
+			buf.len() * std::mem::size_of::<T>(),
+		)
+	};
+	unsafe { DeviceCapabilities(device, port, cap, Some(bytes), None) }?;
+	Ok(buf)
+}
+
+/// Queries a capability which returns an array of fixed-length strings.
+fn query_names(
+	device: &str,
+	port: Option<&str>,
+	cap: co::DC_CAP,
+	chars_each: usize,
+) -> SysResult<Vec<String>>
+{
+	let count = unsafe { DeviceCapabilities(device, port, cap, None, None) }?;
+	if count <= 0 {
+		return Ok(Vec::default());
+	}
+
+	let mut buf = vec![0u16; count as usize * chars_each];
+	let bytes = unsafe {
+		std::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, buf.len() * 2)
+	};
+	unsafe { DeviceCapabilities(device, port, cap, Some(bytes), None) }?;
+
+	Ok(
+		buf.chunks_exact(chars_each)
+			.map(|chars| {
+				let len = chars.iter().position(|ch| *ch == 0).unwrap_or(chars.len()); // not null-terminated if full
+				String::from_utf16_lossy(&chars[..len])
+			})
+			.collect(),
+	)
+}
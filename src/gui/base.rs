@@ -1,3 +1,4 @@
+use std::future::Future;
 use std::ptr::NonNull;
 
 use crate::co;
@@ -25,9 +26,10 @@ pub(in crate::gui) struct Base {
 
 impl Base {
 	const WM_UI_THREAD: co::WM = unsafe { co::WM::from_raw(co::WM::APP.raw() + 0x3fff) };
+	pub(in crate::gui) const WM_UI_FUTURE: co::WM = unsafe { co::WM::from_raw(co::WM::APP.raw() + 0x3ffe) };
 
 	pub(in crate::gui) unsafe fn from_guiparent<'a>(
-		p: &impl GuiParent,
+		p: &(impl GuiParent + ?Sized),
 	) -> &'a Self
 	{
 		let ptr = p.as_base() as *mut Self;
@@ -104,10 +106,11 @@ impl Base {
 
 	/// If the library added a closure to the given message, run it.
 	///
-	/// Returns `true` if at least one message was processed.
+	/// A meaningful return value is only kept for `WM_NOTIFY`, so the library
+	/// can answer notifications without taking the user events.
 	pub(in crate::gui) fn process_privileged_messages(&self,
 		wm_any: WndMsg,
-	) -> AnyResult<bool>
+	) -> AnyResult<ProcessResult>
 	{
 		self.privileged_events.process_all_messages(wm_any)
 	}
@@ -174,6 +177,55 @@ impl Base {
 			});
 	}
 
+	pub(in crate::gui) fn spawn_local<F>(&self, fut: F)
+		where F: Future<Output = AnyResult<()>> + 'static,
+	{
+		if self.hwnd == HWND::NULL {
+			panic!("Cannot spawn a local task before window creation.");
+		}
+
+		// Wake up messages go straight to the main window, like WM_UI_THREAD.
+		let hwnd = self.hwnd.GetAncestor(co::GA::ROOTOWNER)
+			.unwrap_or(unsafe { self.hwnd.raw_copy() });
+		spawn_local_task(hwnd, fut);
+	}
+
+	pub(in crate::gui) fn spawn_new_thread_async<F, T>(&self, func: F) -> AsyncResult<T>
+		where F: FnOnce() -> AnyResult<T> + Send + 'static,
+			T: Send + 'static,
+	{
+		let (sender, fut) = AsyncResult::new_pair();
+		std::thread::spawn(move || sender.send(func()));
+		fut
+	}
+
+	pub(in crate::gui) fn run_ui_thread_async<F, T>(&self, func: F) -> AsyncResult<T>
+		where F: FnOnce() -> AnyResult<T> + Send + 'static,
+			T: Send + 'static,
+	{
+		// Unlike run_ui_thread, the message is posted, so the calling thread is
+		// not blocked; the result is delivered through the future.
+		let (sender, fut) = AsyncResult::new_pair();
+		let pack = Box::new(ThreadPack {
+			func: Box::new(move || {
+				sender.send(func());
+				Ok(())
+			}),
+		});
+		let ptr_pack = Box::into_raw(pack);
+
+		let posted = self.hwnd.GetAncestor(co::GA::ROOTOWNER)
+			.is_some_and(|hwnd| hwnd.PostMessage(WndMsg {
+				msg_id: Self::WM_UI_THREAD,
+				wparam: Self::WM_UI_THREAD.raw() as _,
+				lparam: ptr_pack as _, // send pointer
+			}).is_ok());
+		if !posted {
+			let _ = unsafe { Box::from_raw(ptr_pack) }; // the dropped sender resolves the future to an error
+		}
+		fut
+	}
+
 	fn default_message_handlers(&self) {
 		// We cant pass a pointer to Self because at this moment the parent
 		// struct isn't created and pinned yet, so we make LayoutArranger
@@ -193,6 +245,14 @@ impl Base {
 			}
 			Ok(None) // not meaningful
 		});
+
+		self.privileged_events.wm(Self::WM_UI_FUTURE, |p| {
+			if unsafe { co::WM::from_raw(p.wparam as _) } == Self::WM_UI_FUTURE { // additional safety check
+				poll_local_task(p.lparam as _) // task ID
+					.unwrap_or_else(|err| post_quit_error(p, err));
+			}
+			Ok(None) // not meaningful
+		});
 	}
 
 	pub(in crate::gui) fn run_main_loop(
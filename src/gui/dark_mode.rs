@@ -0,0 +1,229 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::msg::*;
+use crate::prelude::*;
+
+/// Color scheme of the `gui` windows, set with
+/// [`gui::set_dark_mode`](crate::gui::set_dark_mode).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DarkMode {
+	/// Ordinary light colors. This is the default.
+	Light,
+	/// Dark colors.
+	Dark,
+	/// Follows the "app mode" chosen by the user in the Windows settings, and
+	/// switches live when it changes.
+	System,
+}
+
+impl DarkMode {
+	const fn from_u8(v: u8) -> Self {
+		match v {
+			1 => Self::Dark,
+			2 => Self::System,
+			_ => Self::Light,
+		}
+	}
+}
+
+static DARK_MODE: AtomicU8 = AtomicU8::new(DarkMode::Light as _);
+static DARK_ACTIVE: AtomicBool = AtomicBool::new(false);
+static DARK_BRUSHES: Mutex<Option<[DeleteObjectGuard<HBRUSH>; 2]>> = Mutex::new(None); // window and control backgrounds
+
+const CLR_WND_BG: COLORREF = COLORREF::new(0x20, 0x20, 0x20);
+const CLR_CTRL_BG: COLORREF = COLORREF::new(0x2d, 0x2d, 0x2d);
+const CLR_TEXT: COLORREF = COLORREF::new(0xf0, 0xf0, 0xf0);
+
+/// Sets the color scheme for all `gui` windows of the application.
+///
+/// When dark, title bars are darkened with
+/// [`HWND::DwmSetWindowAttribute`](crate::prelude::dwm_Hwnd::DwmSetWindowAttribute),
+/// the dark Explorer theme is applied with
+/// [`HWND::SetWindowTheme`](crate::prelude::uxtheme_Hwnd::SetWindowTheme) to
+/// list views, tree views, buttons, edits and scroll bars, and `WM_CTLCOLOR*`
+/// messages not handled by the user are answered with dark colors.
+///
+/// Can be called before or after the windows are created; existing windows of
+/// the current thread are updated immediately.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, gui};
+///
+/// gui::set_dark_mode(gui::DarkMode::System);
+///
+/// let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+/// wnd.run_main(None)?;
+/// # w::AnyResult::Ok(())
+/// ```
+pub fn set_dark_mode(mode: DarkMode) {
+	DARK_MODE.store(mode as _, Ordering::Relaxed);
+	if update_dark_active() {
+		refresh_thread_windows();
+	}
+}
+
+/// Tells whether the dark colors are currently being used, according to
+/// [`gui::set_dark_mode`](crate::gui::set_dark_mode).
+#[must_use]
+pub fn is_dark_mode() -> bool {
+	DARK_ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Recomputes whether dark colors are active. Returns `true` if it changed.
+fn update_dark_active() -> bool {
+	let active = match dark_mode() {
+		DarkMode::Light => false,
+		DarkMode::Dark => true,
+		DarkMode::System => system_uses_dark(),
+	};
+	DARK_ACTIVE.swap(active, Ordering::Relaxed) != active
+}
+
+fn dark_mode() -> DarkMode {
+	DarkMode::from_u8(DARK_MODE.load(Ordering::Relaxed))
+}
+
+/// Reads the user "app mode" from the registry.
+fn system_uses_dark() -> bool {
+	HKEY::CURRENT_USER.RegGetValue(
+		Some("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+		Some("AppsUseLightTheme"),
+	).is_ok_and(|val| matches!(val, RegistryValue::Dword(0)))
+}
+
+/// Applies the current color scheme to all top-level windows of the current
+/// thread, and their children.
+fn refresh_thread_windows() {
+	EnumThreadWindows(GetCurrentThreadId(), |hwnd| {
+		apply_dark_mode(&hwnd);
+		true
+	}).unwrap_or_default();
+}
+
+/// Called after a window and its children are created. In light mode nothing
+/// is done, so the default themes are left untouched.
+pub(in crate::gui) fn dark_mode_window_created(hwnd: &HWND) {
+	if is_dark_mode() && !is_child(hwnd) { // top-level windows enumerate all their children
+		apply_dark_mode(hwnd);
+	}
+}
+
+fn is_child(hwnd: &HWND) -> bool {
+	let style = unsafe { co::WS::from_raw(hwnd.GetWindowLongPtr(co::GWLP::STYLE) as _) };
+	style.has(co::WS::CHILD)
+}
+
+/// Applies the current color scheme to the window and its children.
+fn apply_dark_mode(hwnd: &HWND) {
+	let dark = is_dark_mode();
+	if !is_child(hwnd) {
+		hwnd.DwmSetWindowAttribute(DwmAttr::UseImmersiveDarkMode(dark))
+			.unwrap_or_default(); // not supported before Windows 10 build 18985
+		hwnd.SetWindowPos(HwndPlace::None, POINT::default(), SIZE::default(),
+			co::SWP::FRAMECHANGED | co::SWP::NOMOVE | co::SWP::NOSIZE
+				| co::SWP::NOZORDER | co::SWP::NOACTIVATE)
+			.unwrap_or_default(); // repaint the title bar
+	}
+
+	theme_control(hwnd, dark);
+	hwnd.EnumChildWindows(|hchild| {
+		theme_control(&hchild, dark);
+		true
+	});
+}
+
+/// Applies the theme to a native control, according to its class.
+fn theme_control(hwnd: &HWND, dark: bool) {
+	let theme = |dark_name: &str| {
+		hwnd.SetWindowTheme(if dark { Some(dark_name) } else { None }, None)
+			.unwrap_or_default();
+	};
+
+	match hwnd.GetClassName().unwrap_or_default().as_str() {
+		"SysListView32" => {
+			theme("DarkMode_Explorer");
+			let (bg, text) = if dark {
+				(CLR_WND_BG, CLR_TEXT)
+			} else {
+				(GetSysColor(co::COLOR::WINDOW), GetSysColor(co::COLOR::WINDOWTEXT))
+			};
+			hwnd.SendMessage(lvm::SetBkColor { color: Some(bg) }).unwrap_or_default();
+			hwnd.SendMessage(lvm::SetTextBkColor { color: Some(bg) }).unwrap_or_default();
+			hwnd.SendMessage(lvm::SetTextColor { color: Some(text) }).unwrap_or_default();
+		},
+		"SysTreeView32" => {
+			theme("DarkMode_Explorer");
+			hwnd.SendMessage(tvm::SetBkColor { color: dark.then_some(CLR_WND_BG) });
+			hwnd.SendMessage(tvm::SetTextColor { color: dark.then_some(CLR_TEXT) });
+		},
+		"SysHeader32" => theme("DarkMode_ItemsView"),
+		"ComboBox" => theme("DarkMode_CFD"),
+		"Button" | "Edit" | "ListBox" | "ScrollBar" => theme("DarkMode_Explorer"),
+		_ => {},
+	}
+	hwnd.InvalidateRect(None, true).unwrap_or_default();
+}
+
+/// Answers the messages which paint backgrounds, if in dark mode and the user
+/// didn't handle them.
+pub(in crate::gui) fn dark_mode_default_proc(hwnd: &HWND, wm_any: WndMsg) -> Option<isize> {
+	if !is_dark_mode() {
+		return None;
+	}
+
+	let [hbr_wnd, hbr_ctrl] = dark_brushes();
+	let ctl_color = |hbr: &HBRUSH, bg: COLORREF| {
+		let hdc = unsafe { HDC::from_ptr(wm_any.wparam as _) };
+		hdc.SetTextColor(CLR_TEXT).unwrap_or_default();
+		hdc.SetBkColor(bg).unwrap_or_default();
+		Some(hbr.ptr() as _)
+	};
+
+	match wm_any.msg_id {
+		co::WM::CTLCOLORBTN
+			| co::WM::CTLCOLORDLG
+			| co::WM::CTLCOLORSTATIC => ctl_color(&hbr_wnd, CLR_WND_BG),
+		co::WM::CTLCOLOREDIT
+			| co::WM::CTLCOLORLISTBOX => ctl_color(&hbr_ctrl, CLR_CTRL_BG),
+		co::WM::ERASEBKGND => {
+			let hdc = unsafe { HDC::from_ptr(wm_any.wparam as _) };
+			let rc = hwnd.GetClientRect().ok()?;
+			hdc.FillRect(rc, &hbr_wnd).ok()?;
+			Some(1) // background erased
+		},
+		_ => None,
+	}
+}
+
+/// Handles `WM_SETTINGCHANGE`, following the system color scheme.
+pub(in crate::gui) fn dark_mode_setting_change(wm_any: WndMsg) {
+	if dark_mode() != DarkMode::System || wm_any.lparam == 0 {
+		return;
+	}
+	let area = unsafe { WString::from_wchars_nullt(wm_any.lparam as _) };
+	if area.to_string() == "ImmersiveColorSet" && update_dark_active() {
+		refresh_thread_windows(); // each top-level window receives the message, but only the first will see a change
+	}
+}
+
+/// Retrieves the dark background brushes, creating them if needed. The
+/// returned handles are valid until `delete_dark_brushes` is called.
+fn dark_brushes() -> [HBRUSH; 2] {
+	let mut brushes = DARK_BRUSHES.lock().unwrap();
+	let [hbr_wnd, hbr_ctrl] = brushes.get_or_insert_with(|| [
+		HBRUSH::CreateSolidBrush(CLR_WND_BG).unwrap(),
+		HBRUSH::CreateSolidBrush(CLR_CTRL_BG).unwrap(),
+	]);
+	unsafe { [hbr_wnd.raw_copy(), hbr_ctrl.raw_copy()] }
+}
+
+/// Frees the dark background brushes, if created.
+pub(in crate::gui) fn delete_dark_brushes() {
+	*DARK_BRUSHES.lock().unwrap() = None;
+}
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use crate::co;
 use crate::decl::*;
 use crate::gui::{events::*, privs::*};
@@ -10,6 +12,7 @@ use crate::prelude::*;
 pub(in crate::gui) struct DlgBase {
 	base: Base,
 	dialog_id: u16,
+	is_property_page: Cell<bool>,
 }
 
 impl Drop for DlgBase {
@@ -25,6 +28,7 @@ impl DlgBase {
 		Self {
 			base: Base::new(true, parent),
 			dialog_id,
+			is_property_page: Cell::new(false),
 		}
 	}
 
@@ -45,6 +49,10 @@ impl DlgBase {
 		self.base.privileged_on()
 	}
 
+	pub(in crate::gui) const fn dialog_id(&self) -> u16 {
+		self.dialog_id
+	}
+
 	pub(in crate::gui) fn parent(&self) -> Option<&Base> {
 		self.base.parent()
 	}
@@ -103,6 +111,37 @@ impl DlgBase {
 		self.base.run_ui_thread(func);
 	}
 
+	/// Fills the page struct so the property sheet creates the page with our
+	/// dialog procedure. The template must be set by the caller.
+	pub(in crate::gui) fn fill_property_sheet_page(&self, psp: &mut PROPSHEETPAGE) {
+		if *self.base.hwnd() != HWND::NULL {
+			panic!("Cannot create property sheet page twice.");
+		}
+
+		psp.pfnDlgProc = Some(Self::property_page_proc);
+		self.is_property_page.set(true);
+		// Pass pointer to Self.
+		// At this moment, the parent struct is already created and pinned.
+		psp.lParam = self as *const _ as _;
+	}
+
+	extern "system" fn property_page_proc(
+		hwnd: HWND,
+		msg: co::WM,
+		wparam: usize,
+		lparam: isize,
+	) -> isize
+	{
+		// In WM_INITDIALOG, lParam points to the PROPSHEETPAGE, whose own
+		// lParam carries the pointer to Self.
+		let lparam = if msg == co::WM::INITDIALOG {
+			unsafe { (*(lparam as *const PROPSHEETPAGE)).lParam }
+		} else {
+			lparam
+		};
+		Self::dialog_proc(hwnd, msg, wparam, lparam)
+	}
+
 	extern "system" fn dialog_proc(
 		hwnd: HWND,
 		msg: co::WM,
@@ -136,7 +175,8 @@ impl DlgBase {
 
 		// Execute privileged closures, keep track if at least one was executed.
 		let ref_self = unsafe { &mut *ptr_self };
-		let at_least_one_privileged = ref_self.base.process_privileged_messages(wm_any)?;
+		let privileged_result = ref_self.base.process_privileged_messages(wm_any)?;
+		let at_least_one_privileged = !matches!(privileged_result, ProcessResult::NotHandled);
 
 		if wm_any.msg_id == co::WM::INITDIALOG {
 			// Child controls are created in privileged closures, so we set the
@@ -154,19 +194,50 @@ impl DlgBase {
 			});
 		}
 
-		// Execute user closure, if any.
-		let process_result = ref_self.base.process_user_message(wm_any)?;
+		// Execute user closure, if any; if it doesn't answer, a privileged
+		// notification closure may still have answered. Property pages, and
+		// notifications answered by the library, return the result through
+		// DWLP_MSGRESULT; other dialogs return user results directly.
+		let (process_result, via_msgresult) = match (
+			ref_self.base.process_user_message(wm_any)?,
+			privileged_result,
+		) {
+			(ProcessResult::NotHandled, ProcessResult::HandledWithRet(res)) =>
+				(ProcessResult::HandledWithRet(res), true),
+			(user_result, _) => (user_result, ref_self.is_property_page.get()),
+		};
+		let via_msgresult = via_msgresult && wm_any.msg_id == co::WM::NOTIFY;
+
+		match wm_any.msg_id {
+			co::WM::INITDIALOG => dark_mode_window_created(&hwnd), // children already created
+			co::WM::WININICHANGE => dark_mode_setting_change(wm_any),
+			co::WM::INPUT if !matches!(process_result, ProcessResult::NotHandled) => {
+				hwnd.DefWindowProc(wm_any); // let the system release the raw input data
+			},
+			_ => {},
+		}
 
 		if wm_any.msg_id == co::WM::NCDESTROY { // always check
 			hwnd.SetWindowLongPtr(co::GWLP::DWLP_USER, 0); // clear passed pointer
 			ref_self.base.set_hwnd(HWND::NULL); // clear stored HWND
 			ref_self.base.clear_events(); // prevents circular references
+			drop_local_tasks(&hwnd); // tasks woken through this window would leak
+		}
+
+		if via_msgresult {
+			hwnd.SetWindowLongPtr(co::GWLP::DWLP_MSGRESULT, match process_result {
+				ProcessResult::HandledWithRet(res) => res,
+				_ => 0,
+			});
 		}
 
 		Ok(match process_result {
+			ProcessResult::HandledWithRet(_) if via_msgresult => 1, // TRUE
 			ProcessResult::HandledWithRet(res) => res,
 			ProcessResult::HandledWithoutRet => 1, // TRUE
-			ProcessResult::NotHandled => if at_least_one_privileged {
+			ProcessResult::NotHandled => if let Some(res) = dark_mode_default_proc(&hwnd, wm_any) {
+				res // brushes are returned directly, not through DWLP_MSGRESULT
+			} else if at_least_one_privileged {
 				1 // TRUE
 			} else {
 				0 // FALSE
@@ -11,6 +11,7 @@ mod label_events;
 mod list_box_events;
 mod list_view_events;
 mod month_calendar_events;
+mod property_sheet_page_events;
 mod radio_group_events;
 mod status_bar_events;
 mod tab_events;
@@ -39,6 +40,7 @@ pub use label_events::LabelEvents;
 pub use list_box_events::ListBoxEvents;
 pub use list_view_events::ListViewEvents;
 pub use month_calendar_events::MonthCalendarEvents;
+pub use property_sheet_page_events::PropertySheetPageEvents;
 pub use radio_group_events::RadioGroupEvents;
 pub use status_bar_events::StatusBarEvents;
 pub use tab_events::TabEvents;
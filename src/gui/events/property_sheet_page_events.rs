@@ -0,0 +1,127 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{*, privs::*};
+
+/// Exposes property sheet page
+/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-property-sheets-reference-notifications).
+///
+/// The property sheet sends these notifications to the page itself, so the
+/// event methods are proxies to the
+/// [`WindowEventsAll`](crate::gui::events::WindowEventsAll) of the page, under
+/// control ID zero.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// page.
+pub struct PropertySheetPageEvents(BaseEventsProxy);
+
+impl PropertySheetPageEvents {
+	pub(in crate::gui) fn new(page_base: &Base) -> Self {
+		Self(BaseEventsProxy::new(page_base, 0))
+	}
+
+	/// [`PSN_APPLY`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-apply)
+	/// notification.
+	///
+	/// Sent when the user clicks OK, Apply or Close. The `lParam` field is
+	/// nonzero if the sheet is about to be closed. Return
+	/// [`co::PSNRET::INVALID_NOCHANGEPAGE`](crate::co::PSNRET::INVALID_NOCHANGEPAGE)
+	/// to keep the sheet open, with the focus on this page.
+	pub fn psn_apply<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<co::PSNRET> + 'static,
+	{
+		self.0.wm_notify(co::PSN::APPLY,
+			move |p| Ok(Some(func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })?.raw())));
+	}
+
+	pub_fn_nfy_withparm_noret! { psn_help, co::PSN::HELP, PSHNOTIFY;
+		/// [`PSN_HELP`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-help)
+		/// notification.
+	}
+
+	/// [`PSN_KILLACTIVE`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-killactive)
+	/// notification.
+	///
+	/// Sent when the page is about to lose the activation, or before
+	/// [`psn_apply`](crate::gui::events::PropertySheetPageEvents::psn_apply).
+	/// Return `false` if the page contents are invalid, so the page keeps the
+	/// activation.
+	pub fn psn_kill_active<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<bool> + 'static,
+	{
+		self.0.wm_notify(co::PSN::KILLACTIVE,
+			move |p| Ok(Some(!func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })? as _)));
+	}
+
+	/// [`PSN_QUERYCANCEL`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-querycancel)
+	/// notification.
+	///
+	/// Return `false` to prevent the sheet from being cancelled.
+	pub fn psn_query_cancel<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<bool> + 'static,
+	{
+		self.0.wm_notify(co::PSN::QUERYCANCEL,
+			move |p| Ok(Some(!func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })? as _)));
+	}
+
+	pub_fn_nfy_withparm_noret! { psn_reset, co::PSN::RESET, PSHNOTIFY;
+		/// [`PSN_RESET`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-reset)
+		/// notification.
+		///
+		/// Sent when the user clicks Cancel; changes should be discarded.
+	}
+
+	/// [`PSN_SETACTIVE`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-setactive)
+	/// notification.
+	///
+	/// Return `false` to skip the page; in a wizard, the sheet then moves on to
+	/// the next page in the current direction.
+	///
+	/// In a wizard, Back, Next and Finish buttons are set before this event is
+	/// called, according to the page position. You can change them with
+	/// [`PropertySheetPage::set_wizard_buttons`](crate::gui::PropertySheetPage::set_wizard_buttons).
+	pub fn psn_set_active<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<bool> + 'static,
+	{
+		self.0.wm_notify(co::PSN::SETACTIVE,
+			move |p| Ok(Some(
+				if func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })? { 0 } else { -1 },
+			)));
+	}
+
+	/// [`PSN_WIZBACK`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-wizback)
+	/// notification.
+	///
+	/// Sent when the user clicks the Back button of a wizard.
+	pub fn psn_wiz_back<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<WizardNav> + 'static,
+	{
+		self.0.wm_notify(co::PSN::WIZBACK, move |p| {
+			let nav = func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })?;
+			Ok(Some(nav.apply(&p.nmhdr.hwndFrom)))
+		});
+	}
+
+	/// [`PSN_WIZFINISH`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-wizfinish)
+	/// notification.
+	///
+	/// Return `false` to prevent the wizard from finishing.
+	pub fn psn_wiz_finish<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<bool> + 'static,
+	{
+		self.0.wm_notify(co::PSN::WIZFINISH,
+			move |p| Ok(Some(!func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })? as _)));
+	}
+
+	/// [`PSN_WIZNEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/psn-wiznext)
+	/// notification.
+	///
+	/// Sent when the user clicks the Next button of a wizard.
+	pub fn psn_wiz_next<F>(&self, func: F)
+		where F: Fn(&PSHNOTIFY) -> AnyResult<WizardNav> + 'static,
+	{
+		self.0.wm_notify(co::PSN::WIZNEXT, move |p| {
+			let nav = func(unsafe { p.cast_nmhdr::<PSHNOTIFY>() })?;
+			Ok(Some(nav.apply(&p.nmhdr.hwndFrom)))
+		});
+	}
+}
@@ -23,6 +23,11 @@ impl TreeViewEvents {
 		/// notification.
 	}
 
+	pub_fn_nfy_withmutparm_noret! { tvn_get_disp_info, co::TVN::GETDISPINFO, NMTVDISPINFO;
+		/// [`TVN_GETDISPINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/tvn-getdispinfo)
+		/// notification.
+	}
+
 	pub_fn_nfy_withparm_noret! { tvn_item_changed, co::TVN::ITEMCHANGED, NMTREEVIEW;
 		/// [`TVN_ITEMCHANGED`](https://learn.microsoft.com/en-us/windows/win32/controls/tvn-itemchanged)
 		/// notification.
@@ -133,14 +133,14 @@ impl WindowEventsAll {
 	}
 
 	/// Searches for all user functions for the given message, and runs all of
-	/// them, discarding the results.
-	///
-	/// Returns `true` if at least one message was processed.
+	/// them, discarding the results, except for `WM_NOTIFY`, whose last
+	/// meaningful return value is kept.
 	pub(in crate::gui) fn process_all_messages(&self,
 		wm_any: WndMsg,
-	) -> AnyResult<bool>
+	) -> AnyResult<ProcessResult>
 	{
 		let mut at_least_one = false;
+		let mut notify_ret = None;
 
 		match wm_any.msg_id {
 			co::WM::NOTIFY => {
@@ -149,7 +149,9 @@ impl WindowEventsAll {
 				let nfys = unsafe { &mut *self.nfys.get() };
 				for func in nfys.find_all(key) {
 					at_least_one = true;
-					func(wm::Notify::from_generic_wm(wm_any))?; // execute stored function
+					if let Some(res) = func(wm::Notify::from_generic_wm(wm_any))? { // execute stored function
+						notify_ret = Some(res);
+					}
 				}
 			},
 			co::WM::COMMAND => {
@@ -174,6 +176,10 @@ impl WindowEventsAll {
 			},
 		}
 
-		Ok(at_least_one)
+		Ok(match notify_ret {
+			Some(res) => ProcessResult::HandledWithRet(res),
+			None if at_least_one => ProcessResult::HandledWithoutRet,
+			None => ProcessResult::NotHandled,
+		})
 	}
 }
@@ -336,6 +336,48 @@ pub trait GuiEvents {
 		/// message.
 	}
 
+	/// [`FINDMSGSTRING`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/findmsgstring)
+	/// registered message, sent by the modeless dialogs created with
+	/// [`FindText`](crate::FindText) and [`ReplaceText`](crate::ReplaceText)
+	/// to their owner window.
+	///
+	/// The `Flags` field of the [`FINDREPLACE`](crate::FINDREPLACE) tells what
+	/// the user requested; if it has
+	/// [`co::FR::DIALOGTERM`](crate::co::FR::DIALOGTERM), the dialog is being
+	/// closed.
+	///
+	/// Fails if the message cannot be registered, in which case no handler is
+	/// added.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// wnd.on().wm_find_msg_string(move |fr: &w::FINDREPLACE| -> w::AnyResult<()> {
+	///     if fr.Flags.has(co::FR::FINDNEXT) {
+	///         println!("Find: {}", fr.lpstrFindWhat().unwrap_or_default());
+	///     }
+	///     Ok(())
+	/// })?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	fn wm_find_msg_string<F>(&self, func: F) -> SysResult<()>
+		where F: Fn(&FINDREPLACE) -> AnyResult<()> + 'static,
+	{
+		let wm_find = unsafe {
+			co::WM::from_raw(RegisterWindowMessage("commdlg_FindReplace")?)
+		};
+		self.wm(wm_find, move |p| {
+			func(unsafe { &*(p.lparam as *const FINDREPLACE) })?;
+			Ok(None) // not meaningful
+		});
+		Ok(())
+	}
+
 	fn_wm_withparm_coret! { wm_get_dlg_code, co::WM::GETDLGCODE, wm::GetDlgCode, co::DLGC;
 		/// [`WM_GETDLGCODE`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/wm-getdlgcode)
 		/// message.
@@ -397,6 +439,84 @@ pub trait GuiEvents {
 		/// message.
 	}
 
+	fn_wm_withparm_noret! { wm_hotkey, co::WM::HOTKEY, wm::Hotkey;
+		/// [`WM_HOTKEY`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-hotkey)
+		/// message, sent when a hotkey registered with
+		/// [`HWND::RegisterHotKey`](crate::prelude::user_Hwnd::RegisterHotKey)
+		/// is pressed.
+		///
+		/// # Examples
+		///
+		/// ```no_run
+		/// use winsafe::{self as w, prelude::*, co, gui};
+		///
+		/// let wnd: gui::WindowMain; // initialized somewhere
+		/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+		///
+		/// const HOTKEY_ID: i32 = 1;
+		///
+		/// let wnd2 = wnd.clone(); // to pass into the closure
+		/// wnd.on().wm_create(move |_| {
+		///     wnd2.hwnd().RegisterHotKey(HOTKEY_ID,
+		///         co::MOD::WIN | co::MOD::SHIFT, co::VK::CHAR_Q)?;
+		///     Ok(0)
+		/// });
+		///
+		/// wnd.on().wm_hotkey(|p| {
+		///     if p.id == HOTKEY_ID {
+		///         println!("Hotkey pressed");
+		///     }
+		///     Ok(())
+		/// });
+		///
+		/// let wnd2 = wnd.clone();
+		/// wnd.on().wm_destroy(move || {
+		///     wnd2.hwnd().UnregisterHotKey(HOTKEY_ID)?;
+		///     Ok(())
+		/// });
+		/// ```
+	}
+
+	fn_wm_withparm_noret! { wm_input, co::WM::INPUT, wm::Input;
+		/// [`WM_INPUT`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-input)
+		/// message, sent to the windows which registered for raw input with
+		/// [`RegisterRawInputDevices`](crate::RegisterRawInputDevices).
+		///
+		/// The raw input data is released automatically after the closure
+		/// returns.
+		///
+		/// # Examples
+		///
+		/// ```no_run
+		/// use winsafe::{self as w, prelude::*, co, gui};
+		///
+		/// let wnd: gui::WindowMain; // initialized somewhere
+		/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+		///
+		/// let wnd2 = wnd.clone(); // to pass into the closure
+		/// wnd.on().wm_create(move |_| {
+		///     w::RegisterRawInputDevices(&[
+		///         w::RAWINPUTDEVICE {
+		///             usUsagePage: 0x01, // generic desktop controls
+		///             usUsage: 0x02, // mouse
+		///             dwFlags: co::RIDEV::NoValue,
+		///             hwndTarget: unsafe { wnd2.hwnd().raw_copy() },
+		///         },
+		///     ])?;
+		///     Ok(0)
+		/// });
+		///
+		/// wnd.on().wm_input(|p| {
+		///     if let w::RawInput::Mouse(_, ms) =
+		///         w::RawInput::parse(&p.hraw_input.GetRawInputData()?)?
+		///     {
+		///         println!("Mouse moved {}, {}", ms.lLastX, ms.lLastY);
+		///     }
+		///     Ok(())
+		/// });
+		/// ```
+	}
+
 	fn_wm_withparm_boolret! { wm_init_dialog, co::WM::INITDIALOG, wm::InitDialog;
 		/// [`WM_INITDIALOG`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/wm-initdialog)
 		/// message, sent only to dialog windows. Non-dialog windows receive
@@ -542,6 +662,11 @@ pub trait GuiEvents {
 		/// message.
 	}
 
+	fn_wm_withparm_noret! { wm_mouse_h_wheel, co::WM::MOUSEHWHEEL, wm::MouseHWheel;
+		/// [`WM_MOUSEHWHEEL`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousehwheel)
+		/// message.
+	}
+
 	fn_wm_noparm_noret! { wm_mouse_leave, co::WM::MOUSELEAVE;
 		/// [`WM_MOUSELEAVE`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mouseleave)
 		/// message.
@@ -552,6 +677,11 @@ pub trait GuiEvents {
 		/// message.
 	}
 
+	fn_wm_withparm_noret! { wm_mouse_wheel, co::WM::MOUSEWHEEL, wm::MouseWheel;
+		/// [`WM_MOUSEWHEEL`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousewheel)
+		/// message.
+	}
+
 	fn_wm_withparm_noret! { wm_move, co::WM::MOVE, wm::Move;
 		/// [`WM_MOVE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-move)
 		/// message.
@@ -1,8 +1,9 @@
 use std::any::Any;
+use std::future::Future;
 
 use crate::co;
 use crate::decl::*;
-use crate::gui::{*, events::*};
+use crate::gui::{*, events::*, privs::*};
 use crate::msg::*;
 use crate::prelude::*;
 use crate::user::privs::*;
@@ -214,6 +215,96 @@ pub trait GuiThread: GuiParent {
 	/// ```
 	fn run_ui_thread<F>(&self, func: F)
 		where F: FnOnce() -> AnyResult<()> + Send + 'static;
+
+	/// Runs a [`Future`](std::future::Future) in the window's original UI
+	/// thread, driven by the window message loop, so it can be `await`ed
+	/// inside an event closure without blocking the UI.
+	///
+	/// The future is first polled after the current event returns. Whenever
+	/// it's woken – possibly from another thread – a message is posted to the
+	/// main window, and the future is polled again.
+	///
+	/// If the future returns an error, it will be forwarded to
+	/// [`WindowMain::run_main`](crate::gui::WindowMain::run_main), just like an
+	/// event closure error.
+	///
+	/// Pending futures are dropped when the main window is destroyed.
+	///
+	/// # Panics
+	///
+	/// Panics if the window is not created yet, or if called from a thread
+	/// other than the window's UI thread.
+	///
+	/// # Examples
+	///
+	/// The example below shows the event of a
+	/// [button click](crate::gui::events::ButtonEvents::bn_clicked) which loads
+	/// some data in a parallel thread, then updates the UI.
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// let btn: gui::Button;
+	/// # let btn = gui::Button::new(&wnd, gui::ButtonOpts::default());
+	///
+	/// btn.on().bn_clicked({
+	///     let wnd = wnd.clone();
+	///     move || -> w::AnyResult<()> {
+	///         let wnd2 = wnd.clone();
+	///         wnd.spawn_local(async move {
+	///             let data = wnd2.spawn_new_thread_async(|| {
+	///                 w::Sleep(2000); // some long task
+	///                 Ok("Loaded".to_owned())
+	///             }).await?;
+	///
+	///             wnd2.hwnd().SetWindowText(&data)?; // back in the UI thread
+	///             Ok(())
+	///         });
+	///         Ok(())
+	///     }
+	/// });
+	/// ```
+	fn spawn_local<F>(&self, fut: F)
+		where F: Future<Output = AnyResult<()>> + 'static,
+	{
+		unsafe { Base::from_guiparent(self) }.spawn_local(fut);
+	}
+
+	/// Runs a closure in a new thread, returning an
+	/// [`AsyncResult`](crate::gui::AsyncResult) which resolves to the value
+	/// returned by the closure.
+	///
+	/// Unlike [`spawn_new_thread`](crate::prelude::GuiThread::spawn_new_thread),
+	/// an error returned by the closure is not forwarded to the main loop, but
+	/// rather delivered to whoever awaits the result, usually a future started
+	/// with [`spawn_local`](crate::prelude::GuiThread::spawn_local).
+	#[must_use]
+	fn spawn_new_thread_async<F, T>(&self, func: F) -> AsyncResult<T>
+		where F: FnOnce() -> AnyResult<T> + Send + 'static,
+			T: Send + 'static,
+	{
+		unsafe { Base::from_guiparent(self) }.spawn_new_thread_async(func)
+	}
+
+	/// Runs a closure asynchronously in the window's original UI thread,
+	/// returning an [`AsyncResult`](crate::gui::AsyncResult) which resolves to
+	/// the value returned by the closure.
+	///
+	/// Unlike [`run_ui_thread`](crate::prelude::GuiThread::run_ui_thread), the
+	/// calling thread is not blocked: the closure is queued in the message
+	/// loop. The result can be awaited by any executor in the calling thread.
+	///
+	/// **Note:** Do not block the UI thread waiting for this result, or it
+	/// will deadlock.
+	#[must_use]
+	fn run_ui_thread_async<F, T>(&self, func: F) -> AsyncResult<T>
+		where F: FnOnce() -> AnyResult<T> + Send + 'static,
+			T: Send + 'static,
+	{
+		unsafe { Base::from_guiparent(self) }.run_ui_thread_async(func)
+	}
 }
 
 /// Any child window.
@@ -284,3 +375,75 @@ pub trait GuiTab {
 	#[must_use]
 	fn as_ctrl(&self) -> &WindowControl;
 }
+
+/// Data model which feeds a [`VirtualListView`](crate::gui::VirtualListView).
+///
+/// The control never stores the items: whenever it needs to paint a row, it
+/// asks the model. This allows lists with millions of rows, since no item is
+/// ever created.
+///
+/// Row indexes are zero-based, and column indexes follow the columns added to
+/// the control.
+pub trait GuiListViewModel {
+	/// Returns the total number of rows.
+	///
+	/// This value is read when the control is created, and whenever
+	/// [`VirtualListView::refresh`](crate::gui::VirtualListView::refresh) is
+	/// called.
+	#[must_use]
+	fn row_count(&self) -> u32;
+
+	/// Returns the text of the given cell.
+	#[must_use]
+	fn cell_text(&self, row: u32, column: u32) -> String;
+
+	/// Returns the image list index of the icon of the given row, if any.
+	///
+	/// Default implementation returns `None`.
+	#[must_use]
+	fn image(&self, _row: u32) -> Option<u32> {
+		None
+	}
+
+	/// Returns the check state of the given row, which is displayed when the
+	/// control has the [`LVS_EX::CHECKBOXES`](crate::co::LVS_EX::CHECKBOXES)
+	/// extended style.
+	///
+	/// Default implementation returns `false`.
+	#[must_use]
+	fn is_checked(&self, _row: u32) -> bool {
+		false
+	}
+
+	/// Called when the user toggles the checkbox of the given row, either by
+	/// clicking it or by pressing the space bar.
+	///
+	/// Default implementation does nothing, so the check state is read-only.
+	fn set_checked(&self, _row: u32, _checked: bool) {}
+
+	/// Called upon an
+	/// [`LVN_ODCACHEHINT`](https://learn.microsoft.com/en-us/windows/win32/controls/lvn-odcachehint)
+	/// notification, with the inclusive range of rows about to be displayed.
+	/// Models backed by slow storage can prefetch these rows.
+	///
+	/// Default implementation does nothing.
+	fn cache_hint(&self, _from: u32, _to: u32) {}
+
+	/// Called upon an
+	/// [`LVN_ODFINDITEM`](https://learn.microsoft.com/en-us/windows/win32/controls/lvn-odfinditem)
+	/// notification, usually fired by incremental keyboard search, and returns
+	/// the row whose first column matches `text`, case-insensitive.
+	///
+	/// Default implementation performs a linear search starting at `start` and
+	/// wrapping around; models with many rows should provide a faster one.
+	#[must_use]
+	fn find_row(&self, text: &str, start: u32, partial: bool) -> Option<u32> {
+		let count = self.row_count();
+		let text = text.to_lowercase();
+		(start..count).chain(0..start.min(count))
+			.find(|row| {
+				let cell = self.cell_text(*row, 0).to_lowercase();
+				if partial { cell.starts_with(&text) } else { cell == text }
+			})
+	}
+}
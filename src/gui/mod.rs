@@ -9,6 +9,7 @@
 #![cfg(feature = "gui")]
 
 mod base;
+mod dark_mode;
 mod dlg_base;
 mod dlg_control;
 mod dlg_main;
@@ -18,12 +19,19 @@ mod gui_traits;
 mod layout_arranger;
 mod msg_error;
 mod native_controls;
+mod print_preview;
 mod privs_gui;
+mod property_sheet;
+mod property_sheet_page;
 mod raw_base;
 mod raw_control;
 mod raw_main;
 mod raw_modal;
 mod raw_modeless;
+mod scroll_panel;
+mod splitter;
+mod taskbar;
+mod ui_executor;
 mod window_control;
 mod window_main;
 mod window_message_only;
@@ -32,6 +40,7 @@ mod window_modeless;
 
 pub(in crate::gui) mod privs {
 	pub(in crate::gui) use super::base::Base;
+	pub(in crate::gui) use super::dark_mode::{dark_mode_default_proc, dark_mode_setting_change, dark_mode_window_created, delete_dark_brushes};
 	pub(in crate::gui) use super::dlg_base::DlgBase;
 	pub(in crate::gui) use super::dlg_control::DlgControl;
 	pub(in crate::gui) use super::dlg_main::DlgMain;
@@ -46,18 +55,27 @@ pub(in crate::gui) mod privs {
 	pub(in crate::gui) use super::raw_main::RawMain;
 	pub(in crate::gui) use super::raw_modal::RawModal;
 	pub(in crate::gui) use super::raw_modeless::RawModeless;
+	pub(in crate::gui) use super::ui_executor::{drop_local_tasks, poll_local_task, spawn_local_task};
 }
 
 pub mod events;
 
+pub use dark_mode::{DarkMode, is_dark_mode, set_dark_mode};
 pub use layout_arranger::{Horz, Vert};
 pub use msg_error::MsgError;
 pub use native_controls::*;
+pub use print_preview::{PrintPreview, PrintPreviewOpts};
+pub use property_sheet::{PropertySheet, PropertySheetOpts};
+pub use property_sheet_page::{PropertySheetPage, PropertySheetPageOpts, WizardNav};
 pub use raw_base::{Brush, Cursor, Icon};
 pub use raw_control::WindowControlOpts;
 pub use raw_main::WindowMainOpts;
 pub use raw_modal::WindowModalOpts;
 pub use raw_modeless::WindowModelessOpts;
+pub use scroll_panel::{ScrollPanel, ScrollPanelOpts};
+pub use splitter::{Splitter, SplitterDir, SplitterOpts};
+pub use taskbar::{Taskbar, TaskbarButton, TaskbarButtonOpts};
+pub use ui_executor::AsyncResult;
 pub use window_control::WindowControl;
 pub use window_main::WindowMain;
 pub use window_message_only::WindowMessageOnly;
@@ -30,6 +30,7 @@ mod tree_view_item;
 mod tree_view_items;
 mod tree_view;
 mod up_down;
+mod virtual_list_view;
 
 pub(in crate::gui) mod privs {
 	pub(in crate::gui) use super::base_native_control::*;
@@ -53,6 +54,7 @@ pub use tab::{Tab, TabOpts};
 pub use trackbar::{Trackbar, TrackbarOpts};
 pub use tree_view::{TreeView, TreeViewOpts};
 pub use up_down::{UpDown, UpDownOpts};
+pub use virtual_list_view::VirtualListView;
 
 pub mod spec {
 	//! Structs which expose specialized methods of controls.
@@ -1,7 +1,9 @@
 use std::any::Any;
+use std::cell::UnsafeCell;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomPinned;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::co;
 use crate::decl::*;
@@ -9,9 +11,15 @@ use crate::gui::{*, events::*, privs::*, spec::*};
 use crate::msg::*;
 use crate::prelude::*;
 
+type ItemData = Arc<dyn Any + Send + Sync>;
+type LazyLoader = Box<dyn Fn(&TreeViewItem) -> AnyResult<()>>;
+
 struct Obj { // actual fields of TreeView
 	base: BaseNativeControl,
 	events: TreeViewEvents,
+	items_data: Mutex<HashMap<isize, ItemData>>, // user data of each HTREEITEM; TreeView is Send
+	lazy_pending: Mutex<HashSet<isize>>, // lazy HTREEITEMs whose children weren't loaded yet
+	lazy_loader: UnsafeCell<Option<LazyLoader>>, // set before creation, used in the UI thread only
 	_pin: PhantomPinned,
 }
 
@@ -80,6 +88,9 @@ impl TreeView {
 				Obj {
 					base: BaseNativeControl::new(parent_base_ref, ctrl_id),
 					events: TreeViewEvents::new(parent_base_ref, ctrl_id),
+					items_data: Mutex::new(HashMap::new()),
+					lazy_pending: Mutex::new(HashSet::new()),
+					lazy_loader: UnsafeCell::new(None),
 					_pin: PhantomPinned,
 				},
 			),
@@ -91,6 +102,7 @@ impl TreeView {
 			Ok(None) // not meaningful
 		});
 
+		new_self.default_message_handlers(parent_base_ref, ctrl_id);
 		new_self
 	}
 
@@ -116,6 +128,9 @@ impl TreeView {
 				Obj {
 					base: BaseNativeControl::new(parent_base_ref, ctrl_id),
 					events: TreeViewEvents::new(parent_base_ref, ctrl_id),
+					items_data: Mutex::new(HashMap::new()),
+					lazy_pending: Mutex::new(HashSet::new()),
+					lazy_loader: UnsafeCell::new(None),
 					_pin: PhantomPinned,
 				},
 			),
@@ -127,6 +142,7 @@ impl TreeView {
 			Ok(true) // not meaningful
 		});
 
+		new_self.default_message_handlers(parent_base_ref, ctrl_id);
 		new_self
 	}
 
@@ -146,9 +162,17 @@ impl TreeView {
 				self.0.base.create_window( // may panic
 					"SysTreeView32", None, pos, sz,
 					opts.window_ex_style,
-					opts.window_style | opts.tree_view_style.into(),
+					opts.window_style | (opts.tree_view_style & !co::TVS::CHECKBOXES).into(),
 				)?;
 
+				if opts.tree_view_style.has(co::TVS::CHECKBOXES) {
+					// https://learn.microsoft.com/en-us/windows/win32/controls/tree-view-control-window-styles
+					// Checkboxes must be set after creation, so the state image list is built.
+					let cur_style = self.hwnd().GetWindowLongPtr(co::GWLP::STYLE);
+					self.hwnd().SetWindowLongPtr(co::GWLP::STYLE,
+						cur_style | co::TVS::CHECKBOXES.raw() as isize);
+				}
+
 				if opts.tree_view_ex_style != co::TVS_EX::NoValue {
 					self.set_extended_style(true, opts.tree_view_ex_style);
 				}
@@ -159,12 +183,150 @@ impl TreeView {
 		self.0.base.parent().add_to_layout_arranger(self.hwnd(), resize_behavior)
 	}
 
+	fn default_message_handlers(&self, parent: &Base, ctrl_id: u16) {
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::TVN::GETDISPINFO, move |p| {
+			let nmtv = unsafe { p.cast_nmhdr_mut::<NMTVDISPINFO>() };
+			if nmtv.item.mask.has(co::TVIF::CHILDREN) {
+				nmtv.item.cChildren = if self2.is_lazy_pending(&nmtv.item.hItem) {
+					1 // not loaded yet, show the "+" button
+				} else {
+					let item = self2.items().get(unsafe { nmtv.item.hItem.raw_copy() });
+					item.iter_children().next().is_some() as _
+				};
+			}
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::TVN::ITEMEXPANDING, move |p| {
+			let nmtv = unsafe { p.cast_nmhdr::<NMTREEVIEW>() };
+			if nmtv.action & co::TVE::EXPAND.raw() != 0
+				&& self2.set_lazy_pending(&nmtv.itemNew.hItem, false)
+			{
+				if let Some(loader) = unsafe { &*self2.0.lazy_loader.get() } {
+					loader(&self2.items().get(unsafe { nmtv.itemNew.hItem.raw_copy() }))?;
+				}
+			}
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::TVN::DELETEITEM, move |p| {
+			let nmtv = unsafe { p.cast_nmhdr::<NMTREEVIEW>() };
+			self2.set_item_data(&nmtv.itemOld.hItem, None);
+			self2.set_lazy_pending(&nmtv.itemOld.hItem, false);
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_nc_destroy(move || {
+			*unsafe { &mut *self2.0.lazy_loader.get() } = None; // prevents circular references
+			Ok(())
+		});
+	}
+
+	pub(in crate::gui) fn item_data(&self, hitem: &HTREEITEM) -> Option<ItemData> {
+		self.0.items_data.lock().unwrap()
+			.get(&(hitem.ptr() as _))
+			.cloned()
+	}
+
+	pub(in crate::gui) fn set_item_data(&self,
+		hitem: &HTREEITEM, data: Option<ItemData>)
+	{
+		let mut items_data = self.0.items_data.lock().unwrap();
+		let old_data = match data {
+			Some(data) => items_data.insert(hitem.ptr() as _, data),
+			None => items_data.remove(&(hitem.ptr() as _)),
+		};
+		drop(items_data);
+		drop(old_data); // dropped outside the lock, since it may access the control
+	}
+
+	pub(in crate::gui) fn is_lazy_pending(&self, hitem: &HTREEITEM) -> bool {
+		self.0.lazy_pending.lock().unwrap()
+			.contains(&(hitem.ptr() as _))
+	}
+
+	/// Returns whether the item was pending before.
+	pub(in crate::gui) fn set_lazy_pending(&self,
+		hitem: &HTREEITEM, pending: bool) -> bool
+	{
+		let mut lazy_pending = self.0.lazy_pending.lock().unwrap();
+		if pending {
+			!lazy_pending.insert(hitem.ptr() as _)
+		} else {
+			lazy_pending.remove(&(hitem.ptr() as _))
+		}
+	}
+
 	/// Exposes the item methods.
 	#[must_use]
-	pub const fn items(&self) -> TreeViewItems {
+	pub const fn items(&self) -> TreeViewItems<'_> {
 		TreeViewItems::new(self)
 	}
 
+	/// Defines the closure which populates, on demand, the children of the
+	/// items marked with
+	/// [`TreeViewItem::set_lazy_children`](crate::gui::spec::TreeViewItem::set_lazy_children).
+	///
+	/// The closure is called upon the first
+	/// [`TVN_ITEMEXPANDING`](https://learn.microsoft.com/en-us/windows/win32/controls/tvn-itemexpanding)
+	/// notification of each of these items, receiving the item being expanded.
+	/// Until then, the item displays the expand button, without having any
+	/// child.
+	///
+	/// # Panics
+	///
+	/// Panics if the control or the parent window are already created.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let tree: gui::TreeView;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let tree = gui::TreeView::new(&wnd, gui::TreeViewOpts::default());
+	///
+	/// tree.set_lazy_loader(
+	///     move |item: &gui::spec::TreeViewItem| -> w::AnyResult<()> {
+	///         let path = item.data::<String>().unwrap();
+	///         for sub in w::path::dir_list(&path, None) {
+	///             let sub = sub?;
+	///             let child = item.add_child(w::path::get_file_name(&sub).unwrap(), None);
+	///             child.set_data(sub.clone());
+	///             if w::path::is_directory(&sub) {
+	///                 child.set_lazy_children();
+	///             }
+	///         }
+	///         Ok(())
+	///     },
+	/// );
+	///
+	/// wnd.on().wm_create({
+	///     let tree = tree.clone();
+	///     move |_| -> w::AnyResult<i32> {
+	///         let root = tree.items().add_root("C:", None);
+	///         root.set_data("C:\\".to_owned());
+	///         root.set_lazy_children();
+	///         Ok(0)
+	///     }
+	/// });
+	/// ```
+	pub fn set_lazy_loader<F>(&self, func: F)
+		where F: Fn(&TreeViewItem) -> AnyResult<()> + 'static,
+	{
+		if *self.hwnd() != HWND::NULL {
+			panic!("Cannot set the lazy loader after the control creation.");
+		} else if *self.0.base.parent().hwnd() != HWND::NULL {
+			panic!("Cannot set the lazy loader after the parent window creation.");
+		}
+		*unsafe { &mut *self.0.lazy_loader.get() } = Some(Box::new(func));
+	}
+
 	/// Sets or unsets the given extended list view styles by sending a
 	/// [`tvm::SetExtendedStyle`](crate::msg::tvm::SetExtendedStyle) message.
 	pub fn set_extended_style(&self, set: bool, ex_style: co::TVS_EX) {
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::co;
 use crate::decl::*;
 use crate::gui::{*, privs::*};
@@ -57,6 +59,39 @@ impl<'a> TreeViewItem<'a> {
 		Self::new(self.owner, new_hitem)
 	}
 
+	/// Retrieves the check state of the item by sending a
+	/// [`tvm::GetItemState`](crate::msg::tvm::GetItemState) message.
+	///
+	/// Meaningful only if the control has the
+	/// [`TVS::CHECKBOXES`](crate::co::TVS::CHECKBOXES) style.
+	/// [`CheckState::Indeterminate`](crate::gui::CheckState::Indeterminate) is
+	/// returned only if the control also has the
+	/// [`TVS_EX::PARTIALCHECKBOXES`](crate::co::TVS_EX::PARTIALCHECKBOXES)
+	/// extended style.
+	#[must_use]
+	pub fn check_state(&self) -> CheckState {
+		let state = self.owner.hwnd()
+			.SendMessage(tvm::GetItemState {
+				hitem: &self.hitem,
+				mask: co::TVIS::STATEIMAGEMASK,
+			});
+		match state.raw() >> 12 { // STATEIMAGEMASKTOINDEX
+			2 => CheckState::Checked,
+			3 => CheckState::Indeterminate,
+			_ => CheckState::Unchecked,
+		}
+	}
+
+	/// Retrieves the user data previously set with
+	/// [`TreeViewItem::set_data`](crate::gui::spec::TreeViewItem::set_data).
+	///
+	/// Returns `None` if no data was set, or if the data is not of type `T`.
+	#[must_use]
+	pub fn data<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+		self.owner.item_data(&self.hitem)
+			.and_then(|data| data.downcast::<T>().ok())
+	}
+
 	/// Deletes the item by sending a
 	/// [`tvm::DeleteItem`](crate::msg::tvm::DeleteItem) message.
 	pub fn delete(&self) {
@@ -96,6 +131,24 @@ impl<'a> TreeViewItem<'a> {
 			.unwrap();
 	}
 
+	/// Retrieves the image list index of the icon of the item by sending a
+	/// [`tvm::GetItem`](crate::msg::tvm::GetItem) message.
+	#[must_use]
+	pub fn icon_index(&self) -> Option<u32> {
+		let mut tvi = TVITEMEX::default();
+		tvi.hItem = unsafe { self.hitem.raw_copy() };
+		tvi.mask = co::TVIF::IMAGE;
+
+		self.owner.hwnd()
+			.SendMessage(tvm::GetItem { tvitem: &mut tvi })
+			.unwrap();
+
+		match tvi.iImage {
+			-2 | -1 => None, // I_IMAGENONE, I_IMAGECALLBACK
+			idx => Some(idx as _),
+		}
+	}
+
 	/// Returns the underlying handle of the item.
 	#[must_use]
 	pub const fn htreeitem(&self) -> &HTREEITEM {
@@ -114,6 +167,15 @@ impl<'a> TreeViewItem<'a> {
 			.has(co::TVIS::EXPANDED)
 	}
 
+	/// Tells whether the children of the item are pending to be loaded, that
+	/// is, the item was marked with
+	/// [`TreeViewItem::set_lazy_children`](crate::gui::spec::TreeViewItem::set_lazy_children)
+	/// and it wasn't expanded yet.
+	#[must_use]
+	pub fn is_lazy_pending(&self) -> bool {
+		self.owner.is_lazy_pending(&self.hitem)
+	}
+
 	/// Tells if the item is a root by sending a
 	/// [`tvm::GetNextItem`](crate::msg::tvm::GetNextItem) message.
 	#[must_use]
@@ -143,6 +205,21 @@ impl<'a> TreeViewItem<'a> {
 		TreeViewItemIter::new(self.owner, Some(self.raw_clone()), co::TVGN::PREVIOUS)
 	}
 
+	/// Retrieves the image list index of the overlay image of the item by
+	/// sending a [`tvm::GetItemState`](crate::msg::tvm::GetItemState) message.
+	#[must_use]
+	pub fn overlay_index(&self) -> Option<u32> {
+		let state = self.owner.hwnd()
+			.SendMessage(tvm::GetItemState {
+				hitem: &self.hitem,
+				mask: co::TVIS::OVERLAYMASK,
+			});
+		match (state & co::TVIS::OVERLAYMASK).raw() >> 8 {
+			0 => None,
+			idx => Some(idx),
+		}
+	}
+
 	/// Retrieves the parent of the item by sending a
 	/// [`tvm::GetNextItem`](crate::msg::tvm::GetNextItem) message.
 	#[must_use]
@@ -155,6 +232,145 @@ impl<'a> TreeViewItem<'a> {
 			.map(|hitem| TreeViewItem::new(self.owner, hitem))
 	}
 
+	/// Sets the check state of the item by sending a
+	/// [`tvm::SetItem`](crate::msg::tvm::SetItem) message.
+	///
+	/// Meaningful only if the control has the
+	/// [`TVS::CHECKBOXES`](crate::co::TVS::CHECKBOXES) style.
+	/// [`CheckState::Indeterminate`](crate::gui::CheckState::Indeterminate)
+	/// requires the
+	/// [`TVS_EX::PARTIALCHECKBOXES`](crate::co::TVS_EX::PARTIALCHECKBOXES)
+	/// extended style.
+	pub fn set_check_state(&self, state: CheckState) {
+		let idx: u32 = match state { // INDEXTOSTATEIMAGEMASK
+			CheckState::Unchecked => 1,
+			CheckState::Checked => 2,
+			CheckState::Indeterminate => 3,
+		};
+		self.set_state(co::TVIS::STATEIMAGEMASK, unsafe { co::TVIS::from_raw(idx << 12) });
+	}
+
+	/// Stores user data associated to the item, replacing any previous one.
+	///
+	/// The data is kept by the control and it's automatically dropped when the
+	/// item is deleted. It can be retrieved with
+	/// [`TreeViewItem::data`](crate::gui::spec::TreeViewItem::data).
+	///
+	/// Since the control can be used from other threads, the data must be
+	/// [`Send`](std::marker::Send) and [`Sync`](std::marker::Sync).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// struct FileInfo {
+	///     full_path: String,
+	///     size: u64,
+	/// }
+	///
+	/// let tree: gui::TreeView; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let tree = gui::TreeView::new(&wnd, gui::TreeViewOpts::default());
+	///
+	/// let item = tree.items().add_root("foo.txt", None);
+	/// item.set_data(FileInfo {
+	///     full_path: "C:\\Temp\\foo.txt".to_owned(),
+	///     size: 1024,
+	/// });
+	///
+	/// let info = item.data::<FileInfo>().unwrap();
+	/// println!("{} has {} bytes", info.full_path, info.size);
+	/// ```
+	pub fn set_data<T: Send + Sync + 'static>(&self, data: T) {
+		self.owner.set_item_data(&self.hitem, Some(Arc::new(data)));
+	}
+
+	/// Sets the image list index of the icon of the item, both in normal and
+	/// selected states, by sending a
+	/// [`tvm::SetItem`](crate::msg::tvm::SetItem) message.
+	pub fn set_icon_index(&self, icon_index: Option<u32>) {
+		let idx = icon_index.map_or(-2, |idx| idx as _); // I_IMAGENONE
+
+		let mut tvi = TVITEMEX::default();
+		tvi.hItem = unsafe { self.hitem.raw_copy() };
+		tvi.mask = co::TVIF::IMAGE | co::TVIF::SELECTEDIMAGE;
+		tvi.iImage = idx;
+		tvi.iSelectedImage = idx;
+
+		self.owner.hwnd()
+			.SendMessage(tvm::SetItem { tvitem: &tvi })
+			.unwrap();
+	}
+
+	/// Marks the item as having its children loaded on demand, by sending a
+	/// [`tvm::SetItem`](crate::msg::tvm::SetItem) message with
+	/// `I_CHILDRENCALLBACK`.
+	///
+	/// The item will display the expand button, and when first expanded, the
+	/// closure defined in
+	/// [`TreeView::set_lazy_loader`](crate::gui::TreeView::set_lazy_loader)
+	/// will be called to add the children. If the item already has children,
+	/// they are deleted and the item is collapsed, so they will be loaded
+	/// again.
+	pub fn set_lazy_children(&self) {
+		if self.iter_children().next().is_some() {
+			self.owner.hwnd()
+				.SendMessage(tvm::Expand {
+					hitem: &self.hitem,
+					action: unsafe { co::TVE::from_raw(co::TVE::COLLAPSE.raw() | co::TVE::COLLAPSERESET.raw()) }, // also deletes children
+				})
+				.unwrap();
+		}
+
+		self.owner.set_lazy_pending(&self.hitem, true);
+
+		let mut tvi = TVITEMEX::default();
+		tvi.hItem = unsafe { self.hitem.raw_copy() };
+		tvi.mask = co::TVIF::CHILDREN;
+		tvi.cChildren = -1; // I_CHILDRENCALLBACK
+
+		self.owner.hwnd()
+			.SendMessage(tvm::SetItem { tvitem: &tvi })
+			.unwrap();
+	}
+
+	/// Sets the image list index of the overlay image of the item by sending a
+	/// [`tvm::SetItem`](crate::msg::tvm::SetItem) message.
+	///
+	/// The index must have been registered with
+	/// [`HIMAGELIST::SetOverlayImage`](crate::prelude::comctl_Himagelist::SetOverlayImage),
+	/// and it's limited to 15.
+	pub fn set_overlay_index(&self, overlay_index: Option<u32>) {
+		let idx = overlay_index.unwrap_or(0); // INDEXTOOVERLAYMASK
+		self.set_state(co::TVIS::OVERLAYMASK, unsafe { co::TVIS::from_raw(idx << 8) });
+	}
+
+	/// Sets the image list index of the icon of the item when it is selected,
+	/// by sending a [`tvm::SetItem`](crate::msg::tvm::SetItem) message.
+	pub fn set_selected_icon_index(&self, icon_index: Option<u32>) {
+		let mut tvi = TVITEMEX::default();
+		tvi.hItem = unsafe { self.hitem.raw_copy() };
+		tvi.mask = co::TVIF::SELECTEDIMAGE;
+		tvi.iSelectedImage = icon_index.map_or(-2, |idx| idx as _); // I_IMAGENONE
+
+		self.owner.hwnd()
+			.SendMessage(tvm::SetItem { tvitem: &tvi })
+			.unwrap();
+	}
+
+	fn set_state(&self, mask: co::TVIS, state: co::TVIS) {
+		let mut tvi = TVITEMEX::default();
+		tvi.hItem = unsafe { self.hitem.raw_copy() };
+		tvi.mask = co::TVIF::STATE;
+		tvi.stateMask = mask;
+		tvi.state = state;
+
+		self.owner.hwnd()
+			.SendMessage(tvm::SetItem { tvitem: &tvi })
+			.unwrap();
+	}
+
 	/// Sets the text of the item by sending a
 	/// [`tvm::SetItem`](crate::msg::tvm::SetItem) message.
 	pub fn set_text(&self, text: &str) {
@@ -0,0 +1,371 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{*, events::*, privs::*, spec::*};
+use crate::msg::*;
+use crate::prelude::*;
+
+/// Native
+/// [list view](https://learn.microsoft.com/en-us/windows/win32/controls/list-view-controls-overview)
+/// control in
+/// [virtual mode](https://learn.microsoft.com/en-us/windows/win32/controls/list-view-controls-overview#virtual-list-view-style),
+/// whose contents are provided on demand by a
+/// [`GuiListViewModel`](crate::prelude::GuiListViewModel).
+///
+/// The [`LVS::OWNERDATA`](crate::co::LVS::OWNERDATA) style is always set. No
+/// items are ever created: the `LVN_GETDISPINFO`, `LVN_ODCACHEHINT` and
+/// `LVN_ODFINDITEM` notifications are answered automatically by querying the
+/// model, and the zero-based item indexes are the model rows.
+///
+/// The control is [`Send`] only if the model is [`Send`] and [`Sync`].
+pub struct VirtualListView<M: GuiListViewModel + 'static> {
+	list_view: ListView,
+	model: Arc<M>,
+}
+
+impl<M: GuiListViewModel + 'static> Clone for VirtualListView<M> {
+	fn clone(&self) -> Self {
+		Self {
+			list_view: self.list_view.clone(),
+			model: self.model.clone(),
+		}
+	}
+}
+
+// The model is shared with the UI thread, so the control can be sent to
+// another thread only if the model can be used from both.
+unsafe impl<M: GuiListViewModel + Send + Sync + 'static> Send for VirtualListView<M> {}
+
+impl<M: GuiListViewModel + 'static> GuiWindow for VirtualListView<M> {
+	fn hwnd(&self) -> &HWND {
+		self.list_view.hwnd()
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+impl<M: GuiListViewModel + 'static> GuiChild for VirtualListView<M> {
+	fn ctrl_id(&self) -> u16 {
+		self.list_view.ctrl_id()
+	}
+}
+
+impl<M: GuiListViewModel + 'static> GuiChildFocus for VirtualListView<M> {}
+
+impl<M: GuiListViewModel + 'static> GuiNativeControl for VirtualListView<M> {
+	fn on_subclass(&self) -> &WindowEvents {
+		self.list_view.on_subclass()
+	}
+}
+
+impl<M: GuiListViewModel + 'static> GuiNativeControlEvents<ListViewEvents> for VirtualListView<M> {
+	/// Exposes the specific control events.
+	///
+	/// **Note:** The `lvn_od_find_item` notification is already handled by
+	/// calling [`GuiListViewModel::find_row`](crate::prelude::GuiListViewModel::find_row);
+	/// adding it here will override the default behavior.
+	///
+	/// # Panics
+	///
+	/// Panics if the control is already created. Events must be set before
+	/// control creation.
+	fn on(&self) -> &ListViewEvents {
+		self.list_view.on()
+	}
+}
+
+impl<M: GuiListViewModel + 'static> VirtualListView<M> {
+	/// Instantiates a new `VirtualListView` object, to be created on the parent
+	/// window with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// The [`LVS::OWNERDATA`](crate::co::LVS::OWNERDATA) style is added to
+	/// `opts.list_view_style`.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `VirtualListView` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// struct Squares;
+	///
+	/// impl GuiListViewModel for Squares {
+	///     fn row_count(&self) -> u32 {
+	///         5_000_000
+	///     }
+	///
+	///     fn cell_text(&self, row: u32, column: u32) -> String {
+	///         match column {
+	///             0 => row.to_string(),
+	///             _ => (row as u64 * row as u64).to_string(),
+	///         }
+	///     }
+	/// }
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// let list = gui::VirtualListView::new(
+	///     &wnd,
+	///     gui::ListViewOpts {
+	///         size: (300, 200),
+	///         columns: vec![
+	///             ("Number".to_owned(), 100),
+	///             ("Square".to_owned(), 150),
+	///         ],
+	///         ..Default::default()
+	///     },
+	///     Squares,
+	/// );
+	/// ```
+	#[must_use]
+	pub fn new(parent: &impl GuiParent, opts: ListViewOpts, model: M) -> Self {
+		let parent_base_ref = unsafe { Base::from_guiparent(parent) };
+		let mut opts = opts;
+		opts.list_view_style |= co::LVS::OWNERDATA;
+
+		let new_self = Self {
+			list_view: ListView::new(parent, opts),
+			model: Arc::new(model),
+		};
+
+		let self2 = new_self.clone();
+		parent_base_ref.privileged_on().wm(parent_base_ref.wm_create_or_initdialog(), move |_| {
+			self2.init_control(); // control was created by ListView, which ran before
+			Ok(None) // not meaningful
+		});
+
+		new_self.default_message_handlers(parent_base_ref);
+		new_self
+	}
+
+	/// Instantiates a new `VirtualListView` object, to be loaded from a dialog
+	/// resource with
+	/// [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// The list view in the dialog resource must have the
+	/// [`LVS::OWNERDATA`](crate::co::LVS::OWNERDATA) style, which cannot be
+	/// changed after the control is created.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `VirtualListView` in an event closure.
+	#[must_use]
+	pub fn new_dlg(
+		parent: &impl GuiParent,
+		ctrl_id: u16,
+		resize_behavior: (Horz, Vert),
+		context_menu: Option<HMENU>,
+		model: M,
+	) -> Self
+	{
+		let parent_base_ref = unsafe { Base::from_guiparent(parent) };
+
+		let new_self = Self {
+			list_view: ListView::new_dlg(parent, ctrl_id, resize_behavior, context_menu),
+			model: Arc::new(model),
+		};
+
+		let self2 = new_self.clone();
+		parent_base_ref.privileged_on().wm_init_dialog(move |_| {
+			self2.init_control(); // control was loaded by ListView, which ran before
+			Ok(true) // not meaningful
+		});
+
+		new_self.default_message_handlers(parent_base_ref);
+		new_self
+	}
+
+	fn default_message_handlers(&self, parent: &Base) {
+		let ctrl_id = self.ctrl_id();
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::LVN::GETDISPINFO, move |p| {
+			let nmdi = unsafe { p.cast_nmhdr_mut::<NMLVDISPINFO>() };
+			let row = nmdi.item.iItem as u32;
+
+			if nmdi.item.mask.has(co::LVIF::TEXT) {
+				let (ptr, cch) = nmdi.item.raw_pszText();
+				if !ptr.is_null() && cch > 0 { // copy text into the control's own buffer
+					let dest = unsafe { std::slice::from_raw_parts_mut(ptr, cch as _) };
+					WString::from_str(self2.model.cell_text(row, nmdi.item.iSubItem as _))
+						.copy_to_slice(dest);
+				}
+			}
+
+			if nmdi.item.mask.has(co::LVIF::IMAGE) {
+				nmdi.item.iImage = self2.model.image(row)
+					.map_or(-2, |idx| idx as _); // I_IMAGENONE
+			}
+
+			if nmdi.item.mask.has(co::LVIF::STATE) && self2.has_checkboxes() {
+				let state_idx = if self2.model.is_checked(row) { 2 } else { 1 }; // INDEXTOSTATEIMAGEMASK
+				nmdi.item.stateMask |= co::LVIS::STATEIMAGEMASK;
+				nmdi.item.state = (nmdi.item.state & !co::LVIS::STATEIMAGEMASK)
+					| unsafe { co::LVIS::from_raw(state_idx << 12) };
+			}
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::LVN::ODCACHEHINT, move |p| {
+			let nmch = unsafe { p.cast_nmhdr::<NMLVCACHEHINT>() };
+			self2.model.cache_hint(nmch.iFrom as _, nmch.iTo as _);
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::LVN::ODFINDITEM, move |p| {
+			let nmfi = unsafe { p.cast_nmhdr::<NMLVFINDITEM>() };
+			if !nmfi.lvfi.flags.has(co::LVFI::STRING) {
+				return Ok(Some(-1)); // only text searches are supported
+			}
+
+			let text = nmfi.lvfi.psz().unwrap_or_default();
+			let partial = nmfi.lvfi.flags.has(co::LVFI::PARTIAL);
+			Ok(Some(
+				self2.model.find_row(&text, nmfi.iStart.max(0) as _, partial)
+					.map_or(-1, |row| row as _),
+			))
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::NM::CLICK, move |p| {
+			let nmia = unsafe { p.cast_nmhdr::<NMITEMACTIVATE>() };
+			if nmia.iItem != -1 && self2.has_checkboxes() {
+				let mut lvhti = LVHITTESTINFO {
+					pt: nmia.ptAction,
+					..Default::default()
+				};
+				self2.hwnd().SendMessage(lvm::HitTest { info: &mut lvhti });
+				if lvhti.flags.has(co::LVHT::ONITEMSTATEICON) { // clicked the checkbox itself
+					self2.toggle_check(nmia.iItem as _);
+				}
+			}
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		parent.privileged_on().wm_notify(ctrl_id, co::LVN::KEYDOWN, move |p| {
+			let lvnk = unsafe { p.cast_nmhdr::<NMLVKEYDOWN>() };
+			if lvnk.wVKey == co::VK::SPACE && self2.has_checkboxes() {
+				let rows = self2.selected_rows();
+				if let Some(first) = rows.first() {
+					let checked = !self2.model.is_checked(*first); // all selected follow the first
+					rows.iter().for_each(|row| self2.model.set_checked(*row, checked));
+					self2.redraw_rows(rows[0], rows[rows.len() - 1]);
+				}
+			}
+			Ok(None) // not meaningful
+		});
+	}
+
+	/// Exposes the column methods.
+	#[must_use]
+	pub const fn columns(&self) -> ListViewColumns<'_> {
+		self.list_view.columns()
+	}
+
+	/// Exposes the item methods.
+	///
+	/// **Note:** In virtual mode the items cannot be added or deleted, since
+	/// they're defined by the model; use
+	/// [`VirtualListView::refresh`](crate::gui::VirtualListView::refresh)
+	/// after changing it.
+	#[must_use]
+	pub const fn items(&self) -> ListViewItems<'_> {
+		self.list_view.items()
+	}
+
+	/// Returns the underlying [`ListView`](crate::gui::ListView), which exposes
+	/// the remaining methods, like views and image lists.
+	#[must_use]
+	pub const fn list_view(&self) -> &ListView {
+		&self.list_view
+	}
+
+	/// Returns the data model.
+	#[must_use]
+	pub fn model(&self) -> &M {
+		&self.model
+	}
+
+	/// Returns the row which has the focus, if any, by sending an
+	/// [`lvm::GetNextItem`](crate::msg::lvm::GetNextItem) message.
+	#[must_use]
+	pub fn focused_row(&self) -> Option<u32> {
+		self.items().focused().map(|item| item.index())
+	}
+
+	/// Updates the number of rows with the value returned by
+	/// [`GuiListViewModel::row_count`](crate::prelude::GuiListViewModel::row_count),
+	/// by sending an [`lvm::SetItemCount`](crate::msg::lvm::SetItemCount)
+	/// message, then repaints the control.
+	///
+	/// The scroll position is kept. Call this method whenever the model
+	/// changes.
+	pub fn refresh(&self) {
+		self.items().set_count(self.model.row_count(), Some(co::LVSICF::NOSCROLL));
+		self.hwnd().InvalidateRect(None, true).unwrap();
+	}
+
+	/// Repaints the given inclusive range of rows by sending an
+	/// [`lvm::RedrawItems`](crate::msg::lvm::RedrawItems) message, so their
+	/// contents are queried again from the model.
+	pub fn redraw_rows(&self, first: u32, last: u32) {
+		self.hwnd()
+			.SendMessage(lvm::RedrawItems { first_index: first, last_index: last })
+			.unwrap();
+	}
+
+	/// Returns the selected rows, in ascending order, by sending
+	/// [`lvm::GetNextItem`](crate::msg::lvm::GetNextItem) messages.
+	#[must_use]
+	pub fn selected_rows(&self) -> Vec<u32> {
+		self.items()
+			.iter_selected()
+			.map(|item| item.index())
+			.collect()
+	}
+
+	/// Sets or removes the selection of the given row, by sending an
+	/// [`lvm::SetItemState`](crate::msg::lvm::SetItemState) message. If
+	/// selected, the row is also focused and scrolled into view.
+	pub fn select_row(&self, row: u32, set: bool) {
+		let item = self.items().get(row);
+		item.select(set);
+		if set {
+			item.focus();
+			item.ensure_visible();
+		}
+	}
+
+	fn init_control(&self) {
+		// Without the callback mask, the control never asks for the checkbox
+		// state images in LVN_GETDISPINFO.
+		self.hwnd()
+			.SendMessage(lvm::SetCallbackMask { mask: co::LVIS::STATEIMAGEMASK })
+			.unwrap();
+		self.refresh();
+	}
+
+	fn has_checkboxes(&self) -> bool {
+		self.hwnd().SendMessage(lvm::GetExtendedListViewStyle {})
+			.has(co::LVS_EX::CHECKBOXES)
+	}
+
+	fn toggle_check(&self, row: u32) {
+		self.model.set_checked(row, !self.model.is_checked(row));
+		self.redraw_rows(row, row);
+	}
+}
@@ -0,0 +1,306 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{*, privs::*};
+use crate::prelude::*;
+
+type DrawPage = Box<dyn Fn(&HDC, u32, &PageMetrics) -> AnyResult<()>>;
+
+struct Obj { // actual fields of PrintPreview
+	container: WindowControl,
+	metrics: Cell<PageMetrics>,
+	page: Cell<u32>,
+	page_count: Cell<u32>,
+	gap: Cell<i32>, // multiplied by DPI at creation
+	draw_page: RefCell<Option<DrawPage>>,
+	_pin: PhantomPinned,
+}
+
+//------------------------------------------------------------------------------
+
+/// A control which displays a scaled preview of a printed page.
+///
+/// The page is drawn by the closure set in
+/// [`on_draw_page`](crate::gui::PrintPreview::on_draw_page), which receives a
+/// device context whose coordinates match those of the printer device context
+/// described by the [`PageMetrics`](crate::PageMetrics). This way, the same
+/// closure can be passed to [`print_document`](crate::print_document) to
+/// actually print the pages.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, gui};
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+///
+/// let preview = gui::PrintPreview::new(
+///     &wnd,
+///     gui::PrintPreviewOpts {
+///         size: (300, 400),
+///         page_count: 3,
+///         resize_behavior: (gui::Horz::Resize, gui::Vert::Resize),
+///         ..Default::default()
+///     },
+/// );
+///
+/// let margins = w::RECT { left: 1000, top: 1000, right: 1000, bottom: 1000 };
+///
+/// preview.on_draw_page(move |hdc, page, metrics| {
+///     let rc = metrics.content_rect(margins);
+///     hdc.TextOut(rc.left, rc.top, &format!("Page {}", page))?;
+///     Ok(())
+/// });
+/// ```
+#[derive(Clone)]
+pub struct PrintPreview(Pin<Arc<Obj>>);
+
+unsafe impl Send for PrintPreview {}
+
+impl GuiWindow for PrintPreview {
+	fn hwnd(&self) -> &HWND {
+		self.0.container.hwnd()
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+impl GuiChild for PrintPreview {
+	fn ctrl_id(&self) -> u16 {
+		self.0.container.ctrl_id()
+	}
+}
+
+impl PrintPreview {
+	/// Instantiates a new `PrintPreview` object, to be created on the parent
+	/// window with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `PrintPreview` in an event closure.
+	#[must_use]
+	pub fn new(parent: &impl GuiParent, opts: PrintPreviewOpts) -> Self {
+		let container = WindowControl::new(parent, WindowControlOpts {
+			class_bg_brush: Brush::Color(co::COLOR::APPWORKSPACE),
+			position: opts.position,
+			size: opts.size,
+			style: co::WS::CHILD | co::WS::VISIBLE | co::WS::CLIPSIBLINGS,
+			ctrl_id: opts.ctrl_id,
+			resize_behavior: opts.resize_behavior,
+			..Default::default()
+		});
+
+		let new_self = Self(
+			Arc::pin(
+				Obj {
+					container,
+					metrics: Cell::new(opts.page_metrics),
+					page: Cell::new(1),
+					page_count: Cell::new(opts.page_count.max(1)),
+					gap: Cell::new(opts.gap as _),
+					draw_page: RefCell::new(None),
+					_pin: PhantomPinned,
+				},
+			),
+		);
+		new_self.default_message_handlers();
+		new_self
+	}
+
+	/// Sets the closure which draws a page, receiving the device context, the
+	/// page number, starting at 1, and the metrics of the page.
+	///
+	/// The device context is scaled so that one logical unit corresponds to
+	/// one printer pixel, with the origin at the top-left corner of the
+	/// printable area.
+	pub fn on_draw_page<F>(&self, func: F)
+		where F: Fn(&HDC, u32, &PageMetrics) -> AnyResult<()> + 'static,
+	{
+		*self.0.draw_page.borrow_mut() = Some(Box::new(func));
+		self.repaint();
+	}
+
+	/// Returns the metrics of the previewed page.
+	#[must_use]
+	pub fn page_metrics(&self) -> PageMetrics {
+		self.0.metrics.get()
+	}
+
+	/// Sets the metrics of the previewed page, usually retrieved from the
+	/// printer with [`PageMetrics::from_hdc`](crate::PageMetrics::from_hdc).
+	pub fn set_page_metrics(&self, metrics: PageMetrics) {
+		self.0.metrics.set(metrics);
+		self.repaint();
+	}
+
+	/// Returns the currently displayed page number, starting at 1.
+	#[must_use]
+	pub fn page(&self) -> u32 {
+		self.0.page.get()
+	}
+
+	/// Displays the given page number, starting at 1. The number is clamped
+	/// to the page count.
+	pub fn set_page(&self, page: u32) {
+		self.0.page.set(page.clamp(1, self.0.page_count.get()));
+		self.repaint();
+	}
+
+	/// Returns the number of pages.
+	#[must_use]
+	pub fn page_count(&self) -> u32 {
+		self.0.page_count.get()
+	}
+
+	/// Sets the number of pages. If the current page is beyond the new count,
+	/// the last page is displayed.
+	pub fn set_page_count(&self, count: u32) {
+		self.0.page_count.set(count.max(1));
+		self.set_page(self.0.page.get());
+	}
+
+	fn repaint(&self) {
+		if *self.hwnd() != HWND::NULL {
+			self.hwnd().InvalidateRect(None, true).ok(); // ignore errors
+		}
+	}
+
+	fn default_message_handlers(&self) {
+		let self2 = self.clone();
+		self.0.container.on().wm_create(move |_| {
+			let mut sz_gap = SIZE::new(self2.0.gap.get(), 0);
+			multiply_dpi(None, Some(&mut sz_gap))?;
+			self2.0.gap.set(sz_gap.cx);
+			Ok(0)
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_size(move |_| {
+			self2.repaint(); // the page is centered, so everything moves
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_paint(move || {
+			let hdc = self2.hwnd().BeginPaint()?;
+			let rc = self2.hwnd().GetClientRect()?;
+			let gap = self2.0.gap.get();
+			let metrics = self2.0.metrics.get();
+
+			let rc_paper = metrics.fit_rect(RECT {
+				left: gap,
+				top: gap,
+				right: rc.right - gap,
+				bottom: rc.bottom - gap,
+			});
+			if rc_paper.right <= rc_paper.left || rc_paper.bottom <= rc_paper.top {
+				return Ok(()); // nothing to paint
+			}
+
+			let shadow = (gap / 3).max(1);
+			hdc.FillRect(
+				RECT {
+					left: rc_paper.left + shadow,
+					top: rc_paper.top + shadow,
+					right: rc_paper.right + shadow,
+					bottom: rc_paper.bottom + shadow,
+				},
+				&HBRUSH::GetSysColorBrush(co::COLOR::BTNSHADOW)?,
+			)?;
+			hdc.FillRect(rc_paper,
+				&HBRUSH::GetStockObject(co::STOCK_BRUSH::WHITE)?)?;
+
+			if let Some(draw_page) = self2.0.draw_page.borrow().as_ref() {
+				let saved = hdc.SaveDC()?;
+				let clip = HRGN::CreateRectRgnIndirect(rc_paper)?;
+				hdc.SelectClipRgn(&clip)?;
+
+				hdc.SetMapMode(co::MM::ANISOTROPIC)?; // printer pixels to screen pixels
+				hdc.SetWindowExtEx(metrics.paper.cx, metrics.paper.cy)?;
+				hdc.SetViewportExtEx(rc_paper.right - rc_paper.left,
+					rc_paper.bottom - rc_paper.top)?;
+				hdc.SetWindowOrgEx(-metrics.printable_offset.x, -metrics.printable_offset.y)?;
+				hdc.SetViewportOrgEx(rc_paper.left, rc_paper.top)?;
+
+				let res = draw_page(&hdc, self2.0.page.get(), &metrics);
+				hdc.RestoreDC(saved)?;
+				res?;
+			}
+			Ok(())
+		});
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Options to create a [`PrintPreview`](crate::gui::PrintPreview)
+/// programmatically with
+/// [`PrintPreview::new`](crate::gui::PrintPreview::new).
+pub struct PrintPreviewOpts {
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// If the parent window is a dialog, the values are in Dialog Template
+	/// Units; otherwise in pixels, which will be multiplied to match current
+	/// system DPI.
+	///
+	/// Defaults to `(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// If the parent window is a dialog, the values are in Dialog Template
+	/// Units; otherwise in pixels, which will be multiplied to match current
+	/// system DPI.
+	///
+	/// Defaults to `(200, 260)`.
+	pub size: (u32, u32),
+	/// Metrics of the previewed page.
+	///
+	/// Defaults to a Letter paper, 8.5 x 11 inches, at 600 DPI.
+	pub page_metrics: PageMetrics,
+	/// Number of pages.
+	///
+	/// Defaults to `1`.
+	pub page_count: u32,
+	/// Distance between the page and the control borders, in pixels, which
+	/// will be multiplied to match current system DPI.
+	///
+	/// Defaults to `10`.
+	pub gap: u32,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+}
+
+impl Default for PrintPreviewOpts {
+	fn default() -> Self {
+		Self {
+			position: (0, 0),
+			size: (200, 260),
+			page_metrics: PageMetrics::from_paper(SIZE::new(8500, 11000), 600),
+			page_count: 1,
+			gap: 10,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+		}
+	}
+}
@@ -0,0 +1,139 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::*;
+use crate::prelude::*;
+
+/// A modal
+/// [property sheet](https://learn.microsoft.com/en-us/windows/win32/controls/property-sheets),
+/// displaying a set of [`PropertySheetPage`](crate::gui::PropertySheetPage)
+/// objects either as tabs, with OK, Cancel and Apply buttons, or as an Aero
+/// wizard, with Back, Next and Finish buttons.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, co, gui, prelude::*};
+///
+/// fn show_settings(wnd: &gui::WindowMain) -> w::AnyResult<bool> {
+///     let page = gui::PropertySheetPage::new(
+///         gui::PropertySheetPageOpts {
+///             title: "General".to_owned(),
+///             ..Default::default()
+///         },
+///     );
+///     let txt = gui::Edit::new(&page, gui::EditOpts::default());
+///
+///     let txt2 = txt.clone();
+///     page.on_page().psn_kill_active(move |_| {
+///         Ok(!txt2.text().is_empty()) // invalid if empty
+///     });
+///     page.on_page().psn_apply(|_| {
+///         // save the settings...
+///         Ok(co::PSNRET::NOERROR)
+///     });
+///
+///     let sheet = gui::PropertySheet::new(
+///         gui::PropertySheetOpts {
+///             title: "Settings".to_owned(),
+///             ..Default::default()
+///         },
+///         &[&page],
+///     );
+///     sheet.show_modal(wnd)
+/// }
+/// ```
+pub struct PropertySheet {
+	opts: PropertySheetOpts,
+	pages: Vec<PropertySheetPage>,
+}
+
+impl PropertySheet {
+	/// Instantiates a new `PropertySheet` object, with the given pages.
+	#[must_use]
+	pub fn new(opts: PropertySheetOpts, pages: &[&PropertySheetPage]) -> Self {
+		Self {
+			opts,
+			pages: pages.iter().map(|page| (*page).clone()).collect(),
+		}
+	}
+
+	/// Physically creates the property sheet, and displays it as a modal
+	/// window. This method will block until the sheet is closed.
+	///
+	/// Returns `true` if the user saved the changes – that is, clicked OK,
+	/// Apply or Finish.
+	///
+	/// # Panics
+	///
+	/// Panics if one of the pages was already displayed.
+	pub fn show_modal(&self, parent: &impl GuiParent) -> AnyResult<bool> {
+		let num_pages = self.pages.len() as u32;
+		let mut texts = self.pages.iter()
+			.map(|page| page.texts())
+			.collect::<Vec<_>>();
+
+		let mut psps = Vec::<PROPSHEETPAGE>::with_capacity(self.pages.len());
+		for (idx, (page, page_texts)) in self.pages.iter().zip(texts.iter_mut()).enumerate() {
+			let mut psp = PROPSHEETPAGE::default();
+			page.fill_page(
+				&mut psp,
+				self.opts.wizard.then_some((idx as u32, num_pages)),
+				page_texts,
+			)?;
+			psps.push(psp);
+		}
+
+		let mut psh = PROPSHEETHEADER::default();
+		psh.dwFlags = co::PSH::PROPSHEETPAGE | co::PSH::NOCONTEXTHELP;
+		if self.opts.wizard {
+			psh.dwFlags |= co::PSH::WIZARD | co::PSH::AEROWIZARD;
+		} else if !self.opts.apply_button {
+			psh.dwFlags |= co::PSH::NOAPPLYNOW;
+		}
+		psh.hwndParent = unsafe { parent.hwnd().raw_copy() };
+		psh.hInstance = HINSTANCE::GetModuleHandle(None)?;
+
+		let mut title = WString::from_str(&self.opts.title);
+		psh.set_pszCaption(Some(&mut title));
+		psh.set_nStartPage(self.opts.start_page);
+		psh.set_ppsp(Some(&mut psps));
+
+		Ok(PropertySheet(&psh)?)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Options to create a [`PropertySheet`](crate::gui::PropertySheet) with
+/// [`PropertySheet::new`](crate::gui::PropertySheet::new).
+pub struct PropertySheetOpts {
+	/// Title of the property sheet window.
+	///
+	/// Defaults to empty string.
+	pub title: String,
+	/// Displays the pages as an Aero wizard, with Back, Next and Finish
+	/// buttons. Back, Next and Finish buttons are automatically enabled
+	/// according to the page position.
+	///
+	/// Defaults to `false`.
+	pub wizard: bool,
+	/// Zero-based index of the page initially displayed.
+	///
+	/// Defaults to `0`.
+	pub start_page: u32,
+	/// Displays the Apply button. Ignored in wizards.
+	///
+	/// Defaults to `true`.
+	pub apply_button: bool,
+}
+
+impl Default for PropertySheetOpts {
+	fn default() -> Self {
+		Self {
+			title: "".to_owned(),
+			wizard: false,
+			start_page: 0,
+			apply_button: true,
+		}
+	}
+}
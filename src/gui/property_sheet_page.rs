@@ -0,0 +1,306 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct Obj { // actual fields of PropertySheetPage
+	dlg_base: DlgBase,
+	opts: PropertySheetPageOpts,
+	template: Vec<u32>, // in-memory DLGTEMPLATE, DWORD-aligned; empty if loaded from resource
+	wizard_pos: Cell<Option<(u32, u32)>>, // page index and page count, if in a wizard
+	_pin: PhantomPinned,
+}
+
+//------------------------------------------------------------------------------
+
+/// A page of a [`PropertySheet`](crate::gui::PropertySheet), which can handle
+/// events. Can be programmatically created or load a dialog resource from a
+/// `.res` file.
+///
+/// Child controls are added to the page just like any other parent window.
+/// The page notifications are exposed by
+/// [`on_page`](crate::gui::PropertySheetPage::on_page).
+///
+/// A page can be displayed in a single property sheet, only once.
+#[derive(Clone)]
+pub struct PropertySheetPage(Pin<Arc<Obj>>);
+
+unsafe impl Send for PropertySheetPage {}
+
+impl GuiWindow for PropertySheetPage {
+	fn hwnd(&self) -> &HWND {
+		self.0.dlg_base.hwnd()
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+impl GuiParent for PropertySheetPage {
+	fn on(&self) -> &WindowEventsAll {
+		self.0.dlg_base.on()
+	}
+
+	unsafe fn as_base(&self) -> *mut std::ffi::c_void {
+		self.0.dlg_base.as_base()
+	}
+}
+
+impl GuiThread for PropertySheetPage {
+	fn spawn_new_thread<F>(&self, func: F)
+		where F: FnOnce() -> AnyResult<()> + Send + 'static,
+	{
+		self.0.dlg_base.spawn_new_thread(func);
+	}
+
+	fn run_ui_thread<F>(&self, func: F)
+		where F: FnOnce() -> AnyResult<()> + Send + 'static
+	{
+		self.0.dlg_base.run_ui_thread(func);
+	}
+}
+
+impl PropertySheetPage {
+	/// Instantiates a new `PropertySheetPage` object, to be created internally
+	/// by the [`PropertySheet`](crate::gui::PropertySheet) from an in-memory
+	/// dialog template.
+	#[must_use]
+	pub fn new(opts: PropertySheetPageOpts) -> Self {
+		let template = Self::build_template(&opts);
+		Self::create(0, opts, template)
+	}
+
+	/// Instantiates a new `PropertySheetPage` object, to be loaded from a
+	/// dialog resource by the [`PropertySheet`](crate::gui::PropertySheet).
+	///
+	/// The page title is the caption of the dialog resource.
+	#[must_use]
+	pub fn new_dlg(dialog_id: u16) -> Self {
+		Self::create(dialog_id, PropertySheetPageOpts::default(), Vec::default())
+	}
+
+	fn create(
+		dialog_id: u16,
+		opts: PropertySheetPageOpts,
+		template: Vec<u32>,
+	) -> Self
+	{
+		let new_self = Self(
+			Arc::pin(
+				Obj {
+					dlg_base: DlgBase::new(None, dialog_id),
+					opts,
+					template,
+					wizard_pos: Cell::new(None),
+					_pin: PhantomPinned,
+				},
+			),
+		);
+		new_self.default_message_handlers();
+		new_self
+	}
+
+	/// Exposes the specific property sheet page notifications.
+	///
+	/// # Panics
+	///
+	/// Panics if the page is already created. Events must be set before the
+	/// property sheet is displayed.
+	#[must_use]
+	pub fn on_page(&self) -> PropertySheetPageEvents {
+		PropertySheetPageEvents::new(unsafe { Base::from_guiparent(self) })
+	}
+
+	/// Returns the handle to the property sheet window, which is the parent of
+	/// the page.
+	///
+	/// # Panics
+	///
+	/// Panics if the page was not created yet.
+	#[must_use]
+	pub fn property_sheet_hwnd(&self) -> HWND {
+		self.hwnd().GetParent()
+			.unwrap_or_else(|_| panic!("Property sheet page not created yet."))
+	}
+
+	/// Enables or disables the Apply button of the property sheet, by sending
+	/// [`psm::Changed`](crate::msg::psm::Changed) or
+	/// [`psm::Unchanged`](crate::msg::psm::Unchanged) messages.
+	pub fn set_changed(&self, changed: bool) {
+		let hsheet = self.property_sheet_hwnd();
+		if changed {
+			hsheet.SendMessage(psm::Changed { hwnd_page: self.hwnd() });
+		} else {
+			hsheet.SendMessage(psm::Unchanged { hwnd_page: self.hwnd() });
+		}
+	}
+
+	/// Sets which buttons of a wizard are enabled, by sending a
+	/// [`psm::SetWizButtons`](crate::msg::psm::SetWizButtons) message.
+	pub fn set_wizard_buttons(&self, buttons: co::PSWIZB) {
+		self.property_sheet_hwnd()
+			.PostMessage(psm::SetWizButtons { buttons }) // must be posted when called in PSN_SETACTIVE
+			.unwrap_or_default();
+	}
+
+	/// Returns the title, header title and header subtitle, whose buffers must
+	/// be kept alive while the property sheet is displayed.
+	pub(in crate::gui) fn texts(&self) -> [WString; 3] {
+		let opts = &self.0.opts;
+		[&opts.title, &opts.header_title, &opts.header_subtitle]
+			.map(WString::from_str)
+	}
+
+	/// Fills the struct to be passed to the property sheet.
+	pub(in crate::gui) fn fill_page<'a, 'b>(&'b self,
+		psp: &mut PROPSHEETPAGE<'a, 'b>,
+		wizard_pos: Option<(u32, u32)>,
+		texts: &'a mut [WString; 3],
+	) -> SysResult<()>
+	{
+		self.0.dlg_base.fill_property_sheet_page(psp);
+		self.0.wizard_pos.set(wizard_pos);
+
+		if self.0.template.is_empty() {
+			psp.hInstance = HINSTANCE::GetModuleHandle(None)?;
+			psp.set_pszTemplate(self.0.dlg_base.dialog_id());
+		} else {
+			psp.dwFlags |= co::PSP::DLGINDIRECT;
+			psp.set_pResource(unsafe { &*(self.0.template.as_ptr() as *const DLGTEMPLATE) });
+		}
+
+		let opts = &self.0.opts;
+		let [title, header_title, header_subtitle] = texts;
+		if !opts.title.is_empty() {
+			psp.dwFlags |= co::PSP::USETITLE;
+			psp.set_pszTitle(Some(title));
+		}
+		if !opts.header_title.is_empty() {
+			psp.dwFlags |= co::PSP::USEHEADERTITLE;
+			psp.set_pszHeaderTitle(Some(header_title));
+		}
+		if !opts.header_subtitle.is_empty() {
+			psp.dwFlags |= co::PSP::USEHEADERSUBTITLE;
+			psp.set_pszHeaderSubTitle(Some(header_subtitle));
+		}
+		Ok(())
+	}
+
+	/// Builds the in-memory dialog template for a programmatically created
+	/// page.
+	fn build_template(opts: &PropertySheetPageOpts) -> Vec<u32> {
+		let style = co::WS::CHILD | co::WS::DISABLED | co::WS::CAPTION
+			| co::WS::from(co::DS::LOOK3D | co::DS::CONTROL | co::DS::SHELLFONT);
+
+		let mut words = Vec::<u16>::with_capacity(64);
+		words.extend([ // DLGTEMPLATE fields, packed
+			style.raw() as u16, (style.raw() >> 16) as u16,
+			0x0000, 0x0000, // dwExtendedStyle
+			0x0000, // cdit
+			0x0000, 0x0000, // x, y
+			opts.size.0 as u16, opts.size.1 as u16, // cx, cy
+		]);
+		words.push(0x0000); // no menu
+		words.push(0x0000); // default dialog class
+		words.extend(opts.title.encode_utf16().chain(std::iter::once(0))); // caption
+		words.push(8); // font point size, required by DS_SETFONT
+		words.extend("MS Shell Dlg".encode_utf16().chain(std::iter::once(0)));
+
+		words.resize(words.len().next_multiple_of(2), 0x0000); // pad to DWORD
+		words.chunks_exact(2)
+			.map(|pair| (pair[0] as u32) | ((pair[1] as u32) << 16))
+			.collect()
+	}
+
+	fn default_message_handlers(&self) {
+		let self2 = self.clone();
+		self.0.dlg_base.privileged_on().wm_notify(0, co::PSN::SETACTIVE, move |p| {
+			if let Some((index, count)) = self2.0.wizard_pos.get() {
+				let mut buttons = co::PSWIZB::NEXT;
+				if index > 0 {
+					buttons |= co::PSWIZB::BACK;
+				}
+				if index + 1 == count {
+					buttons = (buttons & !co::PSWIZB::NEXT) | co::PSWIZB::FINISH;
+				}
+				p.nmhdr.hwndFrom.PostMessage(psm::SetWizButtons { buttons })?;
+			}
+			Ok(None) // not meaningful
+		});
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Navigation result of
+/// [`psn_wiz_back`](crate::gui::events::PropertySheetPageEvents::psn_wiz_back)
+/// and
+/// [`psn_wiz_next`](crate::gui::events::PropertySheetPageEvents::psn_wiz_next)
+/// events, which allows page skipping in wizards.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WizardNav {
+	/// Moves to the previous or next page, as usual.
+	Default,
+	/// Stays in the current page.
+	Stay,
+	/// Jumps to the page at the given zero-based index. If there is no such
+	/// page, stays in the current one.
+	Goto(u32),
+}
+
+impl WizardNav {
+	/// Returns the value to be passed back in `PSN_WIZBACK` and `PSN_WIZNEXT`;
+	/// to jump, that's the identifier of the target page, and the sheet itself
+	/// moves after the notification returns.
+	pub(in crate::gui) fn apply(self, hsheet: &HWND) -> isize {
+		match self {
+			Self::Default => 0,
+			Self::Stay => -1,
+			Self::Goto(index) => hsheet.SendMessage(psm::IndexToId { index })
+				.unwrap_or(-1),
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Options to create a [`PropertySheetPage`](crate::gui::PropertySheetPage)
+/// programmatically with
+/// [`PropertySheetPage::new`](crate::gui::PropertySheetPage::new).
+pub struct PropertySheetPageOpts {
+	/// Page title, displayed in the tab or in the wizard title bar.
+	///
+	/// Defaults to empty string.
+	pub title: String,
+	/// Width and height of the page, in Dialog Template Units.
+	///
+	/// Defaults to `(252, 218)`, the size of a large property sheet page.
+	pub size: (u32, u32),
+	/// Title displayed in the header area of a wizard page.
+	///
+	/// Defaults to empty string.
+	pub header_title: String,
+	/// Subtitle displayed in the header area of a wizard page.
+	///
+	/// Defaults to empty string.
+	pub header_subtitle: String,
+}
+
+impl Default for PropertySheetPageOpts {
+	fn default() -> Self {
+		Self {
+			title: "".to_owned(),
+			size: (252, 218),
+			header_title: "".to_owned(),
+			header_subtitle: "".to_owned(),
+		}
+	}
+}
@@ -290,21 +290,41 @@ impl RawBase {
 
 		// Execute privileged closures, keep track if at least one was executed.
 		let ref_self = unsafe { &mut *ptr_self };
-		let at_least_one_privileged = ref_self.base.process_privileged_messages(wm_any)?;
+		let privileged_result = ref_self.base.process_privileged_messages(wm_any)?;
+		let at_least_one_privileged = !matches!(privileged_result, ProcessResult::NotHandled);
+
+		// Execute user closure, if any; if it doesn't answer, a privileged
+		// notification closure may still have answered.
+		let process_result = match ref_self.base.process_user_message(wm_any)? {
+			ProcessResult::NotHandled => match privileged_result {
+				ProcessResult::HandledWithRet(res) => ProcessResult::HandledWithRet(res),
+				_ => ProcessResult::NotHandled,
+			},
+			user_result => user_result,
+		};
 
-		// Execute user closure, if any.
-		let process_result = ref_self.base.process_user_message(wm_any)?;
+		match wm_any.msg_id {
+			co::WM::CREATE => dark_mode_window_created(&hwnd), // children already created
+			co::WM::WININICHANGE => dark_mode_setting_change(wm_any),
+			co::WM::INPUT if !matches!(process_result, ProcessResult::NotHandled) => {
+				hwnd.DefWindowProc(wm_any); // let the system release the raw input data
+			},
+			_ => {},
+		}
 
 		if wm_any.msg_id == co::WM::NCDESTROY { // always check
 			hwnd.SetWindowLongPtr(co::GWLP::USERDATA, 0); // clear passed pointer
 			ref_self.base.set_hwnd(HWND::NULL); // clear stored HWND
 			ref_self.base.clear_events(); // prevents circular references
+			drop_local_tasks(&hwnd); // tasks woken through this window would leak
 		}
 
 		Ok(match process_result {
 			ProcessResult::HandledWithRet(res) => res,
 			ProcessResult::HandledWithoutRet => 0,
-			ProcessResult::NotHandled => if at_least_one_privileged {
+			ProcessResult::NotHandled => if let Some(res) = dark_mode_default_proc(&hwnd, wm_any) {
+				res
+			} else if at_least_one_privileged {
 				0
 			} else {
 				hwnd.DefWindowProc(wm_any).into()
@@ -0,0 +1,419 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{*, events::*, privs::*};
+use crate::prelude::*;
+
+struct Obj { // actual fields of ScrollPanel
+	viewport: WindowControl,
+	canvas: WindowControl,
+	content_size: Cell<SIZE>, // in pixels; zero until computed
+	scroll_pos: Cell<POINT>,
+	_pin: PhantomPinned,
+}
+
+//------------------------------------------------------------------------------
+
+/// A container with scroll bars, whose content can be larger than the visible
+/// area.
+///
+/// Child controls are added to the panel just like any other parent window;
+/// they are actually placed on an internal canvas, which is moved when the
+/// user scrolls with the scroll bars or the mouse wheel. The canvas is never
+/// smaller than the visible area, so children with a resize behavior are
+/// rearranged as usual when the panel grows.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, gui};
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+///
+/// let panel = gui::ScrollPanel::new(
+///     &wnd,
+///     gui::ScrollPanelOpts {
+///         size: (300, 200),
+///         content_size: (300, 1000),
+///         resize_behavior: (gui::Horz::Resize, gui::Vert::Resize),
+///         ..Default::default()
+///     },
+/// );
+///
+/// let labels = (0..40)
+///     .map(|i| gui::Label::new(
+///         &panel,
+///         gui::LabelOpts {
+///             text: format!("Item {}", i),
+///             position: (10, 10 + i * 24),
+///             ..Default::default()
+///         },
+///     ))
+///     .collect::<Vec<_>>();
+/// ```
+#[derive(Clone)]
+pub struct ScrollPanel(Pin<Arc<Obj>>);
+
+unsafe impl Send for ScrollPanel {}
+
+impl GuiWindow for ScrollPanel {
+	fn hwnd(&self) -> &HWND {
+		self.0.viewport.hwnd()
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+impl GuiParent for ScrollPanel {
+	fn on(&self) -> &WindowEventsAll {
+		self.0.canvas.on()
+	}
+
+	unsafe fn as_base(&self) -> *mut std::ffi::c_void {
+		self.0.canvas.as_base()
+	}
+}
+
+impl GuiThread for ScrollPanel {
+	fn spawn_new_thread<F>(&self, func: F)
+		where F: FnOnce() -> AnyResult<()> + Send + 'static,
+	{
+		self.0.canvas.spawn_new_thread(func);
+	}
+
+	fn run_ui_thread<F>(&self, func: F)
+		where F: FnOnce() -> AnyResult<()> + Send + 'static
+	{
+		self.0.canvas.run_ui_thread(func);
+	}
+}
+
+impl GuiChild for ScrollPanel {
+	fn ctrl_id(&self) -> u16 {
+		self.0.viewport.ctrl_id()
+	}
+}
+
+impl ScrollPanel {
+	/// Amount of pixels scrolled by a single scroll bar arrow click, or by each
+	/// line of a mouse wheel notch.
+	const LINE: i32 = 20;
+
+	/// Instantiates a new `ScrollPanel` object, to be created on the parent
+	/// window with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `ScrollPanel` in an event closure.
+	#[must_use]
+	pub fn new(parent: &impl GuiParent, opts: ScrollPanelOpts) -> Self {
+		let viewport = WindowControl::new(parent, WindowControlOpts {
+			position: opts.position,
+			size: opts.size,
+			style: co::WS::CHILD | co::WS::VISIBLE | co::WS::CLIPCHILDREN
+				| co::WS::CLIPSIBLINGS | co::WS::HSCROLL | co::WS::VSCROLL,
+			ex_style: co::WS_EX::CONTROLPARENT,
+			ctrl_id: opts.ctrl_id,
+			resize_behavior: opts.resize_behavior,
+			..Default::default()
+		});
+
+		let canvas = WindowControl::new(&viewport, WindowControlOpts {
+			style: co::WS::CHILD | co::WS::VISIBLE | co::WS::CLIPCHILDREN | co::WS::CLIPSIBLINGS,
+			ex_style: co::WS_EX::CONTROLPARENT,
+			..Default::default()
+		});
+
+		let new_self = Self(
+			Arc::pin(
+				Obj {
+					viewport,
+					canvas,
+					content_size: Cell::new(SIZE::new(opts.content_size.0 as _, opts.content_size.1 as _)),
+					scroll_pos: Cell::new(POINT::new(0, 0)),
+					_pin: PhantomPinned,
+				},
+			),
+		);
+		new_self.default_message_handlers();
+		new_self
+	}
+
+	/// Returns the internal canvas, where the child controls are actually
+	/// placed.
+	#[must_use]
+	pub fn canvas(&self) -> &WindowControl {
+		&self.0.canvas
+	}
+
+	/// Returns the current horizontal and vertical scroll positions, in
+	/// pixels.
+	#[must_use]
+	pub fn scroll_pos(&self) -> POINT {
+		self.0.scroll_pos.get()
+	}
+
+	/// Scrolls the content to the given horizontal and vertical positions, in
+	/// pixels. Values out of range are clamped.
+	pub fn scroll_to(&self, x: i32, y: i32) -> SysResult<()> {
+		let (max_x, max_y) = self.max_scroll()?;
+		let new_pos = POINT::new(x.clamp(0, max_x), y.clamp(0, max_y));
+		let old_pos = self.0.scroll_pos.replace(new_pos);
+
+		if new_pos != old_pos {
+			self.hwnd().ScrollWindowEx(old_pos.x - new_pos.x, old_pos.y - new_pos.y,
+				None, None, None, None,
+				co::SCROLLW::SCROLLCHILDREN | co::SCROLLW::INVALIDATE | co::SCROLLW::ERASE)?;
+		}
+		self.update_scroll_bars()
+	}
+
+	/// Sets the size of the scrollable content, in pixels, which will be
+	/// multiplied to match current system DPI.
+	pub fn set_content_size(&self, width: u32, height: u32) -> SysResult<()> {
+		let mut sz = SIZE::new(width as _, height as _);
+		multiply_dpi(None, Some(&mut sz))?;
+		self.0.content_size.set(sz);
+		self.arrange()
+	}
+
+	/// Returns the maximum horizontal and vertical scroll positions, in pixels.
+	fn max_scroll(&self) -> SysResult<(i32, i32)> {
+		let rc = self.hwnd().GetClientRect()?;
+		let content = self.0.content_size.get();
+		Ok(((content.cx - rc.right).max(0), (content.cy - rc.bottom).max(0)))
+	}
+
+	fn update_scroll_bars(&self) -> SysResult<()> {
+		let rc = self.hwnd().GetClientRect()?;
+		let content = self.0.content_size.get();
+		let pos = self.0.scroll_pos.get();
+
+		for (bar, max, page, pos) in [
+			(co::SBB::HORZ, content.cx, rc.right, pos.x),
+			(co::SBB::VERT, content.cy, rc.bottom, pos.y),
+		] {
+			let mut si = SCROLLINFO::default();
+			si.fMask = co::SIF::RANGE | co::SIF::PAGE | co::SIF::POS;
+			si.nMin = 0;
+			si.nMax = (max - 1).max(0);
+			si.nPage = page.max(0) as _;
+			si.nPos = pos;
+			self.hwnd().SetScrollInfo(bar, &si, true);
+		}
+		Ok(())
+	}
+
+	/// Clamps the scroll position, updates the scroll bars, and places the
+	/// canvas accordingly.
+	fn arrange(&self) -> SysResult<()> {
+		let (max_x, max_y) = self.max_scroll()?;
+		let pos = self.0.scroll_pos.get();
+		let pos = POINT::new(pos.x.min(max_x), pos.y.min(max_y));
+		self.0.scroll_pos.set(pos);
+		self.update_scroll_bars()?;
+
+		let rc = self.hwnd().GetClientRect()?; // may have changed with scroll bars
+		let content = self.0.content_size.get();
+		self.0.canvas.hwnd().SetWindowPos(HwndPlace::None,
+			POINT::new(-pos.x, -pos.y),
+			SIZE::new(content.cx.max(rc.right), content.cy.max(rc.bottom)),
+			co::SWP::NOZORDER | co::SWP::NOACTIVATE)
+	}
+
+	/// Computes the bounding box of the canvas children.
+	fn children_bounds(&self) -> SysResult<SIZE> {
+		let hcanvas = self.0.canvas.hwnd();
+		let mut bounds = SIZE::new(0, 0);
+		let mut hchild = hcanvas.GetWindow(co::GW::CHILD).ok();
+		while let Some(h) = hchild {
+			let mut rc = h.GetWindowRect()?;
+			hcanvas.ScreenToClientRc(&mut rc)?;
+			bounds.cx = bounds.cx.max(rc.right);
+			bounds.cy = bounds.cy.max(rc.bottom);
+			hchild = h.GetWindow(co::GW::HWNDNEXT).ok();
+		}
+		Ok(bounds)
+	}
+
+	/// Computes the new scroll position after a scroll bar request.
+	fn scroll_bar_target(&self,
+		bar: co::SBB,
+		request: co::SB_REQ,
+		cur: i32,
+		page: i32,
+		max: i32,
+	) -> SysResult<Option<i32>>
+	{
+		Ok(Some(match request {
+			co::SB_REQ::LINEUP => cur - Self::LINE,
+			co::SB_REQ::LINEDOWN => cur + Self::LINE,
+			co::SB_REQ::PAGEUP => cur - page,
+			co::SB_REQ::PAGEDOWN => cur + page,
+			co::SB_REQ::TOP => 0,
+			co::SB_REQ::BOTTOM => max,
+			co::SB_REQ::THUMBTRACK | co::SB_REQ::THUMBPOSITION => {
+				let mut si = SCROLLINFO::default();
+				si.fMask = co::SIF::TRACKPOS; // 32-bit position, not truncated
+				self.hwnd().GetScrollInfo(bar, &mut si)?;
+				si.nTrackPos
+			},
+			_ => return Ok(None),
+		}))
+	}
+
+	fn default_message_handlers(&self) {
+		let self2 = self.clone();
+		self.0.viewport.on().wm_create(move |_| {
+			let content = self2.0.content_size.get();
+			if content.cx == 0 && content.cy == 0 {
+				self2.0.content_size.set(self2.children_bounds()?); // already in pixels
+			} else {
+				let mut sz = content;
+				multiply_dpi(None, Some(&mut sz))?;
+				self2.0.content_size.set(sz);
+			}
+			self2.arrange()?;
+			Ok(0)
+		});
+
+		let self2 = self.clone();
+		self.0.viewport.on().wm_size(move |_| {
+			self2.arrange()?;
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.viewport.on().wm_h_scroll(move |p| {
+			let rc = self2.hwnd().GetClientRect()?;
+			let (max_x, _) = self2.max_scroll()?;
+			let pos = self2.0.scroll_pos.get();
+			if let Some(x) = self2.scroll_bar_target(co::SBB::HORZ,
+				p.request, pos.x, rc.right, max_x)?
+			{
+				self2.scroll_to(x, pos.y)?;
+			}
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.viewport.on().wm_v_scroll(move |p| {
+			let rc = self2.hwnd().GetClientRect()?;
+			let (_, max_y) = self2.max_scroll()?;
+			let pos = self2.0.scroll_pos.get();
+			if let Some(y) = self2.scroll_bar_target(co::SBB::VERT,
+				p.request, pos.y, rc.bottom, max_y)?
+			{
+				self2.scroll_to(pos.x, y)?;
+			}
+			Ok(())
+		});
+
+		// Wheel messages are sent to the focused window, and bubble up to the
+		// parents through DefWindowProc, so they're caught here even when the
+		// focus is on a child control.
+		let self2 = self.clone();
+		self.0.viewport.on().wm_mouse_wheel(move |p| {
+			let rc = self2.0.viewport.hwnd().GetClientRect()?;
+			let horz = p.keys.has(co::MK::SHIFT);
+			let delta = -wheel_pixels(p.wheel_delta, co::SPI::GETWHEELSCROLLLINES,
+				if horz { rc.right } else { rc.bottom })?;
+			let pos = self2.0.scroll_pos.get();
+			if horz {
+				self2.scroll_to(pos.x + delta, pos.y)?;
+			} else {
+				self2.scroll_to(pos.x, pos.y + delta)?;
+			}
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.viewport.on().wm_mouse_h_wheel(move |p| {
+			let rc = self2.0.viewport.hwnd().GetClientRect()?;
+			let delta = wheel_pixels(p.wheel_delta, co::SPI::GETWHEELSCROLLCHARS, rc.right)?;
+			let pos = self2.0.scroll_pos.get();
+			self2.scroll_to(pos.x + delta, pos.y)?; // positive delta tilts right
+			Ok(())
+		});
+	}
+}
+
+/// Converts a mouse wheel delta into pixels, scrolling the number of lines per
+/// notch set by the user, retrieved with the given `SPI` constant; if the user
+/// chose to scroll one screen at a time, `page` pixels are scrolled per notch.
+fn wheel_pixels(wheel_delta: i16, spi: co::SPI, page: i32) -> SysResult<i32> {
+	let mut lines = 3u32; // system default
+	unsafe { SystemParametersInfo(spi, 0, &mut lines, co::SPIF::NoValue)?; }
+	let per_notch = if lines == u32::MAX { // WHEEL_PAGESCROLL
+		page
+	} else {
+		lines as i32 * ScrollPanel::LINE
+	};
+	Ok(wheel_delta as i32 * per_notch / 120)
+}
+
+//------------------------------------------------------------------------------
+
+/// Options to create a [`ScrollPanel`](crate::gui::ScrollPanel)
+/// programmatically with [`ScrollPanel::new`](crate::gui::ScrollPanel::new).
+pub struct ScrollPanelOpts {
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// If the parent window is a dialog, the values are in Dialog Template
+	/// Units; otherwise in pixels, which will be multiplied to match current
+	/// system DPI.
+	///
+	/// Defaults to `(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of the visible area to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// If the parent window is a dialog, the values are in Dialog Template
+	/// Units; otherwise in pixels, which will be multiplied to match current
+	/// system DPI.
+	///
+	/// Defaults to `(200, 150)`.
+	pub size: (u32, u32),
+	/// Width and height of the scrollable content, in pixels, which will be
+	/// multiplied to match current system DPI.
+	///
+	/// If `(0, 0)`, the content size is computed from the bounding box of the
+	/// child controls, when the panel is created.
+	///
+	/// Defaults to `(0, 0)`.
+	pub content_size: (u32, u32),
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+}
+
+impl Default for ScrollPanelOpts {
+	fn default() -> Self {
+		Self {
+			position: (0, 0),
+			size: (200, 150),
+			content_size: (0, 0),
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+		}
+	}
+}
@@ -0,0 +1,366 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{*, privs::*};
+use crate::prelude::*;
+
+/// Direction of a [`Splitter`](crate::gui::Splitter).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SplitterDir {
+	/// Panes are placed side by side, left and right, separated by a vertical
+	/// bar.
+	Horz,
+	/// Panes are stacked, top and bottom, separated by an horizontal bar.
+	Vert,
+}
+
+struct Obj { // actual fields of Splitter
+	container: WindowControl,
+	panes: [WindowControl; 2],
+	dir: SplitterDir,
+	ratio: Cell<f32>,
+	min_pane_sizes: Cell<(i32, i32)>, // multiplied by DPI at creation
+	bar_width: Cell<i32>, // multiplied by DPI at creation
+	drag_offset: Cell<Option<i32>>, // cursor offset within the bar; set only while dragging, with the mouse captured
+	_pin: PhantomPinned,
+}
+
+//------------------------------------------------------------------------------
+
+/// A container with two panes separated by a bar, which can be dragged by the
+/// user to resize the panes.
+///
+/// Each pane is a [`WindowControl`](crate::gui::WindowControl), where child
+/// controls can be placed; pane children can have their own resize behavior,
+/// which is applied whenever the pane is resized. The splitter itself is
+/// resized along with its parent according to
+/// [`SplitterOpts::resize_behavior`](crate::gui::SplitterOpts::resize_behavior),
+/// keeping the ratio between the panes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, gui};
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+///
+/// let splitter = gui::Splitter::new(
+///     &wnd,
+///     gui::SplitterOpts {
+///         size: (400, 300),
+///         resize_behavior: (gui::Horz::Resize, gui::Vert::Resize),
+///         ..Default::default()
+///     },
+/// );
+///
+/// let tree = gui::TreeView::new(
+///     splitter.first_pane(),
+///     gui::TreeViewOpts {
+///         size: (100, 100),
+///         resize_behavior: (gui::Horz::Resize, gui::Vert::Resize),
+///         ..Default::default()
+///     },
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Splitter(Pin<Arc<Obj>>);
+
+unsafe impl Send for Splitter {}
+
+impl GuiWindow for Splitter {
+	fn hwnd(&self) -> &HWND {
+		self.0.container.hwnd()
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+impl GuiChild for Splitter {
+	fn ctrl_id(&self) -> u16 {
+		self.0.container.ctrl_id()
+	}
+}
+
+impl Splitter {
+	/// Instantiates a new `Splitter` object, to be created on the parent window
+	/// with [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `Splitter` in an event closure.
+	#[must_use]
+	pub fn new(parent: &impl GuiParent, opts: SplitterOpts) -> Self {
+		let container = WindowControl::new(parent, WindowControlOpts {
+			class_cursor: Cursor::Idc(match opts.dir {
+				SplitterDir::Horz => co::IDC::SIZEWE,
+				SplitterDir::Vert => co::IDC::SIZENS,
+			}),
+			class_bg_brush: Brush::Color(co::COLOR::BTNFACE),
+			position: opts.position,
+			size: opts.size,
+			style: co::WS::CHILD | co::WS::VISIBLE | co::WS::CLIPCHILDREN | co::WS::CLIPSIBLINGS,
+			ex_style: co::WS_EX::CONTROLPARENT,
+			ctrl_id: opts.ctrl_id,
+			resize_behavior: opts.resize_behavior,
+			..Default::default()
+		});
+
+		let pane_opts = || WindowControlOpts {
+			style: co::WS::CHILD | co::WS::VISIBLE | co::WS::CLIPCHILDREN | co::WS::CLIPSIBLINGS,
+			ex_style: co::WS_EX::CONTROLPARENT,
+			..Default::default()
+		};
+		let panes = [
+			WindowControl::new(&container, pane_opts()),
+			WindowControl::new(&container, pane_opts()),
+		];
+
+		let new_self = Self(
+			Arc::pin(
+				Obj {
+					container,
+					panes,
+					dir: opts.dir,
+					ratio: Cell::new(opts.ratio.clamp(0.0, 1.0)),
+					min_pane_sizes: Cell::new((opts.min_pane_sizes.0 as _, opts.min_pane_sizes.1 as _)),
+					bar_width: Cell::new(opts.bar_width as _),
+					drag_offset: Cell::new(None),
+					_pin: PhantomPinned,
+				},
+			),
+		);
+		new_self.default_message_handlers();
+		new_self
+	}
+
+	/// Returns the first pane, which is at left or at top.
+	#[must_use]
+	pub fn first_pane(&self) -> &WindowControl {
+		&self.0.panes[0]
+	}
+
+	/// Returns the second pane, which is at right or at bottom.
+	#[must_use]
+	pub fn second_pane(&self) -> &WindowControl {
+		&self.0.panes[1]
+	}
+
+	/// Returns the current ratio between the first pane and the whole
+	/// splitter, from `0.0` to `1.0`.
+	///
+	/// The ratio is updated when the user drags the bar, and it's kept when
+	/// the splitter is resized, so it can be persisted and restored later with
+	/// [`set_ratio`](crate::gui::Splitter::set_ratio).
+	#[must_use]
+	pub fn ratio(&self) -> f32 {
+		self.0.ratio.get()
+	}
+
+	/// Sets the ratio between the first pane and the whole splitter, from `0.0`
+	/// to `1.0`, rearranging the panes if the splitter is already created.
+	///
+	/// Minimum pane sizes are respected when rearranging.
+	pub fn set_ratio(&self, ratio: f32) -> SysResult<()> {
+		self.0.ratio.set(ratio.clamp(0.0, 1.0));
+		if *self.hwnd() != HWND::NULL {
+			self.arrange_panes()?;
+		}
+		Ok(())
+	}
+
+	/// Returns the total length of the splitter along its direction, and the
+	/// size of the first pane, in pixels.
+	fn lengths(&self) -> SysResult<(i32, i32)> {
+		let rc = self.hwnd().GetClientRect()?;
+		let total = match self.0.dir {
+			SplitterDir::Horz => rc.right,
+			SplitterDir::Vert => rc.bottom,
+		};
+		let (min1, min2) = self.0.min_pane_sizes.get();
+		let first = first_pane_length(total, self.0.bar_width.get(),
+			self.0.ratio.get(), min1, min2);
+		Ok((total, first))
+	}
+
+	fn arrange_panes(&self) -> SysResult<()> {
+		let rc = self.hwnd().GetClientRect()?;
+		let (total, first) = self.lengths()?;
+		let second_start = first + self.0.bar_width.get();
+		let second = (total - second_start).max(0);
+
+		let (pos1, sz1, pos2, sz2) = match self.0.dir {
+			SplitterDir::Horz => (
+				POINT::new(0, 0), SIZE::new(first, rc.bottom),
+				POINT::new(second_start, 0), SIZE::new(second, rc.bottom),
+			),
+			SplitterDir::Vert => (
+				POINT::new(0, 0), SIZE::new(rc.right, first),
+				POINT::new(0, second_start), SIZE::new(rc.right, second),
+			),
+		};
+
+		let mut hdwp = HDWP::BeginDeferWindowPos(2)?;
+		hdwp.DeferWindowPos(self.0.panes[0].hwnd(), HwndPlace::None,
+			pos1, sz1, co::SWP::NOZORDER | co::SWP::NOACTIVATE)?;
+		hdwp.DeferWindowPos(self.0.panes[1].hwnd(), HwndPlace::None,
+			pos2, sz2, co::SWP::NOZORDER | co::SWP::NOACTIVATE)?;
+		Ok(())
+	}
+
+	fn default_message_handlers(&self) {
+		let self2 = self.clone();
+		self.0.container.on().wm_create(move |_| {
+			let (min1, min2) = self2.0.min_pane_sizes.get();
+			let mut sz_mins = SIZE::new(min1, min2);
+			let mut sz_bar = SIZE::new(self2.0.bar_width.get(), 0);
+			multiply_dpi(None, Some(&mut sz_mins))?;
+			multiply_dpi(None, Some(&mut sz_bar))?;
+			self2.0.min_pane_sizes.set((sz_mins.cx, sz_mins.cy));
+			self2.0.bar_width.set(sz_bar.cx);
+
+			self2.arrange_panes()?; // panes are already created by now
+			Ok(0)
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_size(move |_| {
+			self2.arrange_panes()?;
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_l_button_down(move |p| {
+			let (_, first) = self2.lengths()?;
+			let cursor = self2.coord_along(p.coords);
+
+			// The capture outlives this handler: it's released on button up,
+			// or taken by another window, both firing WM_CAPTURECHANGED.
+			std::mem::forget(self2.hwnd().SetCapture());
+			self2.0.drag_offset.set(Some(cursor - first));
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_mouse_move(move |p| {
+			if let Some(offset) = self2.0.drag_offset.get() {
+				let (total, _) = self2.lengths()?;
+				let avail = total - self2.0.bar_width.get();
+				if avail > 0 {
+					let first = self2.coord_along(p.coords) - offset;
+					self2.0.ratio.set((first as f32 / avail as f32).clamp(0.0, 1.0));
+					self2.arrange_panes()?;
+				}
+			}
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_l_button_up(move |_| {
+			if self2.0.drag_offset.get().is_some() {
+				ReleaseCapture()?; // fires WM_CAPTURECHANGED
+			}
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		self.0.container.on().wm_capture_changed(move |_| {
+			self2.0.drag_offset.set(None); // dragging finished, the capture is gone
+			Ok(())
+		});
+	}
+
+	fn coord_along(&self, pt: POINT) -> i32 {
+		match self.0.dir {
+			SplitterDir::Horz => pt.x as i16 as _, // may be negative while captured
+			SplitterDir::Vert => pt.y as i16 as _,
+		}
+	}
+}
+
+/// Computes the length of the first pane, given the total splitter length, the
+/// bar width, the desired ratio and the minimum pane lengths. The first pane
+/// minimum length takes precedence.
+fn first_pane_length(total: i32, bar: i32, ratio: f32, min1: i32, min2: i32) -> i32 {
+	let avail = (total - bar).max(0);
+	let first = (avail as f32 * ratio).round() as i32;
+	first.min(avail - min2).max(min1).min(avail)
+}
+
+//------------------------------------------------------------------------------
+
+/// Options to create a [`Splitter`](crate::gui::Splitter) programmatically
+/// with [`Splitter::new`](crate::gui::Splitter::new).
+pub struct SplitterOpts {
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// If the parent window is a dialog, the values are in Dialog Template
+	/// Units; otherwise in pixels, which will be multiplied to match current
+	/// system DPI.
+	///
+	/// Defaults to `(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of the whole splitter to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// If the parent window is a dialog, the values are in Dialog Template
+	/// Units; otherwise in pixels, which will be multiplied to match current
+	/// system DPI.
+	///
+	/// Defaults to `(200, 150)`.
+	pub size: (u32, u32),
+	/// Direction of the splitter.
+	///
+	/// Defaults to `gui::SplitterDir::Horz`.
+	pub dir: SplitterDir,
+	/// Initial ratio between the first pane and the whole splitter, from `0.0`
+	/// to `1.0`.
+	///
+	/// Defaults to `0.5`.
+	pub ratio: f32,
+	/// Minimum length of the first and the second panes, in pixels, which will
+	/// be multiplied to match current system DPI.
+	///
+	/// Defaults to `(20, 20)`.
+	pub min_pane_sizes: (u32, u32),
+	/// Width of the draggable bar, in pixels, which will be multiplied to
+	/// match current system DPI.
+	///
+	/// Defaults to `5`.
+	pub bar_width: u32,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+}
+
+impl Default for SplitterOpts {
+	fn default() -> Self {
+		Self {
+			position: (0, 0),
+			size: (200, 150),
+			dir: SplitterDir::Horz,
+			ratio: 0.5,
+			min_pane_sizes: (20, 20),
+			bar_width: 5,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+		}
+	}
+}
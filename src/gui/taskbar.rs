@@ -0,0 +1,429 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{*, privs::*};
+use crate::guard::*;
+use crate::prelude::*;
+
+/// State of a thumbnail toolbar button, kept to be applied when the taskbar
+/// button is (re)created.
+struct ButtonState {
+	id: u16,
+	icon: Icon,
+	tooltip: String,
+	flags: co::THBF,
+}
+
+struct Obj { // actual fields of Taskbar
+	base_ptr: NonNull<Base>, // base of the main window
+	taskbar_list: RefCell<Option<ITaskbarList4>>,
+	_com_guard: RefCell<Option<CoUninitializeGuard>>, // declared after the COM object, so it's dropped last
+	used: Cell<bool>, // COM object is created only if the user touched something
+	button_created: Cell<bool>,
+	progress_state: Cell<co::TBPF>,
+	progress_value: Cell<Option<(u64, u64)>>,
+	overlay: RefCell<Option<(Icon, String)>>,
+	thumbnail_clip: Cell<Option<RECT>>,
+	thumbnail_tooltip: RefCell<Option<String>>,
+	buttons: RefCell<Vec<ButtonState>>,
+	_pin: PhantomPinned,
+}
+
+//------------------------------------------------------------------------------
+
+/// The taskbar button of a [`WindowMain`](crate::gui::WindowMain), exposed by
+/// [`WindowMain::taskbar`](crate::gui::WindowMain::taskbar).
+///
+/// Wraps an [`ITaskbarList4`](crate::ITaskbarList4) object, which is created
+/// only when the taskbar button is ready – that is, when the registered
+/// `TaskbarButtonCreated` message arrives. Until then, all values are stored
+/// and applied later. Since this message is sent again when Explorer
+/// restarts, all values are automatically reapplied to the new taskbar button.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co, gui};
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+///
+/// let btn_play = wnd.taskbar().add_button(
+///     gui::TaskbarButtonOpts {
+///         icon: gui::Icon::Id(101),
+///         tooltip: "Play".to_owned(),
+///         ..Default::default()
+///     },
+/// );
+///
+/// btn_play.on_click({
+///     let wnd = wnd.clone();
+///     move || {
+///         wnd.taskbar().set_progress_state(co::TBPF::NORMAL)?;
+///         wnd.taskbar().set_progress_value(30, 100)?;
+///         Ok(())
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct Taskbar(Pin<Arc<Obj>>);
+
+impl Taskbar {
+	/// Maximum number of thumbnail toolbar buttons.
+	const MAX_BUTTONS: usize = 7;
+
+	pub(in crate::gui) fn new(base: &Base) -> Self {
+		let new_self = Self(
+			Arc::pin(
+				Obj {
+					base_ptr: NonNull::from(base),
+					taskbar_list: RefCell::new(None),
+					_com_guard: RefCell::new(None),
+					used: Cell::new(false),
+					button_created: Cell::new(false),
+					progress_state: Cell::new(co::TBPF::NOPROGRESS),
+					progress_value: Cell::new(None),
+					overlay: RefCell::new(None),
+					thumbnail_clip: Cell::new(None),
+					thumbnail_tooltip: RefCell::new(None),
+					buttons: RefCell::new(Vec::default()),
+					_pin: PhantomPinned,
+				},
+			),
+		);
+		new_self.default_message_handlers(base);
+		new_self
+	}
+
+	fn base(&self) -> &Base {
+		unsafe { self.0.base_ptr.as_ref() }
+	}
+
+	fn hwnd(&self) -> &HWND {
+		self.base().hwnd()
+	}
+
+	/// Calls the function with the COM object, if the taskbar button already
+	/// exists; creates the COM object if needed.
+	fn with_list<F>(&self, func: F) -> AnyResult<()>
+		where F: FnOnce(&ITaskbarList4) -> AnyResult<()>,
+	{
+		self.0.used.set(true);
+		if !self.0.button_created.get() {
+			return Ok(()); // will be applied when the button is created
+		}
+
+		if self.0.taskbar_list.borrow().is_none() {
+			// If COM was already initialized by the user, possibly with another
+			// concurrency model, we simply use it.
+			*self.0._com_guard.borrow_mut() = CoInitializeEx(
+				co::COINIT::APARTMENTTHREADED | co::COINIT::DISABLE_OLE1DDE).ok();
+
+			let taskbar_list = CoCreateInstance::<ITaskbarList4>(
+				&co::CLSID::TaskbarList, None, co::CLSCTX::INPROC_SERVER)?;
+			taskbar_list.HrInit()?;
+			*self.0.taskbar_list.borrow_mut() = Some(taskbar_list);
+		}
+
+		func(self.0.taskbar_list.borrow().as_ref().unwrap())
+	}
+
+	/// Sets the type of the progress indicator, by calling
+	/// [`ITaskbarList3::SetProgressState`](crate::prelude::shell_ITaskbarList3::SetProgressState).
+	pub fn set_progress_state(&self, state: co::TBPF) -> AnyResult<()> {
+		self.0.progress_state.set(state);
+		self.with_list(|tl| Ok(tl.SetProgressState(self.hwnd(), state)?))
+	}
+
+	/// Sets the value of the progress indicator, by calling
+	/// [`ITaskbarList3::SetProgressValue`](crate::prelude::shell_ITaskbarList3::SetProgressValue).
+	///
+	/// If the progress state is
+	/// [`co::TBPF::NOPROGRESS`](crate::co::TBPF::NOPROGRESS) or
+	/// [`co::TBPF::INDETERMINATE`](crate::co::TBPF::INDETERMINATE), it's
+	/// automatically changed to [`co::TBPF::NORMAL`](crate::co::TBPF::NORMAL).
+	pub fn set_progress_value(&self, completed: u64, total: u64) -> AnyResult<()> {
+		self.0.progress_value.set(Some((completed, total)));
+		let state = self.0.progress_state.get();
+		if state == co::TBPF::NOPROGRESS || state == co::TBPF::INDETERMINATE {
+			self.0.progress_state.set(co::TBPF::NORMAL); // implicitly changed by the shell
+		}
+		self.with_list(|tl| Ok(tl.SetProgressValue(self.hwnd(), completed, total)?))
+	}
+
+	/// Sets or removes the overlay icon of the taskbar button, along with an
+	/// accessibility description, by calling
+	/// [`ITaskbarList3::SetOverlayIcon`](crate::prelude::shell_ITaskbarList3::SetOverlayIcon).
+	///
+	/// Pass [`Icon::None`](crate::gui::Icon::None) to remove the overlay.
+	pub fn set_overlay_icon(&self, icon: Icon, description: &str) -> AnyResult<()> {
+		*self.0.overlay.borrow_mut() = match icon {
+			Icon::None => None,
+			icon => Some((icon, description.to_owned())),
+		};
+		self.with_list(|tl| self.apply_overlay(tl))
+	}
+
+	/// Sets the area of the window to be displayed in the taskbar thumbnail, by
+	/// calling
+	/// [`ITaskbarList3::SetThumbnailClip`](crate::prelude::shell_ITaskbarList3::SetThumbnailClip).
+	///
+	/// The rectangle is in client area coordinates. Pass `None` to display the
+	/// whole window.
+	pub fn set_thumbnail_clip(&self, clip: Option<RECT>) -> AnyResult<()> {
+		self.0.thumbnail_clip.set(clip);
+		self.with_list(|tl| Ok(tl.SetThumbnailClip(self.hwnd(), clip)?))
+	}
+
+	/// Sets the tooltip of the taskbar thumbnail, by calling
+	/// [`ITaskbarList3::SetThumbnailTooltip`](crate::prelude::shell_ITaskbarList3::SetThumbnailTooltip).
+	///
+	/// Pass `None` to display the window title.
+	pub fn set_thumbnail_tooltip(&self, tip: Option<&str>) -> AnyResult<()> {
+		*self.0.thumbnail_tooltip.borrow_mut() = tip.map(|s| s.to_owned());
+		self.with_list(|tl| Ok(tl.SetThumbnailTooltip(self.hwnd(), tip)?))
+	}
+
+	/// Adds a button to the thumbnail toolbar, displayed below the taskbar
+	/// thumbnail.
+	///
+	/// # Panics
+	///
+	/// Panics if the window is already created, or if more than 7 buttons are
+	/// added.
+	pub fn add_button(&self, opts: TaskbarButtonOpts) -> TaskbarButton {
+		if *self.hwnd() != HWND::NULL {
+			panic!("Cannot add a taskbar button after the window is created.");
+		}
+		let mut buttons = self.0.buttons.borrow_mut();
+		if buttons.len() == Self::MAX_BUTTONS {
+			panic!("A thumbnail toolbar cannot have more than {} buttons.", Self::MAX_BUTTONS);
+		}
+
+		let mut flags = if opts.enabled { co::THBF::ENABLED } else { co::THBF::DISABLED };
+		if opts.dismiss_on_click {
+			flags |= co::THBF::DISMISSONCLICK;
+		}
+		if opts.no_background {
+			flags |= co::THBF::NOBACKGROUND;
+		}
+
+		buttons.push(ButtonState {
+			id: auto_ctrl_id(),
+			icon: opts.icon,
+			tooltip: opts.tooltip,
+			flags,
+		});
+		self.0.used.set(true);
+
+		TaskbarButton {
+			taskbar: self.clone(),
+			index: buttons.len() - 1,
+		}
+	}
+
+	fn apply_overlay(&self, tl: &ITaskbarList4) -> AnyResult<()> {
+		match &*self.0.overlay.borrow() {
+			Some((icon, description)) => {
+				let hicon = icon.as_hicon(&self.hwnd().hinstance())?;
+				tl.SetOverlayIcon(self.hwnd(), Some(&hicon), description)?;
+			},
+			None => tl.SetOverlayIcon(self.hwnd(), None, "")?,
+		}
+		Ok(())
+	}
+
+	fn build_thumbbuttons(&self) -> SysResult<Vec<THUMBBUTTON>> {
+		let hinst = self.hwnd().hinstance();
+		self.0.buttons.borrow().iter()
+			.map(|btn| {
+				let mut tb = THUMBBUTTON::default();
+				tb.dwMask = co::THB::FLAGS | co::THB::TOOLTIP;
+				tb.iId = btn.id as _;
+				tb.dwFlags = btn.flags;
+				tb.set_szTip(&btn.tooltip);
+				if !matches!(btn.icon, Icon::None) {
+					tb.dwMask |= co::THB::ICON;
+					tb.hIcon = btn.icon.as_hicon(&hinst)?;
+				}
+				Ok(tb)
+			})
+			.collect()
+	}
+
+	fn update_buttons(&self) -> AnyResult<()> {
+		self.with_list(|tl| {
+			let tbs = self.build_thumbbuttons()?;
+			tl.ThumbBarUpdateButtons(self.hwnd(), &tbs)?;
+			Ok(())
+		})
+	}
+
+	/// Applies all stored values to a newly created taskbar button.
+	fn apply_all(&self) -> AnyResult<()> {
+		*self.0.taskbar_list.borrow_mut() = None; // Explorer may have restarted
+		self.with_list(|tl| {
+			let hwnd = self.hwnd();
+			let state = self.0.progress_state.get();
+			if let Some((completed, total)) = self.0.progress_value.get() {
+				tl.SetProgressValue(hwnd, completed, total)?;
+			}
+			tl.SetProgressState(hwnd, state)?;
+
+			if self.0.overlay.borrow().is_some() {
+				self.apply_overlay(tl)?;
+			}
+			if let Some(clip) = self.0.thumbnail_clip.get() {
+				tl.SetThumbnailClip(hwnd, Some(clip))?;
+			}
+			if let Some(tip) = &*self.0.thumbnail_tooltip.borrow() {
+				tl.SetThumbnailTooltip(hwnd, Some(tip))?;
+			}
+			if !self.0.buttons.borrow().is_empty() {
+				let tbs = self.build_thumbbuttons()?;
+				tl.ThumbBarAddButtons(hwnd, &tbs)?;
+			}
+			Ok(())
+		})
+	}
+
+	fn default_message_handlers(&self, base: &Base) {
+		let wm_button_created = RegisterWindowMessage("TaskbarButtonCreated")
+			.map(|id| unsafe { co::WM::from_raw(id) });
+		let Ok(wm_button_created) = wm_button_created else {
+			return; // shouldn't happen; taskbar simply won't work
+		};
+
+		let self2 = self.clone();
+		base.privileged_on().wm(wm_button_created, move |_| {
+			self2.0.button_created.set(true);
+			if self2.0.used.get() {
+				self2.apply_all()?;
+			}
+			Ok(None) // not meaningful
+		});
+
+		let self2 = self.clone();
+		base.privileged_on().wm_destroy(move || {
+			*self2.0.taskbar_list.borrow_mut() = None;
+			*self2.0._com_guard.borrow_mut() = None;
+			self2.0.button_created.set(false);
+			Ok(())
+		});
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A button of the thumbnail toolbar, created with
+/// [`Taskbar::add_button`](crate::gui::Taskbar::add_button).
+#[derive(Clone)]
+pub struct TaskbarButton {
+	taskbar: Taskbar,
+	index: usize,
+}
+
+impl TaskbarButton {
+	/// Returns the command ID of the button, which is notified in a
+	/// [`wm::Command`](crate::msg::wm::Command) message with
+	/// [`co::THBN::CLICKED`](crate::co::THBN::CLICKED) code.
+	#[must_use]
+	pub fn ctrl_id(&self) -> u16 {
+		self.taskbar.0.buttons.borrow()[self.index].id
+	}
+
+	/// Sets the function to be called when the button is clicked.
+	///
+	/// # Panics
+	///
+	/// Panics if the window is already created. Events must be set before
+	/// window creation.
+	pub fn on_click<F>(&self, func: F)
+		where F: Fn() -> AnyResult<()> + 'static,
+	{
+		self.taskbar.base().on().wm_command(co::THBN::CLICKED, self.ctrl_id(), func);
+	}
+
+	fn modify<F>(&self, func: F) -> AnyResult<()>
+		where F: FnOnce(&mut ButtonState),
+	{
+		func(&mut self.taskbar.0.buttons.borrow_mut()[self.index]);
+		self.taskbar.update_buttons()
+	}
+
+	/// Enables or disables the button.
+	pub fn set_enabled(&self, enabled: bool) -> AnyResult<()> {
+		self.modify(|btn| {
+			if enabled {
+				btn.flags &= !co::THBF::DISABLED;
+			} else {
+				btn.flags |= co::THBF::DISABLED;
+			}
+		})
+	}
+
+	/// Shows or hides the button.
+	pub fn set_hidden(&self, hidden: bool) -> AnyResult<()> {
+		self.modify(|btn| {
+			if hidden {
+				btn.flags |= co::THBF::HIDDEN;
+			} else {
+				btn.flags &= !co::THBF::HIDDEN;
+			}
+		})
+	}
+
+	/// Sets the icon of the button.
+	pub fn set_icon(&self, icon: Icon) -> AnyResult<()> {
+		self.modify(|btn| btn.icon = icon)
+	}
+
+	/// Sets the tooltip of the button.
+	pub fn set_tooltip(&self, tooltip: &str) -> AnyResult<()> {
+		self.modify(|btn| btn.tooltip = tooltip.to_owned())
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Options to create a [`TaskbarButton`](crate::gui::TaskbarButton) with
+/// [`Taskbar::add_button`](crate::gui::Taskbar::add_button).
+pub struct TaskbarButtonOpts {
+	/// Icon of the button, which should be 16x16 pixels.
+	///
+	/// Defaults to `gui::Icon::None`.
+	pub icon: Icon,
+	/// Tooltip of the button.
+	///
+	/// Defaults to empty string.
+	pub tooltip: String,
+	/// Initial enabled state of the button.
+	///
+	/// Defaults to `true`.
+	pub enabled: bool,
+	/// Closes the thumbnail when the button is clicked.
+	///
+	/// Defaults to `false`.
+	pub dismiss_on_click: bool,
+	/// Does not draw a button border, only the icon.
+	///
+	/// Defaults to `false`.
+	pub no_background: bool,
+}
+
+impl Default for TaskbarButtonOpts {
+	fn default() -> Self {
+		Self {
+			icon: Icon::None,
+			tooltip: "".to_owned(),
+			enabled: true,
+			dismiss_on_click: false,
+			no_background: false,
+		}
+	}
+}
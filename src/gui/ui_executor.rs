@@ -0,0 +1,179 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use crate::decl::*;
+use crate::gui::privs::*;
+use crate::msg::*;
+use crate::prelude::*;
+
+/// A future being driven by the UI thread, along with the window which
+/// receives its wake up messages.
+struct LocalTask {
+	hwnd: HWND,
+	fut: Pin<Box<dyn Future<Output = AnyResult<()>>>>,
+}
+
+thread_local! {
+	static TASKS: RefCell<HashMap<usize, LocalTask>> = RefCell::new(HashMap::new());
+	static NEXT_TASK_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+/// Wakes a task by posting a message to a window of the UI thread, so the
+/// task is polled by the message loop. Can be called from any thread.
+struct UiWaker {
+	hwnd: HWND,
+	task_id: usize,
+}
+
+unsafe impl Sync for UiWaker {} // the handle is only used to post messages
+
+impl Wake for UiWaker {
+	fn wake(self: Arc<Self>) {
+		self.wake_by_ref();
+	}
+
+	fn wake_by_ref(self: &Arc<Self>) {
+		self.hwnd.PostMessage(WndMsg {
+			msg_id: Base::WM_UI_FUTURE,
+			wparam: Base::WM_UI_FUTURE.raw() as _,
+			lparam: self.task_id as _,
+		}).unwrap_or_default(); // if the window is gone, the task is never polled again
+	}
+}
+
+/// Stores the future in the UI thread executor, and schedules its first poll.
+///
+/// Panics if not called from the thread which created the window, because the
+/// tasks are stored per thread.
+pub(in crate::gui) fn spawn_local_task<F>(hwnd: HWND, fut: F)
+	where F: Future<Output = AnyResult<()>> + 'static,
+{
+	if hwnd.GetWindowThreadProcessId().0 != GetCurrentThreadId() {
+		panic!("Cannot spawn a local task outside the window's UI thread.");
+	}
+
+	let task_id = NEXT_TASK_ID.with(|next| next.replace(next.get() + 1));
+	let waker = Arc::new(UiWaker { hwnd: unsafe { hwnd.raw_copy() }, task_id });
+	TASKS.with(|tasks| {
+		tasks.borrow_mut().insert(task_id, LocalTask { hwnd, fut: Box::pin(fut) });
+	});
+	waker.wake();
+}
+
+/// Polls the task, if it still exists. Returns the error of a finished task.
+pub(in crate::gui) fn poll_local_task(task_id: usize) -> AnyResult<()> {
+	// The task is removed while being polled, because it may spawn other
+	// tasks, which would borrow the map again.
+	let task = TASKS.with(|tasks| tasks.borrow_mut().remove(&task_id));
+	if let Some(mut task) = task {
+		let waker = Waker::from(Arc::new(
+			UiWaker { hwnd: unsafe { task.hwnd.raw_copy() }, task_id },
+		));
+		match task.fut.as_mut().poll(&mut Context::from_waker(&waker)) {
+			Poll::Ready(res) => return res,
+			Poll::Pending => if task.hwnd.IsWindow() { // window may be destroyed while polling
+				TASKS.with(|tasks| {
+					tasks.borrow_mut().insert(task_id, task);
+				});
+			},
+		}
+	}
+	Ok(()) // already finished, or pending
+}
+
+/// Drops the pending tasks woken through the given window, which is being
+/// destroyed, so they would never be polled again.
+pub(in crate::gui) fn drop_local_tasks(hwnd: &HWND) {
+	// The tasks are dropped outside the borrow, because their destructors may
+	// spawn or drop other tasks.
+	let dead_tasks = TASKS.with(|tasks| {
+		let mut tasks = tasks.borrow_mut();
+		let dead_ids = tasks.iter()
+			.filter(|(_, task)| task.hwnd == *hwnd)
+			.map(|(task_id, _)| *task_id)
+			.collect::<Vec<_>>();
+		dead_ids.iter()
+			.filter_map(|task_id| tasks.remove(task_id))
+			.collect::<Vec<_>>()
+	});
+	drop(dead_tasks);
+}
+
+//------------------------------------------------------------------------------
+
+struct Shared<T> {
+	result: Option<AnyResult<T>>,
+	waker: Option<Waker>,
+}
+
+/// Sending half of an [`AsyncResult`](crate::gui::AsyncResult), moved into
+/// the closure which produces the value.
+pub(in crate::gui) struct AsyncResultSender<T> {
+	shared: Arc<Mutex<Shared<T>>>,
+	sent: bool,
+}
+
+impl<T> Drop for AsyncResultSender<T> {
+	fn drop(&mut self) {
+		if !self.sent { // closure panicked, or was never run
+			self.complete(Err("The result was dropped before being produced.".into()));
+		}
+	}
+}
+
+impl<T> AsyncResultSender<T> {
+	pub(in crate::gui) fn send(mut self, result: AnyResult<T>) {
+		self.complete(result);
+	}
+
+	fn complete(&mut self, result: AnyResult<T>) {
+		self.sent = true;
+		let mut shared = self.shared.lock().unwrap();
+		shared.result = Some(result);
+		if let Some(waker) = shared.waker.take() {
+			waker.wake();
+		}
+	}
+}
+
+/// A [`Future`](std::future::Future) which resolves to the value returned by
+/// a closure running in another thread.
+///
+/// Returned by
+/// [`GuiThread::spawn_new_thread_async`](crate::prelude::GuiThread::spawn_new_thread_async)
+/// and
+/// [`GuiThread::run_ui_thread_async`](crate::prelude::GuiThread::run_ui_thread_async).
+/// If the closure panics, the future resolves to an error.
+pub struct AsyncResult<T> {
+	shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> Future for AsyncResult<T> {
+	type Output = AnyResult<T>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut shared = self.shared.lock().unwrap();
+		match shared.result.take() {
+			Some(result) => Poll::Ready(result),
+			None => {
+				shared.waker = Some(cx.waker().clone());
+				Poll::Pending
+			},
+		}
+	}
+}
+
+impl<T> AsyncResult<T> {
+	/// Creates a new pair of sender and future.
+	pub(in crate::gui) fn new_pair() -> (AsyncResultSender<T>, Self) {
+		let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+		(
+			AsyncResultSender { shared: shared.clone(), sent: false },
+			Self { shared },
+		)
+	}
+}
@@ -16,7 +16,7 @@ enum RawDlg { Raw(RawMain), Dlg(DlgMain) }
 /// window of your application, launched directly from the `main` function. Can
 /// be programmatically created or load a dialog resource from a `.res` file.
 #[derive(Clone)]
-pub struct WindowMain(RawDlg);
+pub struct WindowMain(RawDlg, Taskbar);
 
 unsafe impl Send for WindowMain {}
 
@@ -76,11 +76,7 @@ impl WindowMain {
 	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
 	#[must_use]
 	pub fn new(opts: WindowMainOpts) -> Self {
-		Self(
-			RawDlg::Raw(
-				RawMain::new(opts),
-			),
-		)
+		Self::with_taskbar(RawDlg::Raw(RawMain::new(opts)))
 	}
 
 	/// Instantiates a new `WindowMain` object, to be loaded from a dialog
@@ -93,13 +89,28 @@ impl WindowMain {
 		accel_table_id: Option<u16>,
 	) -> Self
 	{
-		Self(
-			RawDlg::Dlg(
-				DlgMain::new(dialog_id, icon_id, accel_table_id),
-			),
+		Self::with_taskbar(
+			RawDlg::Dlg(DlgMain::new(dialog_id, icon_id, accel_table_id)),
 		)
 	}
 
+	fn with_taskbar(raw_dlg: RawDlg) -> Self {
+		let base_ptr = match &raw_dlg {
+			RawDlg::Raw(r) => unsafe { r.as_base() },
+			RawDlg::Dlg(d) => unsafe { d.as_base() },
+		};
+		let taskbar = Taskbar::new(unsafe { &*(base_ptr as *const Base) });
+		Self(raw_dlg, taskbar)
+	}
+
+	/// Returns the [`Taskbar`](crate::gui::Taskbar) object, which manages the
+	/// taskbar button of this window: progress indicator, overlay icon and
+	/// thumbnail toolbar.
+	#[must_use]
+	pub fn taskbar(&self) -> &Taskbar {
+		&self.1
+	}
+
 	/// Physically creates the window, then runs the main application loop. This
 	/// method will block until the window is closed.
 	///
@@ -139,6 +150,7 @@ impl WindowMain {
 		};
 
 		delete_ui_font(); // cleanup
+		delete_dark_brushes();
 		res
 	}
 }
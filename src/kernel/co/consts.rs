@@ -15,6 +15,55 @@ const_bitflag! { ACCESS_RIGHTS: u32;
 	SYNCHRONIZE 0x0010_0000
 }
 
+const_bitflag! { ACE_FLAG: u8;
+	/// [`ACE_HEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-ace_header)
+	/// `AceFlags` (`u8`).
+	///
+	/// Originally has `ACE` suffix.
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	OBJECT_INHERIT 0x01
+	CONTAINER_INHERIT 0x02
+	NO_PROPAGATE_INHERIT 0x04
+	INHERIT_ONLY 0x08
+	INHERITED 0x10
+	SUCCESSFUL_ACCESS 0x40
+	FAILED_ACCESS 0x80
+}
+
+const_ordinary! { ACE_TYPE: u8;
+	/// [`ACE_HEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-ace_header)
+	/// `AceType` (`u8`).
+	///
+	/// Originally has `ACE_TYPE` suffix.
+	=>
+	=>
+	ACCESS_ALLOWED 0x00
+	ACCESS_DENIED 0x01
+	SYSTEM_AUDIT 0x02
+	SYSTEM_ALARM 0x03
+	ACCESS_ALLOWED_COMPOUND 0x04
+	ACCESS_ALLOWED_OBJECT 0x05
+	ACCESS_DENIED_OBJECT 0x06
+	SYSTEM_AUDIT_OBJECT 0x07
+	SYSTEM_ALARM_OBJECT 0x08
+	ACCESS_ALLOWED_CALLBACK 0x09
+	ACCESS_DENIED_CALLBACK 0x0a
+	ACCESS_ALLOWED_CALLBACK_OBJECT 0x0b
+	ACCESS_DENIED_CALLBACK_OBJECT 0x0c
+	SYSTEM_AUDIT_CALLBACK 0x0d
+	SYSTEM_ALARM_CALLBACK 0x0e
+	SYSTEM_AUDIT_CALLBACK_OBJECT 0x0f
+	SYSTEM_ALARM_CALLBACK_OBJECT 0x10
+	SYSTEM_MANDATORY_LABEL 0x11
+	SYSTEM_RESOURCE_ATTRIBUTE 0x12
+	SYSTEM_SCOPED_POLICY_ID 0x13
+	SYSTEM_PROCESS_TRUST_LABEL 0x14
+	SYSTEM_ACCESS_FILTER 0x15
+}
+
 const_bitflag! { CONSOLE: u32;
 	/// [`HSTD::SetConsoleMode`](crate::prelude::kernel_Hstd::SetConsoleMode)
 	/// `mode` (`u32`).
@@ -252,6 +301,17 @@ const_ordinary! { DRIVE: u32;
 	RAMDISK 6
 }
 
+const_bitflag! { DUPLICATE: u32;
+	/// [`HPROCESS::DuplicateHandle`](crate::prelude::kernel_Hprocess::DuplicateHandle)
+	/// `options` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	CLOSE_SOURCE 0x0000_0001
+	SAME_ACCESS 0x0000_0002
+}
+
 const_ordinary! { EVENTLOG: u16;
 	/// [`HEVENTLOG::ReportEvent`](crate::prelude::kernel_Heventlog::ReportEvent)
 	/// `event_type` [`u16`].
@@ -311,6 +371,18 @@ const_bitflag! { FILE_ATTRIBUTE: u32;
 	RECALL_ON_DATA_ACCESS 0x0040_0000
 }
 
+const_ordinary! { FILE_ACTION: u32;
+	/// [`FILE_NOTIFY_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-file_notify_information)
+	/// `Action` (`u32`).
+	=>
+	=>
+	ADDED 0x0000_0001
+	REMOVED 0x0000_0002
+	MODIFIED 0x0000_0003
+	RENAMED_OLD_NAME 0x0000_0004
+	RENAMED_NEW_NAME 0x0000_0005
+}
+
 const_bitflag! { FILE_CACHE: u32;
 	/// [`GetSystemFileCacheSize`](crate::GetSystemFileCacheSize) returned flags
 	/// (`u32`).
@@ -355,6 +427,23 @@ const_bitflag! { FILE_MAP: u32;
 	TARGETS_INVALID 0x4000_0000
 }
 
+const_bitflag! { FILE_NOTIFY_CHANGE: u32;
+	/// [`HFILE::ReadDirectoryChanges`](crate::prelude::kernel_Hfile::ReadDirectoryChanges)
+	/// and
+	/// [`HFINDCHANGE::FindFirstChangeNotification`](crate::prelude::kernel_Hfindchange::FindFirstChangeNotification)
+	/// `notify_filter` (`u32`).
+	=>
+	=>
+	FILE_NAME 0x0000_0001
+	DIR_NAME 0x0000_0002
+	ATTRIBUTES 0x0000_0004
+	SIZE 0x0000_0008
+	LAST_WRITE 0x0000_0010
+	LAST_ACCESS 0x0000_0020
+	CREATION 0x0000_0040
+	SECURITY 0x0000_0100
+}
+
 const_bitflag! { FILE_SHARE: u32;
 	/// [`HFILE::CreateFile`](crate::prelude::kernel_Hfile::CreateFile)
 	/// `share_mode` (`u32`).
@@ -521,6 +610,16 @@ const_bitflag! { GR: u32;
 	USEROBJECTS_PEAK 4
 }
 
+const_bitflag! { HANDLE_FLAG: u32;
+	/// [`SetHandleInformation`](crate::SetHandleInformation) flags (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	INHERIT 0x0000_0001
+	PROTECT_FROM_CLOSE 0x0000_0002
+}
+
 const_bitflag! { HEAP_ALLOC: u32;
 	/// [`HHEAP::HeapAlloc`](crate::prelude::kernel_Hheap::HeapAlloc) `flags`
 	/// (`u32`).
@@ -589,6 +688,106 @@ const_ordinary! { HF32: u32;
 	DEFAULT 1
 }
 
+const_bitflag! { JOB_OBJECT: u32;
+	/// Job object
+	/// [security and access rights](https://learn.microsoft.com/en-us/windows/win32/procthread/job-object-security-and-access-rights)
+	/// (`u32`).
+	=>
+	=>
+	DELETE ACCESS_RIGHTS::DELETE.0
+	READ_CONTROL ACCESS_RIGHTS::READ_CONTROL.0
+	SYNCHRONIZE ACCESS_RIGHTS::SYNCHRONIZE.0
+	WRITE_DAC ACCESS_RIGHTS::WRITE_DAC.0
+	WRITE_OWNER ACCESS_RIGHTS::WRITE_OWNER.0
+
+	ALL_ACCESS STANDARD_RIGHTS::REQUIRED.0 | ACCESS_RIGHTS::SYNCHRONIZE.0 | 0x3f
+	ASSIGN_PROCESS 0x0001
+	QUERY 0x0004
+	SET_ATTRIBUTES 0x0002
+	SET_SECURITY_ATTRIBUTES 0x0010
+	TERMINATE 0x0008
+}
+
+const_bitflag! { JOB_OBJECT_CPU_RATE_CONTROL: u32;
+	/// [`JOBOBJECT_CPU_RATE_CONTROL_INFORMATION`](crate::JOBOBJECT_CPU_RATE_CONTROL_INFORMATION)
+	/// `ControlFlags` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	ENABLE 0x0000_0001
+	WEIGHT_BASED 0x0000_0002
+	HARD_CAP 0x0000_0004
+	NOTIFY 0x0000_0008
+	MIN_MAX_RATE 0x0000_0010
+}
+
+const_bitflag! { JOB_OBJECT_LIMIT: u32;
+	/// [`JOBOBJECT_BASIC_LIMIT_INFORMATION`](crate::JOBOBJECT_BASIC_LIMIT_INFORMATION)
+	/// `LimitFlags` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	WORKINGSET 0x0000_0001
+	PROCESS_TIME 0x0000_0002
+	JOB_TIME 0x0000_0004
+	ACTIVE_PROCESS 0x0000_0008
+	AFFINITY 0x0000_0010
+	PRIORITY_CLASS 0x0000_0020
+	PRESERVE_JOB_TIME 0x0000_0040
+	SCHEDULING_CLASS 0x0000_0080
+	PROCESS_MEMORY 0x0000_0100
+	JOB_MEMORY 0x0000_0200
+	DIE_ON_UNHANDLED_EXCEPTION 0x0000_0400
+	BREAKAWAY_OK 0x0000_0800
+	SILENT_BREAKAWAY_OK 0x0000_1000
+	KILL_ON_JOB_CLOSE 0x0000_2000
+	SUBSET_AFFINITY 0x0000_4000
+}
+
+const_ordinary! { JOB_OBJECT_MSG: u32;
+	/// Job object
+	/// [notification](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_associate_completion_port)
+	/// messages (`u32`).
+	=>
+	=>
+	END_OF_JOB_TIME 1
+	END_OF_PROCESS_TIME 2
+	ACTIVE_PROCESS_LIMIT 3
+	ACTIVE_PROCESS_ZERO 4
+	NEW_PROCESS 6
+	EXIT_PROCESS 7
+	ABNORMAL_EXIT_PROCESS 8
+	PROCESS_MEMORY_LIMIT 9
+	JOB_MEMORY_LIMIT 10
+	NOTIFICATION_LIMIT 11
+	JOB_CYCLE_TIME_LIMIT 12
+	SILO_TERMINATED 13
+}
+
+const_ordinary! { JOBOBJECTINFOCLASS: u32;
+	/// [`JOBOBJECTINFOCLASS`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setinformationjobobject)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `JobObject` prefix.
+	=>
+	=>
+	BasicAccountingInformation 1
+	BasicLimitInformation 2
+	BasicProcessIdList 3
+	BasicUIRestrictions 4
+	EndOfJobTimeInformation 6
+	AssociateCompletionPortInformation 7
+	BasicAndIoAccountingInformation 8
+	ExtendedLimitInformation 9
+	GroupInformation 11
+	NotificationLimitInformation 12
+	LimitViolationInformation 13
+	GroupInformationEx 14
+	CpuRateControlInformation 15
+}
+
 const_bitflag! { KEY: u32;
 	/// [Registry access rights](https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-key-security-and-access-rights)
 	/// (`u32`).
@@ -863,6 +1062,43 @@ const_ordinary! { PBT: u32;
 	POWERSETTINGCHANGE 0x8013
 }
 
+const_bitflag! { PIPE: u32;
+	/// [`HPIPE::CreateNamedPipe`](crate::prelude::kernel_Hpipe::CreateNamedPipe)
+	/// `pipe_mode` (`u32`).
+	///
+	/// Originally has `PIPE` prefix.
+	=>
+	=>
+	TYPE_BYTE 0x0000_0000
+	TYPE_MESSAGE 0x0000_0004
+	READMODE_BYTE 0x0000_0000
+	READMODE_MESSAGE 0x0000_0002
+	WAIT 0x0000_0000
+	NOWAIT 0x0000_0001
+	ACCEPT_REMOTE_CLIENTS 0x0000_0000
+	REJECT_REMOTE_CLIENTS 0x0000_0008
+}
+
+const_bitflag! { PIPE_ACCESS: u32;
+	/// [`HPIPE::CreateNamedPipe`](crate::prelude::kernel_Hpipe::CreateNamedPipe)
+	/// `open_mode` (`u32`).
+	///
+	/// Also includes the `FILE_FLAG` values which can be used in the call.
+	=>
+	=>
+	INBOUND 0x0000_0001
+	OUTBOUND 0x0000_0002
+	DUPLEX 0x0000_0003
+
+	FIRST_PIPE_INSTANCE 0x0008_0000
+	WRITE_THROUGH 0x8000_0000
+	OVERLAPPED 0x4000_0000
+
+	WRITE_DAC ACCESS_RIGHTS::WRITE_DAC.0
+	WRITE_OWNER ACCESS_RIGHTS::WRITE_OWNER.0
+	ACCESS_SYSTEM_SECURITY 0x0100_0000
+}
+
 const_bitflag! { PRIORITY_CLASS: u32;
 	/// [`GetPriorityClass`](crate::prelude::kernel_Hprocess::GetPriorityClass)
 	/// and
@@ -992,6 +1228,17 @@ const_ordinary! { PROCESSOR_ARCHITECTURE: u16;
 	UNKNOWN 0xffff
 }
 
+const_ordinary! { READ_DIRECTORY_NOTIFY_INFORMATION_CLASS: u32;
+	/// [`READ_DIRECTORY_NOTIFY_INFORMATION_CLASS`](https://learn.microsoft.com/en-us/windows/win32/api/minwinbase/ne-minwinbase-read_directory_notify_information_class)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `ReadDirectoryNotify` prefix.
+	=>
+	=>
+	Information 1
+	ExtendedInformation 2
+}
+
 const_ordinary! { REG: u32;
 	/// Registry
 	/// [value types](https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-value-types)
@@ -1027,6 +1274,18 @@ const_ordinary! { REG_DISPOSITION: u32;
 	OPENED_EXISTING_KEY 0x0000_0002
 }
 
+const_bitflag! { REG_NOTIFY: u32;
+	/// [`HKEY::RegNotifyChangeKeyValue`](crate::prelude::kernel_Hkey::RegNotifyChangeKeyValue)
+	/// `notify_filter` (`u32`).
+	=>
+	=>
+	CHANGE_NAME 0x0000_0001
+	CHANGE_ATTRIBUTES 0x0000_0002
+	CHANGE_LAST_SET 0x0000_0004
+	CHANGE_SECURITY 0x0000_0008
+	THREAD_AGNOSTIC 0x1000_0000
+}
+
 const_bitflag! { REG_OPTION: u32;
 	/// [`HKEY::RegOpenKeyEx`](crate::prelude::kernel_Hkey::RegOpenKeyEx)
 	/// `options` (`u32`).
@@ -1339,6 +1598,8 @@ const_bitflag! { SE: u16;
 	/// bit flags (`u16`).
 	=>
 	=>
+	/// None of the actual values (zero).
+	NoValue 0
 	OWNER_DEFAULTED 0x0001
 	GROUP_DEFAULTED 0x0002
 	DACL_PRESENT 0x0004
@@ -1490,6 +1751,41 @@ const_bitflag! { SERVICE: u32;
 	GENERIC_EXECUTE STANDARD_RIGHTS::EXECUTE.0 | Self::START.0 | Self::STOP.0 | Self::PAUSE_CONTINUE.0 | Self::USER_DEFINED_CONTROL.0
 }
 
+const_bitflag! { SERVICE_ACCEPT: u32;
+	/// [`SERVICE_STATUS`](crate::SERVICE_STATUS) `dwControlsAccepted` (`u32`).
+	=>
+	=>
+	STOP 0x0000_0001
+	PAUSE_CONTINUE 0x0000_0002
+	SHUTDOWN 0x0000_0004
+	PARAMCHANGE 0x0000_0008
+	NETBINDCHANGE 0x0000_0010
+	HARDWAREPROFILECHANGE 0x0000_0020
+	POWEREVENT 0x0000_0040
+	SESSIONCHANGE 0x0000_0080
+	PRESHUTDOWN 0x0000_0100
+	TIMECHANGE 0x0000_0200
+	TRIGGEREVENT 0x0000_0400
+	USERMODEREBOOT 0x0000_0800
+}
+
+const_ordinary! { SERVICE_CONFIG: u32;
+	/// [`HSERVICE::ChangeServiceConfig2`](crate::prelude::kernel_Hservice::ChangeServiceConfig2)
+	/// `info_level` (`u32`).
+	=>
+	=>
+	DESCRIPTION 1
+	FAILURE_ACTIONS 2
+	DELAYED_AUTO_START_INFO 3
+	FAILURE_ACTIONS_FLAG 4
+	SERVICE_SID_INFO 5
+	REQUIRED_PRIVILEGES_INFO 6
+	PRESHUTDOWN_INFO 7
+	TRIGGER_INFO 8
+	PREFERRED_NODE 9
+	LAUNCH_PROTECTED 12
+}
+
 const_bitflag! { SERVICE_CONTROL: u32;
 	/// [`LPHANDLER_FUNCTION_EX`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nc-winsvc-lphandler_function_ex)
 	/// `dwControl` (`u32`).
@@ -1541,10 +1837,41 @@ const_ordinary! { SERVICE_START: u32;
 	SYSTEM_START 0x0000_0001
 }
 
+const_ordinary! { SERVICE_STATE: u32;
+	/// [`SERVICE_STATUS`](crate::SERVICE_STATUS) `dwCurrentState` (`u32`).
+	///
+	/// Originally has `SERVICE` prefix.
+	=>
+	=>
+	STOPPED 0x0000_0001
+	START_PENDING 0x0000_0002
+	STOP_PENDING 0x0000_0003
+	RUNNING 0x0000_0004
+	CONTINUE_PENDING 0x0000_0005
+	PAUSE_PENDING 0x0000_0006
+	PAUSED 0x0000_0007
+}
+
+const_ordinary! { SERVICE_STATE_FILTER: u32;
+	/// [`HSC::EnumServicesStatusEx`](crate::prelude::kernel_Hsc::EnumServicesStatusEx)
+	/// `service_state` (`u32`).
+	///
+	/// Originally has `SERVICE` prefix.
+	=>
+	=>
+	ACTIVE 0x0000_0001
+	INACTIVE 0x0000_0002
+	STATE_ALL 0x0000_0003
+}
+
 const_ordinary! { SERVICE_TYPE: u32;
 	/// [`HSC::CreateService`](crate::prelude::kernel_Hsc::CreateService)
 	/// `service_type` (`u32`).
 	///
+	/// [`HSC::EnumServicesStatusEx`](crate::prelude::kernel_Hsc::EnumServicesStatusEx)
+	/// also accepts [`DRIVER`](crate::co::SERVICE_TYPE::DRIVER) and
+	/// [`WIN32`](crate::co::SERVICE_TYPE::WIN32).
+	///
 	/// Originally has `SERVICE` prefix.
 	=>
 	=>
@@ -1557,6 +1884,9 @@ const_ordinary! { SERVICE_TYPE: u32;
 
 	WIN32_OWN_PROCESS_INTERACTIVE Self::WIN32_OWN_PROCESS.0 | 0x0000_0100
 	WIN32_SHARE_PROCESS_INTERACTIVE Self::WIN32_SHARE_PROCESS.0 | 0x0000_0100
+
+	DRIVER Self::KERNEL_DRIVER.0 | Self::FILE_SYSTEM_DRIVER.0 | Self::RECOGNIZER_DRIVER.0
+	WIN32 Self::WIN32_OWN_PROCESS.0 | Self::WIN32_SHARE_PROCESS.0
 }
 
 const_bitflag! { SHTDN_REASON: u32;
@@ -1995,6 +2325,16 @@ const_ordinary! { SW: i32;
 	FORCEMINIMIZE 11
 }
 
+const_bitflag! { SYSTEM_MANDATORY_LABEL: u32;
+	/// [`SYSTEM_MANDATORY_LABEL_ACE`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-system_mandatory_label_ace)
+	/// `Mask` (`u32`).
+	=>
+	=>
+	NO_WRITE_UP 0x1
+	NO_READ_UP 0x2
+	NO_EXECUTE_UP 0x4
+}
+
 const_bitflag! { TH32CS: u32;
 	/// [`HPROCESSLIST::CreateToolhelp32Snapshot`](crate::HPROCESSLIST) `flags`
 	/// (`u32`).
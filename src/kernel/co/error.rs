@@ -250,6 +250,7 @@ const_values! { ERROR;
 	VC_DISCONNECTED 240
 	INVALID_EA_NAME 254
 	EA_LIST_INCONSISTENT 255
+	WAIT_TIMEOUT 258
 	NO_MORE_ITEMS 259
 	CANNOT_COPY 266
 	DIRECTORY 267
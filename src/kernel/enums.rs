@@ -96,22 +96,39 @@ pub enum RegistryValue {
 	ExpandSz(String),
 	/// Multiple strings, defined as [`REG::MULTI_SZ`](crate::co::REG::MULTI_SZ).
 	MultiSz(Vec<String>),
-	/// No value, defined as [`REG::NONE`](crate::co::REG::NONE). Also used for
-	/// non-implemented value types.
+	/// An `u32` integer value stored in big-endian format, defined as
+	/// [`REG::DWORD_BIG_ENDIAN`](crate::co::REG::DWORD_BIG_ENDIAN).
+	DwordBigEndian(u32),
+	/// Symbolic link to another registry key, defined as
+	/// [`REG::LINK`](crate::co::REG::LINK).
+	Link(String),
+	/// Device driver resource list, defined as
+	/// [`REG::RESOURCE_LIST`](crate::co::REG::RESOURCE_LIST).
+	ResourceList(Vec<u8>),
+	/// Device driver hardware resource descriptor, defined as
+	/// [`REG::FULL_RESOURCE_DESCRIPTOR`](crate::co::REG::FULL_RESOURCE_DESCRIPTOR).
+	FullResourceDescriptor(Vec<u8>),
+	/// Device driver resource requirements list, defined as
+	/// [`REG::RESOURCE_REQUIREMENTS_LIST`](crate::co::REG::RESOURCE_REQUIREMENTS_LIST).
+	ResourceRequirementsList(Vec<u8>),
+	/// Raw data of any other value type, including
+	/// [`REG::NONE`](crate::co::REG::NONE) values which carry data.
+	Other(co::REG, Vec<u8>),
+	/// No value, defined as [`REG::NONE`](crate::co::REG::NONE).
 	None,
 }
 
 impl std::fmt::Display for RegistryValue {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let bytes = |b: &[u8]| {
+			b.iter()
+				.map(|n| format!("{:02}", *n))
+				.collect::<Vec<_>>()
+				.join(" ")
+		};
+
 		match self {
-			Self::Binary(b) => write!(
-				f,
-				"[REG_BINARY] {}",
-				b.iter()
-					.map(|n| format!("{:02}", *n))
-					.collect::<Vec<_>>()
-					.join(" "),
-			),
+			Self::Binary(b) => write!(f, "[REG_BINARY] {}", bytes(b)),
 			Self::Dword(n) => write!(f, "[REG_DWORD] {}", *n),
 			Self::Qword(n) => write!(f, "[REG_QWORD] {}", *n),
 			Self::Sz(s) => write!(f, "[REG_SZ] \"{}\"", s),
@@ -124,6 +141,12 @@ impl std::fmt::Display for RegistryValue {
 					.collect::<Vec<_>>()
 					.join(", "),
 			),
+			Self::DwordBigEndian(n) => write!(f, "[REG_DWORD_BIG_ENDIAN] {}", *n),
+			Self::Link(s) => write!(f, "[REG_LINK] \"{}\"", s),
+			Self::ResourceList(b) => write!(f, "[REG_RESOURCE_LIST] {}", bytes(b)),
+			Self::FullResourceDescriptor(b) => write!(f, "[REG_FULL_RESOURCE_DESCRIPTOR] {}", bytes(b)),
+			Self::ResourceRequirementsList(b) => write!(f, "[REG_RESOURCE_REQUIREMENTS_LIST] {}", bytes(b)),
+			Self::Other(t, b) => write!(f, "[REG {}] {}", t.raw(), bytes(b)),
 			Self::None => write!(f, "[REG_NONE]"),
 		}
 	}
@@ -138,35 +161,94 @@ impl RegistryValue {
 	/// # Safety
 	///
 	/// Assumes the binary data block has the correct content, according to the
-	/// informed [`co::REG`](crate::co::REG).
+	/// informed [`co::REG`](crate::co::REG). Numbers shorter than expected are
+	/// padded with zeros.
 	#[must_use]
 	pub unsafe fn from_raw(buf: Vec<u8>, reg_type: co::REG) -> RegistryValue {
+		let num_bytes = |buf: &[u8]| {
+			let mut arr = [0u8; 8];
+			let len = buf.len().min(8);
+			arr[..len].copy_from_slice(&buf[..len]);
+			arr
+		};
+		let vec16 = |buf: &[u8]| {
+			buf.chunks_exact(2)
+				.map(|ch| u16::from_ne_bytes([ch[0], ch[1]]))
+				.collect::<Vec<_>>()
+		};
+
 		match reg_type {
-			co::REG::NONE => RegistryValue::None,
-			co::REG::DWORD => RegistryValue::Dword(
-				u32::from_ne_bytes(unsafe {
-					*std::mem::transmute::<_, *const [u8; 4]>(buf.as_ptr())
-				})
+			co::REG::NONE if buf.is_empty() => RegistryValue::None,
+			co::REG::DWORD => {
+				let arr = num_bytes(&buf);
+				RegistryValue::Dword(u32::from_ne_bytes([arr[0], arr[1], arr[2], arr[3]]))
+			},
+			co::REG::DWORD_BIG_ENDIAN => {
+				let arr = num_bytes(&buf);
+				RegistryValue::DwordBigEndian(u32::from_be_bytes([arr[0], arr[1], arr[2], arr[3]]))
+			},
+			co::REG::QWORD => RegistryValue::Qword(u64::from_ne_bytes(num_bytes(&buf))),
+			co::REG::SZ => RegistryValue::Sz(
+				WString::from_wchars_slice(&vec16(&buf)).to_string(),
 			),
-			co::REG::QWORD => RegistryValue::Qword(
-				u64::from_ne_bytes(unsafe {
-					*std::mem::transmute::<_, *const [u8; 8]>(buf.as_ptr())
-				})
+			co::REG::EXPAND_SZ => RegistryValue::ExpandSz(
+				WString::from_wchars_slice(&vec16(&buf)).to_string(),
 			),
-			co::REG::SZ => {
-				let (_, vec16, _) = unsafe { buf.align_to::<u16>() };
-				RegistryValue::Sz(WString::from_wchars_slice(&vec16).to_string())
-			},
-			co::REG::EXPAND_SZ => {
-				let (_, vec16, _) = unsafe { buf.align_to::<u16>() };
-				RegistryValue::Sz(WString::from_wchars_slice(&vec16).to_string())
+			co::REG::LINK => RegistryValue::Link(
+				WString::from_wchars_slice(&vec16(&buf)).to_string(), // usually not null-terminated
+			),
+			co::REG::MULTI_SZ => RegistryValue::MultiSz(
+				vec16(&buf)
+					.split(|ch| *ch == 0)
+					.take_while(|s| !s.is_empty()) // an empty string terminates the list
+					.map(String::from_utf16_lossy)
+					.collect(),
+			),
+			co::REG::BINARY => RegistryValue::Binary(buf),
+			co::REG::RESOURCE_LIST => RegistryValue::ResourceList(buf),
+			co::REG::FULL_RESOURCE_DESCRIPTOR => RegistryValue::FullResourceDescriptor(buf),
+			co::REG::RESOURCE_REQUIREMENTS_LIST => RegistryValue::ResourceRequirementsList(buf),
+			_ => RegistryValue::Other(reg_type, buf),
+		}
+	}
+
+	/// Serializes the value into the binary data block stored in the registry.
+	///
+	/// Strings are encoded as UTF-16 with a terminating null, except
+	/// [`Link`](crate::RegistryValue::Link), which has no terminating null.
+	#[must_use]
+	pub fn to_raw(&self) -> Vec<u8> {
+		let utf16 = |s: &str| {
+			s.encode_utf16()
+				.flat_map(|ch| ch.to_ne_bytes())
+				.collect::<Vec<_>>()
+		};
+
+		match self {
+			Self::Binary(b)
+				| Self::ResourceList(b)
+				| Self::FullResourceDescriptor(b)
+				| Self::ResourceRequirementsList(b)
+				| Self::Other(_, b) => b.clone(),
+			Self::Dword(n) => n.to_ne_bytes().to_vec(),
+			Self::Qword(n) => n.to_ne_bytes().to_vec(),
+			Self::DwordBigEndian(n) => n.to_be_bytes().to_vec(),
+			Self::Sz(s) | Self::ExpandSz(s) => {
+				let mut buf = utf16(s);
+				buf.extend_from_slice(&[0, 0]);
+				buf
 			},
-			co::REG::MULTI_SZ => {
-				let (_, vec16, _) = unsafe { buf.align_to::<u16>() };
-				RegistryValue::MultiSz(parse_multi_z_str(vec16.as_ptr()))
+			Self::Link(s) => utf16(s),
+			Self::MultiSz(v) => {
+				let mut buf = Vec::<u8>::default();
+				for s in v.iter() {
+					buf.extend(utf16(s));
+					buf.extend_from_slice(&[0, 0]);
+				}
+				buf.extend_from_slice(&[0, 0]);
+				buf
 			},
-			co::REG::BINARY => RegistryValue::Binary(buf),
-			_ => RegistryValue::None, // other types not implemented yet
+			Self::None => Vec::default(),
 		}
 	}
 
@@ -192,6 +274,21 @@ impl RegistryValue {
 				*str_buf = WString::from_str_vec(v);
 				Self::as_ptr_with_len_str(&str_buf)
 			},
+			Self::DwordBigEndian(n) => {
+				let be = n.to_be_bytes(); // stored in the buffer, since it must outlive the call
+				*str_buf = WString::new_alloc_buf(2);
+				str_buf.as_mut_slice()[0] = u16::from_ne_bytes([be[0], be[1]]);
+				str_buf.as_mut_slice()[1] = u16::from_ne_bytes([be[2], be[3]]);
+				(str_buf.as_ptr() as _, std::mem::size_of::<u32>() as _)
+			},
+			Self::Link(s) => {
+				*str_buf = WString::from_str(s);
+				(str_buf.as_ptr() as _, (str_buf.str_len() * std::mem::size_of::<u16>()) as _) // no terminating null
+			},
+			Self::ResourceList(b)
+				| Self::FullResourceDescriptor(b)
+				| Self::ResourceRequirementsList(b)
+				| Self::Other(_, b) => (vec_ptr(b) as _, b.len() as _),
 			Self::None => (std::ptr::null(), 0),
 		}
 	}
@@ -213,6 +310,12 @@ impl RegistryValue {
 			Self::Sz(_) => co::REG::SZ,
 			Self::ExpandSz(_) => co::REG::EXPAND_SZ,
 			Self::MultiSz(_) => co::REG::MULTI_SZ,
+			Self::DwordBigEndian(_) => co::REG::DWORD_BIG_ENDIAN,
+			Self::Link(_) => co::REG::LINK,
+			Self::ResourceList(_) => co::REG::RESOURCE_LIST,
+			Self::FullResourceDescriptor(_) => co::REG::FULL_RESOURCE_DESCRIPTOR,
+			Self::ResourceRequirementsList(_) => co::REG::RESOURCE_REQUIREMENTS_LIST,
+			Self::Other(t, _) => *t,
 			Self::None => co::REG::NONE,
 		}
 	}
@@ -364,4 +467,32 @@ impl<'a> ServiceControl<'a> {
 			_ => Self::UserDefined(control as _, event_type, event_data as _),
 		}
 	}
+
+	/// Returns the control code of the notification.
+	#[must_use]
+	pub const fn code(&self) -> co::SERVICE_CONTROL {
+		match self {
+			Self::Continue => co::SERVICE_CONTROL::CONTINUE,
+			Self::Interrogate => co::SERVICE_CONTROL::INTERROGATE,
+			Self::NetBindAdd => co::SERVICE_CONTROL::NETBINDADD,
+			Self::NetBindDisable => co::SERVICE_CONTROL::NETBINDDISABLE,
+			Self::NetBindEnable => co::SERVICE_CONTROL::NETBINDENABLE,
+			Self::NetBindRemove => co::SERVICE_CONTROL::NETBINDREMOVE,
+			Self::ParamChange => co::SERVICE_CONTROL::PARAMCHANGE,
+			Self::Pause => co::SERVICE_CONTROL::PAUSE,
+			Self::PreShutdown => co::SERVICE_CONTROL::PRESHUTDOWN,
+			Self::Shutdown => co::SERVICE_CONTROL::SHUTDOWN,
+			Self::Stop => co::SERVICE_CONTROL::STOP,
+
+			Self::DeviceEvent(_, _) => co::SERVICE_CONTROL::DEVICEEVENT,
+			Self::HardwareProfileChange(_) => co::SERVICE_CONTROL::HARDWAREPROFILECHANGE,
+			Self::PowerEvent(_, _) => co::SERVICE_CONTROL::POWEREVENT,
+			Self::SessionChange(_, _) => co::SERVICE_CONTROL::SESSIONCHANGE,
+			Self::TimeChange(_) => co::SERVICE_CONTROL::TIMECHANGE,
+			Self::TriggerEvent => co::SERVICE_CONTROL::TRIGGEREVENT,
+			Self::UserModeReboot => co::SERVICE_CONTROL::USERMODEREBOOT,
+
+			Self::UserDefined(control, _, _) => unsafe { co::SERVICE_CONTROL::from_raw(*control as _) },
+		}
+	}
 }
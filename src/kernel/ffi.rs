@@ -3,10 +3,13 @@ use crate::kernel::ffi_types::*;
 extern_sys! { "advapi32";
 	AdjustTokenPrivileges(HANDLE, BOOL, PCVOID, u32, PVOID, *mut u32) -> BOOL
 	AllocateAndInitializeSid(PCVOID, u8, u32, u32, u32, u32, u32, u32, u32, u32, *mut u8) -> BOOL
+	ChangeServiceConfig2W(HANDLE, u32, PCVOID) -> BOOL
 	CheckTokenCapability(HANDLE, PCVOID, *mut BOOL) -> BOOL
 	CheckTokenMembership(HANDLE, PCVOID, *mut BOOL) -> BOOL
 	CloseServiceHandle(HANDLE) -> BOOL
+	ControlService(HANDLE, u32, PVOID) -> BOOL
 	ConvertSidToStringSidW(PCVOID, *mut PSTR) -> BOOL
+	ConvertStringSecurityDescriptorToSecurityDescriptorW(PCSTR, u32, *mut PVOID, *mut u32) -> BOOL
 	ConvertStringSidToSidW(PCSTR, *mut *mut u8) -> BOOL
 	CopySid(u32, PVOID, PCVOID) -> BOOL
 	CreateServiceW(HANDLE, PCSTR, PCSTR, u32, u32, u32, u32, PCSTR, PCSTR, *mut u32, PCSTR, PCSTR, PCSTR) -> HANDLE
@@ -17,6 +20,7 @@ extern_sys! { "advapi32";
 	DuplicateToken(HANDLE, u32, *mut HANDLE) -> BOOL
 	EncryptFileW(PCSTR) -> BOOL
 	EncryptionDisable(PCSTR, BOOL) -> BOOL
+	EnumServicesStatusExW(HANDLE, u32, u32, u32, PVOID, u32, *mut u32, *mut u32, *mut u32, PCSTR) -> BOOL
 	EqualDomainSid(PVOID, PVOID, *mut BOOL) -> BOOL
 	EqualPrefixSid(PVOID, PVOID) -> BOOL
 	EqualSid(PVOID, PVOID) -> BOOL
@@ -44,6 +48,7 @@ extern_sys! { "advapi32";
 	OpenSCManagerW(PCSTR, PCSTR, u32) -> HANDLE
 	OpenServiceW(HANDLE, PCSTR, u32) -> HANDLE
 	OpenThreadToken(HANDLE, u32, BOOL, *mut HANDLE) -> BOOL
+	QueryServiceStatusEx(HANDLE, u32, PVOID, u32, *mut u32) -> BOOL
 	RegCloseKey(HANDLE) -> i32
 	RegConnectRegistryW(PCSTR, HANDLE, *mut HANDLE) -> i32
 	RegCopyTreeW(HANDLE, PCSTR, HANDLE) -> i32
@@ -65,6 +70,7 @@ extern_sys! { "advapi32";
 	RegisterEventSourceW(PCSTR, PCSTR) -> HANDLE
 	RegisterServiceCtrlHandlerExW(PCSTR, PFUNC, PVOID) -> HANDLE
 	RegLoadKeyW(HANDLE, PCSTR, PCSTR) -> i32
+	RegNotifyChangeKeyValue(HANDLE, BOOL, u32, HANDLE, BOOL) -> i32
 	RegOpenCurrentUser(u32, *mut HANDLE) -> i32
 	RegOpenKeyExW(HANDLE, PCSTR, u32, u32, *mut HANDLE) -> i32
 	RegOpenKeyTransactedW(HANDLE, PCSTR, u32, u32, *mut HANDLE, HANDLE, PVOID) -> i32
@@ -81,23 +87,35 @@ extern_sys! { "advapi32";
 	RegSetValueExW(HANDLE, PCSTR, u32, u32, *const u8, u32) -> i32
 	RegUnLoadKeyW(HANDLE, PCSTR) -> i32
 	ReportEventW(HANDLE, u16, u16, u32, PCVOID, u16, u32, *const PCSTR, PCVOID) -> BOOL
+	SetServiceStatus(HANDLE, PCVOID) -> BOOL
+	StartServiceCtrlDispatcherW(PCVOID) -> BOOL
+	StartServiceW(HANDLE, u32, *const PCSTR) -> BOOL
 }
 
 extern_sys! { "kernel32";
+	AssignProcessToJobObject(HANDLE, HANDLE) -> BOOL
 	BeginUpdateResourceW(PCSTR, BOOL) -> HANDLE
+	CancelIoEx(HANDLE, PVOID) -> BOOL
 	CheckRemoteDebuggerPresent(HANDLE, *mut BOOL) -> BOOL
 	CloseHandle(HANDLE) -> BOOL
+	ConnectNamedPipe(HANDLE, PVOID) -> BOOL
 	CopyFileW(PCSTR, PCSTR, BOOL) -> BOOL
 	CreateDirectoryW(PCSTR, PVOID) -> BOOL
 	CreateEventExW(PCVOID, PCSTR, u32, u32) -> HANDLE
 	CreateEventW(PCVOID, BOOL, BOOL, PCSTR) -> HANDLE
 	CreateFileMappingFromApp(HANDLE, PVOID, u32, u64, PCSTR) -> HANDLE
 	CreateFileW(PCSTR, u32, u32, PVOID, u32, u32, HANDLE) -> HANDLE
+	CreateIoCompletionPort(HANDLE, HANDLE, usize, u32) -> HANDLE
+	CreateJobObjectW(PVOID, PCSTR) -> HANDLE
+	CreateNamedPipeW(PCSTR, u32, u32, u32, u32, u32, u32, PVOID) -> HANDLE
 	CreatePipe(*mut HANDLE, *mut HANDLE, PVOID, u32) -> BOOL
 	CreateProcessW(PCSTR, PSTR, PVOID, PVOID, BOOL, u32, PVOID, PCSTR, PVOID, PVOID) -> BOOL
 	CreateThread(PVOID, usize, PVOID, PVOID, u32, *mut u32) -> HANDLE
 	CreateToolhelp32Snapshot(u32, u32) -> HANDLE
 	DeleteFileW(PCSTR) -> BOOL
+	DeleteProcThreadAttributeList(PVOID)
+	DisconnectNamedPipe(HANDLE) -> BOOL
+	DuplicateHandle(HANDLE, HANDLE, HANDLE, *mut HANDLE, u32, BOOL, u32) -> BOOL
 	EndUpdateResourceW(HANDLE, BOOL) -> BOOL
 	EnumResourceLanguagesW(HANDLE, PCSTR, PCSTR, PFUNC, isize) -> BOOL
 	EnumResourceNamesW(HANDLE, PCSTR, PFUNC, isize) -> BOOL
@@ -107,11 +125,15 @@ extern_sys! { "kernel32";
 	ExpandEnvironmentStringsW(PCSTR, PSTR, u32) -> u32
 	FileTimeToSystemTime(PCVOID, PVOID) -> BOOL
 	FindClose(HANDLE) -> BOOL
+	FindCloseChangeNotification(HANDLE) -> BOOL
+	FindFirstChangeNotificationW(PCSTR, BOOL, u32) -> HANDLE
 	FindFirstFileW(PCSTR, PVOID) -> HANDLE
+	FindNextChangeNotification(HANDLE) -> BOOL
 	FindNextFileW(HANDLE, PVOID) -> BOOL
 	FindResourceExW(HANDLE, PCSTR, PCSTR, u16) -> HANDLE
 	FindResourceW(HANDLE, PCSTR, PCSTR) -> HANDLE
 	FlushConsoleInputBuffer(HANDLE) -> BOOL
+	FlushFileBuffers(HANDLE) -> BOOL
 	FlushInstructionCache(HANDLE, PCVOID, usize) -> BOOL
 	FlushProcessWriteBuffers()
 	FormatMessageW(u32, PCVOID, u32, u32, PSTR, u32, PVOID) -> u32
@@ -139,6 +161,7 @@ extern_sys! { "kernel32";
 	GetFileType(HANDLE) -> u32
 	GetFirmwareType(*mut u32) -> BOOL
 	GetGuiResources(HANDLE, u32) -> u32
+	GetHandleInformation(HANDLE, *mut u32) -> BOOL
 	GetLargePageMinimum() -> usize
 	GetLastError() -> u32
 	GetLocalTime(PVOID)
@@ -146,7 +169,10 @@ extern_sys! { "kernel32";
 	GetLogicalDriveStringsW(u32, PSTR) -> u32
 	GetModuleFileNameW(HANDLE, PSTR, u32) -> u32
 	GetModuleHandleW(PCSTR) -> HANDLE
+	GetNamedPipeClientProcessId(HANDLE, *mut u32) -> BOOL
+	GetNamedPipeServerProcessId(HANDLE, *mut u32) -> BOOL
 	GetNativeSystemInfo(PVOID)
+	GetOverlappedResult(HANDLE, PVOID, *mut u32, BOOL) -> BOOL
 	GetPriorityClass(HANDLE) -> u32
 	GetProcAddress(HANDLE, *const u8) -> PCVOID
 	GetProcessHandleCount(HANDLE, &mut u32) -> BOOL
@@ -155,6 +181,8 @@ extern_sys! { "kernel32";
 	GetProcessId(HANDLE) -> u32
 	GetProcessIdOfThread(HANDLE) -> u32
 	GetProcessTimes(HANDLE, PVOID, PVOID, PVOID, PVOID) -> BOOL
+	GetQueuedCompletionStatus(HANDLE, *mut u32, *mut usize, *mut PVOID, u32) -> BOOL
+	GetQueuedCompletionStatusEx(HANDLE, PVOID, u32, *mut u32, u32, BOOL) -> BOOL
 	GetStartupInfoW(PVOID)
 	GetStdHandle(u32) -> HANDLE
 	GetSystemDirectoryW(PSTR, u32) -> u32
@@ -193,9 +221,11 @@ extern_sys! { "kernel32";
 	HeapUnlock(HANDLE) -> BOOL
 	HeapValidate(HANDLE, u32, PVOID) -> BOOL
 	HeapWalk(HANDLE, PVOID) -> BOOL
+	InitializeProcThreadAttributeList(PVOID, u32, u32, *mut usize) -> BOOL
 	IsDebuggerPresent() -> BOOL
 	IsNativeVhdBoot(*mut BOOL) -> BOOL
 	IsProcessCritical(HANDLE, *mut BOOL) -> BOOL
+	IsProcessInJob(HANDLE, HANDLE, *mut BOOL) -> BOOL
 	IsWow64Process(HANDLE, *mut BOOL) -> BOOL
 	LoadLibraryW(PCSTR) -> HANDLE
 	LoadResource(HANDLE, HANDLE) -> HANDLE
@@ -217,16 +247,22 @@ extern_sys! { "kernel32";
 	MulDiv(i32, i32, i32) -> i32
 	MultiByteToWideChar(u32, u32, *const u8, i32, PSTR, i32) -> i32
 	OpenEventW(u32, BOOL, PCSTR) -> HANDLE
+	OpenJobObjectW(u32, BOOL, PCSTR) -> HANDLE
 	OpenProcess(u32, BOOL, u32) -> HANDLE
 	OutputDebugStringW(PCSTR)
+	PeekNamedPipe(HANDLE, PVOID, u32, *mut u32, *mut u32, *mut u32) -> BOOL
+	PostQueuedCompletionStatus(HANDLE, u32, usize, PCVOID) -> BOOL
 	Process32FirstW(HANDLE, PVOID) -> BOOL
 	Process32NextW(HANDLE, PVOID) -> BOOL
 	PulseEvent(HANDLE) -> BOOL
 	QueryFullProcessImageNameW(HANDLE, u32, PSTR, *mut u32) -> BOOL
+	QueryInformationJobObject(HANDLE, u32, PVOID, u32, *mut u32) -> BOOL
 	QueryPerformanceCounter(*mut i64) -> BOOL
 	QueryPerformanceFrequency(*mut i64) -> BOOL
 	QueryProcessAffinityUpdateMode(HANDLE, *mut u32) -> BOOL
 	ReadConsoleW(HANDLE, PVOID, u32, *mut u32, PVOID) -> BOOL
+	ReadDirectoryChangesExW(HANDLE, PVOID, u32, BOOL, u32, *mut u32, PVOID, PFUNC, u32) -> BOOL
+	ReadDirectoryChangesW(HANDLE, PVOID, u32, BOOL, u32, *mut u32, PVOID, PFUNC) -> BOOL
 	ReadFile(HANDLE, PVOID, u32, *mut u32, PVOID) -> BOOL
 	ReplaceFileW(PCSTR, PCSTR, PCSTR, u32, PVOID, PVOID) -> BOOL
 	ResetEvent(HANDLE) -> BOOL
@@ -238,7 +274,10 @@ extern_sys! { "kernel32";
 	SetFileAttributesW(PCSTR, u32) -> BOOL
 	SetFilePointerEx(HANDLE, i64, *mut i64, u32) -> BOOL
 	SetFileTime(HANDLE, PCVOID, PCVOID, PCVOID) -> BOOL
+	SetHandleInformation(HANDLE, u32, u32) -> BOOL
+	SetInformationJobObject(HANDLE, u32, PCVOID, u32) -> BOOL
 	SetLastError(u32)
+	SetNamedPipeHandleState(HANDLE, *const u32, *const u32, *const u32) -> BOOL
 	SetPriorityClass(HANDLE, u32) -> BOOL
 	SetProcessAffinityUpdateMode(HANDLE, u32) -> BOOL
 	SetProcessPriorityBoost(HANDLE, BOOL) -> BOOL
@@ -252,16 +291,20 @@ extern_sys! { "kernel32";
 	SwitchToThread() -> BOOL
 	SystemTimeToFileTime(PCVOID, PVOID) -> BOOL
 	SystemTimeToTzSpecificLocalTime(PCVOID, PCVOID, PVOID) -> BOOL
+	TerminateJobObject(HANDLE, u32) -> BOOL
 	TerminateProcess(HANDLE, u32) -> BOOL
 	TerminateThread(HANDLE, u32) -> BOOL
 	Thread32First(HANDLE, PVOID) -> BOOL
 	Thread32Next(HANDLE, PVOID) -> BOOL
+	TransactNamedPipe(HANDLE, PCVOID, u32, PVOID, u32, *mut u32, PVOID) -> BOOL
 	UnlockFile(HANDLE, u32, u32, u32, u32) -> BOOL
 	UnmapViewOfFile(PCVOID) -> BOOL
+	UpdateProcThreadAttribute(PVOID, u32, usize, PCVOID, usize, PVOID, *mut usize) -> BOOL
 	UpdateResourceW(HANDLE, PCSTR, PCSTR, u16, PVOID, u32) -> BOOL
 	VerifyVersionInfoW(PVOID, u32, u64) -> BOOL
 	VerSetConditionMask(u64, u32, u8) -> u64
 	WaitForSingleObject(HANDLE, u32) -> u32
+	WaitNamedPipeW(PCSTR, u32) -> BOOL
 	WideCharToMultiByte(u32, u32, PCSTR, i32, PSTR, i32, *const u8, *mut BOOL) -> i32
 	WriteConsoleW(HANDLE, PCVOID, u32, *mut u32, PVOID) -> BOOL
 	WriteFile(HANDLE, PCVOID, u32, *mut u32, PVOID) -> BOOL
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use crate::co;
 use crate::decl::*;
@@ -97,6 +98,44 @@ pub fn ConvertSidToStringSid(sid: &SID) -> SysResult<String> {
 	Ok(name)
 }
 
+/// [`ConvertStringSecurityDescriptorToSecurityDescriptor`](https://learn.microsoft.com/en-us/windows/win32/api/sddl/nf-sddl-convertstringsecuritydescriptortosecuritydescriptorw)
+/// function.
+///
+/// Returns the memory block which contains the self-relative security
+/// descriptor.
+///
+/// # Examples
+///
+/// A descriptor granting full access to SYSTEM and administrators, and
+/// read/write access to interactive users:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let hsd = w::ConvertStringSecurityDescriptorToSecurityDescriptor(
+///     "D:(A;;GA;;;SY)(A;;GA;;;BA)(A;;GRGW;;;IU)",
+/// )?;
+/// println!("{} bytes", hsd.LocalSize()?);
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[must_use]
+pub fn ConvertStringSecurityDescriptorToSecurityDescriptor(
+	sddl: &str,
+) -> SysResult<LocalFreeGuard>
+{
+	let mut pbuf = std::ptr::null_mut();
+	unsafe {
+		bool_to_sysresult(
+			ffi::ConvertStringSecurityDescriptorToSecurityDescriptorW(
+				WString::from_str(sddl).as_ptr(),
+				SDDL_REVISION_1,
+				&mut pbuf,
+				std::ptr::null_mut(),
+			),
+		).map(|_| LocalFreeGuard::new(HLOCAL::from_ptr(pbuf)))
+	}
+}
+
 /// [`ConvertStringSidToSid`](https://learn.microsoft.com/en-us/windows/win32/api/sddl/nf-sddl-convertstringsidtosidw)
 /// function.
 #[must_use]
@@ -533,6 +572,9 @@ pub fn GetDiskSpaceInformation(
 /// environment block with
 /// [`FreeEnvironmentStrings`](https://learn.microsoft.com/en-us/windows/win32/api/processenv/nf-processenv-freeenvironmentstringsw).
 ///
+/// The hidden per-drive current directory variables, like `=C:`, are
+/// returned too, with their leading `=`.
+///
 /// # Examples
 ///
 /// Retrieving and printing the key/value pairs of all environment strings:
@@ -553,12 +595,7 @@ pub fn GetEnvironmentStrings() -> SysResult<HashMap<String, String>> {
 			let vec_env_strs = parse_multi_z_str(ptr as *mut _ as _);
 			unsafe { ffi::FreeEnvironmentStringsW(ptr); }
 			vec_env_strs.iter()
-				.map(|env_str| {
-					let mut pair = env_str.split("="); // assumes correctly formatted pairs
-					let key = pair.next().unwrap();
-					let val = pair.next().unwrap();
-					(key.to_owned(), val.to_owned())
-				})
+				.map(|env_str| split_env_str(env_str))
 				.collect()
 		})
 }
@@ -1594,6 +1631,21 @@ pub fn SetFileAttributes(
 	)
 }
 
+/// [`SetHandleInformation`](https://learn.microsoft.com/en-us/windows/win32/api/handleapi/nf-handleapi-sethandleinformation)
+/// function.
+///
+/// Only the flags present in `mask` are changed.
+pub fn SetHandleInformation(
+	handle: &impl Handle,
+	mask: co::HANDLE_FLAG,
+	flags: co::HANDLE_FLAG,
+) -> SysResult<()>
+{
+	bool_to_sysresult(
+		unsafe { ffi::SetHandleInformation(handle.ptr(), mask.raw(), flags.raw()) },
+	)
+}
+
 /// [`SetLastError`](https://learn.microsoft.com/en-us/windows/win32/api/errhandlingapi/nf-errhandlingapi-setlasterror)
 /// function.
 pub fn SetLastError(err_code: co::ERROR) {
@@ -1616,6 +1668,83 @@ pub fn Sleep(milliseconds: u32) {
 	unsafe { ffi::Sleep(milliseconds) }
 }
 
+/// [`StartServiceCtrlDispatcher`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-startservicectrldispatcherw)
+/// function.
+///
+/// Connects the main thread of a service process to the Service Control
+/// Manager, blocking until all the services have stopped. Each service runs
+/// `service_main` in its own thread, receiving the service name followed by
+/// the start arguments.
+///
+/// The function can be called only once at a time. When the process is not
+/// started by the Service Control Manager, it fails with
+/// [`co::ERROR::FAILED_SERVICE_CONTROLLER_CONNECT`](crate::co::ERROR::FAILED_SERVICE_CONTROLLER_CONNECT).
+///
+/// Note that this function may return right after a service reports
+/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED), while the
+/// service thread is still running `service_main`; the closure is dropped only
+/// after all its calls have returned.
+///
+/// Unless you need something specific, consider using the
+/// [`ServiceDispatcher`](crate::ServiceDispatcher) high-level abstraction.
+pub fn StartServiceCtrlDispatcher<F>(
+	service_names: &[impl AsRef<str>],
+	service_main: F,
+) -> SysResult<()>
+	where F: Fn(Vec<String>) + Send + Sync + 'static,
+{
+	let wnames = service_names.iter()
+		.map(|name| WString::from_str(name.as_ref()))
+		.collect::<Vec<_>>();
+	let mut table = wnames.iter()
+		.map(|wname| SERVICE_TABLE_ENTRY {
+			lpServiceName: wname.as_ptr(),
+			lpServiceProc: service_main_proc as _,
+		})
+		.collect::<Vec<_>>();
+	table.push(SERVICE_TABLE_ENTRY { // terminator
+		lpServiceName: std::ptr::null(),
+		lpServiceProc: std::ptr::null(),
+	});
+
+	{
+		let mut current = SERVICE_MAIN_FUNC.lock().unwrap();
+		if current.is_some() {
+			return Err(co::ERROR::SERVICE_ALREADY_RUNNING); // another dispatcher running
+		}
+		*current = Some(Arc::new(service_main));
+	}
+	let res = bool_to_sysresult(
+		unsafe { ffi::StartServiceCtrlDispatcherW(table.as_ptr() as _) },
+	);
+	*SERVICE_MAIN_FUNC.lock().unwrap() = None; // running services keep their own Arc
+	res
+}
+
+type ServiceMainFunc = Arc<dyn Fn(Vec<String>) + Send + Sync>;
+
+static SERVICE_MAIN_FUNC: Mutex<Option<ServiceMainFunc>> = Mutex::new(None);
+
+#[repr(C)]
+struct SERVICE_TABLE_ENTRY {
+	lpServiceName: *const u16,
+	lpServiceProc: *const std::ffi::c_void,
+}
+
+extern "system" fn service_main_proc(argc: u32, argv: *mut *mut u16) {
+	let args = if argv.is_null() {
+		Vec::new()
+	} else {
+		unsafe { std::slice::from_raw_parts(argv, argc as _) }.iter()
+			.map(|parg| unsafe { WString::from_wchars_nullt(*parg) }.to_string())
+			.collect()
+	};
+	let func = SERVICE_MAIN_FUNC.lock().unwrap().clone();
+	if let Some(func) = func {
+		func(args);
+	}
+}
+
 /// [`SwitchToThread`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-switchtothread)
 /// function.
 pub fn SwitchToThread() -> SysResult<()> {
@@ -215,6 +215,14 @@ impl EndUpdateResourceGuard {
 
 //------------------------------------------------------------------------------
 
+handle_guard! { FindCloseChangeNotificationGuard: HFINDCHANGE;
+	ffi::FindCloseChangeNotification;
+	/// RAII implementation for [`HFINDCHANGE`](crate::HFINDCHANGE) which
+	/// automatically calls
+	/// [`FindCloseChangeNotification`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-findclosechangenotification)
+	/// when the object goes out of scope.
+}
+
 handle_guard! { FindCloseGuard: HFINDFILE;
 	ffi::FindClose;
 	/// RAII implementation for [`HFINDFILE`](crate::HFINDFILE) which
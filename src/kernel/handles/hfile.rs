@@ -26,6 +26,27 @@ impl kernel_Hfile for HFILE {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait kernel_Hfile: Handle {
+	/// [`CancelIoEx`](https://learn.microsoft.com/en-us/windows/win32/fileio/cancelioex-func)
+	/// function.
+	///
+	/// If `overlapped` is `None`, cancels all pending I/O operations issued by
+	/// any thread for this handle.
+	///
+	/// Note that the operation is not finished when this function returns: you
+	/// must still wait for its completion – for example, with
+	/// [`HFILE::GetOverlappedResult`](crate::prelude::kernel_Hfile::GetOverlappedResult)
+	/// – before freeing the `OVERLAPPED` and its buffer.
+	fn CancelIoEx(&self, overlapped: Option<&OVERLAPPED>) -> SysResult<()> {
+		bool_to_sysresult(
+			unsafe {
+				ffi::CancelIoEx(
+					self.ptr(),
+					overlapped.map_or(std::ptr::null_mut(), |lp| lp as *const _ as _),
+				)
+			},
+		)
+	}
+
 	/// [`CreateFile`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-createfilew)
 	/// function.
 	///
@@ -133,6 +154,12 @@ pub trait kernel_Hfile: Handle {
 		}
 	}
 
+	/// [`FlushFileBuffers`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-flushfilebuffers)
+	/// function.
+	fn FlushFileBuffers(&self) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::FlushFileBuffers(self.ptr()) })
+	}
+
 	/// [`GetFileInformationByHandle`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-getfileinformationbyhandle)
 	/// function.
 	fn GetFileInformationByHandle(&self,
@@ -188,6 +215,30 @@ pub trait kernel_Hfile: Handle {
 		}
 	}
 
+	/// [`GetOverlappedResult`](https://learn.microsoft.com/en-us/windows/win32/api/ioapiset/nf-ioapiset-getoverlappedresult)
+	/// function.
+	///
+	/// Returns the number of bytes transferred by the operation. If `wait` is
+	/// false and the operation is still pending, fails with
+	/// [`co::ERROR::IO_INCOMPLETE`](crate::co::ERROR::IO_INCOMPLETE).
+	fn GetOverlappedResult(&self,
+		overlapped: &OVERLAPPED,
+		wait: bool,
+	) -> SysResult<u32>
+	{
+		let mut bytes_transferred = u32::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::GetOverlappedResult(
+					self.ptr(),
+					overlapped as *const _ as _,
+					&mut bytes_transferred,
+					wait as _,
+				)
+			},
+		).map(|_| bytes_transferred)
+	}
+
 	/// [`LockFile`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-lockfile)
 	/// function.
 	///
@@ -237,6 +288,79 @@ pub trait kernel_Hfile: Handle {
 		}
 	}
 
+	/// [`ReadDirectoryChangesW`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-readdirectorychangesw)
+	/// function.
+	///
+	/// The handle must be a directory opened with
+	/// [`FILE_FLAG::BACKUP_SEMANTICS`](crate::co::FILE_FLAG::BACKUP_SEMANTICS).
+	/// The buffer must be aligned on a `u32` boundary, and is filled with
+	/// `FILE_NOTIFY_INFORMATION` records, which can be parsed with
+	/// [`FileNotifyRecord::parse`](crate::FileNotifyRecord::parse).
+	///
+	/// Returns the number of bytes written to the buffer; zero means it
+	/// overflowed and the changes were lost.
+	///
+	/// Unless you need something specific, consider using the
+	/// [`DirWatcher`](crate::DirWatcher) high-level abstraction.
+	fn ReadDirectoryChanges(&self,
+		buffer: &mut [u8],
+		watch_subtree: bool,
+		notify_filter: co::FILE_NOTIFY_CHANGE,
+		overlapped: Option<&mut OVERLAPPED>,
+	) -> SysResult<u32>
+	{
+		let mut bytes_returned = u32::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::ReadDirectoryChangesW(
+					self.ptr(),
+					buffer.as_mut_ptr() as _,
+					buffer.len() as _,
+					watch_subtree as _,
+					notify_filter.raw(),
+					&mut bytes_returned,
+					overlapped.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+					std::ptr::null(),
+				)
+			},
+		).map(|_| bytes_returned)
+	}
+
+	/// [`ReadDirectoryChangesExW`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-readdirectorychangesexw)
+	/// function.
+	///
+	/// Same as
+	/// [`HFILE::ReadDirectoryChanges`](crate::prelude::kernel_Hfile::ReadDirectoryChanges),
+	/// but with
+	/// [`READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation`](crate::co::READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation)
+	/// the buffer is filled with `FILE_NOTIFY_EXTENDED_INFORMATION` records,
+	/// which must be aligned on a `u64` boundary.
+	fn ReadDirectoryChangesEx(&self,
+		buffer: &mut [u8],
+		watch_subtree: bool,
+		notify_filter: co::FILE_NOTIFY_CHANGE,
+		overlapped: Option<&mut OVERLAPPED>,
+		information_class: co::READ_DIRECTORY_NOTIFY_INFORMATION_CLASS,
+	) -> SysResult<u32>
+	{
+		let mut bytes_returned = u32::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::ReadDirectoryChangesExW(
+					self.ptr(),
+					buffer.as_mut_ptr() as _,
+					buffer.len() as _,
+					watch_subtree as _,
+					notify_filter.raw(),
+					&mut bytes_returned,
+					overlapped.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+					std::ptr::null(),
+					information_class.raw(),
+				)
+			},
+		).map(|_| bytes_returned)
+	}
+
 	/// [`ReadFile`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile)
 	/// function.
 	///
@@ -0,0 +1,83 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::kernel::{ffi, privs::*};
+use crate::prelude::*;
+
+impl_handle! { HFINDCHANGE;
+	/// Handle to a
+	/// [change notification](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-findfirstchangenotificationw).
+	/// Originally just a `HANDLE`.
+}
+
+impl kernel_Hfindchange for HFINDCHANGE {}
+
+/// This trait is enabled with the `kernel` feature, and provides methods for
+/// [`HFINDCHANGE`](crate::HFINDCHANGE).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait kernel_Hfindchange: Handle {
+	/// [`FindFirstChangeNotification`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-findfirstchangenotificationw)
+	/// function.
+	///
+	/// The notification only tells that something changed; to know what, use
+	/// [`DirWatcher`](crate::DirWatcher).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hfind = w::HFINDCHANGE::FindFirstChangeNotification(
+	///     "C:\\Temp", false, co::FILE_NOTIFY_CHANGE::FILE_NAME)?;
+	///
+	/// loop {
+	///     hfind.WaitForSingleObject(None)?;
+	///     println!("Something changed.");
+	///     hfind.FindNextChangeNotification()?;
+	/// }
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn FindFirstChangeNotification(
+		path_name: &str,
+		watch_subtree: bool,
+		notify_filter: co::FILE_NOTIFY_CHANGE,
+	) -> SysResult<FindCloseChangeNotificationGuard>
+	{
+		unsafe {
+			match HFINDCHANGE(
+				ffi::FindFirstChangeNotificationW(
+					WString::from_str(path_name).as_ptr(),
+					watch_subtree as _,
+					notify_filter.raw(),
+				) as _,
+			) {
+				HFINDCHANGE::NULL | HFINDCHANGE::INVALID => Err(GetLastError()),
+				handle => Ok(FindCloseChangeNotificationGuard::new(handle)),
+			}
+		}
+	}
+
+	/// [`FindNextChangeNotification`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-findnextchangenotification)
+	/// function.
+	fn FindNextChangeNotification(&self) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::FindNextChangeNotification(self.ptr()) })
+	}
+
+	/// [`WaitForSingleObject`](https://learn.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitforsingleobject)
+	/// function.
+	fn WaitForSingleObject(&self,
+		milliseconds: Option<u32>,
+	) -> SysResult<co::WAIT>
+	{
+		unsafe { HEVENT::from_ptr(self.ptr()) }
+			.WaitForSingleObject(milliseconds)
+	}
+}
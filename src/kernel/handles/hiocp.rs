@@ -0,0 +1,156 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::decl::*;
+use crate::guard::*;
+use crate::kernel::{ffi, privs::*};
+use crate::prelude::*;
+
+impl_handle! { HIOCP;
+	/// Handle to an
+	/// [I/O completion port](https://learn.microsoft.com/en-us/windows/win32/fileio/i-o-completion-ports).
+	/// Originally just a `HANDLE`.
+}
+
+impl kernel_Hiocp for HIOCP {}
+
+/// This trait is enabled with the `kernel` feature, and provides methods for
+/// [`HIOCP`](crate::HIOCP).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait kernel_Hiocp: Handle {
+	/// [`CreateIoCompletionPort`](https://learn.microsoft.com/en-us/windows/win32/fileio/createiocompletionport)
+	/// function.
+	///
+	/// Associates a handle opened for overlapped I/O – like a file, a pipe or
+	/// a directory – to this completion port, so each operation on it queues a
+	/// completion packet with the given key.
+	fn AssociateIoCompletionPort(&self,
+		handle: &impl Handle,
+		completion_key: usize,
+	) -> SysResult<()>
+	{
+		ptr_to_sysresult(
+			unsafe {
+				ffi::CreateIoCompletionPort(handle.ptr(), self.ptr(), completion_key, 0)
+			},
+		).map(|_| ())
+	}
+
+	/// [`CreateIoCompletionPort`](https://learn.microsoft.com/en-us/windows/win32/fileio/createiocompletionport)
+	/// function.
+	///
+	/// Creates a new completion port, not associated to any file handle.
+	/// `concurrent_threads` of zero means one thread per processor.
+	#[must_use]
+	fn CreateIoCompletionPort(
+		concurrent_threads: u32,
+	) -> SysResult<CloseHandleGuard<HIOCP>>
+	{
+		unsafe {
+			ptr_to_sysresult_handle(
+				ffi::CreateIoCompletionPort(
+					HFILE::INVALID.ptr(),
+					std::ptr::null_mut(),
+					0,
+					concurrent_threads,
+				),
+			).map(|h| CloseHandleGuard::new(h))
+		}
+	}
+
+	/// [`GetQueuedCompletionStatus`](https://learn.microsoft.com/en-us/windows/win32/api/ioapiset/nf-ioapiset-getqueuedcompletionstatus)
+	/// function.
+	///
+	/// Returns the number of bytes transferred, the completion key and the
+	/// `OVERLAPPED` pointer of the dequeued packet. If the timeout elapses,
+	/// fails with [`co::ERROR::WAIT_TIMEOUT`](crate::co::ERROR::WAIT_TIMEOUT).
+	///
+	/// For job object notifications, the number of bytes is actually the
+	/// [`co::JOB_OBJECT_MSG`](crate::co::JOB_OBJECT_MSG), and the pointer is the
+	/// process ID.
+	///
+	/// If the dequeued packet is of a failed I/O operation, this function fails
+	/// with the error of the operation, and the packet data is lost. To receive
+	/// failed operations, use
+	/// [`HIOCP::GetQueuedCompletionStatusEx`](crate::prelude::kernel_Hiocp::GetQueuedCompletionStatusEx).
+	#[must_use]
+	fn GetQueuedCompletionStatus(&self,
+		milliseconds: Option<u32>,
+	) -> SysResult<(u32, usize, *mut OVERLAPPED)>
+	{
+		let mut num_bytes = u32::default();
+		let mut key = usize::default();
+		let mut overlapped = std::ptr::null_mut();
+
+		bool_to_sysresult(
+			unsafe {
+				ffi::GetQueuedCompletionStatus(
+					self.ptr(),
+					&mut num_bytes,
+					&mut key,
+					&mut overlapped,
+					milliseconds.unwrap_or(INFINITE),
+				)
+			},
+		).map(|_| (num_bytes, key, overlapped as _))
+	}
+
+	/// [`GetQueuedCompletionStatusEx`](https://learn.microsoft.com/en-us/windows/win32/fileio/getqueuedcompletionstatusex-func)
+	/// function.
+	///
+	/// Dequeues up to `entries.len()` packets at once, returning how many were
+	/// written to `entries`. If the timeout elapses, fails with
+	/// [`co::ERROR::WAIT_TIMEOUT`](crate::co::ERROR::WAIT_TIMEOUT).
+	///
+	/// Packets of failed I/O operations are also returned; their result can be
+	/// retrieved with
+	/// [`HFILE::GetOverlappedResult`](crate::prelude::kernel_Hfile::GetOverlappedResult).
+	fn GetQueuedCompletionStatusEx(&self,
+		entries: &mut [OVERLAPPED_ENTRY],
+		milliseconds: Option<u32>,
+		alertable: bool,
+	) -> SysResult<usize>
+	{
+		let mut num_removed = u32::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::GetQueuedCompletionStatusEx(
+					self.ptr(),
+					entries.as_mut_ptr() as _,
+					entries.len() as _,
+					&mut num_removed,
+					milliseconds.unwrap_or(INFINITE),
+					alertable as _,
+				)
+			},
+		).map(|_| num_removed as _)
+	}
+
+	/// [`PostQueuedCompletionStatus`](https://learn.microsoft.com/en-us/windows/win32/api/ioapiset/nf-ioapiset-postqueuedcompletionstatus)
+	/// function.
+	///
+	/// Queues a packet with arbitrary values, which will be returned by the
+	/// next dequeue call. This is commonly used to wake up or stop the threads
+	/// waiting on the port.
+	fn PostQueuedCompletionStatus(&self,
+		num_bytes: u32,
+		completion_key: usize,
+		overlapped: Option<&OVERLAPPED>,
+	) -> SysResult<()>
+	{
+		bool_to_sysresult(
+			unsafe {
+				ffi::PostQueuedCompletionStatus(
+					self.ptr(),
+					num_bytes,
+					completion_key,
+					overlapped.map_or(std::ptr::null(), |lp| lp as *const _ as _),
+				)
+			},
+		)
+	}
+}
@@ -0,0 +1,242 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::kernel::{ffi, privs::*};
+use crate::prelude::*;
+
+impl_handle! { HJOB;
+	/// Handle to a
+	/// [job object](https://learn.microsoft.com/en-us/windows/win32/procthread/job-objects).
+	/// Originally just a `HANDLE`.
+}
+
+impl kernel_Hjob for HJOB {}
+
+/// This trait is enabled with the `kernel` feature, and provides methods for
+/// [`HJOB`](crate::HJOB).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait kernel_Hjob: Handle {
+	/// [`AssignProcessToJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-assignprocesstojobobject)
+	/// function.
+	fn AssignProcessToJobObject(&self, process: &HPROCESS) -> SysResult<()> {
+		bool_to_sysresult(
+			unsafe { ffi::AssignProcessToJobObject(self.ptr(), process.ptr()) },
+		)
+	}
+
+	/// [`CreateJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-createjobobjectw)
+	/// function.
+	///
+	/// # Examples
+	///
+	/// Making all processes in the job die when the handle is closed:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hjob = w::HJOB::CreateJobObject(None, None)?;
+	///
+	/// let mut info = w::JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+	/// info.BasicLimitInformation.LimitFlags = co::JOB_OBJECT_LIMIT::KILL_ON_JOB_CLOSE;
+	/// unsafe {
+	///     hjob.SetInformationJobObject(
+	///         co::JOBOBJECTINFOCLASS::ExtendedLimitInformation,
+	///         &info,
+	///     )?;
+	/// }
+	///
+	/// hjob.AssignProcessToJobObject(&w::HPROCESS::GetCurrentProcess())?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn CreateJobObject(
+		security_attributes: Option<&mut SECURITY_ATTRIBUTES>,
+		name: Option<&str>,
+	) -> SysResult<CloseHandleGuard<HJOB>>
+	{
+		unsafe {
+			ptr_to_sysresult_handle(
+				ffi::CreateJobObjectW(
+					security_attributes.map_or(std::ptr::null_mut(), |sa| sa as *mut _ as _),
+					WString::from_opt_str(name).as_ptr(),
+				),
+			).map(|h| CloseHandleGuard::new(h))
+		}
+	}
+
+	/// [`OpenJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-openjobobjectw)
+	/// function.
+	#[must_use]
+	fn OpenJobObject(
+		desired_access: co::JOB_OBJECT,
+		inherit_handle: bool,
+		name: &str,
+	) -> SysResult<CloseHandleGuard<HJOB>>
+	{
+		unsafe {
+			ptr_to_sysresult_handle(
+				ffi::OpenJobObjectW(
+					desired_access.raw(),
+					inherit_handle as _,
+					WString::from_str(name).as_ptr(),
+				),
+			).map(|h| CloseHandleGuard::new(h))
+		}
+	}
+
+	/// [`QueryInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryinformationjobobject)
+	/// function.
+	///
+	/// For
+	/// [`co::JOBOBJECTINFOCLASS::BasicProcessIdList`](crate::co::JOBOBJECTINFOCLASS::BasicProcessIdList),
+	/// prefer [`HJOB::QueryJobProcessIdList`](crate::prelude::kernel_Hjob::QueryJobProcessIdList).
+	///
+	/// # Safety
+	///
+	/// Make sure the `information` type is the correct one, matching that in
+	/// `information_class`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hjob: w::HJOB; // initialized somewhere
+	/// # let hjob = w::HJOB::NULL;
+	///
+	/// let mut acc = w::JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION::default();
+	/// unsafe {
+	///     hjob.QueryInformationJobObject(
+	///         co::JOBOBJECTINFOCLASS::BasicAndIoAccountingInformation,
+	///         &mut acc,
+	///     )?;
+	/// }
+	/// println!("Processes: {}", acc.BasicInfo.TotalProcesses);
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	unsafe fn QueryInformationJobObject<T>(&self,
+		information_class: co::JOBOBJECTINFOCLASS,
+		information: &mut T,
+	) -> SysResult<()>
+	{
+		bool_to_sysresult(
+			unsafe {
+				ffi::QueryInformationJobObject(
+					self.ptr(),
+					information_class.raw(),
+					information as *mut _ as _,
+					std::mem::size_of::<T>() as _,
+					std::ptr::null_mut(),
+				)
+			},
+		)
+	}
+
+	/// [`QueryInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryinformationjobobject)
+	/// function with
+	/// [`co::JOBOBJECTINFOCLASS::BasicProcessIdList`](crate::co::JOBOBJECTINFOCLASS::BasicProcessIdList).
+	///
+	/// Returns the IDs of the processes currently associated with the job.
+	#[must_use]
+	fn QueryJobProcessIdList(&self) -> SysResult<Vec<u32>> {
+		// JOBOBJECT_BASIC_PROCESS_ID_LIST is two u32 counters followed by an
+		// array of ULONG_PTR, so a usize buffer keeps the alignment right.
+		const HEADER: usize = 8 / std::mem::size_of::<usize>();
+		let mut capacity = 16;
+
+		loop {
+			let mut buf = vec![0usize; HEADER + capacity];
+			let ret = unsafe {
+				ffi::QueryInformationJobObject(
+					self.ptr(),
+					co::JOBOBJECTINFOCLASS::BasicProcessIdList.raw(),
+					buf.as_mut_ptr() as _,
+					(buf.len() * std::mem::size_of::<usize>()) as _,
+					std::ptr::null_mut(),
+				)
+			};
+
+			let counts = unsafe { std::slice::from_raw_parts(buf.as_ptr() as *const u32, 2) };
+			let (num_assigned, num_in_list) = (counts[0] as usize, counts[1] as usize);
+
+			if ret == 0 {
+				match GetLastError() {
+					co::ERROR::MORE_DATA => {},
+					err => return Err(err),
+				}
+			} else if num_in_list >= num_assigned {
+				return Ok(
+					buf[HEADER..HEADER + num_in_list].iter()
+						.map(|pid| *pid as u32)
+						.collect(),
+				);
+			}
+
+			// Processes may be added between calls, so leave some room.
+			capacity = capacity.max(num_assigned) * 2;
+		}
+	}
+
+	/// [`SetInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setinformationjobobject)
+	/// function.
+	///
+	/// # Safety
+	///
+	/// Make sure the `information` type is the correct one, matching that in
+	/// `information_class`.
+	///
+	/// # Examples
+	///
+	/// Limiting the job to 20% of the CPU:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hjob: w::HJOB; // initialized somewhere
+	/// # let hjob = w::HJOB::NULL;
+	///
+	/// let mut rate = w::JOBOBJECT_CPU_RATE_CONTROL_INFORMATION::default();
+	/// rate.ControlFlags = co::JOB_OBJECT_CPU_RATE_CONTROL::ENABLE
+	///     | co::JOB_OBJECT_CPU_RATE_CONTROL::HARD_CAP;
+	/// rate.set_CpuRate(20 * 100);
+	///
+	/// unsafe {
+	///     hjob.SetInformationJobObject(
+	///         co::JOBOBJECTINFOCLASS::CpuRateControlInformation,
+	///         &rate,
+	///     )?;
+	/// }
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	unsafe fn SetInformationJobObject<T>(&self,
+		information_class: co::JOBOBJECTINFOCLASS,
+		information: &T,
+	) -> SysResult<()>
+	{
+		bool_to_sysresult(
+			unsafe {
+				ffi::SetInformationJobObject(
+					self.ptr(),
+					information_class.raw(),
+					information as *const _ as _,
+					std::mem::size_of::<T>() as _,
+				)
+			},
+		)
+	}
+
+	/// [`TerminateJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-terminatejobobject)
+	/// function.
+	fn TerminateJobObject(&self, exit_code: u32) -> SysResult<()> {
+		bool_to_sysresult(
+			unsafe { ffi::TerminateJobObject(self.ptr(), exit_code) },
+		)
+	}
+}
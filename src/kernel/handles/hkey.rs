@@ -381,6 +381,7 @@ pub trait kernel_Hkey: Handle {
 	///         println!("");
 	///     },
 	///     w::RegistryValue::None => println!("No value"),
+	///     other => println!("Other: {}", other),
 	/// }
 	/// # Ok::<_, winsafe::co::ERROR>(())
 	/// ```
@@ -456,6 +457,37 @@ pub trait kernel_Hkey: Handle {
 		)
 	}
 
+	/// [`RegNotifyChangeKeyValue`](https://learn.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regnotifychangekeyvalue)
+	/// function.
+	///
+	/// If `asynchronous` is `true`, the function returns immediately, and the
+	/// event is signaled when a change occurs; otherwise the function blocks
+	/// until a change occurs, and `event` is ignored.
+	///
+	/// Note that an asynchronous notification is canceled when the calling
+	/// thread exits, unless
+	/// [`REG_NOTIFY::THREAD_AGNOSTIC`](crate::co::REG_NOTIFY::THREAD_AGNOSTIC)
+	/// is passed.
+	fn RegNotifyChangeKeyValue(&self,
+		watch_subtree: bool,
+		notify_filter: co::REG_NOTIFY,
+		event: Option<&HEVENT>,
+		asynchronous: bool,
+	) -> SysResult<()>
+	{
+		error_to_sysresult(
+			unsafe {
+				ffi::RegNotifyChangeKeyValue(
+					self.ptr(),
+					watch_subtree as _,
+					notify_filter.raw(),
+					event.map_or(std::ptr::null_mut(), |h| h.ptr()),
+					asynchronous as _,
+				)
+			},
+		)
+	}
+
 	/// [`RegOpenCurrentUser`](https://learn.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regopencurrentuser)
 	/// function.
 	#[must_use]
@@ -644,6 +676,7 @@ pub trait kernel_Hkey: Handle {
 	///             println!("");
 	///         },
 	///         w::RegistryValue::None => println!("No value"),
+	///         other => println!("Other: {}", other),
 	///     }
 	/// }
 	///
@@ -778,6 +811,7 @@ pub trait kernel_Hkey: Handle {
 	///         println!("");
 	///     },
 	///     w::RegistryValue::None => println!("No value"),
+	///     other => println!("Other: {}", other),
 	/// }
 	/// # Ok::<_, co::ERROR>(())
 	/// ```
@@ -1053,7 +1087,8 @@ fn validate_retrieved_reg_val(
 		return Err(co::ERROR::TRANSACTION_REQUEST_NOT_VALID);
 	}
 
-	if data_type1 == co::REG::DWORD && data_len1 != 4
+	if (data_type1 == co::REG::DWORD || data_type1 == co::REG::DWORD_BIG_ENDIAN)
+		&& data_len1 != 4
 		|| data_type1 == co::REG::QWORD && data_len1 != 8
 	{
 		// Data length makes no sense, possibly corrupted.
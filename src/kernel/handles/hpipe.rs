@@ -1,5 +1,6 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use crate::co;
 use crate::decl::*;
 use crate::guard::*;
 use crate::kernel::{ffi, privs::*};
@@ -7,12 +8,40 @@ use crate::prelude::*;
 
 impl_handle! { HPIPE;
 	/// Handle to an
-	/// [anonymous pipe](https://learn.microsoft.com/en-us/windows/win32/ipc/anonymous-pipes).
+	/// [anonymous pipe](https://learn.microsoft.com/en-us/windows/win32/ipc/anonymous-pipes)
+	/// or a
+	/// [named pipe](https://learn.microsoft.com/en-us/windows/win32/ipc/named-pipes).
 	/// Originally just a `HANDLE`.
+	///
+	/// Implements [`std::io::Read`] and [`std::io::Write`] for synchronous
+	/// handles. A broken pipe is reported as the end of the stream.
 }
 
 impl kernel_Hpipe for HPIPE {}
 
+impl std::io::Read for HPIPE {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		match self.ReadFile(buf, None) {
+			Ok(bytes_read) => Ok(bytes_read as _),
+			Err(co::ERROR::MORE_DATA) => Ok(buf.len()), // partial message, buffer filled
+			Err(co::ERROR::BROKEN_PIPE) => Ok(0),
+			Err(e) => Err(std::io::Error::from_raw_os_error(e.raw() as _)),
+		}
+	}
+}
+
+impl std::io::Write for HPIPE {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.WriteFile(buf, None)
+			.map(|bytes_written| bytes_written as _)
+			.map_err(|e| std::io::Error::from_raw_os_error(e.raw() as _))
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
 /// This trait is enabled with the `kernel` feature, and provides methods for
 /// [`HPIPE`](crate::HPIPE).
 ///
@@ -22,6 +51,102 @@ impl kernel_Hpipe for HPIPE {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait kernel_Hpipe: Handle {
+	/// [`CancelIoEx`](https://learn.microsoft.com/en-us/windows/win32/fileio/cancelioex-func)
+	/// function.
+	fn CancelIoEx(&self, overlapped: Option<&OVERLAPPED>) -> SysResult<()> {
+		unsafe { HFILE::from_ptr(self.ptr()) }
+			.CancelIoEx(overlapped)
+	}
+
+	/// [`ConnectNamedPipe`](https://learn.microsoft.com/en-us/windows/win32/api/namedpipeapi/nf-namedpipeapi-connectnamedpipe)
+	/// function.
+	///
+	/// If a client connected between the creation of the pipe and this call,
+	/// the [`co::ERROR::PIPE_CONNECTED`](crate::co::ERROR::PIPE_CONNECTED) error
+	/// is reported as success.
+	///
+	/// With overlapped I/O, fails with
+	/// [`co::ERROR::IO_PENDING`](crate::co::ERROR::IO_PENDING) while waiting
+	/// for a client.
+	fn ConnectNamedPipe(&self,
+		overlapped: Option<&mut OVERLAPPED>,
+	) -> SysResult<()>
+	{
+		match bool_to_sysresult(
+			unsafe {
+				ffi::ConnectNamedPipe(
+					self.ptr(),
+					overlapped.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+				)
+			},
+		) {
+			Err(co::ERROR::PIPE_CONNECTED) => Ok(()),
+			res => res,
+		}
+	}
+
+	/// [`CreateNamedPipe`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-createnamedpipew)
+	/// function.
+	///
+	/// If `max_instances` is `None`, the number of instances is unlimited. If
+	/// `default_timeout` is `None`, the default of 50 milliseconds is used.
+	///
+	/// # Examples
+	///
+	/// A message-mode pipe which waits for a single client:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hpipe = w::HPIPE::CreateNamedPipe(
+	///     "\\\\.\\pipe\\my-pipe",
+	///     co::PIPE_ACCESS::DUPLEX | co::PIPE_ACCESS::FIRST_PIPE_INSTANCE,
+	///     co::PIPE::TYPE_MESSAGE | co::PIPE::READMODE_MESSAGE
+	///         | co::PIPE::REJECT_REMOTE_CLIENTS,
+	///     Some(1),
+	///     4096,
+	///     4096,
+	///     None,
+	///     None,
+	/// )?;
+	///
+	/// hpipe.ConnectNamedPipe(None)?;
+	/// println!("Client: {}", hpipe.GetNamedPipeClientProcessId()?);
+	/// hpipe.DisconnectNamedPipe()?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	#[allow(clippy::too_many_arguments)]
+	fn CreateNamedPipe(
+		name: &str,
+		open_mode: co::PIPE_ACCESS,
+		pipe_mode: co::PIPE,
+		max_instances: Option<u32>,
+		out_buffer_size: u32,
+		in_buffer_size: u32,
+		default_timeout: Option<u32>,
+		security_attributes: Option<&mut SECURITY_ATTRIBUTES>,
+	) -> SysResult<CloseHandleGuard<HPIPE>>
+	{
+		unsafe {
+			match HPIPE::from_ptr(
+				ffi::CreateNamedPipeW(
+					WString::from_str(name).as_ptr(),
+					open_mode.raw(),
+					pipe_mode.raw(),
+					max_instances.unwrap_or(PIPE_UNLIMITED_INSTANCES),
+					out_buffer_size,
+					in_buffer_size,
+					default_timeout.unwrap_or_default(),
+					security_attributes.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+				),
+			) {
+				HPIPE::NULL | HPIPE::INVALID => Err(GetLastError()),
+				handle => Ok(CloseHandleGuard::new(handle)),
+			}
+		}
+	}
+
 	/// [`CreatePipe`](https://learn.microsoft.com/en-us/windows/win32/api/namedpipeapi/nf-namedpipeapi-createpipe)
 	/// function.
 	///
@@ -45,10 +170,92 @@ pub trait kernel_Hpipe: Handle {
 		}
 	}
 
+	/// [`DisconnectNamedPipe`](https://learn.microsoft.com/en-us/windows/win32/api/namedpipeapi/nf-namedpipeapi-disconnectnamedpipe)
+	/// function.
+	///
+	/// Any unread data is discarded; call
+	/// [`HPIPE::FlushFileBuffers`](crate::prelude::kernel_Hpipe::FlushFileBuffers)
+	/// before to make sure the client has read everything.
+	fn DisconnectNamedPipe(&self) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::DisconnectNamedPipe(self.ptr()) })
+	}
+
+	/// [`FlushFileBuffers`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-flushfilebuffers)
+	/// function.
+	///
+	/// Blocks until the other end has read all the data written to the pipe.
+	fn FlushFileBuffers(&self) -> SysResult<()> {
+		unsafe { HFILE::from_ptr(self.ptr()) }
+			.FlushFileBuffers()
+	}
+
+	/// [`GetNamedPipeClientProcessId`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getnamedpipeclientprocessid)
+	/// function.
+	#[must_use]
+	fn GetNamedPipeClientProcessId(&self) -> SysResult<u32> {
+		let mut pid = u32::default();
+		bool_to_sysresult(
+			unsafe { ffi::GetNamedPipeClientProcessId(self.ptr(), &mut pid) },
+		).map(|_| pid)
+	}
+
+	/// [`GetNamedPipeServerProcessId`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getnamedpipeserverprocessid)
+	/// function.
+	#[must_use]
+	fn GetNamedPipeServerProcessId(&self) -> SysResult<u32> {
+		let mut pid = u32::default();
+		bool_to_sysresult(
+			unsafe { ffi::GetNamedPipeServerProcessId(self.ptr(), &mut pid) },
+		).map(|_| pid)
+	}
+
+	/// [`GetOverlappedResult`](https://learn.microsoft.com/en-us/windows/win32/api/ioapiset/nf-ioapiset-getoverlappedresult)
+	/// function.
+	///
+	/// Returns the number of bytes transferred by the operation.
+	fn GetOverlappedResult(&self,
+		overlapped: &OVERLAPPED,
+		wait: bool,
+	) -> SysResult<u32>
+	{
+		unsafe { HFILE::from_ptr(self.ptr()) }
+			.GetOverlappedResult(overlapped, wait)
+	}
+
+	/// [`PeekNamedPipe`](https://learn.microsoft.com/en-us/windows/win32/api/namedpipeapi/nf-namedpipeapi-peeknamedpipe)
+	/// function.
+	///
+	/// Copies the data available without removing it from the pipe; `buffer`
+	/// can be empty. Returns the number of bytes copied, the total number of
+	/// bytes available and, in message mode, the bytes left in the current
+	/// message.
+	///
+	/// Unlike [`HPIPE::ReadFile`](crate::prelude::kernel_Hpipe::ReadFile),
+	/// this function never blocks.
+	fn PeekNamedPipe(&self, buffer: &mut [u8]) -> SysResult<(u32, u32, u32)> {
+		let (mut bytes_read, mut total_avail, mut left_this_msg) = (0, 0, 0);
+		bool_to_sysresult(
+			unsafe {
+				ffi::PeekNamedPipe(
+					self.ptr(),
+					buffer.as_mut_ptr() as _,
+					buffer.len() as _,
+					&mut bytes_read,
+					&mut total_avail,
+					&mut left_this_msg,
+				)
+			},
+		).map(|_| (bytes_read, total_avail, left_this_msg))
+	}
+
 	/// [`ReadFile`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile)
 	/// function.
 	///
 	/// Returns the number of bytes read.
+	///
+	/// In message mode, if the message is larger than `buffer`, fails with
+	/// [`co::ERROR::MORE_DATA`](crate::co::ERROR::MORE_DATA) after filling the
+	/// whole buffer; the rest of the message is returned by the next reads.
 	fn ReadFile(&self,
 		buffer: &mut [u8],
 		overlapped: Option<&mut OVERLAPPED>,
@@ -58,6 +265,80 @@ pub trait kernel_Hpipe: Handle {
 			.ReadFile(buffer, overlapped)
 	}
 
+	/// [`SetNamedPipeHandleState`](https://learn.microsoft.com/en-us/windows/win32/api/namedpipeapi/nf-namedpipeapi-setnamedpipehandlestate)
+	/// function.
+	///
+	/// Values passed as `None` are left unchanged. A client must call this
+	/// function with
+	/// [`co::PIPE::READMODE_MESSAGE`](crate::co::PIPE::READMODE_MESSAGE) to
+	/// read whole messages.
+	fn SetNamedPipeHandleState(&self,
+		mode: Option<co::PIPE>,
+		max_collection_count: Option<u32>,
+		collect_data_timeout: Option<u32>,
+	) -> SysResult<()>
+	{
+		let mode = mode.map(|m| m.raw());
+		bool_to_sysresult(
+			unsafe {
+				ffi::SetNamedPipeHandleState(
+					self.ptr(),
+					mode.as_ref().map_or(std::ptr::null(), |m| m),
+					max_collection_count.as_ref().map_or(std::ptr::null(), |c| c),
+					collect_data_timeout.as_ref().map_or(std::ptr::null(), |t| t),
+				)
+			},
+		)
+	}
+
+	/// [`TransactNamedPipe`](https://learn.microsoft.com/en-us/windows/win32/api/namedpipeapi/nf-namedpipeapi-transactnamedpipe)
+	/// function.
+	///
+	/// Writes a message and reads the reply, returning the number of bytes
+	/// read. The pipe must be in message read mode. If the reply is larger than
+	/// `read_buffer`, fails with
+	/// [`co::ERROR::MORE_DATA`](crate::co::ERROR::MORE_DATA), and the rest of
+	/// the reply must be read with
+	/// [`HPIPE::ReadFile`](crate::prelude::kernel_Hpipe::ReadFile).
+	fn TransactNamedPipe(&self,
+		write_buffer: &[u8],
+		read_buffer: &mut [u8],
+		overlapped: Option<&mut OVERLAPPED>,
+	) -> SysResult<u32>
+	{
+		let mut bytes_read = u32::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::TransactNamedPipe(
+					self.ptr(),
+					vec_ptr(write_buffer) as _,
+					write_buffer.len() as _,
+					read_buffer.as_mut_ptr() as _,
+					read_buffer.len() as _,
+					&mut bytes_read,
+					overlapped.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+				)
+			},
+		).map(|_| bytes_read)
+	}
+
+	/// [`WaitNamedPipe`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-waitnamedpipew)
+	/// function.
+	///
+	/// Waits until an instance of the pipe is available for connection. If
+	/// `milliseconds` is `None`, waits forever; zero uses the default timeout
+	/// of the pipe.
+	fn WaitNamedPipe(name: &str, milliseconds: Option<u32>) -> SysResult<()> {
+		bool_to_sysresult(
+			unsafe {
+				ffi::WaitNamedPipeW(
+					WString::from_str(name).as_ptr(),
+					milliseconds.unwrap_or(NMPWAIT_WAIT_FOREVER),
+				)
+			},
+		)
+	}
+
 	/// [`WriteFile`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-writefile)
 	/// function.
 	fn WriteFile(&self,
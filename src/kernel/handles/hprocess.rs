@@ -75,6 +75,57 @@ pub trait kernel_Hprocess: Handle {
 		}
 	}
 
+	/// [`DuplicateHandle`](https://learn.microsoft.com/en-us/windows/win32/api/handleapi/nf-handleapi-duplicatehandle)
+	/// function, called on the source process.
+	///
+	/// The new handle belongs to `target_process`, so it's returned without a
+	/// guard. If the target is the current process, the handle must be closed
+	/// with [`CloseHandleGuard`](crate::guard::CloseHandleGuard).
+	///
+	/// # Examples
+	///
+	/// Creating an inheritable copy of a handle:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, guard};
+	///
+	/// let hfile: w::HFILE; // initialized somewhere
+	/// # let hfile = w::HFILE::NULL;
+	///
+	/// let hproc = w::HPROCESS::GetCurrentProcess();
+	/// let hcopy = unsafe {
+	///     guard::CloseHandleGuard::new(
+	///         hproc.DuplicateHandle(&hfile, &hproc, 0, true, co::DUPLICATE::SAME_ACCESS)?,
+	///     )
+	/// };
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn DuplicateHandle<H>(&self,
+		source_handle: &H,
+		target_process: &HPROCESS,
+		desired_access: u32,
+		inherit_handle: bool,
+		options: co::DUPLICATE,
+	) -> SysResult<H>
+		where H: Handle,
+	{
+		let mut handle = H::NULL;
+		unsafe {
+			bool_to_sysresult(
+				ffi::DuplicateHandle(
+					self.ptr(),
+					source_handle.ptr(),
+					target_process.ptr(),
+					handle.as_mut(),
+					desired_access,
+					inherit_handle as _,
+					options.raw(),
+				),
+			).map(|_| handle)
+		}
+	}
+
 	/// [`FlushInstructionCache`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-flushinstructioncache)
 	/// function.
 	fn FlushInstructionCache(&self,
@@ -176,6 +227,25 @@ pub trait kernel_Hprocess: Handle {
 		).map(|_| critical != 0)
 	}
 
+	/// [`IsProcessInJob`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi/nf-jobapi-isprocessinjob)
+	/// function.
+	///
+	/// If `job` is `None`, checks whether the process is running under any
+	/// job.
+	#[must_use]
+	fn IsProcessInJob(&self, job: Option<&HJOB>) -> SysResult<bool> {
+		let mut in_job: BOOL = 0;
+		bool_to_sysresult(
+			unsafe {
+				ffi::IsProcessInJob(
+					self.ptr(),
+					job.map_or(std::ptr::null_mut(), |h| h.ptr()),
+					&mut in_job,
+				)
+			},
+		).map(|_| in_job != 0)
+	}
+
 	/// [`IsWow64Process`](https://learn.microsoft.com/en-us/windows/win32/api/wow64apiset/nf-wow64apiset-iswow64process)
 	/// function.
 	#[must_use]
@@ -44,6 +44,8 @@ pub trait kernel_Hsc: Handle {
 			if s.starts_with('"') { s.to_owned() }
 			else { format!("\"{}\"", s) }
 		});
+		let wload_order_group = load_order_group.map(|v| WString::from_str_vec(v));
+		let wdependencies = dependencies.map(|v| WString::from_str_vec(v));
 
 		unsafe {
 			ptr_to_sysresult_handle(
@@ -56,9 +58,9 @@ pub trait kernel_Hsc: Handle {
 					start_type.raw(),
 					error_control.raw(),
 					WString::from_opt_str(binary_path_name_quoted).as_ptr(),
-					load_order_group.map_or(std::ptr::null_mut(), |v| WString::from_str_vec(v).as_ptr()),
+					wload_order_group.as_ref().map_or(std::ptr::null(), |ws| ws.as_ptr()),
 					tag_id.map_or(std::ptr::null_mut(), |n| n),
-					dependencies.map_or(std::ptr::null_mut(), |v| WString::from_str_vec(v).as_ptr()),
+					wdependencies.as_ref().map_or(std::ptr::null(), |ws| ws.as_ptr()),
 					WString::from_opt_str(service_start_name).as_ptr(),
 					WString::from_opt_str(password).as_ptr(),
 				)
@@ -66,6 +68,86 @@ pub trait kernel_Hsc: Handle {
 		}
 	}
 
+	/// [`EnumServicesStatusEx`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-enumservicesstatusexw)
+	/// function.
+	///
+	/// Returns the name, the display name and the status of each service.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hsc = w::HSC::OpenSCManager(None, co::SC_MANAGER::ENUMERATE_SERVICE)?;
+	///
+	/// for (name, display_name, status) in hsc.EnumServicesStatusEx(
+	///     co::SERVICE_TYPE::WIN32,
+	///     co::SERVICE_STATE_FILTER::ACTIVE,
+	///     None,
+	/// )? {
+	///     println!("{} ({}) PID {}", name, display_name, status.dwProcessId);
+	/// }
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn EnumServicesStatusEx(&self,
+		service_type: co::SERVICE_TYPE,
+		service_state: co::SERVICE_STATE_FILTER,
+		group_name: Option<&str>,
+	) -> SysResult<Vec<(String, String, SERVICE_STATUS_PROCESS)>>
+	{
+		let wgroup_name = WString::from_opt_str(group_name);
+		let mut buf = Vec::<u64>::new(); // aligned for the pointers
+		let mut resume_handle = u32::default();
+		let mut services = Vec::new();
+
+		loop {
+			let mut bytes_needed = u32::default();
+			let mut services_returned = u32::default();
+			let more_data = match bool_to_sysresult(
+				unsafe {
+					ffi::EnumServicesStatusExW(
+						self.ptr(),
+						SC_ENUM_PROCESS_INFO,
+						service_type.raw(),
+						service_state.raw(),
+						buf.as_mut_ptr() as _,
+						(buf.len() * 8) as _,
+						&mut bytes_needed,
+						&mut services_returned,
+						&mut resume_handle,
+						wgroup_name.as_ptr(),
+					)
+				},
+			) {
+				Ok(_) => false,
+				Err(co::ERROR::MORE_DATA) => true,
+				Err(e) => return Err(e),
+			};
+
+			let entries = unsafe {
+				std::slice::from_raw_parts(
+					buf.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESS,
+					services_returned as _,
+				)
+			};
+			services.extend(entries.iter().map(|entry| unsafe {
+				(
+					WString::from_wchars_nullt(entry.lpServiceName).to_string(),
+					WString::from_wchars_nullt(entry.lpDisplayName).to_string(),
+					entry.ServiceStatusProcess,
+				)
+			}));
+
+			if !more_data {
+				return Ok(services);
+			}
+			if services_returned == 0 { // buffer too small for a single entry
+				buf = vec![0; (bytes_needed as usize).div_ceil(8)];
+			}
+		}
+	}
+
 	/// [`OpenSCManager`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-openscmanagerw)
 	/// function.
 	#[must_use]
@@ -104,3 +186,15 @@ pub trait kernel_Hsc: Handle {
 		}
 	}
 }
+
+//------------------------------------------------------------------------------
+
+/// [`ENUM_SERVICE_STATUS_PROCESS`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-enum_service_status_processw)
+/// struct, filled by
+/// [`HSC::EnumServicesStatusEx`](crate::prelude::kernel_Hsc::EnumServicesStatusEx).
+#[repr(C)]
+struct ENUM_SERVICE_STATUS_PROCESS {
+	lpServiceName: *mut u16,
+	lpDisplayName: *mut u16,
+	ServiceStatusProcess: SERVICE_STATUS_PROCESS,
+}
@@ -1,5 +1,6 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use crate::co;
 use crate::decl::*;
 use crate::kernel::{ffi, privs::*};
 use crate::prelude::*;
@@ -21,9 +22,114 @@ impl kernel_Hservice for HSERVICE {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait kernel_Hservice: Handle {
+	/// [`ChangeServiceConfig2`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-changeserviceconfig2w)
+	/// function.
+	///
+	/// # Safety
+	///
+	/// The `info` type must match the `info_level`:
+	///
+	/// * [`SERVICE_CONFIG::DELAYED_AUTO_START_INFO`](crate::co::SERVICE_CONFIG::DELAYED_AUTO_START_INFO) – [`SERVICE_DELAYED_AUTO_START_INFO`](crate::SERVICE_DELAYED_AUTO_START_INFO);
+	/// * [`SERVICE_CONFIG::DESCRIPTION`](crate::co::SERVICE_CONFIG::DESCRIPTION) – [`SERVICE_DESCRIPTION`](crate::SERVICE_DESCRIPTION);
+	/// * [`SERVICE_CONFIG::PRESHUTDOWN_INFO`](crate::co::SERVICE_CONFIG::PRESHUTDOWN_INFO) – [`SERVICE_PRESHUTDOWN_INFO`](crate::SERVICE_PRESHUTDOWN_INFO).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hservice: w::HSERVICE; // initialized somewhere
+	/// # let hservice = w::HSERVICE::NULL;
+	///
+	/// let mut text = w::WString::from_str("Does important things.");
+	/// let mut desc = w::SERVICE_DESCRIPTION::default();
+	/// desc.set_lpDescription(Some(&mut text));
+	///
+	/// unsafe {
+	///     hservice.ChangeServiceConfig2(co::SERVICE_CONFIG::DESCRIPTION, &desc)?;
+	/// }
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	unsafe fn ChangeServiceConfig2<T>(&self,
+		info_level: co::SERVICE_CONFIG,
+		info: &T,
+	) -> SysResult<()>
+	{
+		bool_to_sysresult(
+			ffi::ChangeServiceConfig2W(
+				self.ptr(),
+				info_level.raw(),
+				info as *const _ as _,
+			),
+		)
+	}
+
+	/// [`ControlService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-controlservice)
+	/// function.
+	///
+	/// Returns the latest status reported by the service.
+	fn ControlService(&self,
+		control: co::SERVICE_CONTROL,
+	) -> SysResult<SERVICE_STATUS>
+	{
+		let mut status = SERVICE_STATUS::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::ControlService(
+					self.ptr(),
+					control.raw(),
+					&mut status as *mut _ as _,
+				)
+			},
+		).map(|_| status)
+	}
+
 	/// [`DeleteService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-deleteservice)
 	/// function.
 	fn DeleteService(&self) -> SysResult<()> {
 		bool_to_sysresult(unsafe { ffi::DeleteService(self.ptr()) })
 	}
+
+	/// [`QueryServiceStatusEx`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-queryservicestatusex)
+	/// function.
+	#[must_use]
+	fn QueryServiceStatusEx(&self) -> SysResult<SERVICE_STATUS_PROCESS> {
+		let mut status = SERVICE_STATUS_PROCESS::default();
+		let mut bytes_needed = u32::default();
+		bool_to_sysresult(
+			unsafe {
+				ffi::QueryServiceStatusEx(
+					self.ptr(),
+					SC_STATUS_PROCESS_INFO,
+					&mut status as *mut _ as _,
+					std::mem::size_of::<SERVICE_STATUS_PROCESS>() as _,
+					&mut bytes_needed,
+				)
+			},
+		).map(|_| status)
+	}
+
+	/// [`StartService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-startservicew)
+	/// function.
+	///
+	/// The arguments are passed to the service entry point, after the service
+	/// name.
+	fn StartService(&self, args: &[impl AsRef<str>]) -> SysResult<()> {
+		let wargs = args.iter()
+			.map(|arg| WString::from_str(arg.as_ref()))
+			.collect::<Vec<_>>();
+		let pargs = wargs.iter()
+			.map(|warg| warg.as_ptr())
+			.collect::<Vec<_>>();
+
+		bool_to_sysresult(
+			unsafe {
+				ffi::StartServiceW(
+					self.ptr(),
+					pargs.len() as _,
+					if pargs.is_empty() { std::ptr::null() } else { pargs.as_ptr() },
+				)
+			},
+		)
+	}
 }
@@ -23,20 +23,37 @@ impl kernel_Hservicestatus for HSERVICESTATUS {}
 pub trait kernel_Hservicestatus: Handle {
 	/// [`RegisterServiceCtrlHandlerEx`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-registerservicectrlhandlerexw)
 	/// function.
+	///
+	/// The handler is called by the control dispatcher thread until the
+	/// process exits, so the closure is never freed.
+	///
+	/// Unless you need something specific, consider using the
+	/// [`ServiceDispatcher`](crate::ServiceDispatcher) high-level abstraction.
 	fn RegisterServiceCtrlHandlerEx<F>(
 		service_name: &str,
 		handler_proc: F,
 	) -> SysResult<HSERVICESTATUS>
-		where F: FnMut(ServiceControl) -> u32,
+		where F: FnMut(ServiceControl) -> u32 + Send + 'static,
 	{
+		let handler_ptr = Box::into_raw(Box::new(handler_proc));
 		ptr_to_sysresult_handle(
 			unsafe {
 				ffi::RegisterServiceCtrlHandlerExW(
 					WString::from_str(service_name).as_ptr(),
 					register_service_ctrl_handler_ex_proc::<F> as _,
-					&handler_proc as *const _ as _,
+					handler_ptr as _,
 				)
 			},
+		).inspect_err(|_| {
+			let _ = unsafe { Box::from_raw(handler_ptr) }; // handler will never be called
+		})
+	}
+
+	/// [`SetServiceStatus`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-setservicestatus)
+	/// function.
+	fn SetServiceStatus(&self, status: &SERVICE_STATUS) -> SysResult<()> {
+		bool_to_sysresult(
+			unsafe { ffi::SetServiceStatus(self.ptr(), status as *const _ as _) },
 		)
 	}
 }
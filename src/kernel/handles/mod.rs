@@ -5,10 +5,13 @@ mod heventlog;
 mod hfile;
 mod hfilemap;
 mod hfilemapview;
+mod hfindchange;
 mod hfindfile;
 mod hglobal;
 mod hheap;
 mod hinstance;
+mod hiocp;
+mod hjob;
 mod hkey;
 mod hlocal;
 mod hpipe;
@@ -29,10 +32,13 @@ pub mod decl {
 	pub use super::hfile::HFILE;
 	pub use super::hfilemap::HFILEMAP;
 	pub use super::hfilemapview::HFILEMAPVIEW;
+	pub use super::hfindchange::HFINDCHANGE;
 	pub use super::hfindfile::HFINDFILE;
 	pub use super::hglobal::HGLOBAL;
 	pub use super::hheap::HHEAP;
 	pub use super::hinstance::HINSTANCE;
+	pub use super::hiocp::HIOCP;
+	pub use super::hjob::HJOB;
 	pub use super::hkey::HKEY;
 	pub use super::hlocal::HLOCAL;
 	pub use super::hpipe::HPIPE;
@@ -73,10 +79,13 @@ pub mod traits {
 	pub use super::hfile::kernel_Hfile;
 	pub use super::hfilemap::kernel_Hfilemap;
 	pub use super::hfilemapview::kernel_Hfilemapview;
+	pub use super::hfindchange::kernel_Hfindchange;
 	pub use super::hfindfile::kernel_Hfindfile;
 	pub use super::hglobal::kernel_Hglobal;
 	pub use super::hheap::kernel_Hheap;
 	pub use super::hinstance::kernel_Hinstance;
+	pub use super::hiocp::kernel_Hiocp;
+	pub use super::hjob::kernel_Hjob;
 	pub use super::hkey::kernel_Hkey;
 	pub use super::hlocal::kernel_Hlocal;
 	pub use super::hpipe::kernel_Hpipe;
@@ -27,4 +27,5 @@ pub mod decl {
 pub mod traits {
 	pub use super::base_traits::*;
 	pub use super::handles::traits::*;
+	pub use super::utilities::reg_mapped::{RegConvert, RegMapped, RegStore};
 }
@@ -12,6 +12,12 @@ pub(crate) const LMEM_INVALID_HANDLE: u32 = 0x8000;
 pub(crate) const MAX_COMPUTERNAME_LENGTH: usize = 15;
 pub(crate) const MAX_MODULE_NAME32: usize = 255;
 pub(crate) const MAX_PATH: usize = 260;
+pub(crate) const NMPWAIT_WAIT_FOREVER: u32 = 0xffff_ffff;
+pub(crate) const PIPE_UNLIMITED_INSTANCES: u32 = 255;
+pub(crate) const PROC_THREAD_ATTRIBUTE_HANDLE_LIST: usize = 0x0002_0002;
+pub(crate) const SC_ENUM_PROCESS_INFO: u32 = 0;
+pub(crate) const SC_STATUS_PROCESS_INFO: u32 = 0;
+pub(crate) const SDDL_REVISION_1: u32 = 1;
 pub(crate) const SECURITY_DESCRIPTOR_REVISION: u32 = 1;
 pub(crate) const SECURITY_SQOS_PRESENT: u32 = 0x0010_0000;
 
@@ -108,6 +114,18 @@ pub(crate) fn parse_multi_z_str(src: *const u16) -> Vec<String> {
 	strings
 }
 
+/// Splits a `name=value` environment string at the first `=` after the first
+/// char, since the names of drive variables, like `=C:`, start with `=`.
+pub(crate) fn split_env_str(env_str: &str) -> (String, String) {
+	let idx = env_str.char_indices()
+		.skip(1) // names of drive variables start with "="
+		.find(|(_, ch)| *ch == '=')
+		.map_or(env_str.len(), |(idx, _)| idx);
+	let key = &env_str[..idx];
+	let val = env_str.get(idx + 1..).unwrap_or_default();
+	(key.to_owned(), val.to_owned())
+}
+
 /// If the vector is empty, returns null, otherwise calls `as_ptr`.
 ///
 /// This is necessary because an empty vector returns garbage as its underlying
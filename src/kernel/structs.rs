@@ -270,6 +270,138 @@ pub struct HEAPLIST32 {
 
 impl_default_with_size!(HEAPLIST32, dwSize);
 
+/// [`IO_COUNTERS`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-io_counters)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct IO_COUNTERS {
+	pub ReadOperationCount: u64,
+	pub WriteOperationCount: u64,
+	pub OtherOperationCount: u64,
+	pub ReadTransferCount: u64,
+	pub WriteTransferCount: u64,
+	pub OtherTransferCount: u64,
+}
+
+/// [`JOBOBJECT_ASSOCIATE_COMPLETION_PORT`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_associate_completion_port)
+/// struct.
+#[repr(C)]
+pub struct JOBOBJECT_ASSOCIATE_COMPLETION_PORT {
+	pub CompletionKey: usize,
+	pub CompletionPort: HIOCP,
+}
+
+impl_default!(JOBOBJECT_ASSOCIATE_COMPLETION_PORT);
+
+/// [`JOBOBJECT_BASIC_ACCOUNTING_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_basic_accounting_information)
+/// struct.
+///
+/// Times are in 100-nanosecond ticks.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct JOBOBJECT_BASIC_ACCOUNTING_INFORMATION {
+	pub TotalUserTime: i64,
+	pub TotalKernelTime: i64,
+	pub ThisPeriodTotalUserTime: i64,
+	pub ThisPeriodTotalKernelTime: i64,
+	pub TotalPageFaultCount: u32,
+	pub TotalProcesses: u32,
+	pub ActiveProcesses: u32,
+	pub TotalTerminatedProcesses: u32,
+}
+
+/// [`JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_basic_and_io_accounting_information)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION {
+	pub BasicInfo: JOBOBJECT_BASIC_ACCOUNTING_INFORMATION,
+	pub IoInfo: IO_COUNTERS,
+}
+
+/// [`JOBOBJECT_BASIC_LIMIT_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_basic_limit_information)
+/// struct.
+///
+/// Times are in 100-nanosecond ticks.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct JOBOBJECT_BASIC_LIMIT_INFORMATION {
+	pub PerProcessUserTimeLimit: i64,
+	pub PerJobUserTimeLimit: i64,
+	pub LimitFlags: co::JOB_OBJECT_LIMIT,
+	pub MinimumWorkingSetSize: usize,
+	pub MaximumWorkingSetSize: usize,
+	pub ActiveProcessLimit: u32,
+	pub Affinity: usize,
+	pub PriorityClass: co::PRIORITY_CLASS,
+	pub SchedulingClass: u32,
+}
+
+/// [`JOBOBJECT_CPU_RATE_CONTROL_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_cpu_rate_control_information)
+/// struct.
+///
+/// The meaning of the rate depends on `ControlFlags`, so it's accessed through
+/// methods.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct JOBOBJECT_CPU_RATE_CONTROL_INFORMATION {
+	pub ControlFlags: co::JOB_OBJECT_CPU_RATE_CONTROL,
+	rate: u32, // union
+}
+
+impl JOBOBJECT_CPU_RATE_CONTROL_INFORMATION {
+	/// Returns the `CpuRate` field: the portion of processor cycles, in
+	/// hundredths of a percent, from 1 to 10000.
+	#[must_use]
+	pub const fn CpuRate(&self) -> u32 {
+		self.rate
+	}
+
+	/// Sets the `CpuRate` field.
+	pub fn set_CpuRate(&mut self, val: u32) {
+		self.rate = val;
+	}
+
+	/// Returns the `Weight` field, used with
+	/// [`JOB_OBJECT_CPU_RATE_CONTROL::WEIGHT_BASED`](crate::co::JOB_OBJECT_CPU_RATE_CONTROL::WEIGHT_BASED),
+	/// from 1 to 9.
+	#[must_use]
+	pub const fn Weight(&self) -> u32 {
+		self.rate
+	}
+
+	/// Sets the `Weight` field.
+	pub fn set_Weight(&mut self, val: u32) {
+		self.rate = val;
+	}
+
+	/// Returns the `MinRate` and `MaxRate` fields, used with
+	/// [`JOB_OBJECT_CPU_RATE_CONTROL::MIN_MAX_RATE`](crate::co::JOB_OBJECT_CPU_RATE_CONTROL::MIN_MAX_RATE),
+	/// in hundredths of a percent.
+	#[must_use]
+	pub const fn MinMaxRate(&self) -> (u16, u16) {
+		(LOWORD(self.rate), HIWORD(self.rate))
+	}
+
+	/// Sets the `MinRate` and `MaxRate` fields.
+	pub fn set_MinMaxRate(&mut self, min_rate: u16, max_rate: u16) {
+		self.rate = MAKEDWORD(min_rate, max_rate);
+	}
+}
+
+/// [`JOBOBJECT_EXTENDED_LIMIT_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_extended_limit_information)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct JOBOBJECT_EXTENDED_LIMIT_INFORMATION {
+	pub BasicLimitInformation: JOBOBJECT_BASIC_LIMIT_INFORMATION,
+	pub IoInfo: IO_COUNTERS,
+	pub ProcessMemoryLimit: usize,
+	pub JobMemoryLimit: usize,
+	pub PeakProcessMemoryUsed: usize,
+	pub PeakJobMemoryUsed: usize,
+}
+
 /// [`LANGID`](https://learn.microsoft.com/en-us/windows/win32/intl/language-identifiers)
 /// language identifier.
 #[repr(transparent)]
@@ -486,12 +618,55 @@ impl OSVERSIONINFOEX {
 pub struct OVERLAPPED {
 	pub Internal: usize,
 	pub InternalHigh: usize,
-	pub Pointer: usize,
+	pub Offset: u32,
+	pub OffsetHigh: u32,
 	pub hEvent: HEVENT,
 }
 
 impl_default!(OVERLAPPED);
 
+impl OVERLAPPED {
+	/// Returns the `Offset` and `OffsetHigh` fields as a single 64-bit file
+	/// offset.
+	#[must_use]
+	pub const fn offset64(&self) -> u64 {
+		MAKEQWORD(self.Offset, self.OffsetHigh)
+	}
+
+	/// Sets the `Offset` and `OffsetHigh` fields from a single 64-bit file
+	/// offset.
+	pub fn set_offset64(&mut self, offset: u64) {
+		self.Offset = LODWORD(offset);
+		self.OffsetHigh = HIDWORD(offset);
+	}
+
+	/// Returns the `Pointer` field, which shares its memory with `Offset` and
+	/// `OffsetHigh`.
+	#[must_use]
+	pub const fn Pointer(&self) -> usize {
+		self.offset64() as _
+	}
+
+	/// Sets the `Pointer` field, which shares its memory with `Offset` and
+	/// `OffsetHigh`.
+	pub fn set_Pointer(&mut self, val: usize) {
+		self.set_offset64(val as _);
+	}
+}
+
+/// [`OVERLAPPED_ENTRY`](https://learn.microsoft.com/en-us/windows/win32/api/minwinbase/ns-minwinbase-overlapped_entry)
+/// struct.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct OVERLAPPED_ENTRY {
+	pub lpCompletionKey: usize,
+	pub lpOverlapped: *mut OVERLAPPED,
+	pub Internal: usize,
+	pub dwNumberOfBytesTransferred: u32,
+}
+
+impl_default!(OVERLAPPED_ENTRY);
+
 /// [`POWERBROADCAST_SETTING`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-powerbroadcast_setting)
 /// struct.
 #[repr(C)]
@@ -644,6 +819,71 @@ impl Default for SECURITY_DESCRIPTOR {
 	}
 }
 
+/// [`SERVICE_DELAYED_AUTO_START_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_delayed_auto_start_info)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct SERVICE_DELAYED_AUTO_START_INFO {
+	fDelayedAutostart: i32,
+}
+
+impl SERVICE_DELAYED_AUTO_START_INFO {
+	pub_fn_bool_get_set!(fDelayedAutostart, set_fDelayedAutostart);
+}
+
+/// [`SERVICE_DESCRIPTION`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_descriptionw)
+/// struct.
+#[repr(C)]
+pub struct SERVICE_DESCRIPTION<'a> {
+	lpDescription: *mut u16,
+
+	_lpDescription: PhantomData<&'a mut u16>,
+}
+
+impl_default!(SERVICE_DESCRIPTION, 'a);
+
+impl<'a> SERVICE_DESCRIPTION<'a> {
+	pub_fn_string_ptr_get_set!('a, lpDescription, set_lpDescription);
+}
+
+/// [`SERVICE_PRESHUTDOWN_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_preshutdown_info)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct SERVICE_PRESHUTDOWN_INFO {
+	pub dwPreshutdownTimeout: u32,
+}
+
+/// [`SERVICE_STATUS`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_status)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct SERVICE_STATUS {
+	pub dwServiceType: co::SERVICE_TYPE,
+	pub dwCurrentState: co::SERVICE_STATE,
+	pub dwControlsAccepted: co::SERVICE_ACCEPT,
+	pub dwWin32ExitCode: co::ERROR,
+	pub dwServiceSpecificExitCode: u32,
+	pub dwCheckPoint: u32,
+	pub dwWaitHint: u32,
+}
+
+/// [`SERVICE_STATUS_PROCESS`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_status_process)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct SERVICE_STATUS_PROCESS {
+	pub dwServiceType: co::SERVICE_TYPE,
+	pub dwCurrentState: co::SERVICE_STATE,
+	pub dwControlsAccepted: co::SERVICE_ACCEPT,
+	pub dwWin32ExitCode: co::ERROR,
+	pub dwServiceSpecificExitCode: u32,
+	pub dwCheckPoint: u32,
+	pub dwWaitHint: u32,
+	pub dwProcessId: u32,
+	pub dwServiceFlags: u32,
+}
+
 /// [`SERVICE_TIMECHANGE_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_timechange_info)
 /// struct.
 #[repr(C)]
@@ -829,6 +1069,28 @@ impl<'a, 'b> STARTUPINFO<'a, 'b> {
 	}
 }
 
+/// [`STARTUPINFOEX`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/ns-winbase-startupinfoexw)
+/// struct.
+///
+/// Must be passed along with
+/// [`CREATE::EXTENDED_STARTUPINFO_PRESENT`](crate::co::CREATE::EXTENDED_STARTUPINFO_PRESENT).
+#[repr(C)]
+pub struct STARTUPINFOEX<'a, 'b> {
+	pub StartupInfo: STARTUPINFO<'a, 'b>,
+	pub lpAttributeList: *mut std::ffi::c_void,
+}
+
+impl<'a, 'b> Default for STARTUPINFOEX<'a, 'b> {
+	fn default() -> Self {
+		let mut obj = Self {
+			StartupInfo: STARTUPINFO::default(),
+			lpAttributeList: std::ptr::null_mut(),
+		};
+		obj.StartupInfo.cb = std::mem::size_of::<Self>() as _;
+		obj
+	}
+}
+
 /// [`SYSTEM_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/ns-sysinfoapi-system_info)
 /// struct.
 #[repr(C)]
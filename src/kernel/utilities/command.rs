@@ -0,0 +1,832 @@
+use std::collections::BTreeMap;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::kernel::{ffi, privs::*};
+use crate::prelude::*;
+
+/// Standard I/O stream of a process spawned by [`Command`](crate::Command).
+pub enum Stdio {
+	/// The stream of the current process is inherited.
+	Inherit,
+	/// The stream is redirected to the `NUL` device.
+	Null,
+	/// A pipe is created, and its other end is available in the
+	/// [`ChildProcess`](crate::ChildProcess).
+	Pipe,
+	/// The stream is redirected to the given file, which is made inheritable
+	/// only while the child is spawned.
+	File(CloseHandleGuard<HFILE>),
+}
+
+/// A builder to spawn a process, similar to [`std::process::Command`], on top
+/// of [`HPROCESS::CreateProcess`](crate::prelude::kernel_Hprocess::CreateProcess).
+///
+/// The arguments are quoted according to the rules of the Microsoft C runtime,
+/// and the program is searched by `CreateProcess` itself.
+///
+/// When the standard streams are redirected, only the handles needed by the
+/// child – the standard streams and those passed to
+/// [`Command::inherit_handle`](crate::Command::inherit_handle) – are inherited,
+/// through `PROC_THREAD_ATTRIBUTE_HANDLE_LIST`. This way, concurrent spawns
+/// don't leak pipe handles to each other.
+///
+/// # Examples
+///
+/// Capturing the output:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let out = w::Command::new("cmd.exe")
+///     .args(["/c", "dir", "C:\\Program Files"])
+///     .current_dir("C:\\")
+///     .env("LANG", "en")
+///     .output()?;
+///
+/// println!("Exit code: {}", out.exit_code);
+/// println!("{}", String::from_utf8_lossy(&out.stdout));
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+///
+/// Writing to the standard input:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut child = w::Command::new("sort.exe")
+///     .stdin(w::Stdio::Pipe)
+///     .stdout(w::Stdio::Pipe)
+///     .spawn()?;
+///
+/// child.stdin.as_ref().unwrap().WriteFile(b"b\r\na\r\n", None)?;
+/// let out = child.wait_with_output()?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct Command {
+	program: String,
+	args: Vec<String>, // already quoted
+	env_clear: bool,
+	env: Vec<(String, Option<String>)>,
+	current_dir: Option<String>,
+	creation_flags: co::CREATE,
+	stdin: Option<Stdio>,
+	stdout: Option<Stdio>,
+	stderr: Option<Stdio>,
+	inherit_handles: Vec<*mut std::ffi::c_void>,
+}
+
+impl Command {
+	/// Creates a new builder for the given program, which can be a full path
+	/// or just an executable name.
+	#[must_use]
+	pub fn new(program: &str) -> Self {
+		Self {
+			program: program.to_owned(),
+			args: Vec::default(),
+			env_clear: false,
+			env: Vec::default(),
+			current_dir: None,
+			creation_flags: co::CREATE::NoValue,
+			stdin: None,
+			stdout: None,
+			stderr: None,
+			inherit_handles: Vec::default(),
+		}
+	}
+
+	/// Adds an argument, which will be quoted if needed.
+	#[must_use]
+	pub fn arg(mut self, arg: &str) -> Self {
+		let mut quoted = String::default();
+		quote_arg(arg, &mut quoted);
+		self.args.push(quoted);
+		self
+	}
+
+	/// Adds several arguments, which will be quoted if needed.
+	#[must_use]
+	pub fn args<I, S>(mut self, args: I) -> Self
+		where I: IntoIterator<Item = S>,
+			S: AsRef<str>,
+	{
+		for arg in args {
+			self = self.arg(arg.as_ref());
+		}
+		self
+	}
+
+	/// Adds an argument verbatim, without quoting. This is needed by programs
+	/// which don't follow the C runtime rules, like `cmd.exe`.
+	#[must_use]
+	pub fn raw_arg(mut self, arg: &str) -> Self {
+		self.args.push(arg.to_owned());
+		self
+	}
+
+	/// Sets an environment variable for the child. Names are
+	/// case-insensitive.
+	#[must_use]
+	pub fn env(mut self, name: &str, val: &str) -> Self {
+		self.env.push((name.to_owned(), Some(val.to_owned())));
+		self
+	}
+
+	/// Removes an environment variable from the child.
+	#[must_use]
+	pub fn env_remove(mut self, name: &str) -> Self {
+		self.env.push((name.to_owned(), None));
+		self
+	}
+
+	/// Clears all environment variables, including those previously set with
+	/// [`Command::env`](crate::Command::env), so the child won't inherit the
+	/// environment of the current process.
+	#[must_use]
+	pub fn env_clear(mut self) -> Self {
+		self.env_clear = true;
+		self.env.clear();
+		self
+	}
+
+	/// Sets the working directory of the child.
+	#[must_use]
+	pub fn current_dir(mut self, dir: &str) -> Self {
+		self.current_dir = Some(dir.to_owned());
+		self
+	}
+
+	/// Sets the process creation flags, like
+	/// [`CREATE::NO_WINDOW`](crate::co::CREATE::NO_WINDOW).
+	#[must_use]
+	pub fn creation_flags(mut self, flags: co::CREATE) -> Self {
+		self.creation_flags = flags;
+		self
+	}
+
+	/// Sets the standard input of the child.
+	///
+	/// Defaults to [`Stdio::Inherit`](crate::Stdio::Inherit), or
+	/// [`Stdio::Null`](crate::Stdio::Null) when calling
+	/// [`Command::output`](crate::Command::output).
+	#[must_use]
+	pub fn stdin(mut self, stdio: Stdio) -> Self {
+		self.stdin = Some(stdio);
+		self
+	}
+
+	/// Sets the standard output of the child.
+	///
+	/// Defaults to [`Stdio::Inherit`](crate::Stdio::Inherit), or
+	/// [`Stdio::Pipe`](crate::Stdio::Pipe) when calling
+	/// [`Command::output`](crate::Command::output).
+	#[must_use]
+	pub fn stdout(mut self, stdio: Stdio) -> Self {
+		self.stdout = Some(stdio);
+		self
+	}
+
+	/// Sets the standard error of the child.
+	///
+	/// Defaults to [`Stdio::Inherit`](crate::Stdio::Inherit), or
+	/// [`Stdio::Pipe`](crate::Stdio::Pipe) when calling
+	/// [`Command::output`](crate::Command::output).
+	#[must_use]
+	pub fn stderr(mut self, stdio: Stdio) -> Self {
+		self.stderr = Some(stdio);
+		self
+	}
+
+	/// Adds a handle to be inherited by the child, besides the standard
+	/// streams. The handle is made inheritable only while the child is
+	/// spawned, and it must stay open until then.
+	#[must_use]
+	pub fn inherit_handle(mut self, handle: &impl Handle) -> Self {
+		self.inherit_handles.push(handle.ptr());
+		self
+	}
+
+	/// Returns the command line which will be passed to the child, with the
+	/// program and the quoted arguments.
+	#[must_use]
+	pub fn command_line(&self) -> String {
+		let mut cmd_line = String::default();
+		quote_program(&self.program, &mut cmd_line);
+		for arg in self.args.iter() {
+			cmd_line.push(' ');
+			cmd_line.push_str(arg);
+		}
+		cmd_line
+	}
+
+	/// Spawns the child process.
+	pub fn spawn(&self) -> SysResult<ChildProcess> {
+		self.spawn_with(Stdio::Inherit, Stdio::Inherit, Stdio::Inherit)
+	}
+
+	/// Spawns the child process, waits for it to finish, and returns its exit
+	/// code.
+	pub fn status(&self) -> SysResult<u32> {
+		self.spawn()?.wait()
+	}
+
+	/// Spawns the child process, waits for it to finish, and returns its exit
+	/// code along with everything it wrote to the standard output and error.
+	pub fn output(&self) -> SysResult<CommandOutput> {
+		self.spawn_with(Stdio::Null, Stdio::Pipe, Stdio::Pipe)?
+			.wait_with_output()
+	}
+
+	fn spawn_with(&self,
+		default_in: Stdio,
+		default_out: Stdio,
+		default_err: Stdio,
+	) -> SysResult<ChildProcess>
+	{
+		let streams = [
+			(self.stdin.as_ref().unwrap_or(&default_in), co::STD_HANDLE::INPUT),
+			(self.stdout.as_ref().unwrap_or(&default_out), co::STD_HANDLE::OUTPUT),
+			(self.stderr.as_ref().unwrap_or(&default_err), co::STD_HANDLE::ERROR),
+		];
+		let redirect = !self.inherit_handles.is_empty()
+			|| streams.iter().any(|(stdio, _)| !matches!(stdio, Stdio::Inherit));
+
+		let hproc = HPROCESS::GetCurrentProcess();
+		let mut caller_owned = InheritFlagsGuard::default(); // restored after spawning
+		let mut child_owned = Vec::<CloseHandleGuard<HFILE>>::default(); // closed after spawning
+		let mut child_std = [std::ptr::null_mut(); 3];
+		let mut parent_ends = [None, None, None];
+
+		if redirect {
+			for (i, (stdio, std_id)) in streams.iter().enumerate() {
+				let hchild = match stdio {
+					Stdio::Inherit => {
+						let hstd = unsafe { HFILE::from_ptr(ffi::GetStdHandle(std_id.raw())) };
+						if hstd == HFILE::NULL || hstd == HFILE::INVALID {
+							continue; // no console, the child gets no stream
+						}
+						unsafe {
+							CloseHandleGuard::new(
+								hproc.DuplicateHandle(&hstd, &hproc, 0, true, co::DUPLICATE::SAME_ACCESS)?,
+							)
+						}
+					},
+					Stdio::Null => {
+						HFILE::CreateFile("NUL",
+							co::GENERIC::READ | co::GENERIC::WRITE,
+							Some(co::FILE_SHARE::READ | co::FILE_SHARE::WRITE),
+							None, co::DISPOSITION::OPEN_EXISTING,
+							co::FILE_ATTRIBUTE::NORMAL, None, None, None)?.0
+					},
+					Stdio::Pipe => {
+						let (hread, hwrite) = HPIPE::CreatePipe(None, 0)?;
+						let (hparent, mut hchild) = if i == 0 { (hwrite, hread) } else { (hread, hwrite) };
+						parent_ends[i] = Some(hparent);
+						unsafe { CloseHandleGuard::new(HFILE::from_ptr(hchild.leak().ptr())) }
+					},
+					Stdio::File(hfile) => {
+						caller_owned.make_inheritable(hfile.ptr())?;
+						child_std[i] = hfile.ptr();
+						continue; // owned by the caller
+					},
+				};
+				SetHandleInformation(&*hchild, co::HANDLE_FLAG::INHERIT, co::HANDLE_FLAG::INHERIT)?;
+				child_std[i] = hchild.ptr();
+				child_owned.push(hchild);
+			}
+		}
+
+		let mut handle_list = Vec::<*mut std::ffi::c_void>::default();
+		for h in child_std.iter().chain(self.inherit_handles.iter()) {
+			if !h.is_null() && !handle_list.contains(h) { // duplicates are rejected
+				handle_list.push(*h);
+			}
+		}
+		for h in self.inherit_handles.iter() {
+			caller_owned.make_inheritable(*h)?;
+		}
+
+		let mut attr_list = if handle_list.is_empty() {
+			None
+		} else {
+			let mut attr_list = ProcThreadAttrList::new(1)?;
+			unsafe {
+				attr_list.update(
+					PROC_THREAD_ATTRIBUTE_HANDLE_LIST,
+					handle_list.as_ptr() as _,
+					handle_list.len() * std::mem::size_of::<*mut std::ffi::c_void>(),
+				)?;
+			}
+			Some(attr_list)
+		};
+
+		let mut siex = STARTUPINFOEX::default();
+		if redirect {
+			siex.StartupInfo.dwFlags |= co::STARTF::USESTDHANDLES;
+			siex.StartupInfo.hStdInput = unsafe { HPIPE::from_ptr(child_std[0]) };
+			siex.StartupInfo.hStdOutput = unsafe { HPIPE::from_ptr(child_std[1]) };
+			siex.StartupInfo.hStdError = unsafe { HPIPE::from_ptr(child_std[2]) };
+		}
+
+		let mut flags = self.creation_flags;
+		if let Some(attr_list) = attr_list.as_mut() {
+			siex.lpAttributeList = attr_list.ptr();
+			flags |= co::CREATE::EXTENDED_STARTUPINFO_PRESENT;
+		}
+
+		let env_block = self.env_block()?;
+		if env_block.is_some() {
+			flags |= co::CREATE::UNICODE_ENVIRONMENT;
+		}
+
+		let mut cmd_line = WString::from_str(self.command_line());
+		let mut pi = PROCESS_INFORMATION::default();
+
+		unsafe {
+			bool_to_sysresult(
+				ffi::CreateProcessW(
+					std::ptr::null(),
+					cmd_line.as_mut_ptr(),
+					std::ptr::null_mut(),
+					std::ptr::null_mut(),
+					!handle_list.is_empty() as _,
+					flags.raw(),
+					env_block.as_ref().map_or(std::ptr::null_mut(), |b| b.as_ptr() as _),
+					WString::from_opt_str(self.current_dir.as_deref()).as_ptr(),
+					&mut siex as *mut _ as _,
+					&mut pi as *mut _ as _,
+				),
+			)?;
+		}
+		drop(caller_owned);
+
+		let [stdin, stdout, stderr] = parent_ends;
+		Ok(ChildProcess {
+			pi: unsafe { CloseHandlePiGuard::new(pi) },
+			stdin,
+			stdout,
+			stderr,
+		})
+	}
+
+	fn env_block(&self) -> SysResult<Option<Vec<u16>>> {
+		if !self.env_clear && self.env.is_empty() {
+			return Ok(None); // inherit the environment as it is
+		}
+
+		let mut vars = BTreeMap::<String, (String, String)>::default(); // uppercase name => (name, value)
+		if !self.env_clear {
+			for (name, val) in GetEnvironmentStrings()? {
+				vars.insert(name.to_uppercase(), (name, val));
+			}
+		}
+		for (name, val) in self.env.iter() {
+			match val {
+				Some(val) => vars.insert(name.to_uppercase(), (name.clone(), val.clone())),
+				None => vars.remove(&name.to_uppercase()),
+			};
+		}
+		build_env_block(vars.values()).map(Some)
+	}
+}
+
+/// Makes caller-owned handles inheritable while a process is spawned, restoring
+/// their original flags when dropped.
+#[derive(Default)]
+struct InheritFlagsGuard {
+	orig_flags: Vec<(*mut std::ffi::c_void, co::HANDLE_FLAG)>,
+}
+
+impl Drop for InheritFlagsGuard {
+	fn drop(&mut self) {
+		for (h, flags) in self.orig_flags.iter() {
+			unsafe {
+				ffi::SetHandleInformation(*h, // ignore errors
+					co::HANDLE_FLAG::INHERIT.raw(), (*flags & co::HANDLE_FLAG::INHERIT).raw());
+			}
+		}
+	}
+}
+
+impl InheritFlagsGuard {
+	fn make_inheritable(&mut self, h: *mut std::ffi::c_void) -> SysResult<()> {
+		if !self.orig_flags.iter().any(|(saved, _)| *saved == h) { // keep the first, original flags
+			let mut flags = u32::default();
+			bool_to_sysresult(unsafe { ffi::GetHandleInformation(h, &mut flags) })?;
+			self.orig_flags.push((h, unsafe { co::HANDLE_FLAG::from_raw(flags) }));
+		}
+		bool_to_sysresult(
+			unsafe {
+				ffi::SetHandleInformation(h,
+					co::HANDLE_FLAG::INHERIT.raw(), co::HANDLE_FLAG::INHERIT.raw())
+			},
+		)
+	}
+}
+
+/// Output of a process spawned by
+/// [`Command::output`](crate::Command::output) or
+/// [`ChildProcess::wait_with_output`](crate::ChildProcess::wait_with_output).
+#[derive(Clone, Default)]
+pub struct CommandOutput {
+	/// The exit code of the process.
+	pub exit_code: u32,
+	/// Everything written to the standard output, if it was piped.
+	pub stdout: Vec<u8>,
+	/// Everything written to the standard error, if it was piped.
+	pub stderr: Vec<u8>,
+}
+
+//------------------------------------------------------------------------------
+
+/// A process spawned by [`Command::spawn`](crate::Command::spawn).
+///
+/// Dropping this object closes the process handles, but doesn't kill the
+/// process.
+pub struct ChildProcess {
+	pi: CloseHandlePiGuard,
+	/// Write end of the standard input, if it was piped.
+	pub stdin: Option<CloseHandleGuard<HPIPE>>,
+	/// Read end of the standard output, if it was piped.
+	pub stdout: Option<CloseHandleGuard<HPIPE>>,
+	/// Read end of the standard error, if it was piped.
+	pub stderr: Option<CloseHandleGuard<HPIPE>>,
+}
+
+impl ChildProcess {
+	/// Returns the process handle.
+	#[must_use]
+	pub fn hprocess(&self) -> &HPROCESS {
+		&self.pi.hProcess
+	}
+
+	/// Returns the handle of the main thread.
+	#[must_use]
+	pub fn hthread(&self) -> &HTHREAD {
+		&self.pi.hThread
+	}
+
+	/// Returns the process ID.
+	#[must_use]
+	pub fn pid(&self) -> u32 {
+		self.pi.dwProcessId
+	}
+
+	/// Resumes the main thread, if the process was created with
+	/// [`CREATE::SUSPENDED`](crate::co::CREATE::SUSPENDED).
+	pub fn resume(&self) -> SysResult<()> {
+		self.pi.hThread.ResumeThread().map(|_| ())
+	}
+
+	/// Blocks until the process exits, returning its exit code.
+	///
+	/// The standard input, if piped, is not closed, so the child may block
+	/// waiting for it.
+	pub fn wait(&self) -> SysResult<u32> {
+		self.wait_timeout(None)
+			.map(|exit_code| exit_code.unwrap_or_default())
+	}
+
+	/// Blocks until the process exits or the timeout expires, returning the
+	/// exit code, or `None` if the process is still running. A timeout of `0`
+	/// just checks the process.
+	pub fn wait_timeout(&self, milliseconds: Option<u32>) -> SysResult<Option<u32>> {
+		match self.pi.hProcess.WaitForSingleObject(milliseconds)? {
+			co::WAIT::TIMEOUT => Ok(None),
+			_ => self.pi.hProcess.GetExitCodeProcess().map(Some),
+		}
+	}
+
+	/// Forcibly terminates the process, with exit code `1`. If the process has
+	/// already exited, nothing happens.
+	pub fn kill(&self) -> SysResult<()> {
+		match self.pi.hProcess.TerminateProcess(1) {
+			Err(e) => match self.wait_timeout(Some(0))? {
+				Some(_) => Ok(()), // already exited
+				None => Err(e),
+			},
+			res => res,
+		}
+	}
+
+	/// Closes the standard input, reads the standard output and error until
+	/// the process closes them, then waits for it to exit.
+	///
+	/// Both streams are read simultaneously, so the child won't block when
+	/// writing to a full pipe.
+	pub fn wait_with_output(mut self) -> SysResult<CommandOutput> {
+		self.stdin = None; // signal end of input
+
+		let stderr_thread = self.stderr.take()
+			.map(|hpipe| std::thread::spawn(move || read_to_end(&hpipe)));
+		let stdout = match self.stdout.take() {
+			Some(hpipe) => read_to_end(&hpipe)?,
+			None => Vec::default(),
+		};
+		let stderr = match stderr_thread {
+			Some(thread) => thread.join().unwrap()?, // read_to_end doesn't panic
+			None => Vec::default(),
+		};
+
+		Ok(CommandOutput { exit_code: self.wait()?, stdout, stderr })
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A `PROC_THREAD_ATTRIBUTE_LIST` allocated in the heap.
+struct ProcThreadAttrList(Vec<usize>); // usize for pointer alignment
+
+impl Drop for ProcThreadAttrList {
+	fn drop(&mut self) {
+		unsafe { ffi::DeleteProcThreadAttributeList(self.ptr()); }
+	}
+}
+
+impl ProcThreadAttrList {
+	fn new(num_attrs: u32) -> SysResult<Self> {
+		let mut sz = usize::default();
+		unsafe { // retrieve the needed size; fails with INSUFFICIENT_BUFFER
+			ffi::InitializeProcThreadAttributeList(std::ptr::null_mut(), num_attrs, 0, &mut sz);
+		}
+
+		let mut buf = vec![0usize; sz.div_ceil(std::mem::size_of::<usize>())];
+		bool_to_sysresult(
+			unsafe {
+				ffi::InitializeProcThreadAttributeList(buf.as_mut_ptr() as _, num_attrs, 0, &mut sz)
+			},
+		).map(|_| Self(buf))
+	}
+
+	fn ptr(&mut self) -> *mut std::ffi::c_void {
+		self.0.as_mut_ptr() as _
+	}
+
+	/// The value must be kept alive while the list is in use.
+	unsafe fn update(&mut self,
+		attr: usize,
+		val: *const std::ffi::c_void,
+		val_size: usize,
+	) -> SysResult<()>
+	{
+		bool_to_sysresult(
+			ffi::UpdateProcThreadAttribute(self.ptr(), 0, attr, val, val_size,
+				std::ptr::null_mut(), std::ptr::null_mut()),
+		)
+	}
+}
+
+/// Reads a pipe until its write end is closed.
+fn read_to_end(hpipe: &HPIPE) -> SysResult<Vec<u8>> {
+	let mut data = Vec::<u8>::default();
+	let mut buf = [0u8; 4096];
+	loop {
+		match hpipe.ReadFile(&mut buf, None) {
+			Ok(0) | Err(co::ERROR::BROKEN_PIPE) => return Ok(data),
+			Ok(num_read) => data.extend_from_slice(&buf[..num_read as usize]),
+			Err(e) => return Err(e),
+		}
+	}
+}
+
+/// Appends the program name to the command line. The C runtime parses it
+/// without escapes, so it's simply quoted if needed.
+fn quote_program(program: &str, cmd_line: &mut String) {
+	if program.is_empty() || program.contains([' ', '\t']) {
+		cmd_line.push('"');
+		cmd_line.push_str(program);
+		cmd_line.push('"');
+	} else {
+		cmd_line.push_str(program);
+	}
+}
+
+/// Appends the argument to the command line, quoted according to the C
+/// runtime rules: backslashes are literal unless they precede a quote.
+pub(super) fn quote_arg(arg: &str, cmd_line: &mut String) {
+	if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '\x0b', '"']) {
+		cmd_line.push_str(arg);
+		return;
+	}
+
+	cmd_line.push('"');
+	let mut backslashes = 0;
+	for ch in arg.chars() {
+		match ch {
+			'\\' => backslashes += 1,
+			'"' => {
+				cmd_line.push_str(&"\\".repeat(backslashes * 2 + 1)); // escape them all, and the quote
+				cmd_line.push('"');
+				backslashes = 0;
+			},
+			_ => {
+				cmd_line.push_str(&"\\".repeat(backslashes));
+				cmd_line.push(ch);
+				backslashes = 0;
+			},
+		}
+	}
+	cmd_line.push_str(&"\\".repeat(backslashes * 2)); // don't escape the closing quote
+	cmd_line.push('"');
+}
+
+/// Builds an environment block from `(name, value)` pairs already sorted by
+/// their uppercase names: `name=value` strings, each one null-terminated,
+/// followed by a final null.
+fn build_env_block<'a>(
+	vars: impl Iterator<Item = &'a (String, String)>,
+) -> SysResult<Vec<u16>>
+{
+	let mut block = Vec::<u16>::default();
+	for (name, val) in vars {
+		if name.is_empty() || name.chars().skip(1).any(|ch| ch == '=')
+			|| name.contains('\0') || val.contains('\0')
+		{
+			return Err(co::ERROR::INVALID_PARAMETER); // a leading "=" is used by drive variables
+		}
+		block.extend(name.encode_utf16());
+		block.push('=' as _);
+		block.extend(val.encode_utf16());
+		block.push(0);
+	}
+	if block.is_empty() {
+		block.push(0); // an empty block still needs two nulls
+	}
+	block.push(0);
+	Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn quote(arg: &str) -> String {
+		let mut cmd_line = String::default();
+		quote_arg(arg, &mut cmd_line);
+		cmd_line
+	}
+
+	/// Splits a command line following the C runtime rules, the inverse of
+	/// `quote_arg`.
+	fn split(cmd_line: &str) -> Vec<String> {
+		let mut args = Vec::<String>::default();
+		let mut cur: Option<String> = None;
+		let mut in_quotes = false;
+		let mut chars = cmd_line.chars().peekable();
+		while let Some(ch) = chars.next() {
+			match ch {
+				'\\' => {
+					let mut n = 1;
+					while chars.peek() == Some(&'\\') {
+						chars.next();
+						n += 1;
+					}
+					let arg = cur.get_or_insert_with(String::default);
+					if chars.peek() == Some(&'"') {
+						arg.push_str(&"\\".repeat(n / 2));
+						if n % 2 == 1 {
+							chars.next();
+							arg.push('"'); // escaped quote
+						}
+					} else {
+						arg.push_str(&"\\".repeat(n)); // literal
+					}
+				},
+				'"' => {
+					cur.get_or_insert_with(String::default);
+					in_quotes = !in_quotes;
+				},
+				' ' | '\t' if !in_quotes => {
+					if let Some(arg) = cur.take() {
+						args.push(arg);
+					}
+				},
+				_ => cur.get_or_insert_with(String::default).push(ch),
+			}
+		}
+		args.extend(cur);
+		args
+	}
+
+	#[test]
+	fn quote_plain() {
+		assert_eq!(quote("foo.txt"), "foo.txt");
+		assert_eq!(quote("C:\\dir\\"), "C:\\dir\\"); // unquoted, backslashes literal
+		assert_eq!(quote("a\\\\b"), "a\\\\b");
+	}
+
+	#[test]
+	fn quote_empty() {
+		assert_eq!(quote(""), "\"\"");
+		assert_eq!(split(&quote("")), [""]);
+	}
+
+	#[test]
+	fn quote_whitespace() {
+		assert_eq!(quote("a b"), "\"a b\"");
+		assert_eq!(quote("a\tb"), "\"a\tb\"");
+		assert_eq!(quote("a\nb"), "\"a\nb\"");
+		assert_eq!(quote("a\x0bb"), "\"a\x0bb\"");
+		assert_eq!(quote(" "), "\" \"");
+	}
+
+	#[test]
+	fn quote_quotes() {
+		assert_eq!(quote("a\"b"), "\"a\\\"b\"");
+		assert_eq!(quote("\""), "\"\\\"\"");
+		assert_eq!(quote("\"\""), "\"\\\"\\\"\"");
+	}
+
+	#[test]
+	fn quote_trailing_backslashes() {
+		assert_eq!(quote("C:\\my dir\\"), "\"C:\\my dir\\\\\"");
+		assert_eq!(quote("a b\\\\"), "\"a b\\\\\\\\\"");
+		assert_eq!(quote("a b\\x"), "\"a b\\x\""); // not before a quote, literal
+	}
+
+	#[test]
+	fn quote_backslash_quote_runs() {
+		assert_eq!(quote("a\\\"b"), "\"a\\\\\\\"b\""); // \" becomes \\\"
+		assert_eq!(quote("a\\\\\"b"), "\"a\\\\\\\\\\\"b\""); // \\" becomes \\\\\"
+		assert_eq!(quote("\\\\\" \\"), "\"\\\\\\\\\\\" \\\\\"");
+	}
+
+	#[test]
+	fn quote_round_trip() {
+		let args = [
+			"", " ", "plain", "two words", "tab\there", "\"", "\\", "\\\\",
+			"\\\"", "a\\\\\"b", "end\\", "end \\\\", "C:\\Program Files\\",
+			"\"quoted\"", "mixed \\\"\\\\\" \t run\\\\",
+		];
+		let cmd_line = args.iter()
+			.map(|arg| quote(arg))
+			.collect::<Vec<_>>()
+			.join(" ");
+		assert_eq!(split(&cmd_line), args);
+	}
+
+	fn env_block(cmd: Command) -> String {
+		let block = cmd.env_block().unwrap().unwrap();
+		String::from_utf16(&block).unwrap()
+	}
+
+	#[test]
+	fn env_block_sorted() {
+		let cmd = Command::new("x.exe")
+			.env_clear()
+			.env("b", "2")
+			.env("A", "1")
+			.env("=C:", "C:\\dir")
+			.env("a", "3") // replaces "A", case-insensitive
+			.env("Path", "p")
+			.env("c", "gone")
+			.env_remove("C");
+		assert_eq!(env_block(cmd), "=C:=C:\\dir\0a=3\0b=2\0Path=p\0\0");
+	}
+
+	#[test]
+	fn env_block_nulls() {
+		let block = build_env_block([].iter()).unwrap();
+		assert_eq!(block, [0, 0]); // empty block still has two nulls
+
+		let vars = [("A".to_owned(), "".to_owned())];
+		let block = build_env_block(vars.iter()).unwrap();
+		assert_eq!(block, ['A' as u16, '=' as _, 0, 0]);
+
+		let vars = [("A".to_owned(), "x=y".to_owned())];
+		let block = build_env_block(vars.iter()).unwrap();
+		assert_eq!(String::from_utf16(&block).unwrap(), "A=x=y\0\0");
+		assert!(Command::new("x.exe").env_block().unwrap().is_none()); // inherited
+	}
+
+	#[test]
+	fn env_block_drive_vars() {
+		let vars = [
+			("=C:".to_owned(), "C:\\Windows".to_owned()),
+			("=D:".to_owned(), "D:\\".to_owned()),
+		];
+		let block = build_env_block(vars.iter()).unwrap();
+		assert_eq!(String::from_utf16(&block).unwrap(), "=C:=C:\\Windows\0=D:=D:\\\0\0");
+
+		// parsed back as GetEnvironmentStrings does
+		let strs = parse_multi_z_str(block.as_ptr());
+		let pairs = strs.iter().map(|s| split_env_str(s)).collect::<Vec<_>>();
+		assert_eq!(pairs, [
+			("=C:".to_owned(), "C:\\Windows".to_owned()),
+			("=D:".to_owned(), "D:\\".to_owned()),
+		]);
+		assert_eq!(split_env_str("A=x=y"), ("A".to_owned(), "x=y".to_owned()));
+		assert_eq!(split_env_str("A="), ("A".to_owned(), "".to_owned()));
+		assert_eq!(split_env_str("=="), ("=".to_owned(), "".to_owned()));
+		assert_eq!(split_env_str("NOVALUE"), ("NOVALUE".to_owned(), "".to_owned()));
+	}
+
+	#[test]
+	fn env_block_invalid() {
+		for (name, val) in [("", "x"), ("A=B", "x"), ("=C:=", "x"), ("A\0", "x"), ("A", "x\0")] {
+			let vars = [(name.to_owned(), val.to_owned())];
+			assert_eq!(build_env_block(vars.iter()).err(), Some(co::ERROR::INVALID_PARAMETER));
+		}
+	}
+}
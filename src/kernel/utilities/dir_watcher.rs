@@ -0,0 +1,647 @@
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+const BUFFER_SIZE: usize = 64 * 1024; // larger buffers fail on network shares
+const HEADER_SIZE: usize = 12; // FILE_NOTIFY_INFORMATION up to FileName
+const HEADER_SIZE_EXTENDED: usize = 84; // FILE_NOTIFY_EXTENDED_INFORMATION up to FileName
+const RENAME_GRACE_MS: u32 = 50;
+
+/// Watches a directory for changes with
+/// [`HFILE::ReadDirectoryChanges`](crate::prelude::kernel_Hfile::ReadDirectoryChanges),
+/// reporting them as [`DirEvent`](crate::DirEvent) values. The directory handle
+/// is closed automatically when the object goes out of scope.
+///
+/// The next read is issued as soon as the previous one completes, so no changes
+/// are missed between calls to [`DirWatcher::wait`](crate::DirWatcher::wait).
+/// If too many changes happen at once, they are lost and a
+/// [`DirEvent::Rescan`](crate::DirEvent::Rescan) is reported instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let mut watcher = w::DirWatcher::new(
+///     "C:\\Temp",
+///     true,
+///     co::FILE_NOTIFY_CHANGE::FILE_NAME
+///         | co::FILE_NOTIFY_CHANGE::DIR_NAME
+///         | co::FILE_NOTIFY_CHANGE::LAST_WRITE,
+///     false,
+/// )?;
+///
+/// loop {
+///     for event in watcher.wait(None)? {
+///         match event {
+///             w::DirEvent::Added(f) => println!("+ {}", f.name),
+///             w::DirEvent::Removed(f) => println!("- {}", f.name),
+///             w::DirEvent::Modified(f) => println!("* {}", f.name),
+///             w::DirEvent::Renamed { from, to } => println!("{} -> {}", from.name, to.name),
+///             w::DirEvent::Rescan => println!("Changes were lost, rescan the directory."),
+///         }
+///     }
+/// }
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct DirWatcher {
+	hdir: CloseHandleGuard<HFILE>,
+	hevent: CloseHandleGuard<HEVENT>,
+	watch_subtree: bool,
+	notify_filter: co::FILE_NOTIFY_CHANGE,
+	extended: bool,
+	pending: Option<PendingRead>,
+	carry: Option<FileNotifyRecord>,
+}
+
+impl Drop for DirWatcher {
+	fn drop(&mut self) {
+		if let Some(pending) = &self.pending {
+			let _ = self.hdir.CancelIoEx(Some(&pending.ov));
+			let _ = self.hdir.GetOverlappedResult(&pending.ov, true); // wait cancellation
+		}
+	}
+}
+
+impl DirWatcher {
+	/// Opens the directory and starts watching it for the given changes. If
+	/// `watch_subtree` is `true`, changes in subdirectories are also reported,
+	/// with names relative to the watched directory.
+	///
+	/// If `extended` is `true`, the events carry
+	/// [`FileNotifyExtended`](crate::FileNotifyExtended) information, which
+	/// requires Windows 10 version 1709 or later.
+	pub fn new(
+		dir_path: &str,
+		watch_subtree: bool,
+		notify_filter: co::FILE_NOTIFY_CHANGE,
+		extended: bool,
+	) -> SysResult<Self>
+	{
+		let (hdir, _) = HFILE::CreateFile(
+			dir_path,
+			co::GENERIC::READ, // READ includes FILE_LIST_DIRECTORY
+			Some(co::FILE_SHARE::READ | co::FILE_SHARE::WRITE | co::FILE_SHARE::DELETE),
+			None,
+			co::DISPOSITION::OPEN_EXISTING,
+			co::FILE_ATTRIBUTE::NORMAL,
+			Some(co::FILE_FLAG::BACKUP_SEMANTICS | co::FILE_FLAG::OVERLAPPED),
+			None,
+			None,
+		)?;
+		let hevent = HEVENT::CreateEvent(None, true, false, None)?;
+
+		let mut new_self = Self {
+			hdir,
+			hevent,
+			watch_subtree,
+			notify_filter,
+			extended,
+			pending: None,
+			carry: None,
+		};
+		new_self.arm()?;
+		Ok(new_self)
+	}
+
+	/// Returns the watched directory, opened with
+	/// [`FILE_FLAG::BACKUP_SEMANTICS`](crate::co::FILE_FLAG::BACKUP_SEMANTICS)
+	/// and [`FILE_FLAG::OVERLAPPED`](crate::co::FILE_FLAG::OVERLAPPED).
+	#[must_use]
+	pub fn hdir(&self) -> &HFILE {
+		&self.hdir
+	}
+
+	/// Returns the manual-reset event which is signaled when changes are
+	/// available, so it can be waited along with other objects. The changes
+	/// are then retrieved with [`DirWatcher::wait`](crate::DirWatcher::wait).
+	#[must_use]
+	pub fn hevent(&self) -> &HEVENT {
+		&self.hevent
+	}
+
+	/// Blocks until changes occur or the timeout expires, returning the
+	/// changes; an empty `Vec` means the timeout expired.
+	///
+	/// A rename whose new name didn't arrive yet is held for a few
+	/// milliseconds; if the new name doesn't show up – because the file was
+	/// moved out of the watched directory – it's reported as
+	/// [`DirEvent::Removed`](crate::DirEvent::Removed).
+	pub fn wait(&mut self, milliseconds: Option<u32>) -> SysResult<Vec<DirEvent>> {
+		loop {
+			if self.pending.is_none() {
+				self.arm()?; // a previous read failed
+			}
+
+			let wait_ms = match (&self.carry, milliseconds) {
+				(Some(_), Some(ms)) => Some(ms.min(RENAME_GRACE_MS)),
+				(Some(_), None) => Some(RENAME_GRACE_MS),
+				(None, ms) => ms,
+			};
+			if self.hevent.WaitForSingleObject(wait_ms)? != co::WAIT::OBJECT_0 {
+				return Ok(self.carry.take()
+					.map(|old| vec![DirEvent::Removed(old)])
+					.unwrap_or_default());
+			}
+
+			let pending = self.pending.take().unwrap();
+			let res = self.hdir.GetOverlappedResult(&pending.ov, false);
+			let num_bytes = match res {
+				Ok(0) | Err(co::ERROR::NOTIFY_ENUM_DIR) => { // buffer overflow
+					self.arm()?;
+					let mut events = self.carry.take()
+						.map(|old| vec![DirEvent::Removed(old)])
+						.unwrap_or_default();
+					events.push(DirEvent::Rescan);
+					return Ok(events);
+				},
+				Ok(num_bytes) => num_bytes as usize,
+				Err(e) => return Err(e),
+			};
+			self.arm()?; // start the next read before parsing this one
+
+			let records = FileNotifyRecord::parse(
+				&pending.as_bytes()[..num_bytes], self.extended)?;
+			let events = coalesce_carried(&mut self.carry, records);
+			if !events.is_empty() {
+				return Ok(events);
+			}
+		}
+	}
+
+	fn arm(&mut self) -> SysResult<()> {
+		let mut pending = PendingRead {
+			ov: Box::new(OVERLAPPED {
+				hEvent: unsafe { self.hevent.raw_copy() },
+				..Default::default()
+			}),
+			buf: vec![0u64; BUFFER_SIZE / 8], // u64 for FILE_NOTIFY_EXTENDED_INFORMATION alignment
+		};
+		let buf = unsafe {
+			std::slice::from_raw_parts_mut(pending.buf.as_mut_ptr() as *mut u8, BUFFER_SIZE)
+		};
+
+		let res = if self.extended {
+			self.hdir.ReadDirectoryChangesEx(buf, self.watch_subtree, self.notify_filter,
+				Some(&mut pending.ov), co::READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation)
+		} else {
+			self.hdir.ReadDirectoryChanges(buf, self.watch_subtree, self.notify_filter,
+				Some(&mut pending.ov))
+		};
+		match res {
+			Ok(_) | Err(co::ERROR::IO_PENDING) => {
+				self.pending = Some(pending);
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+}
+
+/// A read issued by a [`DirWatcher`](crate::DirWatcher), whose `OVERLAPPED`
+/// and buffer must outlive the operation.
+struct PendingRead {
+	ov: Box<OVERLAPPED>,
+	buf: Vec<u64>,
+}
+
+impl PendingRead {
+	fn as_bytes(&self) -> &[u8] {
+		unsafe { std::slice::from_raw_parts(self.buf.as_ptr() as *const u8, BUFFER_SIZE) }
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A change reported by a [`DirWatcher`](crate::DirWatcher).
+#[derive(Clone, PartialEq, Eq)]
+pub enum DirEvent {
+	/// A file or directory was created, or moved into the watched directory.
+	Added(FileNotifyRecord),
+	/// A file or directory was deleted, or moved out of the watched directory.
+	Removed(FileNotifyRecord),
+	/// A file or directory had its contents, size, timestamps, attributes or
+	/// security changed, depending on the
+	/// [`FILE_NOTIFY_CHANGE`](crate::co::FILE_NOTIFY_CHANGE) filter.
+	Modified(FileNotifyRecord),
+	/// A file or directory was renamed within the watched directory.
+	Renamed {
+		/// The old name.
+		from: FileNotifyRecord,
+		/// The new name.
+		to: FileNotifyRecord,
+	},
+	/// The changes didn't fit the buffer and were lost, so the directory
+	/// contents must be scanned again.
+	Rescan,
+}
+
+impl DirEvent {
+	/// Converts records parsed with
+	/// [`FileNotifyRecord::parse`](crate::FileNotifyRecord::parse) into events,
+	/// pairing each
+	/// [`FILE_ACTION::RENAMED_OLD_NAME`](crate::co::FILE_ACTION::RENAMED_OLD_NAME)
+	/// with the
+	/// [`FILE_ACTION::RENAMED_NEW_NAME`](crate::co::FILE_ACTION::RENAMED_NEW_NAME)
+	/// which immediately follows it.
+	///
+	/// An old name without a new name is reported as
+	/// [`DirEvent::Removed`](crate::DirEvent::Removed), and a new name without
+	/// an old name as [`DirEvent::Added`](crate::DirEvent::Added). Records with
+	/// unknown actions are ignored.
+	#[must_use]
+	pub fn coalesce(records: Vec<FileNotifyRecord>) -> Vec<DirEvent> {
+		let mut events = Vec::with_capacity(records.len());
+		let mut old_name: Option<FileNotifyRecord> = None;
+
+		for record in records {
+			if record.action == co::FILE_ACTION::RENAMED_NEW_NAME {
+				events.push(match old_name.take() {
+					Some(from) => DirEvent::Renamed { from, to: record },
+					None => DirEvent::Added(record),
+				});
+				continue;
+			}
+			if let Some(old) = old_name.take() {
+				events.push(DirEvent::Removed(old));
+			}
+			match record.action {
+				co::FILE_ACTION::ADDED => events.push(DirEvent::Added(record)),
+				co::FILE_ACTION::REMOVED => events.push(DirEvent::Removed(record)),
+				co::FILE_ACTION::MODIFIED => events.push(DirEvent::Modified(record)),
+				co::FILE_ACTION::RENAMED_OLD_NAME => old_name = Some(record),
+				_ => {},
+			}
+		}
+
+		if let Some(old) = old_name {
+			events.push(DirEvent::Removed(old));
+		}
+		events
+	}
+}
+
+/// A record of a `FILE_NOTIFY_INFORMATION` or
+/// `FILE_NOTIFY_EXTENDED_INFORMATION` buffer, filled by
+/// [`HFILE::ReadDirectoryChanges`](crate::prelude::kernel_Hfile::ReadDirectoryChanges)
+/// or
+/// [`HFILE::ReadDirectoryChangesEx`](crate::prelude::kernel_Hfile::ReadDirectoryChangesEx).
+#[derive(Clone, PartialEq, Eq)]
+pub struct FileNotifyRecord {
+	/// The kind of change.
+	pub action: co::FILE_ACTION,
+	/// Name of the file or directory, relative to the watched directory.
+	pub name: String,
+	/// Additional information, if the buffer was read with
+	/// [`READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation`](crate::co::READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation).
+	pub extended: Option<FileNotifyExtended>,
+}
+
+/// Fields of a `FILE_NOTIFY_EXTENDED_INFORMATION` record, after the file has
+/// changed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FileNotifyExtended {
+	/// Creation time.
+	pub creation_time: FILETIME,
+	/// Last time the contents were written.
+	pub last_modification_time: FILETIME,
+	/// Last time the contents or the metadata changed.
+	pub last_change_time: FILETIME,
+	/// Last access time.
+	pub last_access_time: FILETIME,
+	/// Size allocated on disk, in bytes.
+	pub allocated_length: u64,
+	/// Size of the contents, in bytes.
+	pub file_size: u64,
+	/// File attributes.
+	pub file_attributes: co::FILE_ATTRIBUTE,
+	/// Reparse point tag if the file is a reparse point, otherwise the size of
+	/// its extended attributes.
+	pub reparse_point_tag: u32,
+	/// File ID, unique within the volume.
+	pub file_id: u64,
+	/// File ID of the parent directory.
+	pub parent_file_id: u64,
+}
+
+impl FileNotifyRecord {
+	/// Parses the records of a buffer filled by
+	/// [`HFILE::ReadDirectoryChanges`](crate::prelude::kernel_Hfile::ReadDirectoryChanges)
+	/// or, if `extended` is `true`, by
+	/// [`HFILE::ReadDirectoryChangesEx`](crate::prelude::kernel_Hfile::ReadDirectoryChangesEx)
+	/// with
+	/// [`READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation`](crate::co::READ_DIRECTORY_NOTIFY_INFORMATION_CLASS::ExtendedInformation).
+	/// The buffer must be truncated to the number of bytes returned.
+	///
+	/// This is useful to watch directories through a
+	/// [`Reactor`](crate::Reactor), which is not done by
+	/// [`DirWatcher`](crate::DirWatcher).
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if a record
+	/// goes past the end of the buffer, or if the last record points to a next
+	/// one.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let reactor: w::Reactor; // initialized somewhere
+	/// let hdir: w::HFILE; // opened with BACKUP_SEMANTICS and OVERLAPPED
+	/// # let reactor = w::Reactor::new()?;
+	/// # let hdir = w::HFILE::NULL;
+	///
+	/// reactor.associate(&hdir)?;
+	/// let (res, buf) = reactor.block_on(
+	///     reactor.submit(&hdir, vec![0; 64 * 1024], 0, |h, buf, ov| {
+	///         h.ReadDirectoryChanges(buf, true, co::FILE_NOTIFY_CHANGE::FILE_NAME, Some(ov))
+	///     }),
+	/// )?;
+	///
+	/// let records = w::FileNotifyRecord::parse(&buf[..res? as usize], false)?;
+	/// for event in w::DirEvent::coalesce(records) {
+	///     // ...
+	/// }
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	pub fn parse(buf: &[u8], extended: bool) -> SysResult<Vec<Self>> {
+		let mut records = Vec::new();
+		if buf.is_empty() {
+			return Ok(records);
+		}
+		let mut pos = 0;
+
+		loop {
+			let next_entry_offset = u32_at(buf, pos)? as usize;
+			let action = unsafe { co::FILE_ACTION::from_raw(u32_at(buf, pos + 4)?) };
+
+			let (name_len_pos, name_pos) = if extended {
+				(pos + HEADER_SIZE_EXTENDED - 4, pos + HEADER_SIZE_EXTENDED)
+			} else {
+				(pos + HEADER_SIZE - 4, pos + HEADER_SIZE)
+			};
+			let name_len = u32_at(buf, name_len_pos)? as usize; // in bytes
+			let name = utf16_name(slice_at(buf, name_pos, name_len)?);
+
+			let extended = if extended {
+				Some(FileNotifyExtended {
+					creation_time: filetime_at(buf, pos + 8)?,
+					last_modification_time: filetime_at(buf, pos + 16)?,
+					last_change_time: filetime_at(buf, pos + 24)?,
+					last_access_time: filetime_at(buf, pos + 32)?,
+					allocated_length: u64_at(buf, pos + 40)?,
+					file_size: u64_at(buf, pos + 48)?,
+					file_attributes: unsafe { co::FILE_ATTRIBUTE::from_raw(u32_at(buf, pos + 56)?) },
+					reparse_point_tag: u32_at(buf, pos + 60)?,
+					file_id: u64_at(buf, pos + 64)?,
+					parent_file_id: u64_at(buf, pos + 72)?,
+				})
+			} else {
+				None
+			};
+
+			records.push(Self { action, name, extended });
+			if next_entry_offset == 0 {
+				break;
+			}
+			pos = pos.checked_add(next_entry_offset)
+				.ok_or(co::ERROR::INVALID_DATA)?;
+		}
+		Ok(records)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Converts the records of a buffer into events, prepending the old name
+/// carried from the previous buffer, and carrying a trailing old name, whose
+/// new name may come in the next buffer.
+fn coalesce_carried(
+	carry: &mut Option<FileNotifyRecord>,
+	mut records: Vec<FileNotifyRecord>,
+) -> Vec<DirEvent>
+{
+	if let Some(old) = carry.take() {
+		records.insert(0, old);
+	}
+	if records.last().map(|r| r.action) == Some(co::FILE_ACTION::RENAMED_OLD_NAME) {
+		*carry = records.pop();
+	}
+	DirEvent::coalesce(records)
+}
+
+fn slice_at(buf: &[u8], pos: usize, len: usize) -> SysResult<&[u8]> {
+	pos.checked_add(len)
+		.and_then(|end| buf.get(pos..end))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> SysResult<u32> {
+	slice_at(buf, pos, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn u64_at(buf: &[u8], pos: usize) -> SysResult<u64> {
+	Ok(u32_at(buf, pos)? as u64 | (u32_at(buf, pos + 4)? as u64) << 32)
+}
+
+fn filetime_at(buf: &[u8], pos: usize) -> SysResult<FILETIME> {
+	Ok(FILETIME {
+		dwLowDateTime: u32_at(buf, pos)?,
+		dwHighDateTime: u32_at(buf, pos + 4)?,
+	})
+}
+
+fn utf16_name(raw: &[u8]) -> String {
+	let chars = raw.chunks_exact(2)
+		.map(|ch| u16::from_le_bytes([ch[0], ch[1]]))
+		.collect::<Vec<_>>();
+	String::from_utf16_lossy(&chars)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Assembles a buffer of `FILE_NOTIFY_INFORMATION` records, or
+	/// `FILE_NOTIFY_EXTENDED_INFORMATION` ones, chained by their
+	/// `NextEntryOffset`, each one aligned to 8 bytes.
+	fn buffer(records: &[(u32, &str)], extended: bool) -> Vec<u8> {
+		let mut buf = Vec::<u8>::default();
+		for (idx, (action, name)) in records.iter().enumerate() {
+			let start = buf.len();
+			let name = name.encode_utf16().flat_map(|ch| ch.to_le_bytes()).collect::<Vec<_>>();
+			buf.extend_from_slice(&[0; 4]); // NextEntryOffset, set below
+			buf.extend_from_slice(&action.to_le_bytes());
+			if extended {
+				for field in 0..6u64 { // 4 times, AllocatedLength, FileSize
+					buf.extend_from_slice(&(0x1000 * (field + 1) + idx as u64).to_le_bytes());
+				}
+				buf.extend_from_slice(&co::FILE_ATTRIBUTE::ARCHIVE.raw().to_le_bytes());
+				buf.extend_from_slice(&0u32.to_le_bytes()); // ReparsePointTag
+				buf.extend_from_slice(&(100 + idx as u64).to_le_bytes()); // FileId
+				buf.extend_from_slice(&5u64.to_le_bytes()); // ParentFileId
+			}
+			buf.extend_from_slice(&(name.len() as u32).to_le_bytes());
+			buf.extend_from_slice(&name);
+			if idx < records.len() - 1 {
+				buf.resize((buf.len() + 7) & !7, 0);
+				let next = (buf.len() - start) as u32;
+				buf[start..start + 4].copy_from_slice(&next.to_le_bytes());
+			}
+		}
+		buf
+	}
+
+	fn record(action: co::FILE_ACTION, name: &str) -> FileNotifyRecord {
+		FileNotifyRecord { action, name: name.to_owned(), extended: None }
+	}
+
+	const ADDED: u32 = 1;
+	const REMOVED: u32 = 2;
+	const MODIFIED: u32 = 3;
+	const OLD_NAME: u32 = 4;
+	const NEW_NAME: u32 = 5;
+
+	#[test]
+	fn parse_empty() {
+		assert!(FileNotifyRecord::parse(&[], false).unwrap().is_empty());
+	}
+
+	#[test]
+	fn parse_chain() {
+		let buf = buffer(&[(ADDED, "a.txt"), (MODIFIED, "dir\\b\u{e9}.txt"), (REMOVED, "c")], false);
+		let records = FileNotifyRecord::parse(&buf, false).unwrap();
+		assert!(records == [
+			record(co::FILE_ACTION::ADDED, "a.txt"),
+			record(co::FILE_ACTION::MODIFIED, "dir\\b\u{e9}.txt"),
+			record(co::FILE_ACTION::REMOVED, "c"),
+		]);
+	}
+
+	#[test]
+	fn parse_extended() {
+		let buf = buffer(&[(ADDED, "a"), (NEW_NAME, "bb")], true);
+		let records = FileNotifyRecord::parse(&buf, true).unwrap();
+		assert_eq!(records.len(), 2);
+		assert_eq!(records[1].name, "bb");
+		assert_eq!(records[1].action, co::FILE_ACTION::RENAMED_NEW_NAME);
+
+		let ext = records[1].extended.unwrap();
+		assert_eq!(ext.creation_time.dwLowDateTime, 0x1001);
+		assert_eq!(ext.last_access_time.dwLowDateTime, 0x4001);
+		assert_eq!(ext.file_size, 0x6001);
+		assert_eq!(ext.file_attributes, co::FILE_ATTRIBUTE::ARCHIVE);
+		assert_eq!(ext.file_id, 101);
+		assert_eq!(ext.parent_file_id, 5);
+	}
+
+	#[test]
+	fn parse_out_of_bounds() {
+		let buf = buffer(&[(ADDED, "a.txt"), (REMOVED, "b.txt")], false);
+
+		let mut bad = buf.clone(); // next record past the end
+		bad[..4].copy_from_slice(&(buf.len() as u32).to_le_bytes());
+		assert_eq!(FileNotifyRecord::parse(&bad, false).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut bad = buf.clone(); // huge offset, must not overflow
+		bad[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+		assert_eq!(FileNotifyRecord::parse(&bad, false).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut bad = buf.clone(); // name past the end
+		bad[8..12].copy_from_slice(&0xffff_fff0u32.to_le_bytes());
+		assert_eq!(FileNotifyRecord::parse(&bad, false).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut bad = buf.clone(); // last record points to a next one
+		let last = u32_at(&buf, 0).unwrap() as usize;
+		bad[last..last + 4].copy_from_slice(&8u32.to_le_bytes());
+		assert_eq!(FileNotifyRecord::parse(&bad, false).err(), Some(co::ERROR::INVALID_DATA));
+
+		assert_eq!(FileNotifyRecord::parse(&buf[..buf.len() - 1], false).err(),
+			Some(co::ERROR::INVALID_DATA)); // truncated name
+		assert_eq!(FileNotifyRecord::parse(&buf[..6], false).err(),
+			Some(co::ERROR::INVALID_DATA)); // truncated header
+	}
+
+	#[test]
+	fn coalesce_renames() {
+		let events = DirEvent::coalesce(vec![
+			record(co::FILE_ACTION::RENAMED_OLD_NAME, "old"),
+			record(co::FILE_ACTION::RENAMED_NEW_NAME, "new"),
+			record(co::FILE_ACTION::MODIFIED, "new"),
+		]);
+		assert!(events == [
+			DirEvent::Renamed {
+				from: record(co::FILE_ACTION::RENAMED_OLD_NAME, "old"),
+				to: record(co::FILE_ACTION::RENAMED_NEW_NAME, "new"),
+			},
+			DirEvent::Modified(record(co::FILE_ACTION::MODIFIED, "new")),
+		]);
+	}
+
+	#[test]
+	fn coalesce_unpaired() {
+		let events = DirEvent::coalesce(vec![
+			record(co::FILE_ACTION::RENAMED_OLD_NAME, "moved out"),
+			record(co::FILE_ACTION::ADDED, "a"),
+			record(co::FILE_ACTION::RENAMED_NEW_NAME, "moved in"),
+			record(unsafe { co::FILE_ACTION::from_raw(99) }, "unknown"),
+			record(co::FILE_ACTION::RENAMED_OLD_NAME, "last"),
+		]);
+		assert!(events == [
+			DirEvent::Removed(record(co::FILE_ACTION::RENAMED_OLD_NAME, "moved out")),
+			DirEvent::Added(record(co::FILE_ACTION::ADDED, "a")),
+			DirEvent::Added(record(co::FILE_ACTION::RENAMED_NEW_NAME, "moved in")),
+			DirEvent::Removed(record(co::FILE_ACTION::RENAMED_OLD_NAME, "last")),
+		]);
+		assert!(DirEvent::coalesce(Vec::default()).is_empty());
+	}
+
+	#[test]
+	fn rename_across_buffers() {
+		let mut carry = None;
+
+		let buf1 = buffer(&[(ADDED, "a"), (OLD_NAME, "old")], false);
+		let events = coalesce_carried(&mut carry, FileNotifyRecord::parse(&buf1, false).unwrap());
+		assert!(events == [DirEvent::Added(record(co::FILE_ACTION::ADDED, "a"))]);
+		assert!(carry == Some(record(co::FILE_ACTION::RENAMED_OLD_NAME, "old")));
+
+		let buf2 = buffer(&[(NEW_NAME, "new"), (REMOVED, "b")], false);
+		let events = coalesce_carried(&mut carry, FileNotifyRecord::parse(&buf2, false).unwrap());
+		assert!(events == [
+			DirEvent::Renamed {
+				from: record(co::FILE_ACTION::RENAMED_OLD_NAME, "old"),
+				to: record(co::FILE_ACTION::RENAMED_NEW_NAME, "new"),
+			},
+			DirEvent::Removed(record(co::FILE_ACTION::REMOVED, "b")),
+		]);
+		assert!(carry.is_none());
+	}
+
+	#[test]
+	fn carried_name_without_new_name() {
+		let mut carry = None;
+		let events = coalesce_carried(&mut carry, vec![
+			record(co::FILE_ACTION::RENAMED_OLD_NAME, "old"),
+		]);
+		assert!(events.is_empty()); // nothing to report yet
+		assert!(carry.is_some());
+
+		let events = coalesce_carried(&mut carry, vec![
+			record(co::FILE_ACTION::ADDED, "x"),
+		]);
+		assert!(events == [
+			DirEvent::Removed(record(co::FILE_ACTION::RENAMED_OLD_NAME, "old")),
+			DirEvent::Added(record(co::FILE_ACTION::ADDED, "x")),
+		]);
+		assert!(carry.is_none());
+
+		let events = coalesce_carried(&mut carry, vec![
+			record(co::FILE_ACTION::RENAMED_OLD_NAME, "a"),
+			record(co::FILE_ACTION::RENAMED_OLD_NAME, "b"),
+		]);
+		assert!(events == [DirEvent::Removed(record(co::FILE_ACTION::RENAMED_OLD_NAME, "a"))]);
+		assert!(carry == Some(record(co::FILE_ACTION::RENAMED_OLD_NAME, "b")));
+	}
+}
@@ -0,0 +1,256 @@
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// Manages an [`HJOB`](crate::HJOB) handle associated to its own
+/// [`HIOCP`](crate::HIOCP), so the processes in the job can be limited and its
+/// notifications can be received. Both handles are closed automatically when
+/// the object goes out of scope.
+///
+/// # Examples
+///
+/// Running a child process which is killed if the current process dies, with
+/// its memory capped at 512 MB:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let job = w::JobObject::new(true)?;
+/// job.set_process_memory_limit(Some(512 * 1024 * 1024))?;
+///
+/// let child = w::Command::new("worker.exe")
+///     .creation_flags(co::CREATE::SUSPENDED)
+///     .spawn()?;
+/// job.assign(child.hprocess())?;
+/// child.resume()?;
+///
+/// while let Some(ev) = job.next_event(None)? {
+///     if ev.msg == co::JOB_OBJECT_MSG::ACTIVE_PROCESS_ZERO {
+///         break;
+///     }
+///     println!("{} {:?}", ev.msg, ev.process_id);
+/// }
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct JobObject {
+	hjob: CloseHandleGuard<HJOB>,
+	hiocp: CloseHandleGuard<HIOCP>,
+}
+
+/// A notification received by
+/// [`JobObject::next_event`](crate::JobObject::next_event).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct JobEvent {
+	/// The notification message.
+	pub msg: co::JOB_OBJECT_MSG,
+	/// ID of the process which caused the notification, if the message refers
+	/// to a single process.
+	pub process_id: Option<u32>,
+}
+
+impl JobObject {
+	/// Creates a new unnamed job object and associates it to a new completion
+	/// port. If `kill_on_close` is true, all processes in the job are
+	/// terminated when the object is dropped – including when the current
+	/// process dies.
+	#[must_use]
+	pub fn new(kill_on_close: bool) -> SysResult<Self> {
+		let hjob = HJOB::CreateJobObject(None, None)?;
+		let hiocp = HIOCP::CreateIoCompletionPort(1)?;
+
+		let port = JOBOBJECT_ASSOCIATE_COMPLETION_PORT {
+			CompletionKey: hjob.ptr() as _,
+			CompletionPort: unsafe { hiocp.raw_copy() },
+		};
+		unsafe {
+			hjob.SetInformationJobObject(
+				co::JOBOBJECTINFOCLASS::AssociateCompletionPortInformation,
+				&port,
+			)?;
+		}
+
+		let job = Self { hjob, hiocp };
+		if kill_on_close {
+			job.modify_limits(|info| {
+				info.BasicLimitInformation.LimitFlags |= co::JOB_OBJECT_LIMIT::KILL_ON_JOB_CLOSE;
+			})?;
+		}
+		Ok(job)
+	}
+
+	/// Returns the underlying job handle.
+	#[must_use]
+	pub fn hjob(&self) -> &HJOB {
+		&self.hjob
+	}
+
+	/// Returns the underlying completion port handle.
+	#[must_use]
+	pub fn hiocp(&self) -> &HIOCP {
+		&self.hiocp
+	}
+
+	/// Assigns a process to the job. Child processes created afterwards by this
+	/// process are also assigned to the job, unless breakaway is allowed.
+	///
+	/// To make sure the process doesn't spawn anything before being assigned,
+	/// create it with
+	/// [`co::CREATE::SUSPENDED`](crate::co::CREATE::SUSPENDED).
+	pub fn assign(&self, process: &HPROCESS) -> SysResult<()> {
+		self.hjob.AssignProcessToJobObject(process)
+	}
+
+	/// Retrieves the accounting information of the job: CPU times, process
+	/// counts and I/O counters.
+	#[must_use]
+	pub fn accounting(&self,
+	) -> SysResult<JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION>
+	{
+		let mut info = JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION::default();
+		unsafe {
+			self.hjob.QueryInformationJobObject(
+				co::JOBOBJECTINFOCLASS::BasicAndIoAccountingInformation,
+				&mut info,
+			)?;
+		}
+		Ok(info)
+	}
+
+	/// Retrieves the current limits of the job, including the peak memory
+	/// usage.
+	#[must_use]
+	pub fn limits(&self) -> SysResult<JOBOBJECT_EXTENDED_LIMIT_INFORMATION> {
+		let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+		unsafe {
+			self.hjob.QueryInformationJobObject(
+				co::JOBOBJECTINFOCLASS::ExtendedLimitInformation,
+				&mut info,
+			)?;
+		}
+		Ok(info)
+	}
+
+	/// Replaces all the limits of the job.
+	pub fn set_limits(&self,
+		info: &JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+	) -> SysResult<()>
+	{
+		unsafe {
+			self.hjob.SetInformationJobObject(
+				co::JOBOBJECTINFOCLASS::ExtendedLimitInformation,
+				info,
+			)
+		}
+	}
+
+	/// Sets the maximum number of simultaneously active processes in the job,
+	/// or removes the limit if `None`.
+	pub fn set_active_process_limit(&self, limit: Option<u32>) -> SysResult<()> {
+		self.modify_limits(|info| {
+			let basic = &mut info.BasicLimitInformation;
+			set_flag(&mut basic.LimitFlags, co::JOB_OBJECT_LIMIT::ACTIVE_PROCESS, limit.is_some());
+			basic.ActiveProcessLimit = limit.unwrap_or_default();
+		})
+	}
+
+	/// Sets the maximum committed memory, in bytes, of all processes in the
+	/// job combined, or removes the limit if `None`.
+	pub fn set_job_memory_limit(&self, bytes: Option<usize>) -> SysResult<()> {
+		self.modify_limits(|info| {
+			set_flag(&mut info.BasicLimitInformation.LimitFlags, co::JOB_OBJECT_LIMIT::JOB_MEMORY, bytes.is_some());
+			info.JobMemoryLimit = bytes.unwrap_or_default();
+		})
+	}
+
+	/// Sets whether all processes in the job are terminated when the last job
+	/// handle is closed.
+	pub fn set_kill_on_close(&self, kill_on_close: bool) -> SysResult<()> {
+		self.modify_limits(|info| {
+			set_flag(&mut info.BasicLimitInformation.LimitFlags, co::JOB_OBJECT_LIMIT::KILL_ON_JOB_CLOSE, kill_on_close);
+		})
+	}
+
+	/// Sets the maximum committed memory, in bytes, of each process in the job,
+	/// or removes the limit if `None`.
+	pub fn set_process_memory_limit(&self, bytes: Option<usize>) -> SysResult<()> {
+		self.modify_limits(|info| {
+			set_flag(&mut info.BasicLimitInformation.LimitFlags, co::JOB_OBJECT_LIMIT::PROCESS_MEMORY, bytes.is_some());
+			info.ProcessMemoryLimit = bytes.unwrap_or_default();
+		})
+	}
+
+	fn modify_limits<F>(&self, func: F) -> SysResult<()>
+		where F: FnOnce(&mut JOBOBJECT_EXTENDED_LIMIT_INFORMATION),
+	{
+		let mut info = self.limits()?;
+		func(&mut info);
+		self.set_limits(&info)
+	}
+
+	/// Caps the CPU usage of the job to the given rate, in hundredths of a
+	/// percent – 10000 is 100% of all processors –, or removes the cap if
+	/// `None`.
+	pub fn set_cpu_rate_limit(&self, rate: Option<u32>) -> SysResult<()> {
+		let mut info = JOBOBJECT_CPU_RATE_CONTROL_INFORMATION::default();
+		if let Some(rate) = rate {
+			info.ControlFlags = co::JOB_OBJECT_CPU_RATE_CONTROL::ENABLE
+				| co::JOB_OBJECT_CPU_RATE_CONTROL::HARD_CAP;
+			info.set_CpuRate(rate);
+		}
+		unsafe {
+			self.hjob.SetInformationJobObject(
+				co::JOBOBJECTINFOCLASS::CpuRateControlInformation,
+				&info,
+			)
+		}
+	}
+
+	/// Returns the IDs of the processes currently in the job.
+	#[must_use]
+	pub fn process_ids(&self) -> SysResult<Vec<u32>> {
+		self.hjob.QueryJobProcessIdList()
+	}
+
+	/// Terminates all processes currently in the job, with the given exit code.
+	pub fn terminate(&self, exit_code: u32) -> SysResult<()> {
+		self.hjob.TerminateJobObject(exit_code)
+	}
+
+	/// Waits for the next notification of the job, like a process being created
+	/// or exiting, or a limit being exceeded. Returns `None` if the timeout
+	/// elapses.
+	///
+	/// Notifications are queued from the moment the object is created, so none
+	/// is lost between calls.
+	#[must_use]
+	pub fn next_event(&self,
+		milliseconds: Option<u32>,
+	) -> SysResult<Option<JobEvent>>
+	{
+		let (msg, _, pid) = match self.hiocp.GetQueuedCompletionStatus(milliseconds) {
+			Ok(packet) => packet,
+			Err(co::ERROR::WAIT_TIMEOUT) => return Ok(None),
+			Err(e) => return Err(e),
+		};
+
+		let msg = unsafe { co::JOB_OBJECT_MSG::from_raw(msg) };
+		let process_id = match msg {
+			co::JOB_OBJECT_MSG::END_OF_PROCESS_TIME
+				| co::JOB_OBJECT_MSG::NEW_PROCESS
+				| co::JOB_OBJECT_MSG::EXIT_PROCESS
+				| co::JOB_OBJECT_MSG::ABNORMAL_EXIT_PROCESS
+				| co::JOB_OBJECT_MSG::PROCESS_MEMORY_LIMIT => Some(pid as usize as u32),
+			_ => None,
+		};
+		Ok(Some(JobEvent { msg, process_id }))
+	}
+}
+
+fn set_flag(flags: &mut co::JOB_OBJECT_LIMIT, flag: co::JOB_OBJECT_LIMIT, on: bool) {
+	if on {
+		*flags |= flag;
+	} else {
+		*flags &= !flag;
+	}
+}
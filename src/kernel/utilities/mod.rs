@@ -1,15 +1,35 @@
+mod command;
+mod dir_watcher;
 mod encoding;
 mod file_mapped;
 mod file;
 mod heap_block;
 mod ini;
+mod job_object;
+mod named_pipe;
+mod overlapped;
+mod reg_file;
+mod reg_hive;
+pub(in crate::kernel) mod reg_mapped;
+mod security_descriptor;
+mod service;
 mod w_string;
 
 pub mod path;
 
+pub use command::{ChildProcess, Command, CommandOutput, Stdio};
+pub use dir_watcher::{DirEvent, DirWatcher, FileNotifyExtended, FileNotifyRecord};
 pub use encoding::Encoding;
 pub use file_mapped::FileMapped;
 pub use file::{File, FileAccess};
 pub use heap_block::HeapBlock;
 pub use ini::{Ini, IniEntry, IniSection};
+pub use job_object::{JobEvent, JobObject};
+pub use named_pipe::{PipeConnection, PipeServer};
+pub use overlapped::{IoFuture, OverlappedOp, Reactor};
+pub use reg_file::{RegFile, RegFileKey, RegFileValue};
+pub use reg_hive::{RegHive, RegHiveKey, RegHiveValue};
+pub use reg_mapped::{RegMemKey, RegWatcher};
+pub use security_descriptor::{Ace, Acl, SecurityDescriptor, SelfRelativeSd, Sid};
+pub use service::{ServiceContext, ServiceController, ServiceDispatcher, ServiceInstaller, ServiceStateMachine};
 pub use w_string::WString;
@@ -0,0 +1,440 @@
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// Listens on a [named pipe](https://learn.microsoft.com/en-us/windows/win32/ipc/named-pipes),
+/// accepting any number of clients, each one served by its own
+/// [`PipeConnection`](crate::PipeConnection).
+///
+/// The pipe instances use overlapped I/O, so waiting for a client can time out,
+/// and reads and writes on a connection can happen on different threads.
+///
+/// # Examples
+///
+/// A server which replies to each message on its own thread:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut server = w::PipeServer::new("\\\\.\\pipe\\my-service")
+///     .message_mode(true)
+///     .security("D:(A;;GA;;;SY)(A;;GA;;;BA)(A;;GRGW;;;IU)");
+///
+/// while let Some(conn) = server.accept(None)? {
+///     std::thread::spawn(move || -> w::SysResult<()> {
+///         let msg = conn.read_message()?;
+///         conn.write(&msg)?;
+///         conn.disconnect()
+///     });
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct PipeServer {
+	name: String,
+	message_mode: bool,
+	max_instances: Option<u32>,
+	buffer_size: u32,
+	reject_remote: bool,
+	sddl: Option<String>,
+	sd_buf: Option<Vec<u64>>,
+	first_instance: bool,
+	pending: Option<PendingConnect>,
+}
+
+impl PipeServer {
+	/// Creates a new server for the given pipe name, in the form
+	/// `\\.\pipe\name`. No pipe is created until
+	/// [`accept`](crate::PipeServer::accept) is called.
+	///
+	/// By default the pipe is in byte mode, has unlimited instances, 4096-byte
+	/// buffers, rejects remote clients and uses the default security.
+	#[must_use]
+	pub fn new(name: &str) -> Self {
+		Self {
+			name: name.to_owned(),
+			message_mode: false,
+			max_instances: None,
+			buffer_size: 4096,
+			reject_remote: true,
+			sddl: None,
+			sd_buf: None,
+			first_instance: true,
+			pending: None,
+		}
+	}
+
+	/// Sets the suggested size of the input and output buffers of each
+	/// instance.
+	#[must_use]
+	pub fn buffer_size(mut self, size: u32) -> Self {
+		self.buffer_size = size;
+		self
+	}
+
+	/// Limits the number of simultaneous instances, that is, of connected
+	/// clients plus the one waiting for a connection.
+	#[must_use]
+	pub fn max_instances(mut self, max: u32) -> Self {
+		self.max_instances = Some(max);
+		self
+	}
+
+	/// Sets whether the pipe is written and read as whole messages, instead of
+	/// a stream of bytes.
+	#[must_use]
+	pub fn message_mode(mut self, message_mode: bool) -> Self {
+		self.message_mode = message_mode;
+		self
+	}
+
+	/// Sets whether clients from other machines can connect.
+	#[must_use]
+	pub fn remote_clients(mut self, accept: bool) -> Self {
+		self.reject_remote = !accept;
+		self
+	}
+
+	/// Restricts access to the pipe with a security descriptor, given as an
+	/// [SDDL](https://learn.microsoft.com/en-us/windows/win32/secauthz/security-descriptor-string-format)
+	/// string. An invalid string makes [`accept`](crate::PipeServer::accept)
+	/// fail.
+	#[must_use]
+	pub fn security(mut self, sddl: &str) -> Self {
+		self.sddl = Some(sddl.to_owned());
+		self.sd_buf = None;
+		self
+	}
+
+	/// Waits for the next client to connect, returning its connection. If the
+	/// timeout elapses, returns `None`, and the next call keeps waiting on the
+	/// same pipe instance, so no client is lost.
+	///
+	/// The first instance is created with
+	/// [`co::PIPE_ACCESS::FIRST_PIPE_INSTANCE`](crate::co::PIPE_ACCESS::FIRST_PIPE_INSTANCE),
+	/// so this call fails if another process already owns the pipe name.
+	#[must_use]
+	pub fn accept(&mut self,
+		milliseconds: Option<u32>,
+	) -> SysResult<Option<PipeConnection>>
+	{
+		if self.pending.is_none() {
+			self.pending = Some(self.listen()?);
+		}
+
+		let pending = self.pending.as_mut().unwrap();
+		if !pending.connected {
+			if pending.hevent.WaitForSingleObject(milliseconds)? == co::WAIT::TIMEOUT {
+				return Ok(None);
+			}
+			let res = pending.hpipe.GetOverlappedResult(&pending.ov, false);
+			pending.connected = true; // operation is finished either way
+			if let Err(e) = res {
+				self.pending = None;
+				return Err(e);
+			}
+		}
+
+		let hpipe = unsafe { CloseHandleGuard::new(pending.hpipe.leak()) };
+		self.pending = None;
+		Ok(Some(PipeConnection { hpipe, server: true }))
+	}
+
+	fn listen(&mut self) -> SysResult<PendingConnect> {
+		let mut open_mode = co::PIPE_ACCESS::DUPLEX | co::PIPE_ACCESS::OVERLAPPED;
+		if self.first_instance {
+			open_mode |= co::PIPE_ACCESS::FIRST_PIPE_INSTANCE;
+		}
+
+		let mut pipe_mode = if self.message_mode {
+			co::PIPE::TYPE_MESSAGE | co::PIPE::READMODE_MESSAGE
+		} else {
+			co::PIPE::TYPE_BYTE | co::PIPE::READMODE_BYTE
+		};
+		if self.reject_remote {
+			pipe_mode |= co::PIPE::REJECT_REMOTE_CLIENTS;
+		}
+
+		if let (Some(sddl), None) = (&self.sddl, &self.sd_buf) {
+			self.sd_buf = Some(sd_from_sddl(sddl)?);
+		}
+		let has_sd = self.sd_buf.is_some();
+		let mut sa = SECURITY_ATTRIBUTES::default();
+		if let Some(sd_buf) = self.sd_buf.as_mut() {
+			sa.set_lpSecurityDescriptor(
+				Some(unsafe { &mut *(sd_buf.as_mut_ptr() as *mut SECURITY_DESCRIPTOR) }),
+			);
+		}
+
+		let hpipe = HPIPE::CreateNamedPipe(
+			&self.name,
+			open_mode,
+			pipe_mode,
+			self.max_instances,
+			self.buffer_size,
+			self.buffer_size,
+			None,
+			has_sd.then_some(&mut sa),
+		)?;
+		self.first_instance = false;
+
+		let hevent = HEVENT::CreateEvent(None, true, false, None)?;
+		let mut pending = PendingConnect {
+			ov: Box::new(OVERLAPPED {
+				hEvent: unsafe { hevent.raw_copy() },
+				..Default::default()
+			}),
+			hpipe,
+			hevent,
+			connected: false,
+		};
+
+		match pending.hpipe.ConnectNamedPipe(Some(&mut pending.ov)) {
+			Ok(_) => pending.connected = true, // client was already waiting
+			Err(co::ERROR::IO_PENDING) => {},
+			Err(e) => {
+				pending.connected = true; // nothing pending to cancel
+				return Err(e);
+			},
+		}
+		Ok(pending)
+	}
+}
+
+/// Converts an SDDL string into a self-relative security descriptor, stored
+/// in a buffer at least as large as the
+/// [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR) struct.
+fn sd_from_sddl(sddl: &str) -> SysResult<Vec<u64>> {
+	let hsd = ConvertStringSecurityDescriptorToSecurityDescriptor(sddl)?;
+	let sz = hsd.LocalSize()?;
+	let mut buf = vec![0u64; sz.max(std::mem::size_of::<SECURITY_DESCRIPTOR>()).div_ceil(8)];
+	unsafe {
+		std::ptr::copy_nonoverlapping(hsd.ptr() as *const u8, buf.as_mut_ptr() as *mut u8, sz);
+	}
+	Ok(buf)
+}
+
+/// A pipe instance waiting for a client, whose `OVERLAPPED` must outlive the
+/// pending operation.
+struct PendingConnect {
+	hpipe: CloseHandleGuard<HPIPE>,
+	hevent: CloseHandleGuard<HEVENT>,
+	ov: Box<OVERLAPPED>,
+	connected: bool,
+}
+
+impl Drop for PendingConnect {
+	fn drop(&mut self) {
+		if !self.connected {
+			let _ = self.hpipe.CancelIoEx(Some(&self.ov));
+			let _ = self.hpipe.GetOverlappedResult(&self.ov, true); // wait cancellation
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// One end of a connected named pipe: either a client accepted by a
+/// [`PipeServer`](crate::PipeServer), or a connection to a server opened with
+/// [`PipeConnection::connect`](crate::PipeConnection::connect). The pipe is
+/// closed automatically when the object goes out of scope.
+///
+/// All methods take `&self`, so one thread can read while another writes.
+/// The [`std::io::Read`] implementation reports a closed pipe as the end of
+/// the stream.
+///
+/// # Examples
+///
+/// Sending a request and reading the reply from a message-mode server:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let conn = w::PipeConnection::connect("\\\\.\\pipe\\my-service", Some(5000))?;
+/// conn.set_message_mode()?;
+/// let reply = conn.transact(b"ping")?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct PipeConnection {
+	hpipe: CloseHandleGuard<HPIPE>,
+	server: bool,
+}
+
+impl std::io::Read for PipeConnection {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		match self.read_part(buf) {
+			Ok((bytes_read, _)) => Ok(bytes_read),
+			Err(co::ERROR::BROKEN_PIPE) => Ok(0),
+			Err(e) => Err(std::io::Error::from_raw_os_error(e.raw() as _)),
+		}
+	}
+}
+
+impl std::io::Write for PipeConnection {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		PipeConnection::write(self, buf)
+			.map_err(|e| std::io::Error::from_raw_os_error(e.raw() as _))
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+impl PipeConnection {
+	/// Connects to a server, waiting for a free pipe instance if all are busy.
+	/// If `milliseconds` is `None`, waits forever.
+	///
+	/// The connection starts in byte read mode; call
+	/// [`set_message_mode`](crate::PipeConnection::set_message_mode) to read
+	/// whole messages.
+	#[must_use]
+	pub fn connect(name: &str, milliseconds: Option<u32>) -> SysResult<Self> {
+		let deadline = milliseconds.map(|ms| GetTickCount64() + ms as u64);
+		loop {
+			match HFILE::CreateFile(
+				name,
+				co::GENERIC::READ | co::GENERIC::WRITE,
+				None,
+				None,
+				co::DISPOSITION::OPEN_EXISTING,
+				co::FILE_ATTRIBUTE::NORMAL,
+				Some(co::FILE_FLAG::OVERLAPPED),
+				None,
+				None,
+			) {
+				Ok((mut hfile, _)) => return Ok(Self {
+					hpipe: unsafe { CloseHandleGuard::new(HPIPE::from_ptr(hfile.leak().ptr())) },
+					server: false,
+				}),
+				Err(co::ERROR::PIPE_BUSY) => {},
+				Err(e) => return Err(e),
+			}
+
+			let remaining = match deadline {
+				Some(deadline) => match deadline.checked_sub(GetTickCount64()) {
+					Some(remaining) if remaining > 0 => Some(remaining as u32),
+					_ => return Err(co::ERROR::SEM_TIMEOUT),
+				},
+				None => None,
+			};
+			HPIPE::WaitNamedPipe(name, remaining)?;
+		}
+	}
+
+	/// Returns the underlying pipe handle.
+	#[must_use]
+	pub fn hpipe(&self) -> &HPIPE {
+		&self.hpipe
+	}
+
+	/// Returns the process ID of the client.
+	#[must_use]
+	pub fn client_process_id(&self) -> SysResult<u32> {
+		self.hpipe.GetNamedPipeClientProcessId()
+	}
+
+	/// Returns the process ID of the server.
+	#[must_use]
+	pub fn server_process_id(&self) -> SysResult<u32> {
+		self.hpipe.GetNamedPipeServerProcessId()
+	}
+
+	/// Switches the read mode of a client connection to message mode. The
+	/// server must have been created in message mode.
+	pub fn set_message_mode(&self) -> SysResult<()> {
+		self.hpipe.SetNamedPipeHandleState(Some(co::PIPE::READMODE_MESSAGE), None, None)
+	}
+
+	/// Reads data into the buffer, blocking until some is available, and
+	/// returns the number of bytes read.
+	///
+	/// If the other end closed the pipe, fails with
+	/// [`co::ERROR::BROKEN_PIPE`](crate::co::ERROR::BROKEN_PIPE).
+	pub fn read(&self, buffer: &mut [u8]) -> SysResult<usize> {
+		self.read_part(buffer).map(|(bytes_read, _)| bytes_read)
+	}
+
+	/// Reads a whole message, however large. The pipe must be in message read
+	/// mode.
+	#[must_use]
+	pub fn read_message(&self) -> SysResult<Vec<u8>> {
+		let mut msg = Vec::new();
+		self.read_rest(&mut msg)?;
+		Ok(msg)
+	}
+
+	/// Writes the data, returning the number of bytes written. In message mode,
+	/// the data is sent as a single message.
+	pub fn write(&self, data: &[u8]) -> SysResult<usize> {
+		self.overlapped_io(|ov| self.hpipe.WriteFile(data, Some(ov)))
+			.map(|bytes_written| bytes_written as _)
+	}
+
+	/// Writes a message and reads the whole reply. The pipe must be in message
+	/// read mode.
+	#[must_use]
+	pub fn transact(&self, request: &[u8]) -> SysResult<Vec<u8>> {
+		let mut reply = vec![0; 4096];
+		match self.overlapped_io(|ov| self.hpipe.TransactNamedPipe(request, &mut reply, Some(ov))) {
+			Ok(bytes_read) => {
+				reply.truncate(bytes_read as _);
+				Ok(reply)
+			},
+			Err(co::ERROR::MORE_DATA) => {
+				self.read_rest(&mut reply)?;
+				Ok(reply)
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	/// On a server connection, waits until the client has read all the data,
+	/// then disconnects it. On a client connection, just closes the pipe.
+	pub fn disconnect(self) -> SysResult<()> {
+		if self.server {
+			self.hpipe.FlushFileBuffers()?;
+			self.hpipe.DisconnectNamedPipe()?;
+		}
+		Ok(())
+	}
+
+	/// Reads the remaining parts of the current message, appending them.
+	fn read_rest(&self, msg: &mut Vec<u8>) -> SysResult<()> {
+		let mut chunk = [0u8; 4096];
+		loop {
+			let (bytes_read, more) = self.read_part(&mut chunk)?;
+			msg.extend_from_slice(&chunk[..bytes_read]);
+			if !more {
+				return Ok(());
+			}
+		}
+	}
+
+	/// Returns the number of bytes read, and whether there's more data in the
+	/// current message.
+	fn read_part(&self, buffer: &mut [u8]) -> SysResult<(usize, bool)> {
+		match self.overlapped_io(|ov| self.hpipe.ReadFile(buffer, Some(ov))) {
+			Ok(bytes_read) => Ok((bytes_read as _, false)),
+			Err(co::ERROR::MORE_DATA) => Ok((buffer.len(), true)), // buffer was filled
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Issues an overlapped operation and blocks until it's finished, so the
+	/// `OVERLAPPED` and the buffers outlive it.
+	fn overlapped_io<F>(&self, op: F) -> SysResult<u32>
+		where F: FnOnce(&mut OVERLAPPED) -> SysResult<u32>,
+	{
+		let hevent = HEVENT::CreateEvent(None, true, false, None)?;
+		let mut ov = OVERLAPPED {
+			hEvent: unsafe { hevent.raw_copy() },
+			..Default::default()
+		};
+
+		match op(&mut ov) {
+			Ok(_) | Err(co::ERROR::IO_PENDING) => self.hpipe.GetOverlappedResult(&ov, true),
+			Err(e) => Err(e),
+		}
+	}
+}
@@ -0,0 +1,604 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// Completion key of the handles associated by the reactor.
+const IO_KEY: usize = 0x10;
+/// Completion key of the packets posted to wake up the reactor.
+const WAKE_KEY: usize = 0x20;
+/// A task spawned in the reactor.
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Task ID of the future passed to `block_on`.
+const MAIN_TASK: usize = usize::MAX;
+/// `OVERLAPPED::Internal` value while the operation is in progress.
+const STATUS_PENDING: usize = 0x0000_0103;
+
+/// The memory the kernel writes into during an operation. The `OVERLAPPED`
+/// must be the first field, so a completion packet leads back to its state.
+#[repr(C)]
+struct OpState {
+	ov: OVERLAPPED,
+	buf: Vec<u8>,
+	hfile: HFILE, // not owned, used to retrieve the result and cancel
+}
+
+impl OpState {
+	fn new(handle: &impl Handle, buf: Vec<u8>, offset: u64, hevent: HEVENT) -> Self {
+		let mut ov = OVERLAPPED { hEvent: hevent, ..Default::default() };
+		ov.set_offset64(offset);
+		Self { ov, buf, hfile: unsafe { HFILE::from_ptr(handle.ptr()) } }
+	}
+
+	/// Issues the operation. An error means it failed right away, so nothing
+	/// is pending.
+	fn start<H, F>(&mut self, handle: &H, op: F) -> SysResult<()>
+		where H: Handle,
+			F: FnOnce(&H, &mut [u8], &mut OVERLAPPED) -> SysResult<u32>,
+	{
+		match op(handle, &mut self.buf, &mut self.ov) {
+			Ok(_)
+				| Err(co::ERROR::IO_PENDING)
+				| Err(co::ERROR::MORE_DATA) => Ok(()),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Checks whether the kernel finished the operation, without waiting.
+	fn is_complete(&self) -> bool {
+		// The kernel writes the status behind the compiler's back, so it must be
+		// read from memory every time, like HasOverlappedIoCompleted does.
+		let status = unsafe { std::ptr::read_volatile(&self.ov.Internal) };
+		status != STATUS_PENDING
+	}
+}
+
+fn read_op<H: Handle>(h: &H, buf: &mut [u8], ov: &mut OVERLAPPED) -> SysResult<u32> {
+	unsafe { HFILE::from_ptr(h.ptr()) }.ReadFile(buf, Some(ov))
+}
+
+fn write_op<H: Handle>(h: &H, buf: &mut [u8], ov: &mut OVERLAPPED) -> SysResult<u32> {
+	unsafe { HFILE::from_ptr(h.ptr()) }.WriteFile(buf, Some(ov))
+}
+
+//------------------------------------------------------------------------------
+
+/// An overlapped I/O operation which owns its buffer and its
+/// [`OVERLAPPED`](crate::OVERLAPPED), signaling an event when finished.
+///
+/// The handle must have been opened for overlapped I/O, and it's borrowed while
+/// the operation exists. If the object is dropped before the operation
+/// finishes, the operation is canceled and waited for, so the buffer is never
+/// freed while the kernel may still write into it.
+///
+/// Operations started here don't queue completion packets, even if the handle
+/// is associated to a completion port.
+///
+/// # Examples
+///
+/// Reading the beginning of a file while doing something else:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let (hfile, _) = w::HFILE::CreateFile(
+///     "C:\\Temp\\foo.txt",
+///     co::GENERIC::READ,
+///     Some(co::FILE_SHARE::READ),
+///     None,
+///     co::DISPOSITION::OPEN_EXISTING,
+///     co::FILE_ATTRIBUTE::NORMAL,
+///     Some(co::FILE_FLAG::OVERLAPPED),
+///     None,
+///     None,
+/// )?;
+///
+/// let op = w::OverlappedOp::read(&*hfile, vec![0; 4096], 0)?;
+/// while !op.wait(Some(100))? {
+///     println!("Still reading...");
+/// }
+///
+/// let (res, mut buf) = op.finish();
+/// buf.truncate(res? as _);
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct OverlappedOp<'a> {
+	state: Box<OpState>,
+	hevent: CloseHandleGuard<HEVENT>,
+	finished: bool,
+	_handle: PhantomData<&'a ()>,
+}
+
+impl<'a> Drop for OverlappedOp<'a> {
+	fn drop(&mut self) {
+		if !self.finished {
+			if !self.state.is_complete() {
+				let _ = self.state.hfile.CancelIoEx(Some(&self.state.ov));
+			}
+			let _ = self.state.hfile.GetOverlappedResult(&self.state.ov, true);
+		}
+	}
+}
+
+impl<'a> OverlappedOp<'a> {
+	/// Starts reading into the buffer, at the given file offset. The offset is
+	/// ignored by pipes.
+	#[must_use]
+	pub fn read<H: Handle>(handle: &'a H,
+		buffer: Vec<u8>,
+		offset: u64,
+	) -> SysResult<Self>
+	{
+		Self::start(handle, buffer, offset, read_op)
+	}
+
+	/// Starts writing the data, at the given file offset. The offset is ignored
+	/// by pipes.
+	#[must_use]
+	pub fn write<H: Handle>(handle: &'a H,
+		data: Vec<u8>,
+		offset: u64,
+	) -> SysResult<Self>
+	{
+		Self::start(handle, data, offset, write_op)
+	}
+
+	/// Starts an arbitrary overlapped operation, issued by the `op` closure
+	/// with the owned buffer and `OVERLAPPED`.
+	///
+	/// The closure returns the result of the call; both success and
+	/// [`co::ERROR::IO_PENDING`](crate::co::ERROR::IO_PENDING) mean the
+	/// operation was started. Any other error is returned right away, and the
+	/// buffer is dropped.
+	#[must_use]
+	pub fn start<H, F>(handle: &'a H,
+		buffer: Vec<u8>,
+		offset: u64,
+		op: F,
+	) -> SysResult<Self>
+		where H: Handle,
+			F: FnOnce(&H, &mut [u8], &mut OVERLAPPED) -> SysResult<u32>,
+	{
+		let hevent = HEVENT::CreateEvent(None, true, false, None)?;
+		// The low-order bit prevents the completion from being queued to a port.
+		let tagged = unsafe { HEVENT::from_ptr((hevent.ptr() as usize | 1) as _) };
+		let mut state = Box::new(OpState::new(handle, buffer, offset, tagged));
+
+		state.start(handle, op)?;
+		Ok(Self { state, hevent, finished: false, _handle: PhantomData })
+	}
+
+	/// Returns the event which is signaled when the operation finishes.
+	#[must_use]
+	pub fn hevent(&self) -> &HEVENT {
+		&self.hevent
+	}
+
+	/// Tells whether the operation has finished.
+	#[must_use]
+	pub fn is_complete(&self) -> bool {
+		self.state.is_complete()
+	}
+
+	/// Waits until the operation finishes, returning false if the timeout
+	/// elapsed first.
+	pub fn wait(&self, milliseconds: Option<u32>) -> SysResult<bool> {
+		self.hevent.WaitForSingleObject(milliseconds)
+			.map(|wait| wait != co::WAIT::TIMEOUT)
+	}
+
+	/// Requests the cancellation of the operation, which will then finish with
+	/// [`co::ERROR::OPERATION_ABORTED`](crate::co::ERROR::OPERATION_ABORTED),
+	/// unless it had already finished.
+	pub fn cancel(&self) -> SysResult<()> {
+		match self.state.hfile.CancelIoEx(Some(&self.state.ov)) {
+			Err(co::ERROR::NOT_FOUND) => Ok(()), // already finished
+			res => res,
+		}
+	}
+
+	/// Blocks until the operation finishes, returning its result – the number
+	/// of bytes transferred – and the buffer, which is returned even if the
+	/// operation failed.
+	#[must_use]
+	pub fn finish(mut self) -> (SysResult<u32>, Vec<u8>) {
+		let res = self.state.hfile.GetOverlappedResult(&self.state.ov, true);
+		self.finished = true;
+		(res, std::mem::take(&mut self.state.buf))
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// An operation owned by the reactor.
+#[repr(C)]
+struct ReactorOp {
+	state: OpState, // must be the first field
+	result: Option<SysResult<u32>>,
+	waker: Option<Waker>,
+	orphaned: bool, // its future was dropped, just waiting for the packet
+}
+
+/// Task IDs ready to be polled, shared with the wakers, which can be called
+/// from any thread.
+struct WakeQueue {
+	state: Mutex<WakeState>,
+}
+
+struct WakeState {
+	ready: VecDeque<usize>,
+	sleeping: bool,
+	port: Option<usize>, // None after the reactor is gone
+}
+
+impl WakeQueue {
+	fn push(&self, id: usize) {
+		let mut state = self.state.lock().unwrap();
+		state.ready.push_back(id);
+		if state.sleeping {
+			if let Some(port) = state.port {
+				let hiocp = unsafe { HIOCP::from_ptr(port as _) };
+				let _ = hiocp.PostQueuedCompletionStatus(0, WAKE_KEY, None);
+			}
+			state.sleeping = false;
+		}
+	}
+
+	fn take_ready(&self) -> VecDeque<usize> {
+		std::mem::take(&mut self.state.lock().unwrap().ready)
+	}
+
+	/// Returns true if there's nothing ready, so the reactor can block until
+	/// a packet arrives or a waker posts one.
+	fn prepare_sleep(&self) -> bool {
+		let mut state = self.state.lock().unwrap();
+		state.sleeping = state.ready.is_empty();
+		state.sleeping
+	}
+
+	fn end_sleep(&self) {
+		self.state.lock().unwrap().sleeping = false;
+	}
+}
+
+struct TaskWaker {
+	id: usize,
+	queue: Arc<WakeQueue>,
+}
+
+impl Wake for TaskWaker {
+	fn wake(self: Arc<Self>) {
+		self.queue.push(self.id);
+	}
+
+	fn wake_by_ref(self: &Arc<Self>) {
+		self.queue.push(self.id);
+	}
+}
+
+struct Shared {
+	hiocp: CloseHandleGuard<HIOCP>,
+	ops: RefCell<HashMap<usize, Box<ReactorOp>>>,
+	tasks: RefCell<HashMap<usize, Task>>,
+	next_task: Cell<usize>,
+	queue: Arc<WakeQueue>,
+}
+
+impl Drop for Shared {
+	fn drop(&mut self) {
+		self.queue.state.lock().unwrap().port = None;
+
+		let mut ops = std::mem::take(&mut *self.ops.borrow_mut());
+		for op in ops.values().filter(|op| op.result.is_none()) {
+			let _ = op.state.hfile.CancelIoEx(Some(&op.state.ov));
+		}
+		ops.retain(|_, op| op.result.is_none());
+
+		// Wait for the canceled operations; whatever doesn't finish in time is
+		// leaked, since the kernel may still write into it.
+		let mut entries = [OVERLAPPED_ENTRY::default(); 64];
+		while !ops.is_empty() {
+			match self.hiocp.GetQueuedCompletionStatusEx(&mut entries, Some(1000), false) {
+				Ok(num) => entries[..num].iter()
+					.filter(|entry| entry.lpCompletionKey == IO_KEY)
+					.for_each(|entry| { ops.remove(&(entry.lpOverlapped as usize)); }),
+				Err(_) => break,
+			}
+		}
+		std::mem::forget(ops);
+	}
+}
+
+/// A minimal single-threaded executor which resolves futures as overlapped I/O
+/// operations complete on its [`HIOCP`](crate::HIOCP).
+///
+/// Handles must be opened for overlapped I/O and
+/// [associated](crate::Reactor::associate) to the reactor before their
+/// operations are submitted. Each operation owns its buffer, which is given
+/// back when the [`IoFuture`](crate::IoFuture) resolves. If the future is
+/// dropped earlier, the operation is canceled, and the reactor keeps its
+/// memory until the kernel is done with it.
+///
+/// The reactor can be cloned to be used inside the tasks; spawned tasks only
+/// run during [`block_on`](crate::Reactor::block_on).
+///
+/// # Examples
+///
+/// Copying a file asynchronously:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// fn open(path: &str, write: bool) -> w::SysResult<w::guard::CloseHandleGuard<w::HFILE>> {
+///     Ok(w::HFILE::CreateFile(
+///         path,
+///         if write { co::GENERIC::WRITE } else { co::GENERIC::READ },
+///         None,
+///         None,
+///         if write { co::DISPOSITION::CREATE_ALWAYS } else { co::DISPOSITION::OPEN_EXISTING },
+///         co::FILE_ATTRIBUTE::NORMAL,
+///         Some(co::FILE_FLAG::OVERLAPPED),
+///         None,
+///         None,
+///     )?.0)
+/// }
+///
+/// let reactor = w::Reactor::new()?;
+/// let src = open("C:\\Temp\\src.bin", false)?;
+/// let dest = open("C:\\Temp\\dest.bin", true)?;
+/// reactor.associate(&*src)?;
+/// reactor.associate(&*dest)?;
+///
+/// let copied = reactor.block_on(async {
+///     let (mut offset, mut buf) = (0, vec![0; 64 * 1024]);
+///     loop {
+///         let (res, data) = reactor.read(&*src, buf, offset).await;
+///         let num_read = match res {
+///             Ok(0) | Err(co::ERROR::HANDLE_EOF) => return Ok(offset),
+///             Ok(n) => n as usize,
+///             Err(e) => return Err(e),
+///         };
+///         let (res, data) = reactor.write(&*dest, data[..num_read].to_vec(), offset).await;
+///         offset += res? as u64;
+///         buf = data;
+///         buf.resize(64 * 1024, 0);
+///     }
+/// })??;
+/// println!("{} bytes", copied);
+/// # Ok::<_, co::ERROR>(())
+/// ```
+#[derive(Clone)]
+pub struct Reactor {
+	shared: Rc<Shared>,
+}
+
+impl Reactor {
+	/// Creates a new reactor, with its own completion port.
+	#[must_use]
+	pub fn new() -> SysResult<Self> {
+		let hiocp = HIOCP::CreateIoCompletionPort(1)?;
+		let queue = Arc::new(WakeQueue {
+			state: Mutex::new(WakeState {
+				ready: VecDeque::new(),
+				sleeping: false,
+				port: Some(hiocp.ptr() as _),
+			}),
+		});
+		Ok(Self {
+			shared: Rc::new(Shared {
+				hiocp,
+				ops: RefCell::new(HashMap::new()),
+				tasks: RefCell::new(HashMap::new()),
+				next_task: Cell::new(0),
+				queue,
+			}),
+		})
+	}
+
+	/// Returns the underlying completion port handle.
+	#[must_use]
+	pub fn hiocp(&self) -> &HIOCP {
+		&self.shared.hiocp
+	}
+
+	/// Associates a handle opened for overlapped I/O to the reactor. This must
+	/// be done once per handle, before submitting operations on it.
+	pub fn associate(&self, handle: &impl Handle) -> SysResult<()> {
+		self.shared.hiocp.AssociateIoCompletionPort(handle, IO_KEY)
+	}
+
+	/// Reads into the buffer, at the given file offset. The offset is ignored
+	/// by pipes.
+	#[must_use]
+	pub fn read<'a, H: Handle>(&self,
+		handle: &'a H,
+		buffer: Vec<u8>,
+		offset: u64,
+	) -> IoFuture<'a>
+	{
+		self.submit(handle, buffer, offset, read_op)
+	}
+
+	/// Writes the data, at the given file offset. The offset is ignored by
+	/// pipes.
+	#[must_use]
+	pub fn write<'a, H: Handle>(&self,
+		handle: &'a H,
+		data: Vec<u8>,
+		offset: u64,
+	) -> IoFuture<'a>
+	{
+		self.submit(handle, data, offset, write_op)
+	}
+
+	/// Submits an arbitrary overlapped operation, issued by the `op` closure
+	/// with the owned buffer and `OVERLAPPED`.
+	///
+	/// The closure returns the result of the call; both success and
+	/// [`co::ERROR::IO_PENDING`](crate::co::ERROR::IO_PENDING) mean the
+	/// operation was started. Any other error resolves the future right away.
+	#[must_use]
+	pub fn submit<'a, H, F>(&self,
+		handle: &'a H,
+		buffer: Vec<u8>,
+		offset: u64,
+		op: F,
+	) -> IoFuture<'a>
+		where H: Handle,
+			F: FnOnce(&H, &mut [u8], &mut OVERLAPPED) -> SysResult<u32>,
+	{
+		let mut rop = Box::new(ReactorOp {
+			state: OpState::new(handle, buffer, offset, HEVENT::NULL),
+			result: None,
+			waker: None,
+			orphaned: false,
+		});
+
+		let (key, done) = match rop.state.start(handle, op) {
+			Ok(_) => {
+				let key = &rop.state.ov as *const _ as usize;
+				self.shared.ops.borrow_mut().insert(key, rop);
+				(key, None)
+			},
+			Err(e) => (0, Some((Err(e), std::mem::take(&mut rop.state.buf)))),
+		};
+		IoFuture { shared: self.shared.clone(), key, done, _handle: PhantomData }
+	}
+
+	/// Adds a task to be run by [`block_on`](crate::Reactor::block_on).
+	pub fn spawn<F>(&self, future: F)
+		where F: Future<Output = ()> + 'static,
+	{
+		let id = self.shared.next_task.get();
+		self.shared.next_task.set(id + 1);
+		self.shared.tasks.borrow_mut().insert(id, Box::pin(future));
+		self.shared.queue.push(id);
+	}
+
+	/// Runs the future to completion, along with the spawned tasks, dispatching
+	/// the I/O completions. Fails only if the completion port fails.
+	pub fn block_on<F: Future>(&self, future: F) -> SysResult<F::Output> {
+		let mut future = Box::pin(future);
+		let main_waker = self.waker(MAIN_TASK);
+		self.shared.queue.push(MAIN_TASK);
+
+		loop {
+			for id in self.shared.queue.take_ready() {
+				if id == MAIN_TASK {
+					let mut cx = Context::from_waker(&main_waker);
+					if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+						return Ok(output);
+					}
+				} else {
+					let task = self.shared.tasks.borrow_mut().remove(&id);
+					if let Some(mut task) = task { // may have finished already
+						let waker = self.waker(id);
+						if task.as_mut().poll(&mut Context::from_waker(&waker)).is_pending() {
+							self.shared.tasks.borrow_mut().insert(id, task);
+						}
+					}
+				}
+			}
+
+			let sleep = self.shared.queue.prepare_sleep();
+			self.turn(if sleep { None } else { Some(0) })?;
+		}
+	}
+
+	fn waker(&self, id: usize) -> Waker {
+		Waker::from(Arc::new(TaskWaker { id, queue: self.shared.queue.clone() }))
+	}
+
+	/// Dequeues the completion packets, storing the results and waking the
+	/// futures.
+	fn turn(&self, milliseconds: Option<u32>) -> SysResult<()> {
+		let mut entries = [OVERLAPPED_ENTRY::default(); 64];
+		let res = self.shared.hiocp.GetQueuedCompletionStatusEx(&mut entries, milliseconds, false);
+		self.shared.queue.end_sleep(); // wakers called from now on don't need to post
+		let num = match res {
+			Ok(num) => num,
+			Err(co::ERROR::WAIT_TIMEOUT) => 0,
+			Err(e) => return Err(e),
+		};
+
+		let mut wakers = Vec::new();
+		{
+			let mut ops = self.shared.ops.borrow_mut();
+			for entry in entries[..num].iter().filter(|entry| entry.lpCompletionKey == IO_KEY) {
+				let key = entry.lpOverlapped as usize;
+				let Some(op) = ops.get_mut(&key) else { continue };
+				if op.orphaned {
+					ops.remove(&key);
+				} else {
+					op.result = Some(op.state.hfile.GetOverlappedResult(&op.state.ov, false));
+					wakers.extend(op.waker.take());
+				}
+			}
+		}
+		wakers.into_iter().for_each(|waker| waker.wake());
+		Ok(())
+	}
+}
+
+/// A future returned by [`Reactor`](crate::Reactor) operations, which resolves
+/// to the result of the operation – the number of bytes transferred – and its
+/// buffer, which is returned even if the operation failed.
+///
+/// Dropping the future before it resolves cancels the operation.
+pub struct IoFuture<'a> {
+	shared: Rc<Shared>,
+	key: usize,
+	done: Option<(SysResult<u32>, Vec<u8>)>,
+	_handle: PhantomData<&'a ()>,
+}
+
+impl<'a> Future for IoFuture<'a> {
+	type Output = (SysResult<u32>, Vec<u8>);
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		if let Some(done) = this.done.take() {
+			return Poll::Ready(done);
+		}
+
+		let mut ops = this.shared.ops.borrow_mut();
+		let op = ops.get_mut(&this.key).expect("IoFuture polled after completion");
+		if op.result.is_none() {
+			op.waker = Some(cx.waker().clone());
+			return Poll::Pending;
+		}
+
+		let op = ops.remove(&this.key).unwrap();
+		this.key = 0;
+		let ReactorOp { state, result, .. } = *op;
+		Poll::Ready((result.unwrap(), state.buf))
+	}
+}
+
+impl<'a> Drop for IoFuture<'a> {
+	fn drop(&mut self) {
+		if self.key == 0 {
+			return;
+		}
+		let mut ops = self.shared.ops.borrow_mut();
+		if let Some(op) = ops.get_mut(&self.key) {
+			if op.result.is_some() {
+				ops.remove(&self.key);
+			} else {
+				op.orphaned = true;
+				op.waker = None;
+				let _ = op.state.hfile.CancelIoEx(Some(&op.state.ov));
+			}
+		}
+	}
+}
@@ -0,0 +1,763 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+const HEADER_V5: &str = "Windows Registry Editor Version 5.00";
+const HEADER_V4: &str = "REGEDIT4";
+
+/// High-level abstraction to load, apply, export and serialize the keys and
+/// values of a `.reg` file, as used by `regedit.exe`.
+///
+/// Both the current "Windows Registry Editor Version 5.00" format and the old
+/// `REGEDIT4` one are supported. Parsing and serialization are pure, they
+/// don't touch the registry.
+///
+/// # Examples
+///
+/// Parsing and applying a file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let reg = w::RegFile::parse_str(
+///     "Windows Registry Editor Version 5.00\r\n\
+///     \r\n\
+///     [HKEY_CURRENT_USER\\Software\\My Company]\r\n\
+///     \"Color\"=\"blue\"\r\n\
+///     \"Size\"=dword:0000000a\r\n\
+///     \r\n\
+///     [-HKEY_CURRENT_USER\\Software\\My Company\\Old Stuff]\r\n",
+/// )?;
+///
+/// for key in reg.keys.iter() {
+///     println!("{}", key.path);
+///     for val in key.values.iter() {
+///         println!("  {:?}: {:?}", val.name, val.data);
+///     }
+/// }
+///
+/// reg.apply()?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+///
+/// Exporting a key to a file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let reg = w::RegFile::export("HKEY_CURRENT_USER\\Console")?;
+/// reg.serialize_to_file("C:\\Temp\\console.reg")?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct RegFile {
+	/// Whether the file uses the old `REGEDIT4` format, whose strings are
+	/// ANSI, instead of the "Windows Registry Editor Version 5.00" format,
+	/// whose strings are UTF-16.
+	pub regedit4: bool,
+	/// All the keys of the file, in the order they are applied. They can be
+	/// modified at will.
+	pub keys: Vec<RegFileKey>,
+}
+
+impl RegFile {
+	/// Parses a `RegFile` from a string.
+	///
+	/// Returns [`co::ERROR::BAD_FORMAT`](crate::co::ERROR::BAD_FORMAT) if the
+	/// header is missing, and
+	/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if a line
+	/// can't be parsed.
+	#[must_use]
+	pub fn parse_str(contents: &str) -> SysResult<Self> {
+		let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents); // BOM
+		let mut lines = LogicalLines::new(contents);
+
+		let regedit4 = match lines.next() {
+			Some(line) if line == HEADER_V5 => false,
+			Some(line) if line == HEADER_V4 => true,
+			_ => return Err(co::ERROR::BAD_FORMAT),
+		};
+
+		let mut keys = Vec::<RegFileKey>::default();
+		for line in lines {
+			if line.starts_with('[') {
+				let path = line.strip_suffix(']').ok_or(co::ERROR::INVALID_DATA)?;
+				let (path, delete) = match path[1..].strip_prefix('-') {
+					Some(path) => (path, true),
+					None => (&path[1..], false),
+				};
+				if path.is_empty() {
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				keys.push(RegFileKey {
+					path: path.to_owned(),
+					delete,
+					values: Vec::default(),
+				});
+			} else {
+				let key = keys.last_mut().ok_or(co::ERROR::INVALID_DATA)?; // value before any key
+				key.values.push(RegFileValue::parse(&line, regedit4)?);
+			}
+		}
+
+		Ok(Self { regedit4, keys })
+	}
+
+	/// Parses a `RegFile` from raw bytes with
+	/// [`WString::parse`](crate::WString::parse).
+	#[must_use]
+	pub fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		Self::parse_str(&WString::parse(bytes)?.to_string())
+	}
+
+	/// Parses a `RegFile` directly from a file with
+	/// [`WString::parse`](crate::WString::parse). The file will be
+	/// [mapped in memory](crate::FileMapped) during reading for maximum
+	/// performance.
+	#[must_use]
+	pub fn parse_from_file(reg_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(reg_path, FileAccess::ExistingReadOnly)?;
+		Self::parse_bytes(fin.as_slice())
+	}
+
+	/// Reads the given key, along with all its values and subkeys, from the
+	/// registry.
+	///
+	/// The path must start with the name of a root key, like
+	/// `HKEY_CURRENT_USER` or its abbreviation `HKCU`.
+	#[must_use]
+	pub fn export(key_path: &str) -> SysResult<Self> {
+		let (hroot, sub_key) = split_root(key_path)?;
+		let hkey = hroot.RegOpenKeyEx(
+			if sub_key.is_empty() { None } else { Some(sub_key) },
+			co::REG_OPTION::default(),
+			co::KEY::READ,
+		)?;
+
+		let mut keys = Vec::<RegFileKey>::default();
+		export_key(&hkey, key_path.trim_end_matches('\\').to_owned(), &mut keys)?;
+		Ok(Self { regedit4: false, keys })
+	}
+
+	/// Writes the keys and values into the registry, in order.
+	///
+	/// Keys are created if they don't exist. Keys marked for deletion are
+	/// removed along with all their subkeys, and values marked for deletion
+	/// are removed; it's not an error if they don't exist.
+	pub fn apply(&self) -> SysResult<()> {
+		for key in self.keys.iter() {
+			let (hroot, sub_key) = split_root(&key.path)?;
+			apply_key(&hroot, sub_key, key)?;
+		}
+		Ok(())
+	}
+
+	/// Writes the keys and values into the registry, in order, like
+	/// [`apply`](crate::RegFile::apply), but the key paths are relative to the
+	/// given key, and don't start with the name of a root key.
+	///
+	/// This allows applying a file to a loaded hive, or to a key other than the
+	/// one it was exported from.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let reg = w::RegFile::parse_str(
+	///     "Windows Registry Editor Version 5.00\r\n\
+	///     \r\n\
+	///     [Software\\My Company]\r\n\
+	///     \"Color\"=\"blue\"\r\n",
+	/// )?;
+	///
+	/// let hkey = w::HKEY::CURRENT_USER.RegOpenKeyEx(
+	///     Some("Volatile Environment"),
+	///     co::REG_OPTION::default(),
+	///     co::KEY::ALL_ACCESS,
+	/// )?;
+	///
+	/// reg.apply_to(&hkey)?;
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	pub fn apply_to(&self, hkey: &HKEY) -> SysResult<()> {
+		for key in self.keys.iter() {
+			let sub_key = key.path.trim_matches('\\');
+			apply_key(hkey, sub_key, key)?;
+		}
+		Ok(())
+	}
+
+	/// Returns a reference to the [`RegFileKey`](crate::RegFileKey) with the
+	/// given path, if any.
+	///
+	/// The search is case-insensitive.
+	#[must_use]
+	pub fn find_key(&self, path: &str) -> Option<&RegFileKey> {
+		let path_uc = path.to_uppercase();
+		self.keys.iter()
+			.find(|key| key.path.to_uppercase() == path_uc)
+	}
+
+	/// Serializes the keys and values to a string, with `\r\n` line breaks.
+	///
+	/// Strings containing line breaks or null chars are written as hex data,
+	/// so they can be read back.
+	#[must_use]
+	pub fn serialize_to_str(&self) -> String {
+		let mut buf = String::default();
+		buf.push_str(if self.regedit4 { HEADER_V4 } else { HEADER_V5 });
+		buf.push_str("\r\n\r\n");
+
+		for key in self.keys.iter() {
+			buf.push('[');
+			if key.delete {
+				buf.push('-');
+			}
+			buf.push_str(&key.path);
+			buf.push_str("]\r\n");
+
+			if !key.delete {
+				for val in key.values.iter() {
+					val.serialize(&mut buf, self.regedit4);
+				}
+			}
+			buf.push_str("\r\n");
+		}
+		buf
+	}
+
+	/// Serializes the keys and values to raw bytes: UTF-16 with a BOM, as
+	/// written by `regedit.exe`, or ISO-8859-1 for the `REGEDIT4` format.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> Vec<u8> {
+		let text = self.serialize_to_str();
+		if self.regedit4 {
+			latin1_encode(&text)
+		} else {
+			[0xff, 0xfe].into_iter() // UTF-16 LE BOM
+				.chain(text.encode_utf16().flat_map(|ch| ch.to_le_bytes()))
+				.collect()
+		}
+	}
+
+	/// Serializes the data directly to a file with
+	/// [`serialize_to_bytes`](crate::RegFile::serialize_to_bytes).
+	pub fn serialize_to_file(&self, reg_path: &str) -> SysResult<()> {
+		let fout = File::open(reg_path, FileAccess::OpenOrCreateRW)?;
+		fout.erase_and_write(&self.serialize_to_bytes())?;
+		Ok(())
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A single key of a [`RegFile`](crate::RegFile).
+#[derive(Clone, Default)]
+pub struct RegFileKey {
+	/// Full path of the key, starting with the root key name, like
+	/// `HKEY_CURRENT_USER\Software\My Company`.
+	pub path: String,
+	/// Whether the key, with all its subkeys, is to be deleted, written as
+	/// `[-path]`. If so, the values are ignored.
+	pub delete: bool,
+	/// The values of this key. They can be modified at will.
+	pub values: Vec<RegFileValue>,
+}
+
+impl RegFileKey {
+	/// Returns a reference to the [`RegFileValue`](crate::RegFileValue) with
+	/// the given name – `None` for the default value – if any.
+	///
+	/// The search is case-insensitive.
+	#[must_use]
+	pub fn find_value(&self, name: Option<&str>) -> Option<&RegFileValue> {
+		let name_uc = name.map(|n| n.to_uppercase());
+		self.values.iter()
+			.find(|val| val.name.as_ref().map(|n| n.to_uppercase()) == name_uc)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A single value of a [`RegFileKey`](crate::RegFileKey).
+#[derive(Clone)]
+pub struct RegFileValue {
+	/// Name of the value, or `None` for the default value, written as `@`.
+	pub name: Option<String>,
+	/// Data of the value, or `None` if the value is to be deleted, written as
+	/// `-`.
+	pub data: Option<RegistryValue>,
+}
+
+impl RegFileValue {
+	fn parse(line: &str, regedit4: bool) -> SysResult<Self> {
+		let (name, rest) = if let Some(rest) = line.strip_prefix('@') {
+			(None, rest)
+		} else if line.starts_with('"') {
+			let (name, rest) = parse_quoted(line)?;
+			(Some(name), rest)
+		} else {
+			return Err(co::ERROR::INVALID_DATA);
+		};
+
+		let data = rest.trim_start()
+			.strip_prefix('=')
+			.ok_or(co::ERROR::INVALID_DATA)?
+			.trim();
+
+		let data = if data == "-" {
+			None
+		} else if data.starts_with('"') {
+			let (s, rest) = parse_quoted(data)?;
+			if !rest.trim().is_empty() {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			Some(RegistryValue::Sz(s))
+		} else if let Some(num) = data.strip_prefix("dword:") {
+			if num.is_empty() || num.len() > 8 {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			Some(RegistryValue::Dword(
+				u32::from_str_radix(num, 16).map_err(|_| co::ERROR::INVALID_DATA)?,
+			))
+		} else if let Some(hex) = data.strip_prefix("hex:") {
+			Some(RegistryValue::Binary(parse_hex_bytes(hex)?))
+		} else if let Some(typed) = data.strip_prefix("hex(") {
+			let (reg_type, hex) = typed.split_once("):").ok_or(co::ERROR::INVALID_DATA)?;
+			let reg_type = unsafe {
+				co::REG::from_raw(
+					u32::from_str_radix(reg_type, 16).map_err(|_| co::ERROR::INVALID_DATA)?,
+				)
+			};
+			let bytes = parse_hex_bytes(hex)?;
+			Some(match reg_type {
+				co::REG::SZ | co::REG::EXPAND_SZ | co::REG::MULTI_SZ if regedit4 => {
+					latin1_value(&bytes, reg_type)
+				},
+				_ => unsafe { RegistryValue::from_raw(bytes, reg_type) },
+			})
+		} else {
+			return Err(co::ERROR::INVALID_DATA);
+		};
+
+		Ok(Self { name, data })
+	}
+
+	fn serialize(&self, buf: &mut String, regedit4: bool) {
+		let line_start = buf.len();
+		match &self.name {
+			Some(name) => push_quoted(buf, name),
+			None => buf.push('@'),
+		}
+		buf.push('=');
+
+		match &self.data {
+			None => buf.push('-'),
+			Some(RegistryValue::Sz(s)) if !s.contains(['\r', '\n', '\0']) => {
+				push_quoted(buf, s);
+			},
+			Some(RegistryValue::Dword(n)) => buf.push_str(&format!("dword:{:08x}", n)),
+			Some(RegistryValue::Binary(b)) => {
+				buf.push_str("hex:");
+				push_hex_bytes(buf, line_start, b);
+			},
+			Some(data) => {
+				let bytes = match data {
+					RegistryValue::Sz(_)
+						| RegistryValue::ExpandSz(_)
+						| RegistryValue::MultiSz(_) if regedit4 => latin1_raw(data),
+					_ => data.to_raw(),
+				};
+				buf.push_str(&format!("hex({:x}):", data.reg_type().raw()));
+				push_hex_bytes(buf, line_start, &bytes);
+			},
+		}
+		buf.push_str("\r\n");
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Iterates over the non-empty lines which are not comments, joining the ones
+/// continued with a trailing backslash.
+struct LogicalLines<'a> {
+	lines: std::str::Lines<'a>,
+}
+
+impl<'a> LogicalLines<'a> {
+	fn new(contents: &'a str) -> Self {
+		Self { lines: contents.lines() }
+	}
+}
+
+impl<'a> Iterator for LogicalLines<'a> {
+	type Item = String;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let line = self.lines.next()?.trim();
+			if line.is_empty() || line.starts_with(';') {
+				continue;
+			}
+
+			let mut joined = line.to_owned();
+			while joined.ends_with('\\') {
+				joined.pop();
+				match self.lines.next() {
+					Some(next) => joined.push_str(next.trim()),
+					None => break,
+				}
+			}
+			return Some(joined);
+		}
+	}
+}
+
+/// Parses a quoted string with `\\` and `\"` escapes, returning it and the
+/// remaining text after the closing quote.
+fn parse_quoted(text: &str) -> SysResult<(String, &str)> {
+	let mut s = String::default();
+	let mut chars = text.char_indices().skip(1); // opening quote
+	while let Some((_, ch)) = chars.next() {
+		match ch {
+			'"' => {
+				let rest = chars.next().map_or("", |(idx, _)| &text[idx..]);
+				return Ok((s, rest));
+			},
+			'\\' => match chars.next() {
+				Some((_, esc)) => s.push(esc),
+				None => break,
+			},
+			_ => s.push(ch),
+		}
+	}
+	Err(co::ERROR::INVALID_DATA) // no closing quote
+}
+
+/// Writes a quoted string, escaping backslashes and quotes.
+fn push_quoted(buf: &mut String, s: &str) {
+	buf.push('"');
+	for ch in s.chars() {
+		if ch == '\\' || ch == '"' {
+			buf.push('\\');
+		}
+		buf.push(ch);
+	}
+	buf.push('"');
+}
+
+/// Parses comma-separated hex bytes, like `0a,ff,00`.
+fn parse_hex_bytes(hex: &str) -> SysResult<Vec<u8>> {
+	hex.split(',')
+		.map(|b| b.trim())
+		.filter(|b| !b.is_empty()) // trailing comma
+		.map(|b| {
+			if b.len() > 2 {
+				Err(co::ERROR::INVALID_DATA)
+			} else {
+				u8::from_str_radix(b, 16).map_err(|_| co::ERROR::INVALID_DATA)
+			}
+		})
+		.collect()
+}
+
+/// Writes comma-separated hex bytes, wrapping the line at 80 columns with
+/// continuation backslashes, like `regedit.exe` does.
+fn push_hex_bytes(buf: &mut String, line_start: usize, bytes: &[u8]) {
+	let mut col = buf[line_start..].chars().count();
+	for (idx, b) in bytes.iter().enumerate() {
+		buf.push_str(&format!("{:02x}", b));
+		col += 2;
+		if idx < bytes.len() - 1 {
+			buf.push(',');
+			col += 1;
+			if col > 76 {
+				buf.push_str("\\\r\n  ");
+				col = 2;
+			}
+		}
+	}
+}
+
+/// Decodes ANSI string data of a `REGEDIT4` file as ISO-8859-1.
+fn latin1_value(bytes: &[u8], reg_type: co::REG) -> RegistryValue {
+	let strs = bytes.split(|b| *b == 0)
+		.map(|s| s.iter().map(|b| *b as char).collect::<String>());
+
+	match reg_type {
+		co::REG::MULTI_SZ => RegistryValue::MultiSz(
+			strs.take_while(|s| !s.is_empty()).collect(),
+		),
+		co::REG::EXPAND_SZ => RegistryValue::ExpandSz(strs.take(1).collect()),
+		_ => RegistryValue::Sz(strs.take(1).collect()),
+	}
+}
+
+/// Encodes string data as null-terminated ISO-8859-1, for `REGEDIT4` files.
+fn latin1_raw(data: &RegistryValue) -> Vec<u8> {
+	match data {
+		RegistryValue::MultiSz(v) => {
+			let mut bytes = v.iter()
+				.flat_map(|s| latin1_encode(s).into_iter().chain(std::iter::once(0)))
+				.collect::<Vec<_>>();
+			bytes.push(0);
+			bytes
+		},
+		RegistryValue::Sz(s) | RegistryValue::ExpandSz(s) => {
+			let mut bytes = latin1_encode(s);
+			bytes.push(0);
+			bytes
+		},
+		_ => data.to_raw(),
+	}
+}
+
+/// Encodes a string as ISO-8859-1, replacing the unrepresentable chars.
+fn latin1_encode(s: &str) -> Vec<u8> {
+	s.chars()
+		.map(|ch| if (ch as u32) <= 0xff { ch as u8 } else { b'?' })
+		.collect()
+}
+
+/// Splits a full key path into its predefined root key and the subkey path.
+fn split_root(key_path: &str) -> SysResult<(HKEY, &str)> {
+	let (root, sub_key) = key_path.split_once('\\').unwrap_or((key_path, ""));
+	let hroot = match root.to_uppercase().as_str() {
+		"HKEY_CLASSES_ROOT" | "HKCR" => HKEY::CLASSES_ROOT,
+		"HKEY_CURRENT_USER" | "HKCU" => HKEY::CURRENT_USER,
+		"HKEY_LOCAL_MACHINE" | "HKLM" => HKEY::LOCAL_MACHINE,
+		"HKEY_USERS" | "HKU" => HKEY::USERS,
+		"HKEY_CURRENT_CONFIG" | "HKCC" => HKEY::CURRENT_CONFIG,
+		_ => return Err(co::ERROR::BAD_PATHNAME),
+	};
+	Ok((hroot, sub_key.trim_end_matches('\\')))
+}
+
+/// Creates or deletes a single key, relative to the given parent, and writes
+/// its values.
+fn apply_key(hparent: &HKEY, sub_key: &str, key: &RegFileKey) -> SysResult<()> {
+	if key.delete {
+		if sub_key.is_empty() {
+			return Err(co::ERROR::ACCESS_DENIED); // the parent itself can't be deleted
+		}
+		ignore_not_found(hparent.RegDeleteTree(Some(sub_key)))?;
+		return ignore_not_found(hparent.RegDeleteKey(sub_key));
+	}
+
+	let (hkey, _) = hparent.RegCreateKeyEx(sub_key, None,
+		co::REG_OPTION::default(), co::KEY::WRITE, None)?;
+
+	for val in key.values.iter() {
+		match &val.data {
+			Some(data) => hkey.RegSetValueEx(val.name.as_deref(), data.clone())?,
+			None => ignore_not_found(hkey.RegDeleteValue(val.name.as_deref()))?,
+		}
+	}
+	Ok(())
+}
+
+/// Recursively reads the values and subkeys of a key.
+fn export_key(
+	hkey: &HKEY,
+	path: String,
+	keys: &mut Vec<RegFileKey>,
+) -> SysResult<()>
+{
+	let values = hkey.RegEnumValue()?
+		.map(|name_type| {
+			let (name, _) = name_type?;
+			let name = if name.is_empty() { None } else { Some(name) };
+			let data = hkey.RegQueryValueEx(name.as_deref())?;
+			Ok(RegFileValue { name, data: Some(data) })
+		})
+		.collect::<SysResult<Vec<_>>>()?;
+
+	let sub_keys = hkey.RegEnumKeyEx()?
+		.collect::<SysResult<Vec<_>>>()?;
+
+	keys.push(RegFileKey { path: path.clone(), delete: false, values });
+
+	for sub_key in sub_keys.iter() {
+		let hsub = hkey.RegOpenKeyEx(Some(sub_key),
+			co::REG_OPTION::default(), co::KEY::READ)?;
+		export_key(&hsub, format!("{}\\{}", path, sub_key), keys)?;
+	}
+	Ok(())
+}
+
+/// Treats a "not found" error as success.
+fn ignore_not_found(res: SysResult<()>) -> SysResult<()> {
+	match res {
+		Err(co::ERROR::FILE_NOT_FOUND) => Ok(()),
+		res => res,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn single_value(reg: &RegFile) -> &RegFileValue {
+		assert_eq!(reg.keys.len(), 1);
+		assert_eq!(reg.keys[0].values.len(), 1);
+		&reg.keys[0].values[0]
+	}
+
+	#[test]
+	fn header() {
+		assert!(!RegFile::parse_str("Windows Registry Editor Version 5.00\r\n").unwrap().regedit4);
+		assert!(RegFile::parse_str("REGEDIT4\r\n").unwrap().regedit4);
+		assert_eq!(RegFile::parse_str("[HKCU\\Foo]\r\n").err(), Some(co::ERROR::BAD_FORMAT));
+		assert_eq!(RegFile::parse_str("").err(), Some(co::ERROR::BAD_FORMAT));
+	}
+
+	#[test]
+	fn sz_dword_binary() {
+		let reg = RegFile::parse_str(
+			"Windows Registry Editor Version 5.00\r\n\
+			\r\n\
+			; comment\r\n\
+			[HKEY_CURRENT_USER\\Software\\Foo]\r\n\
+			@=\"default \\\"quoted\\\" C:\\\\dir\"\r\n\
+			\"Size\"=dword:0000000a\r\n\
+			\"Blob\"=hex:01,ff,00\r\n",
+		).unwrap();
+
+		let key = &reg.keys[0];
+		assert_eq!(key.path, "HKEY_CURRENT_USER\\Software\\Foo");
+		assert!(!key.delete);
+		assert_eq!(key.values.len(), 3);
+		assert!(matches!(&key.find_value(None).unwrap().data,
+			Some(RegistryValue::Sz(s)) if s == "default \"quoted\" C:\\dir"));
+		assert!(matches!(key.find_value(Some("size")).unwrap().data,
+			Some(RegistryValue::Dword(10))));
+		assert!(matches!(&key.find_value(Some("Blob")).unwrap().data,
+			Some(RegistryValue::Binary(b)) if b == &[0x01, 0xff, 0x00]));
+	}
+
+	#[test]
+	fn hex_expand_sz() {
+		// "%A%" as UTF-16 LE, null-terminated
+		let reg = RegFile::parse_str(
+			"Windows Registry Editor Version 5.00\r\n\
+			[HKCU\\Foo]\r\n\
+			\"Path\"=hex(2):25,00,41,00,25,00,00,00\r\n",
+		).unwrap();
+		assert!(matches!(&single_value(&reg).data,
+			Some(RegistryValue::ExpandSz(s)) if s == "%A%"));
+	}
+
+	#[test]
+	fn hex_multi_sz() {
+		// "ab", "c", as UTF-16 LE, double null-terminated
+		let reg = RegFile::parse_str(
+			"Windows Registry Editor Version 5.00\r\n\
+			[HKCU\\Foo]\r\n\
+			\"List\"=hex(7):61,00,62,00,00,00,63,00,00,00,00,00\r\n",
+		).unwrap();
+		assert!(matches!(&single_value(&reg).data,
+			Some(RegistryValue::MultiSz(v)) if v == &["ab", "c"]));
+	}
+
+	#[test]
+	fn hex_sz_regedit4() {
+		// REGEDIT4 strings are ANSI
+		let reg = RegFile::parse_str(
+			"REGEDIT4\r\n\
+			[HKCU\\Foo]\r\n\
+			\"Path\"=hex(2):25,41,e9,25,00\r\n\
+			\"List\"=hex(7):61,62,00,63,00,00\r\n",
+		).unwrap();
+		let key = &reg.keys[0];
+		assert!(matches!(&key.values[0].data,
+			Some(RegistryValue::ExpandSz(s)) if s == "%A\u{e9}%"));
+		assert!(matches!(&key.values[1].data,
+			Some(RegistryValue::MultiSz(v)) if v == &["ab", "c"]));
+	}
+
+	#[test]
+	fn line_continuation() {
+		let reg = RegFile::parse_str(
+			"Windows Registry Editor Version 5.00\r\n\
+			[HKCU\\Foo]\r\n\
+			\"Blob\"=hex:01,02,\\\r\n\
+			\x20 03,04,\\\r\n\
+			\x20 05\r\n\
+			\"After\"=dword:00000001\r\n",
+		).unwrap();
+		let key = &reg.keys[0];
+		assert_eq!(key.values.len(), 2);
+		assert!(matches!(&key.values[0].data,
+			Some(RegistryValue::Binary(b)) if b == &[1, 2, 3, 4, 5]));
+		assert!(matches!(key.values[1].data, Some(RegistryValue::Dword(1))));
+	}
+
+	#[test]
+	fn deletions() {
+		let reg = RegFile::parse_str(
+			"Windows Registry Editor Version 5.00\r\n\
+			[-HKCU\\Foo\\Old]\r\n\
+			[HKCU\\Foo]\r\n\
+			\"Gone\"=-\r\n\
+			@=-\r\n",
+		).unwrap();
+		assert_eq!(reg.keys.len(), 2);
+		assert!(reg.keys[0].delete);
+		assert_eq!(reg.keys[0].path, "HKCU\\Foo\\Old");
+		assert!(!reg.keys[1].delete);
+		assert!(reg.keys[1].find_value(Some("Gone")).unwrap().data.is_none());
+		assert!(reg.keys[1].find_value(None).unwrap().data.is_none());
+	}
+
+	#[test]
+	fn invalid_lines() {
+		let parse = |body: &str| RegFile::parse_str(
+			&format!("Windows Registry Editor Version 5.00\r\n{}", body)).err();
+		assert_eq!(parse("\"Orphan\"=dword:1\r\n"), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(parse("[HKCU\\Foo\r\n"), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(parse("[]\r\n"), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(parse("[HKCU]\r\n\"A\"=dword:123456789\r\n"), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(parse("[HKCU]\r\n\"A\"=hex:100\r\n"), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(parse("[HKCU]\r\n\"A\"=\"open\r\n"), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(parse("[HKCU]\r\n\"A\"=hex(zz):00\r\n"), Some(co::ERROR::INVALID_DATA));
+	}
+
+	#[test]
+	fn utf16_bytes() {
+		let text = "Windows Registry Editor Version 5.00\r\n\
+			[HKCU\\Foo]\r\n\
+			\"Name\"=\"\u{e9}t\u{e9}\"\r\n";
+		let bytes = [0xff, 0xfe].into_iter() // UTF-16 LE BOM
+			.chain(text.encode_utf16().flat_map(|ch| ch.to_le_bytes()))
+			.collect::<Vec<_>>();
+
+		let reg = RegFile::parse_bytes(&bytes).unwrap();
+		assert!(!reg.regedit4);
+		assert!(matches!(&single_value(&reg).data,
+			Some(RegistryValue::Sz(s)) if s == "\u{e9}t\u{e9}"));
+	}
+
+	#[test]
+	fn round_trip() {
+		let reg = RegFile::parse_str(
+			"Windows Registry Editor Version 5.00\r\n\
+			[HKCU\\Foo]\r\n\
+			\"Multi\"=hex(7):61,00,00,00,62,00,00,00,00,00\r\n\
+			\"Lines\"=hex(1):61,00,0a,00,62,00,00,00\r\n\
+			\"Big\"=hex:00,01,02,03,04,05,06,07,08,09,0a,0b,0c,0d,0e,0f,10,11,12,13,14,15,16,17,18,19,1a,1b,1c,1d,1e,1f\r\n\
+			[-HKCU\\Bar]\r\n",
+		).unwrap();
+
+		let again = RegFile::parse_bytes(&reg.serialize_to_bytes()).unwrap();
+		assert_eq!(again.serialize_to_str(), reg.serialize_to_str());
+		let key = again.find_key("hkcu\\foo").unwrap();
+		assert!(matches!(&key.values[0].data,
+			Some(RegistryValue::MultiSz(v)) if v == &["a", "b"]));
+		assert!(matches!(&key.values[1].data,
+			Some(RegistryValue::Sz(s)) if s == "a\nb"));
+		assert!(matches!(&key.values[2].data,
+			Some(RegistryValue::Binary(b)) if b.len() == 32 && b[31] == 0x1f));
+		assert!(again.keys[1].delete);
+	}
+}
@@ -0,0 +1,756 @@
+use crate::co;
+use crate::decl::*;
+
+const BASE_BLOCK_SIZE: usize = 4096; // hive bins start right after the base block
+const NO_CELL: u32 = 0xffff_ffff;
+const BIG_DATA_SEGMENT: usize = 16_344;
+
+const KEY_HIVE_ENTRY: u16 = 0x0004;
+const KEY_COMP_NAME: u16 = 0x0020;
+const VALUE_COMP_NAME: u16 = 0x0001;
+
+/// A registry hive file in the `regf` format, like `NTUSER.DAT` or `SYSTEM`,
+/// read directly without the registry API.
+///
+/// Unlike [`HKEY::RegLoadKey`](crate::prelude::kernel_Hkey::RegLoadKey), no
+/// privileges are needed, and the parsing is pure, so it works on any
+/// platform. The keys are read on demand, starting at
+/// [`RegHive::root_key`](crate::RegHive::root_key).
+///
+/// Transaction logs (`.LOG1` and `.LOG2` files) are not replayed, so the data
+/// of a [dirty](crate::RegHive::is_dirty) hive may be slightly outdated.
+///
+/// # Examples
+///
+/// Listing the subkeys and values of a key:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let hive = w::RegHive::parse_from_file("C:\\Temp\\NTUSER.DAT")?;
+/// let key = hive.root_key()?.sub_key("Control Panel\\Desktop")?;
+///
+/// for sub_key in key.sub_keys()? {
+///     println!("{}", sub_key.name());
+/// }
+/// for val in key.values()? {
+///     println!("{:?} = {}", val.name, val.data);
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct RegHive {
+	data: Vec<u8>,
+}
+
+impl RegHive {
+	/// Parses a `RegHive` from the raw bytes of a hive file, which are copied.
+	///
+	/// Only the base block and the signature of the first hive bin are
+	/// validated here; errors in the cells are reported as the keys are read.
+	#[must_use]
+	pub fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		if bytes.len() < BASE_BLOCK_SIZE || &bytes[..4] != b"regf"
+			|| slice_at(bytes, BASE_BLOCK_SIZE, 4).ok() != Some(b"hbin")
+		{
+			return Err(co::ERROR::BAD_FORMAT);
+		}
+		let new_self = Self { data: bytes.to_vec() };
+		if new_self.version().0 != 1 {
+			return Err(co::ERROR::BAD_FORMAT);
+		}
+		Ok(new_self)
+	}
+
+	/// Parses a `RegHive` directly from a file. The file will be
+	/// [mapped in memory](crate::FileMapped) during reading for maximum
+	/// performance.
+	///
+	/// Note that the hives in use by the system are locked, so they can't be
+	/// read this way.
+	#[must_use]
+	pub fn parse_from_file(hive_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(hive_path, FileAccess::ExistingReadOnly)?;
+		Self::parse_bytes(fin.as_slice())
+	}
+
+	/// Returns the major and minor version of the format, like `(1, 5)`.
+	#[must_use]
+	pub fn version(&self) -> (u32, u32) {
+		(u32_at(&self.data, 20).unwrap_or_default(), u32_at(&self.data, 24).unwrap_or_default())
+	}
+
+	/// Returns the time the hive was last written, as stored in the base
+	/// block.
+	#[must_use]
+	pub fn last_written(&self) -> FILETIME {
+		filetime_at(&self.data, 12).unwrap_or_default()
+	}
+
+	/// Returns whether the hive was not properly flushed, that is, its primary
+	/// and secondary sequence numbers differ. In this case, the transaction
+	/// logs contain changes which are not in the file.
+	#[must_use]
+	pub fn is_dirty(&self) -> bool {
+		u32_at(&self.data, 4).ok() != u32_at(&self.data, 8).ok()
+	}
+
+	/// Returns the file name stored in the base block, which is usually the
+	/// last characters of the path the hive was loaded from.
+	#[must_use]
+	pub fn file_name(&self) -> String {
+		utf16_name(&self.data[48..112])
+	}
+
+	/// Returns the root key of the hive.
+	#[must_use]
+	pub fn root_key(&self) -> SysResult<RegHiveKey<'_>> {
+		let key = RegHiveKey::at(self, u32_at(&self.data, 36)?)?;
+		if key.flags() & KEY_HIVE_ENTRY == 0 {
+			return Err(co::ERROR::BADDB);
+		}
+		Ok(key)
+	}
+
+	/// Returns the contents of the cell at the given offset, which is relative
+	/// to the first hive bin.
+	fn cell(&self, offset: u32) -> SysResult<&[u8]> {
+		let pos = BASE_BLOCK_SIZE.checked_add(offset as _).ok_or(co::ERROR::BADDB)?;
+		let size = u32_at(&self.data, pos)? as i32; // negative if allocated
+		let size = size.unsigned_abs() as usize;
+		if size < 4 {
+			return Err(co::ERROR::BADDB);
+		}
+		slice_at(&self.data, pos + 4, size - 4)
+	}
+
+	/// Returns the cell at the given offset, checking its 2-char signature.
+	fn cell_sig(&self, offset: u32, sig: &[u8; 2]) -> SysResult<&[u8]> {
+		let cell = self.cell(offset)?;
+		if cell.len() < 2 || &cell[..2] != sig {
+			return Err(co::ERROR::BADDB);
+		}
+		Ok(cell)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A key of a [`RegHive`](crate::RegHive), which is read on demand.
+#[derive(Clone, Copy)]
+pub struct RegHiveKey<'a> {
+	hive: &'a RegHive,
+	nk: &'a [u8],
+}
+
+impl<'a> RegHiveKey<'a> {
+	fn at(hive: &'a RegHive, offset: u32) -> SysResult<Self> {
+		let nk = hive.cell_sig(offset, b"nk")?;
+		if nk.len() < 76 {
+			return Err(co::ERROR::BADDB);
+		}
+		Ok(Self { hive, nk })
+	}
+
+	fn flags(&self) -> u16 {
+		u16_at(self.nk, 2).unwrap_or_default()
+	}
+
+	/// Returns the name of the key.
+	///
+	/// The root key is usually named after the hive, like `ROOT` or
+	/// `CMI-CreateHive{...}`.
+	#[must_use]
+	pub fn name(&self) -> String {
+		let len = u16_at(self.nk, 72).unwrap_or_default() as usize;
+		let raw = slice_at(self.nk, 76, len).unwrap_or_default();
+		if self.flags() & KEY_COMP_NAME != 0 {
+			latin1_name(raw)
+		} else {
+			utf16_name(raw)
+		}
+	}
+
+	/// Returns the time the key was last written.
+	#[must_use]
+	pub fn last_written(&self) -> FILETIME {
+		filetime_at(self.nk, 4).unwrap_or_default()
+	}
+
+	/// Returns the class name of the key, if any.
+	#[must_use]
+	pub fn class_name(&self) -> SysResult<Option<String>> {
+		let offset = u32_at(self.nk, 48)?;
+		let len = u16_at(self.nk, 74)? as usize;
+		if offset == NO_CELL || len == 0 {
+			return Ok(None);
+		}
+		let cell = self.hive.cell(offset)?;
+		Ok(Some(utf16_name(slice_at(cell, 0, len)?)))
+	}
+
+	/// Returns the parent key, or `None` if this is the root key.
+	#[must_use]
+	pub fn parent(&self) -> SysResult<Option<RegHiveKey<'a>>> {
+		if self.flags() & KEY_HIVE_ENTRY != 0 {
+			return Ok(None);
+		}
+		Self::at(self.hive, u32_at(self.nk, 16)?).map(Some)
+	}
+
+	/// Returns the raw self-relative
+	/// [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR) of the key, stored
+	/// in its security cell, if any.
+	#[must_use]
+	pub fn security_descriptor(&self) -> SysResult<Option<&'a [u8]>> {
+		let offset = u32_at(self.nk, 44)?;
+		if offset == NO_CELL {
+			return Ok(None);
+		}
+		let sk = self.hive.cell_sig(offset, b"sk")?;
+		let len = u32_at(sk, 16)? as usize;
+		slice_at(sk, 20, len).map(Some)
+	}
+
+	/// Returns the subkeys, in the order they're stored, which is sorted by
+	/// their uppercase names.
+	#[must_use]
+	pub fn sub_keys(&self) -> SysResult<Vec<RegHiveKey<'a>>> {
+		let count = u32_at(self.nk, 20)?;
+		let list_offset = u32_at(self.nk, 28)?;
+		let mut offsets = Vec::default(); // count is untrusted, don't preallocate
+		if count > 0 && list_offset != NO_CELL {
+			self.collect_sub_key_offsets(list_offset, true, &mut offsets)?;
+		}
+		offsets.iter()
+			.map(|offset| Self::at(self.hive, *offset))
+			.collect()
+	}
+
+	fn collect_sub_key_offsets(&self,
+		list_offset: u32,
+		allow_index_root: bool,
+		offsets: &mut Vec<u32>,
+	) -> SysResult<()>
+	{
+		let list = self.hive.cell(list_offset)?;
+		let count = u16_at(list, 2)? as usize;
+		let stride = match slice_at(list, 0, 2)? {
+			b"lf" | b"lh" => 8, // offset and name hint or hash
+			b"li" | b"ri" => 4, // offset only
+			_ => return Err(co::ERROR::BADDB),
+		};
+		for i in 0..count {
+			let offset = u32_at(list, 4 + i * stride)?;
+			if &list[..2] == b"ri" {
+				if !allow_index_root { // an index root only points to leaves
+					return Err(co::ERROR::BADDB);
+				}
+				self.collect_sub_key_offsets(offset, false, offsets)?;
+			} else {
+				offsets.push(offset);
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the subkey at the given path, which may contain several levels
+	/// separated by backslashes. Names are case-insensitive.
+	///
+	/// Fails with [`ERROR::FILE_NOT_FOUND`](crate::co::ERROR::FILE_NOT_FOUND)
+	/// if the key doesn't exist.
+	#[must_use]
+	pub fn sub_key(&self, path: &str) -> SysResult<RegHiveKey<'a>> {
+		let mut key = *self;
+		for name in path.split('\\').filter(|name| !name.is_empty()) {
+			let name = name.to_uppercase();
+			key = key.sub_keys()?
+				.into_iter()
+				.find(|sub_key| sub_key.name().to_uppercase() == name)
+				.ok_or(co::ERROR::FILE_NOT_FOUND)?;
+		}
+		Ok(key)
+	}
+
+	/// Returns the values, in the order they're stored.
+	#[must_use]
+	pub fn values(&self) -> SysResult<Vec<RegHiveValue>> {
+		let count = u32_at(self.nk, 36)? as usize;
+		let list_offset = u32_at(self.nk, 40)?;
+		if count == 0 || list_offset == NO_CELL {
+			return Ok(Vec::default());
+		}
+		let list = self.hive.cell(list_offset)?;
+		if count > list.len() / 4 {
+			return Err(co::ERROR::BADDB);
+		}
+		(0..count)
+			.map(|i| self.read_value(u32_at(list, i * 4)?))
+			.collect()
+	}
+
+	/// Returns the data of the value with the given name, or the default value
+	/// if `None`. Names are case-insensitive.
+	///
+	/// Fails with [`ERROR::FILE_NOT_FOUND`](crate::co::ERROR::FILE_NOT_FOUND)
+	/// if the value doesn't exist.
+	#[must_use]
+	pub fn value(&self, name: Option<&str>) -> SysResult<RegistryValue> {
+		let name = name.map(|name| name.to_uppercase());
+		self.values()?
+			.into_iter()
+			.find(|val| val.name.as_ref().map(|n| n.to_uppercase()) == name)
+			.map(|val| val.data)
+			.ok_or(co::ERROR::FILE_NOT_FOUND)
+	}
+
+	fn read_value(&self, offset: u32) -> SysResult<RegHiveValue> {
+		let vk = self.hive.cell_sig(offset, b"vk")?;
+		let name_len = u16_at(vk, 2)? as usize;
+		let data_size = u32_at(vk, 4)?;
+		let data_offset = u32_at(vk, 8)?;
+		let reg_type = unsafe { co::REG::from_raw(u32_at(vk, 12)?) };
+		let flags = u16_at(vk, 16)?;
+
+		let name = if name_len == 0 {
+			None // default value
+		} else {
+			let raw = slice_at(vk, 20, name_len)?;
+			Some(if flags & VALUE_COMP_NAME != 0 { latin1_name(raw) } else { utf16_name(raw) })
+		};
+
+		let data = if data_size & 0x8000_0000 != 0 { // data stored in the offset field itself
+			let len = (data_size & 0x7fff_ffff).min(4) as usize;
+			data_offset.to_le_bytes()[..len].to_vec()
+		} else if data_size == 0 {
+			Vec::default()
+		} else {
+			self.read_data(data_offset, data_size as _)?
+		};
+
+		Ok(RegHiveValue {
+			name,
+			data: unsafe { RegistryValue::from_raw(data, reg_type) },
+		})
+	}
+
+	fn read_data(&self, offset: u32, size: usize) -> SysResult<Vec<u8>> {
+		let cell = self.hive.cell(offset)?;
+		if size <= BIG_DATA_SEGMENT || self.hive.version().1 < 4 || slice_at(cell, 0, 2)? != b"db" {
+			return slice_at(cell, 0, size).map(|data| data.to_vec());
+		}
+
+		let num_segments = u16_at(cell, 2)? as usize; // big data, split in segments
+		let segments = self.hive.cell(u32_at(cell, 4)?)?;
+		let mut data = Vec::with_capacity(size.min(self.hive.data.len())); // size is untrusted
+		for i in 0..num_segments {
+			let segment = self.hive.cell(u32_at(segments, i * 4)?)?;
+			let len = (size - data.len()).min(BIG_DATA_SEGMENT).min(segment.len());
+			data.extend_from_slice(&segment[..len]);
+		}
+		if data.len() != size {
+			return Err(co::ERROR::BADDB);
+		}
+		Ok(data)
+	}
+}
+
+/// A value of a [`RegHiveKey`](crate::RegHiveKey).
+#[derive(Clone)]
+pub struct RegHiveValue {
+	/// Value name; `None` for the default value of the key.
+	pub name: Option<String>,
+	/// Value data.
+	pub data: RegistryValue,
+}
+
+//------------------------------------------------------------------------------
+
+fn slice_at(buf: &[u8], pos: usize, len: usize) -> SysResult<&[u8]> {
+	pos.checked_add(len)
+		.and_then(|end| buf.get(pos..end))
+		.ok_or(co::ERROR::BADDB)
+}
+
+fn u16_at(buf: &[u8], pos: usize) -> SysResult<u16> {
+	slice_at(buf, pos, 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> SysResult<u32> {
+	slice_at(buf, pos, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn filetime_at(buf: &[u8], pos: usize) -> SysResult<FILETIME> {
+	Ok(FILETIME {
+		dwLowDateTime: u32_at(buf, pos)?,
+		dwHighDateTime: u32_at(buf, pos + 4)?,
+	})
+}
+
+fn latin1_name(raw: &[u8]) -> String {
+	raw.iter().map(|ch| *ch as char).collect()
+}
+
+fn utf16_name(raw: &[u8]) -> String {
+	let chars = raw.chunks_exact(2)
+		.map(|ch| u16::from_le_bytes([ch[0], ch[1]]))
+		.take_while(|ch| *ch != 0)
+		.collect::<Vec<_>>();
+	String::from_utf16_lossy(&chars)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Assembles a hive with a single hive bin, after the layout of the
+	/// `regf` format: base block, then the `hbin` header, then the cells,
+	/// whose offsets are relative to the start of the hive bin.
+	struct Builder {
+		bin: Vec<u8>,
+	}
+
+	impl Builder {
+		fn new() -> Self {
+			let mut bin = b"hbin".to_vec();
+			bin.resize(32, 0); // hbin header
+			Self { bin }
+		}
+
+		/// Appends an allocated cell, returning its offset.
+		fn cell(&mut self, contents: &[u8]) -> u32 {
+			let offset = self.bin.len() as u32;
+			let size = (4 + contents.len() + 7) & !7;
+			self.bin.extend_from_slice(&(-(size as i32)).to_le_bytes());
+			self.bin.extend_from_slice(contents);
+			self.bin.resize(offset as usize + size, 0);
+			offset
+		}
+
+		fn finish(mut self, root: u32, minor: u32) -> Vec<u8> {
+			let bin_size = (self.bin.len() + 4095) & !4095;
+			self.bin.resize(bin_size, 0);
+			self.bin[8..12].copy_from_slice(&(bin_size as u32).to_le_bytes());
+
+			let mut base = vec![0u8; BASE_BLOCK_SIZE];
+			base[..4].copy_from_slice(b"regf");
+			put_u32(&mut base, 4, 7); // primary sequence
+			put_u32(&mut base, 8, 7); // secondary sequence
+			put_u32(&mut base, 12, 0x1234_5678); // last written
+			put_u32(&mut base, 16, 0x01d9_0000);
+			put_u32(&mut base, 20, 1); // major
+			put_u32(&mut base, 24, minor);
+			put_u32(&mut base, 36, root);
+			put_u32(&mut base, 40, bin_size as u32);
+			for (i, ch) in "\\NTUSER.DAT".encode_utf16().enumerate() {
+				base[48 + i * 2..50 + i * 2].copy_from_slice(&ch.to_le_bytes());
+			}
+			base.extend_from_slice(&self.bin);
+			base
+		}
+	}
+
+	fn put_u32(buf: &mut [u8], pos: usize, val: u32) {
+		buf[pos..pos + 4].copy_from_slice(&val.to_le_bytes());
+	}
+
+	struct Nk<'a> {
+		flags: u16,
+		parent: u32,
+		sub_keys: (u32, u32), // count, list
+		values: (u32, u32), // count, list
+		sk: u32,
+		class: (u32, u16), // cell, length
+		name: &'a [u8],
+	}
+
+	impl<'a> Nk<'a> {
+		fn new(name: &'a [u8], flags: u16, parent: u32) -> Self {
+			Self {
+				flags, parent,
+				sub_keys: (0, NO_CELL),
+				values: (0, NO_CELL),
+				sk: NO_CELL,
+				class: (NO_CELL, 0),
+				name,
+			}
+		}
+
+		fn bytes(&self) -> Vec<u8> {
+			let mut b = vec![0u8; 76];
+			b[..2].copy_from_slice(b"nk");
+			b[2..4].copy_from_slice(&self.flags.to_le_bytes());
+			put_u32(&mut b, 4, 0xaaaa_0000); // last written
+			put_u32(&mut b, 8, 0x01d8_0000);
+			put_u32(&mut b, 16, self.parent);
+			put_u32(&mut b, 20, self.sub_keys.0);
+			put_u32(&mut b, 28, self.sub_keys.1);
+			put_u32(&mut b, 32, NO_CELL); // volatile subkeys
+			put_u32(&mut b, 36, self.values.0);
+			put_u32(&mut b, 40, self.values.1);
+			put_u32(&mut b, 44, self.sk);
+			put_u32(&mut b, 48, self.class.0);
+			b[72..74].copy_from_slice(&(self.name.len() as u16).to_le_bytes());
+			b[74..76].copy_from_slice(&self.class.1.to_le_bytes());
+			b.extend_from_slice(self.name);
+			b
+		}
+	}
+
+	fn vk(name: &[u8], data_size: u32, data_offset: u32, reg_type: u32) -> Vec<u8> {
+		let mut b = vec![0u8; 20];
+		b[..2].copy_from_slice(b"vk");
+		b[2..4].copy_from_slice(&(name.len() as u16).to_le_bytes());
+		put_u32(&mut b, 4, data_size);
+		put_u32(&mut b, 8, data_offset);
+		put_u32(&mut b, 12, reg_type);
+		b[16..18].copy_from_slice(&VALUE_COMP_NAME.to_le_bytes());
+		b.extend_from_slice(name);
+		b
+	}
+
+	fn list(sig: &[u8; 2], offsets: &[u32], with_hints: bool) -> Vec<u8> {
+		let mut b = sig.to_vec();
+		b.extend_from_slice(&(offsets.len() as u16).to_le_bytes());
+		for offset in offsets.iter() {
+			b.extend_from_slice(&offset.to_le_bytes());
+			if with_hints {
+				b.extend_from_slice(&[0; 4]);
+			}
+		}
+		b
+	}
+
+	fn utf16(s: &str) -> Vec<u8> {
+		s.encode_utf16().flat_map(|ch| ch.to_le_bytes()).collect()
+	}
+
+	const BIG_LEN: usize = BIG_DATA_SEGMENT + 100;
+	const SD: &[u8] = &[1, 0, 0x04, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+	/// ROOT
+	///   Alpha\u{e9} (UTF-16 name, class name)
+	///     Gamma
+	///   Beta (security cell)
+	///
+	/// The root subkeys are in an ri list pointing to an lf and an lh leaf;
+	/// Alpha's are in an li list. ROOT has an inline DWORD, a default string
+	/// and a big binary value split in db segments.
+	fn sample() -> Vec<u8> {
+		sample_with(|_, _| {})
+	}
+
+	/// Same as `sample`, letting the caller patch the cells of the root key
+	/// and its values list before they're written.
+	fn sample_with(patch: impl FnOnce(&mut Nk, &mut Vec<u8>)) -> Vec<u8> {
+		let mut b = Builder::new();
+		let root_off = 32; // first cell, written last
+		b.cell(&[0; 76 + 4]);
+
+		let class = b.cell(&utf16("MyClass"));
+		let mut alpha = Nk::new(&[], 0, root_off);
+		let alpha_name = utf16("Alpha\u{e9}");
+		alpha.name = &alpha_name;
+		alpha.class = (class, 14);
+		let alpha_off = b.cell(&[0; 76 + 12]); // written below, once Gamma is known
+
+		let gamma = b.cell(&Nk::new(b"Gamma", KEY_COMP_NAME, alpha_off).bytes());
+		let li = b.cell(&list(b"li", &[gamma], false));
+		alpha.sub_keys = (1, li);
+		let alpha_bytes = alpha.bytes();
+		b.bin[alpha_off as usize + 4..][..alpha_bytes.len()].copy_from_slice(&alpha_bytes);
+
+		let mut sk = b"sk".to_vec();
+		sk.resize(16, 0);
+		sk.extend_from_slice(&(SD.len() as u32).to_le_bytes());
+		sk.extend_from_slice(SD);
+		let sk = b.cell(&sk);
+		let mut beta = Nk::new(b"Beta", KEY_COMP_NAME, root_off);
+		beta.sk = sk;
+		let beta = b.cell(&beta.bytes());
+
+		let lf = b.cell(&list(b"lf", &[alpha_off], true));
+		let lh = b.cell(&list(b"lh", &[beta], true));
+		let ri = b.cell(&list(b"ri", &[lf, lh], false));
+
+		let count = b.cell(&vk(b"Count", 0x8000_0004, 42, co::REG::DWORD.raw()));
+		let sz_data = b.cell(&utf16("hello\0"));
+		let default = b.cell(&vk(b"", 12, sz_data, co::REG::SZ.raw()));
+		let seg1 = b.cell(&[0xab; BIG_DATA_SEGMENT]);
+		let seg2 = b.cell(&[0xcd; 100]);
+		let segs = b.cell(&[seg1, seg2].iter().flat_map(|off| off.to_le_bytes()).collect::<Vec<_>>());
+		let mut db = b"db".to_vec();
+		db.extend_from_slice(&2u16.to_le_bytes());
+		db.extend_from_slice(&segs.to_le_bytes());
+		let db = b.cell(&db);
+		let big = b.cell(&vk(b"Big", BIG_LEN as _, db, co::REG::BINARY.raw()));
+
+		let mut values = [count, default, big].iter()
+			.flat_map(|off| off.to_le_bytes())
+			.collect::<Vec<_>>();
+		let mut root = Nk::new(b"ROOT", KEY_HIVE_ENTRY | KEY_COMP_NAME, NO_CELL);
+		root.sub_keys = (2, ri);
+		root.values.0 = 3;
+		patch(&mut root, &mut values);
+		root.values.1 = b.cell(&values);
+		let root_bytes = root.bytes();
+		b.bin[root_off as usize + 4..][..root_bytes.len()].copy_from_slice(&root_bytes);
+
+		b.finish(root_off, 5)
+	}
+
+	#[test]
+	fn base_block() {
+		let hive = RegHive::parse_bytes(&sample()).unwrap();
+		assert_eq!(hive.version(), (1, 5));
+		assert!(!hive.is_dirty());
+		assert_eq!(hive.file_name(), "\\NTUSER.DAT");
+		assert_eq!(hive.last_written().dwLowDateTime, 0x1234_5678);
+	}
+
+	#[test]
+	fn keys() {
+		let hive = RegHive::parse_bytes(&sample()).unwrap();
+		let root = hive.root_key().unwrap();
+		assert_eq!(root.name(), "ROOT");
+		assert!(root.parent().unwrap().is_none());
+
+		let names = root.sub_keys().unwrap() // ri -> lf + lh
+			.iter().map(|k| k.name()).collect::<Vec<_>>();
+		assert_eq!(names, ["Alpha\u{e9}", "Beta"]);
+
+		let alpha = root.sub_key("ALPHA\u{c9}").unwrap();
+		assert_eq!(alpha.class_name().unwrap().as_deref(), Some("MyClass"));
+		assert_eq!(alpha.last_written().dwLowDateTime, 0xaaaa_0000);
+		assert!(alpha.security_descriptor().unwrap().is_none());
+
+		let gamma = root.sub_key("\\alpha\u{e9}\\gamma\\").unwrap(); // li
+		assert_eq!(gamma.name(), "Gamma");
+		assert!(gamma.sub_keys().unwrap().is_empty());
+		assert!(gamma.values().unwrap().is_empty());
+		assert_eq!(gamma.parent().unwrap().unwrap().name(), "Alpha\u{e9}");
+		assert!(gamma.class_name().unwrap().is_none());
+
+		let beta = root.sub_key("Beta").unwrap();
+		assert_eq!(beta.security_descriptor().unwrap(), Some(SD));
+
+		assert_eq!(root.sub_key("Alpha\u{e9}\\Nope").err(), Some(co::ERROR::FILE_NOT_FOUND));
+	}
+
+	#[test]
+	fn values() {
+		let hive = RegHive::parse_bytes(&sample()).unwrap();
+		let root = hive.root_key().unwrap();
+		let values = root.values().unwrap();
+		assert_eq!(values.len(), 3);
+		assert_eq!(values[0].name.as_deref(), Some("Count"));
+		assert!(values[1].name.is_none());
+
+		assert!(matches!(root.value(Some("count")).unwrap(), RegistryValue::Dword(42)));
+		assert!(matches!(root.value(None).unwrap(), RegistryValue::Sz(s) if s == "hello"));
+		match root.value(Some("Big")).unwrap() { // db segments
+			RegistryValue::Binary(b) => {
+				assert_eq!(b.len(), BIG_LEN);
+				assert_eq!(b[BIG_DATA_SEGMENT - 1], 0xab);
+				assert_eq!(b[BIG_DATA_SEGMENT], 0xcd);
+			},
+			_ => panic!("not binary"),
+		}
+		assert_eq!(root.value(Some("Nope")).err(), Some(co::ERROR::FILE_NOT_FOUND));
+	}
+
+	#[test]
+	fn bad_base_block() {
+		let data = sample();
+		assert_eq!(RegHive::parse_bytes(&data[..4095]).err(), Some(co::ERROR::BAD_FORMAT));
+		assert_eq!(RegHive::parse_bytes(&data[..4096]).err(), Some(co::ERROR::BAD_FORMAT)); // no hbin
+
+		let mut bad = data.clone();
+		bad[0] = b'x';
+		assert_eq!(RegHive::parse_bytes(&bad).err(), Some(co::ERROR::BAD_FORMAT));
+
+		let mut bad = data.clone();
+		bad[4096] = b'x';
+		assert_eq!(RegHive::parse_bytes(&bad).err(), Some(co::ERROR::BAD_FORMAT));
+
+		let mut bad = data.clone();
+		put_u32(&mut bad, 20, 2); // major version
+		assert_eq!(RegHive::parse_bytes(&bad).err(), Some(co::ERROR::BAD_FORMAT));
+
+		let mut dirty = data;
+		put_u32(&mut dirty, 8, 6);
+		assert!(RegHive::parse_bytes(&dirty).unwrap().is_dirty());
+	}
+
+	#[test]
+	fn out_of_range_offsets() {
+		let mut bad = sample();
+		put_u32(&mut bad, 36, 0x7fff_fff0); // root offset
+		assert_eq!(RegHive::parse_bytes(&bad).unwrap().root_key().err(), Some(co::ERROR::BADDB));
+
+		let mut bad = sample();
+		put_u32(&mut bad, 36, NO_CELL);
+		assert_eq!(RegHive::parse_bytes(&bad).unwrap().root_key().err(), Some(co::ERROR::BADDB));
+
+		let mut bad = sample();
+		put_u32(&mut bad, 4096 + 32, 0x8000_0000); // root cell size, i32::MIN
+		assert_eq!(RegHive::parse_bytes(&bad).unwrap().root_key().err(), Some(co::ERROR::BADDB));
+
+		let bad = sample_with(|root, _| root.sub_keys = (2, 0x00ff_ff00));
+		let hive = RegHive::parse_bytes(&bad).unwrap();
+		assert_eq!(hive.root_key().unwrap().sub_keys().err(), Some(co::ERROR::BADDB));
+
+		let bad = sample_with(|_, values| values[..4].copy_from_slice(&32u32.to_le_bytes())); // nk, not vk
+		let hive = RegHive::parse_bytes(&bad).unwrap();
+		assert_eq!(hive.root_key().unwrap().values().err(), Some(co::ERROR::BADDB));
+	}
+
+	#[test]
+	fn truncated() {
+		let data = sample();
+		let hive = RegHive::parse_bytes(&data[..4096 + 64]).unwrap(); // root cell cut
+		assert_eq!(hive.root_key().err(), Some(co::ERROR::BADDB));
+
+		let hive = RegHive::parse_bytes(&data[..data.len() / 2]).unwrap(); // values cut
+		let root = hive.root_key().unwrap();
+		assert_eq!(root.values().err(), Some(co::ERROR::BADDB));
+	}
+
+	#[test]
+	fn untrusted_counts() {
+		// huge counts must fail, not preallocate
+		let bad = sample_with(|root, _| root.sub_keys.0 = u32::MAX);
+		let hive = RegHive::parse_bytes(&bad).unwrap();
+		assert_eq!(hive.root_key().unwrap().sub_keys().unwrap().len(), 2); // list count rules
+
+		let bad = sample_with(|root, _| root.values.0 = u32::MAX);
+		let hive = RegHive::parse_bytes(&bad).unwrap();
+		assert_eq!(hive.root_key().unwrap().values().err(), Some(co::ERROR::BADDB));
+
+		let mut bad = sample();
+		let hive = RegHive::parse_bytes(&bad).unwrap();
+		let big_vk = {
+			let root = hive.root_key().unwrap();
+			let list = hive.cell(u32_at(root.nk, 40).unwrap()).unwrap();
+			u32_at(list, 8).unwrap()
+		};
+		put_u32(&mut bad, 4096 + big_vk as usize + 4 + 4, 0x7fff_ffff); // data size
+		let hive = RegHive::parse_bytes(&bad).unwrap();
+		assert_eq!(hive.root_key().unwrap().value(Some("Big")).err(), Some(co::ERROR::BADDB));
+	}
+
+	#[test]
+	fn nested_index_root() {
+		let mut b = Builder::new();
+		let root_off = b.cell(&[0; 76 + 4]);
+		let ri2 = b.cell(&list(b"ri", &[root_off], false));
+		let ri = b.cell(&list(b"ri", &[ri2], false));
+		let mut root = Nk::new(b"ROOT", KEY_HIVE_ENTRY | KEY_COMP_NAME, NO_CELL);
+		root.sub_keys = (1, ri);
+		let root_bytes = root.bytes();
+		b.bin[root_off as usize + 4..][..root_bytes.len()].copy_from_slice(&root_bytes);
+
+		let data = b.finish(root_off, 5);
+		let hive = RegHive::parse_bytes(&data).unwrap();
+		assert_eq!(hive.root_key().unwrap().sub_keys().err(), Some(co::ERROR::BADDB));
+	}
+}
@@ -0,0 +1,714 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// Conversion of a Rust type to and from a registry value, used by
+/// [`RegStore::get`](crate::prelude::RegStore::get) and
+/// [`RegStore::set`](crate::prelude::RegStore::set).
+///
+/// The implemented mappings are:
+///
+/// | Rust type | Registry type |
+/// | - | - |
+/// | `u32`, `i32`, `bool` | `REG_DWORD` |
+/// | `u64` | `REG_QWORD` |
+/// | `String` | `REG_SZ` |
+/// | `Vec<String>` | `REG_MULTI_SZ` |
+/// | `Vec<u8>` | `REG_BINARY` |
+///
+/// Reading a missing value fails with
+/// [`ERROR::FILE_NOT_FOUND`](crate::co::ERROR::FILE_NOT_FOUND), unless the
+/// type is wrapped in an `Option`, which maps to `None`. Reading a value of an
+/// incompatible type fails with
+/// [`ERROR::DATATYPE_MISMATCH`](crate::co::ERROR::DATATYPE_MISMATCH).
+pub trait RegConvert: Sized {
+	/// Converts the value read from the registry, which is `None` if the
+	/// value doesn't exist.
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self>;
+
+	/// Converts to the value to be written to the registry; `None` means the
+	/// value will be deleted.
+	#[must_use]
+	fn to_reg(&self) -> Option<RegistryValue>;
+}
+
+impl RegConvert for u32 {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		match val {
+			Some(RegistryValue::Dword(n)) => Ok(n),
+			Some(RegistryValue::DwordBigEndian(n)) => Ok(n),
+			Some(_) => Err(co::ERROR::DATATYPE_MISMATCH),
+			None => Err(co::ERROR::FILE_NOT_FOUND),
+		}
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::Dword(*self))
+	}
+}
+
+impl RegConvert for i32 {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		u32::from_reg(val).map(|n| n as _)
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::Dword(*self as _))
+	}
+}
+
+impl RegConvert for u64 {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		match val {
+			Some(RegistryValue::Qword(n)) => Ok(n),
+			Some(RegistryValue::Dword(n)) => Ok(n as _), // widening is harmless
+			Some(_) => Err(co::ERROR::DATATYPE_MISMATCH),
+			None => Err(co::ERROR::FILE_NOT_FOUND),
+		}
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::Qword(*self))
+	}
+}
+
+impl RegConvert for bool {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		u32::from_reg(val).map(|n| n != 0)
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::Dword(*self as _))
+	}
+}
+
+impl RegConvert for String {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		match val {
+			Some(RegistryValue::Sz(s)) => Ok(s),
+			Some(RegistryValue::ExpandSz(s)) => Ok(s), // not expanded
+			Some(_) => Err(co::ERROR::DATATYPE_MISMATCH),
+			None => Err(co::ERROR::FILE_NOT_FOUND),
+		}
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::Sz(self.clone()))
+	}
+}
+
+impl RegConvert for Vec<String> {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		match val {
+			Some(RegistryValue::MultiSz(v)) => Ok(v),
+			Some(_) => Err(co::ERROR::DATATYPE_MISMATCH),
+			None => Err(co::ERROR::FILE_NOT_FOUND),
+		}
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::MultiSz(self.clone()))
+	}
+}
+
+impl RegConvert for Vec<u8> {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		match val {
+			Some(RegistryValue::Binary(v)) => Ok(v),
+			Some(_) => Err(co::ERROR::DATATYPE_MISMATCH),
+			None => Err(co::ERROR::FILE_NOT_FOUND),
+		}
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		Some(RegistryValue::Binary(self.clone()))
+	}
+}
+
+impl<T: RegConvert> RegConvert for Option<T> {
+	fn from_reg(val: Option<RegistryValue>) -> SysResult<Self> {
+		match val {
+			Some(val) => T::from_reg(Some(val)).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	fn to_reg(&self) -> Option<RegistryValue> {
+		self.as_ref().and_then(|val| val.to_reg())
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A registry key, or a stand-in for it, which can be read and written by a
+/// [`RegMapped`](crate::prelude::RegMapped) type.
+///
+/// This trait is implemented by [`HKEY`](crate::HKEY), which accesses the
+/// actual registry, and by [`RegMemKey`](crate::RegMemKey), an in-memory key
+/// tree.
+///
+/// Value names are case-insensitive. An empty name refers to the default value
+/// of the key.
+pub trait RegStore {
+	/// The type of a subkey.
+	type SubKey: RegStore;
+
+	/// Reads a value, returning `None` if it doesn't exist.
+	fn read_value(&self, name: &str) -> SysResult<Option<RegistryValue>>;
+
+	/// Writes a value; `None` deletes it, if it exists.
+	fn write_value(&self, name: &str, val: Option<RegistryValue>) -> SysResult<()>;
+
+	/// Opens an existing subkey for reading, returning `None` if it doesn't
+	/// exist.
+	fn open_sub_key(&self, name: &str) -> SysResult<Option<Self::SubKey>>;
+
+	/// Opens a subkey for reading and writing, creating it if it doesn't
+	/// exist.
+	fn create_sub_key(&self, name: &str) -> SysResult<Self::SubKey>;
+
+	/// Deletes a subkey and all its contents, if it exists.
+	fn delete_sub_key(&self, name: &str) -> SysResult<()>;
+
+	/// Reads a value and converts it to the given type.
+	fn get<T: RegConvert>(&self, name: &str) -> SysResult<T> {
+		T::from_reg(self.read_value(name)?)
+	}
+
+	/// Converts the given value and writes it.
+	fn set<T: RegConvert>(&self, name: &str, val: &T) -> SysResult<()> {
+		self.write_value(name, val.to_reg())
+	}
+
+	/// Loads a nested type from a subkey, returning `None` if the subkey
+	/// doesn't exist.
+	fn get_sub<T: RegMapped>(&self, name: &str) -> SysResult<Option<T>> {
+		match self.open_sub_key(name)? {
+			Some(sub_key) => T::load(&sub_key).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	/// Stores a nested type into a subkey, which is created if needed; `None`
+	/// deletes the subkey.
+	fn set_sub<T: RegMapped>(&self, name: &str, val: Option<&T>) -> SysResult<()> {
+		match val {
+			Some(val) => val.store(&self.create_sub_key(name)?),
+			None => self.delete_sub_key(name),
+		}
+	}
+}
+
+impl RegStore for HKEY {
+	type SubKey = RegCloseKeyGuard;
+
+	fn read_value(&self, name: &str) -> SysResult<Option<RegistryValue>> {
+		match self.RegQueryValueEx(opt_name(name)) {
+			Ok(val) => Ok(Some(val)),
+			Err(co::ERROR::FILE_NOT_FOUND) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	fn write_value(&self, name: &str, val: Option<RegistryValue>) -> SysResult<()> {
+		match val {
+			Some(val) => self.RegSetValueEx(opt_name(name), val),
+			None => match self.RegDeleteValue(opt_name(name)) {
+				Err(co::ERROR::FILE_NOT_FOUND) => Ok(()),
+				res => res,
+			},
+		}
+	}
+
+	fn open_sub_key(&self, name: &str) -> SysResult<Option<Self::SubKey>> {
+		match self.RegOpenKeyEx(Some(name), co::REG_OPTION::default(), co::KEY::READ) {
+			Ok(hkey) => Ok(Some(hkey)),
+			Err(co::ERROR::FILE_NOT_FOUND) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	fn create_sub_key(&self, name: &str) -> SysResult<Self::SubKey> {
+		self.RegCreateKeyEx(name, None, co::REG_OPTION::default(),
+			co::KEY::READ | co::KEY::WRITE, None)
+			.map(|(hkey, _)| hkey)
+	}
+
+	fn delete_sub_key(&self, name: &str) -> SysResult<()> {
+		match self.RegDeleteTree(Some(name)) {
+			Err(co::ERROR::FILE_NOT_FOUND) => return Ok(()),
+			Err(e) => return Err(e),
+			Ok(_) => {},
+		}
+		self.RegDeleteKey(name) // RegDeleteTree removes only the contents
+	}
+}
+
+impl RegStore for RegCloseKeyGuard {
+	type SubKey = RegCloseKeyGuard;
+
+	fn read_value(&self, name: &str) -> SysResult<Option<RegistryValue>> {
+		(**self).read_value(name)
+	}
+
+	fn write_value(&self, name: &str, val: Option<RegistryValue>) -> SysResult<()> {
+		(**self).write_value(name, val)
+	}
+
+	fn open_sub_key(&self, name: &str) -> SysResult<Option<Self::SubKey>> {
+		(**self).open_sub_key(name)
+	}
+
+	fn create_sub_key(&self, name: &str) -> SysResult<Self::SubKey> {
+		(**self).create_sub_key(name)
+	}
+
+	fn delete_sub_key(&self, name: &str) -> SysResult<()> {
+		(**self).delete_sub_key(name)
+	}
+}
+
+fn opt_name(name: &str) -> Option<&str> {
+	if name.is_empty() { None } else { Some(name) }
+}
+
+//------------------------------------------------------------------------------
+
+/// A type which can be loaded from and stored to a registry key, with its
+/// fields mapped to values and subkeys.
+///
+/// The implementation is usually a sequence of
+/// [`RegStore::get`](crate::prelude::RegStore::get) and
+/// [`RegStore::set`](crate::prelude::RegStore::set) calls, one for each field.
+/// Since the key is generic, the same implementation works with the actual
+/// registry and with a [`RegMemKey`](crate::RegMemKey).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// struct Window {
+///     left: u32,
+///     top: u32,
+/// }
+///
+/// impl RegMapped for Window {
+///     fn load(key: &impl RegStore) -> w::SysResult<Self> {
+///         Ok(Self {
+///             left: key.get("Left")?,
+///             top: key.get("Top")?,
+///         })
+///     }
+///
+///     fn store(&self, key: &impl RegStore) -> w::SysResult<()> {
+///         key.set("Left", &self.left)?;
+///         key.set("Top", &self.top)
+///     }
+/// }
+///
+/// struct Config {
+///     name: String,
+///     recent: Vec<String>,
+///     theme: Option<String>,
+///     window: Option<Window>,
+/// }
+///
+/// impl RegMapped for Config {
+///     fn load(key: &impl RegStore) -> w::SysResult<Self> {
+///         Ok(Self {
+///             name: key.get("Name")?,
+///             recent: key.get("Recent")?,
+///             theme: key.get("Theme")?,
+///             window: key.get_sub("Window")?,
+///         })
+///     }
+///
+///     fn store(&self, key: &impl RegStore) -> w::SysResult<()> {
+///         key.set("Name", &self.name)?;
+///         key.set("Recent", &self.recent)?;
+///         key.set("Theme", &self.theme)?;
+///         key.set_sub("Window", self.window.as_ref())
+///     }
+/// }
+///
+/// let hkey = w::HKEY::CURRENT_USER.RegOpenKeyEx(
+///     Some("Software\\My Company\\My App"),
+///     co::REG_OPTION::default(),
+///     co::KEY::READ | co::KEY::WRITE,
+/// )?;
+///
+/// let mut config = Config::load(&hkey)?;
+/// config.recent.push("C:\\Temp\\foo.txt".to_owned());
+/// config.store(&hkey)?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub trait RegMapped: Sized {
+	/// Reads the fields from the key.
+	fn load(key: &impl RegStore) -> SysResult<Self>;
+
+	/// Writes the fields to the key.
+	fn store(&self, key: &impl RegStore) -> SysResult<()>;
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Default)]
+struct MemNode {
+	values: BTreeMap<String, (String, RegistryValue)>, // uppercase name => (name, value)
+	sub_keys: BTreeMap<String, (String, RegMemKey)>,
+}
+
+/// An in-memory registry key tree, which implements
+/// [`RegStore`](crate::prelude::RegStore) without touching the actual
+/// registry.
+///
+/// Cloning a `RegMemKey` doesn't copy its contents: the clone refers to the
+/// same key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let root = w::RegMemKey::new();
+/// root.set("Count", &10u32)?;
+/// root.create_sub_key("Child")?
+///     .set("Names", &vec!["a".to_owned(), "b".to_owned()])?;
+///
+/// assert_eq!(root.get::<u32>("count")?, 10);
+/// assert_eq!(root.sub_key_names(), ["Child"]);
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct RegMemKey(Rc<RefCell<MemNode>>);
+
+impl RegStore for RegMemKey {
+	type SubKey = RegMemKey;
+
+	fn read_value(&self, name: &str) -> SysResult<Option<RegistryValue>> {
+		Ok(
+			self.0.borrow()
+				.values.get(&name.to_uppercase())
+				.map(|(_, val)| val.clone()),
+		)
+	}
+
+	fn write_value(&self, name: &str, val: Option<RegistryValue>) -> SysResult<()> {
+		let mut node = self.0.borrow_mut();
+		match val {
+			Some(val) => {
+				node.values.entry(name.to_uppercase())
+					.or_insert_with(|| (name.to_owned(), RegistryValue::None))
+					.1 = val; // keep the name as first written
+			},
+			None => {
+				node.values.remove(&name.to_uppercase());
+			},
+		}
+		Ok(())
+	}
+
+	fn open_sub_key(&self, name: &str) -> SysResult<Option<Self::SubKey>> {
+		Ok(
+			self.0.borrow()
+				.sub_keys.get(&name.to_uppercase())
+				.map(|(_, key)| key.clone()),
+		)
+	}
+
+	fn create_sub_key(&self, name: &str) -> SysResult<Self::SubKey> {
+		Ok(
+			self.0.borrow_mut()
+				.sub_keys.entry(name.to_uppercase())
+				.or_insert_with(|| (name.to_owned(), RegMemKey::default()))
+				.1.clone(),
+		)
+	}
+
+	fn delete_sub_key(&self, name: &str) -> SysResult<()> {
+		self.0.borrow_mut().sub_keys.remove(&name.to_uppercase());
+		Ok(())
+	}
+}
+
+impl RegMemKey {
+	/// Creates a new, empty key.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the names of the values, as they were first written, sorted
+	/// case-insensitively.
+	#[must_use]
+	pub fn value_names(&self) -> Vec<String> {
+		self.0.borrow().values.values().map(|(name, _)| name.clone()).collect()
+	}
+
+	/// Returns the names of the subkeys, as they were created, sorted
+	/// case-insensitively.
+	#[must_use]
+	pub fn sub_key_names(&self) -> Vec<String> {
+		self.0.borrow().sub_keys.values().map(|(name, _)| name.clone()).collect()
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Watches a registry key for changes, with
+/// [`HKEY::RegNotifyChangeKeyValue`](crate::prelude::kernel_Hkey::RegNotifyChangeKeyValue),
+/// so a [`RegMapped`](crate::prelude::RegMapped) type can be reloaded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let watcher = w::RegWatcher::new(
+///     &w::HKEY::CURRENT_USER, "Software\\My Company\\My App", true)?;
+///
+/// loop {
+///     if watcher.wait(None)? {
+///         let count: u32 = watcher.hkey().get("Count")?; // or watcher.load()
+///         println!("Count is now {}", count);
+///     }
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct RegWatcher {
+	hkey: RegCloseKeyGuard,
+	hevent: CloseHandleGuard<HEVENT>,
+	watch_subtree: bool,
+}
+
+impl RegWatcher {
+	/// Opens the key and starts watching it. If `watch_subtree` is `true`,
+	/// changes to the subkeys are also reported.
+	pub fn new(
+		hkey_parent: &HKEY,
+		sub_key: &str,
+		watch_subtree: bool,
+	) -> SysResult<Self>
+	{
+		let hkey = hkey_parent.RegOpenKeyEx(
+			Some(sub_key), co::REG_OPTION::default(), co::KEY::READ)?; // READ includes NOTIFY
+		let hevent = HEVENT::CreateEvent(None, false, false, None)?;
+		let new_self = Self { hkey, hevent, watch_subtree };
+		new_self.rearm()?;
+		Ok(new_self)
+	}
+
+	/// Returns the watched key, opened with
+	/// [`KEY::READ`](crate::co::KEY::READ) access.
+	#[must_use]
+	pub const fn hkey(&self) -> &RegCloseKeyGuard {
+		&self.hkey
+	}
+
+	/// Returns the auto-reset event which is signaled when a change occurs,
+	/// so it can be waited along with other objects.
+	///
+	/// After the event is signaled,
+	/// [`RegWatcher::rearm`](crate::RegWatcher::rearm) must be called to
+	/// receive further notifications.
+	#[must_use]
+	pub fn hevent(&self) -> &HEVENT {
+		&self.hevent
+	}
+
+	/// Blocks until a change occurs or the timeout expires, returning `true`
+	/// if a change occurred. In this case, the watch is automatically renewed.
+	pub fn wait(&self, milliseconds: Option<u32>) -> SysResult<bool> {
+		match self.hevent.WaitForSingleObject(milliseconds)? {
+			co::WAIT::OBJECT_0 => {
+				self.rearm()?;
+				Ok(true)
+			},
+			_ => Ok(false),
+		}
+	}
+
+	/// Renews the watch, after the event returned by
+	/// [`RegWatcher::hevent`](crate::RegWatcher::hevent) has been signaled.
+	pub fn rearm(&self) -> SysResult<()> {
+		self.hkey.RegNotifyChangeKeyValue(
+			self.watch_subtree,
+			co::REG_NOTIFY::CHANGE_NAME
+				| co::REG_NOTIFY::CHANGE_LAST_SET
+				| co::REG_NOTIFY::THREAD_AGNOSTIC, // not canceled if the thread exits
+			Some(&self.hevent),
+			true,
+		)
+	}
+
+	/// Loads the given type from the watched key.
+	pub fn load<T: RegMapped>(&self) -> SysResult<T> {
+		T::load(&self.hkey)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug, PartialEq)]
+	struct Window {
+		left: u32,
+		top: i32,
+	}
+
+	impl RegMapped for Window {
+		fn load(key: &impl RegStore) -> SysResult<Self> {
+			Ok(Self {
+				left: key.get("Left")?,
+				top: key.get("Top")?,
+			})
+		}
+
+		fn store(&self, key: &impl RegStore) -> SysResult<()> {
+			key.set("Left", &self.left)?;
+			key.set("Top", &self.top)
+		}
+	}
+
+	#[derive(Debug, PartialEq)]
+	struct Config {
+		name: String,
+		size: u64,
+		enabled: bool,
+		recent: Vec<String>,
+		blob: Vec<u8>,
+		theme: Option<String>,
+		window: Option<Window>,
+	}
+
+	impl RegMapped for Config {
+		fn load(key: &impl RegStore) -> SysResult<Self> {
+			Ok(Self {
+				name: key.get("Name")?,
+				size: key.get("Size")?,
+				enabled: key.get("Enabled")?,
+				recent: key.get("Recent")?,
+				blob: key.get("Blob")?,
+				theme: key.get("Theme")?,
+				window: key.get_sub("Window")?,
+			})
+		}
+
+		fn store(&self, key: &impl RegStore) -> SysResult<()> {
+			key.set("Name", &self.name)?;
+			key.set("Size", &self.size)?;
+			key.set("Enabled", &self.enabled)?;
+			key.set("Recent", &self.recent)?;
+			key.set("Blob", &self.blob)?;
+			key.set("Theme", &self.theme)?;
+			key.set_sub("Window", self.window.as_ref())
+		}
+	}
+
+	fn config() -> Config {
+		Config {
+			name: "foo".to_owned(),
+			size: 0x1_0000_0000,
+			enabled: true,
+			recent: vec!["a.txt".to_owned(), "b.txt".to_owned()],
+			blob: vec![0, 1, 255],
+			theme: Some("dark".to_owned()),
+			window: Some(Window { left: 10, top: -20 }),
+		}
+	}
+
+	#[test]
+	fn round_trip() {
+		let root = RegMemKey::new();
+		let cfg = config();
+		cfg.store(&root).unwrap();
+		assert_eq!(Config::load(&root).unwrap(), cfg);
+
+		assert_eq!(root.value_names(),
+			["Blob", "Enabled", "Name", "Recent", "Size", "Theme"]);
+		assert_eq!(root.sub_key_names(), ["Window"]);
+		assert!(matches!(root.read_value("TOP").unwrap(), None));
+		assert!(matches!(root.read_value("size").unwrap(), Some(RegistryValue::Qword(_))));
+	}
+
+	#[test]
+	fn nested_keys() {
+		let root = RegMemKey::new();
+		let mut cfg = config();
+		cfg.store(&root).unwrap();
+
+		let win = root.open_sub_key("window").unwrap().unwrap(); // case-insensitive
+		assert_eq!(win.get::<u32>("Left").unwrap(), 10);
+		assert!(matches!(win.read_value("Top").unwrap(), Some(RegistryValue::Dword(0xffff_ffec))));
+
+		win.set("Left", &30u32).unwrap(); // same key, not a copy
+		assert_eq!(Config::load(&root).unwrap().window, Some(Window { left: 30, top: -20 }));
+
+		cfg.window = None; // deletes the subkey
+		cfg.store(&root).unwrap();
+		assert!(root.sub_key_names().is_empty());
+		assert!(root.open_sub_key("Window").unwrap().is_none());
+		assert_eq!(Config::load(&root).unwrap(), cfg);
+	}
+
+	#[test]
+	fn missing_values() {
+		let root = RegMemKey::new();
+		assert_eq!(Config::load(&root).err(), Some(co::ERROR::FILE_NOT_FOUND));
+		assert_eq!(root.get::<Option<u32>>("Nothing").unwrap(), None);
+		assert_eq!(root.get_sub::<Window>("Nothing").unwrap(), None);
+
+		let mut cfg = config();
+		cfg.theme = None; // deletes the value
+		cfg.store(&root).unwrap();
+		assert!(!root.value_names().contains(&"Theme".to_owned()));
+		assert_eq!(Config::load(&root).unwrap(), cfg);
+
+		root.write_value("Name", None).unwrap();
+		assert_eq!(Config::load(&root).err(), Some(co::ERROR::FILE_NOT_FOUND));
+	}
+
+	#[test]
+	fn type_mismatch() {
+		let root = RegMemKey::new();
+		root.set("Str", &"x".to_owned()).unwrap();
+		root.set("Num", &5u32).unwrap();
+		root.write_value("Exp", Some(RegistryValue::ExpandSz("%A%".to_owned()))).unwrap();
+		root.write_value("Be", Some(RegistryValue::DwordBigEndian(7))).unwrap();
+
+		assert_eq!(root.get::<u32>("Str").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+		assert_eq!(root.get::<Option<u32>>("Str").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+		assert_eq!(root.get::<String>("Num").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+		assert_eq!(root.get::<Vec<String>>("Str").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+		assert_eq!(root.get::<Vec<u8>>("Num").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+		assert_eq!(root.get::<u32>("Be").unwrap(), 7);
+		assert_eq!(root.get::<u64>("Num").unwrap(), 5); // widening
+		assert_eq!(root.get::<String>("Exp").unwrap(), "%A%"); // not expanded
+
+		root.set("Window", &1u32).unwrap(); // a value, not a subkey
+		assert_eq!(root.get_sub::<Window>("Window").unwrap(), None);
+		root.create_sub_key("Window").unwrap().set("Left", &"x".to_owned()).unwrap();
+		assert_eq!(root.get_sub::<Window>("Window").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+	}
+
+	#[test]
+	fn names_keep_case() {
+		let root = RegMemKey::new();
+		root.set("MixedCase", &1u32).unwrap();
+		root.set("MIXEDCASE", &2u32).unwrap(); // overwrites, keeps first name
+		assert_eq!(root.value_names(), ["MixedCase"]);
+		assert_eq!(root.get::<u32>("mixedcase").unwrap(), 2);
+
+		root.create_sub_key("Sub").unwrap();
+		root.create_sub_key("SUB").unwrap().set("A", &true).unwrap();
+		assert_eq!(root.sub_key_names(), ["Sub"]);
+		assert!(root.open_sub_key("sub").unwrap().unwrap().get::<bool>("a").unwrap());
+	}
+}
@@ -0,0 +1,1374 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+const SID_REVISION: u8 = 1;
+const SID_MAX_SUB_AUTHORITIES: usize = 15;
+const ACL_REVISION: u8 = 2;
+const ACL_REVISION_DS: u8 = 4; // needed when the ACL has object ACEs
+const SD_REVISION: u8 = 1;
+const SD_HEADER_SIZE: usize = 20;
+
+const ACE_OBJECT_TYPE_PRESENT: u32 = 0x1;
+const ACE_INHERITED_OBJECT_TYPE_PRESENT: u32 = 0x2;
+
+/// SDDL aliases of the well-known SIDs which don't depend on a domain, as
+/// (alias, authority, sub-authorities).
+const SID_ALIASES: &[(&str, u64, &[u32])] = &[
+	("AA", 5, &[32, 579]), // access control assistance operators
+	("AC", 15, &[2, 1]), // all application packages
+	("AN", 5, &[7]), // anonymous logon
+	("AO", 5, &[32, 548]), // account operators
+	("AS", 18, &[1]), // authentication authority asserted identity
+	("AU", 5, &[11]), // authenticated users
+	("BA", 5, &[32, 544]), // built-in administrators
+	("BG", 5, &[32, 546]), // built-in guests
+	("BO", 5, &[32, 551]), // backup operators
+	("BU", 5, &[32, 545]), // built-in users
+	("CD", 5, &[32, 574]), // certificate service DCOM access
+	("CG", 3, &[1]), // creator group
+	("CO", 3, &[0]), // creator owner
+	("CY", 5, &[32, 569]), // cryptographic operators
+	("ED", 5, &[9]), // enterprise domain controllers
+	("ER", 5, &[32, 573]), // event log readers
+	("HA", 5, &[32, 578]), // Hyper-V administrators
+	("HI", 16, &[12288]), // high integrity level
+	("IS", 5, &[32, 568]), // IIS users
+	("IU", 5, &[4]), // interactive users
+	("LS", 5, &[19]), // local service
+	("LU", 5, &[32, 559]), // performance log users
+	("LW", 16, &[4096]), // low integrity level
+	("ME", 16, &[8192]), // medium integrity level
+	("MP", 16, &[8448]), // medium plus integrity level
+	("MU", 5, &[32, 558]), // performance monitor users
+	("NO", 5, &[32, 556]), // network configuration operators
+	("NS", 5, &[20]), // network service
+	("NU", 5, &[2]), // network logon users
+	("OW", 3, &[4]), // owner rights
+	("PO", 5, &[32, 550]), // printer operators
+	("PS", 5, &[10]), // principal self
+	("PU", 5, &[32, 547]), // power users
+	("RA", 5, &[32, 575]), // RDS remote access servers
+	("RC", 5, &[12]), // restricted code
+	("RD", 5, &[32, 555]), // remote desktop users
+	("RE", 5, &[32, 552]), // replicator
+	("RM", 5, &[32, 580]), // remote management users
+	("RU", 5, &[32, 554]), // pre-Windows 2000 compatible access
+	("SI", 16, &[16384]), // system integrity level
+	("SO", 5, &[32, 549]), // server operators
+	("SS", 18, &[2]), // service asserted identity
+	("SU", 5, &[6]), // service logon users
+	("SY", 5, &[18]), // local system
+	("UD", 5, &[84, 0, 0, 0, 0, 0]), // user-mode drivers
+	("WD", 1, &[0]), // everyone
+	("WR", 5, &[33]), // write restricted code
+];
+
+/// SDDL aliases of the domain-relative SIDs, which can't be resolved without
+/// the domain SID.
+const SID_DOMAIN_ALIASES: &[&str] = &["AP", "CA", "CN", "DA", "DC", "DD", "DG",
+	"DU", "EA", "EK", "KA", "LA", "LG", "PA", "RO", "RS", "SA"];
+
+/// SDDL aliases of masks which are only written when the mask matches exactly.
+const RIGHTS_EXACT: &[(&str, u32)] = &[
+	("FA", 0x001f_01ff), ("FR", 0x0012_0089), ("FW", 0x0012_0116), ("FX", 0x0012_00a0),
+	("KA", 0x000f_003f), ("KR", 0x0002_0019), ("KW", 0x0002_0006), ("KX", 0x0002_0019),
+];
+
+/// SDDL aliases of single access rights, in the order they're written.
+const RIGHTS_BITS: &[(&str, u32)] = &[
+	("GA", 0x1000_0000), ("GR", 0x8000_0000), ("GW", 0x4000_0000), ("GX", 0x2000_0000),
+	("CC", 0x0000_0001), ("DC", 0x0000_0002), ("LC", 0x0000_0004), ("SW", 0x0000_0008),
+	("RP", 0x0000_0010), ("WP", 0x0000_0020), ("DT", 0x0000_0040), ("LO", 0x0000_0080),
+	("CR", 0x0000_0100), ("SD", 0x0001_0000), ("RC", 0x0002_0000), ("WD", 0x0004_0000),
+	("WO", 0x0008_0000),
+];
+
+/// SDDL aliases of the mandatory label policies, which overlap other rights.
+const RIGHTS_LABEL: &[(&str, u32)] = &[
+	("NW", 0x1), ("NR", 0x2), ("NX", 0x4),
+];
+
+const ACE_FLAGS: &[(&str, co::ACE_FLAG)] = &[
+	("OI", co::ACE_FLAG::OBJECT_INHERIT),
+	("CI", co::ACE_FLAG::CONTAINER_INHERIT),
+	("NP", co::ACE_FLAG::NO_PROPAGATE_INHERIT),
+	("IO", co::ACE_FLAG::INHERIT_ONLY),
+	("ID", co::ACE_FLAG::INHERITED),
+	("SA", co::ACE_FLAG::SUCCESSFUL_ACCESS),
+	("FA", co::ACE_FLAG::FAILED_ACCESS),
+];
+
+const ACE_TYPES: &[(&str, co::ACE_TYPE)] = &[
+	("A", co::ACE_TYPE::ACCESS_ALLOWED),
+	("D", co::ACE_TYPE::ACCESS_DENIED),
+	("AU", co::ACE_TYPE::SYSTEM_AUDIT),
+	("AL", co::ACE_TYPE::SYSTEM_ALARM),
+	("OA", co::ACE_TYPE::ACCESS_ALLOWED_OBJECT),
+	("OD", co::ACE_TYPE::ACCESS_DENIED_OBJECT),
+	("OU", co::ACE_TYPE::SYSTEM_AUDIT_OBJECT),
+	("OL", co::ACE_TYPE::SYSTEM_ALARM_OBJECT),
+	("ML", co::ACE_TYPE::SYSTEM_MANDATORY_LABEL),
+	("SP", co::ACE_TYPE::SYSTEM_SCOPED_POLICY_ID),
+];
+
+/// An owned security identifier, which can be parsed from and formatted to its
+/// string form, like `S-1-5-32-544`, and its binary form.
+///
+/// Unlike [`SID`](crate::SID), which is a variable-sized structure allocated
+/// by the system, `Sid` is a plain Rust value, and all its operations are
+/// pure.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let admins = w::Sid::parse_str("BA")?;
+/// assert_eq!(admins.to_string(), "S-1-5-32-544");
+/// assert_eq!(admins.alias(), Some("BA"));
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Sid {
+	authority: u64,
+	sub_authorities: Vec<u32>,
+}
+
+impl std::fmt::Display for Sid {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.authority >> 32 == 0 {
+			write!(f, "S-{}-{}", SID_REVISION, self.authority)?;
+		} else {
+			write!(f, "S-{}-{:#014X}", SID_REVISION, self.authority)?;
+		}
+		self.sub_authorities.iter()
+			.try_for_each(|sub| write!(f, "-{}", sub))
+	}
+}
+
+impl From<&SID> for Sid {
+	fn from(sid: &SID) -> Self {
+		let mut auth = [0u8; 8];
+		auth[2..].copy_from_slice(&sid.IdentifierAuthority.Value);
+		Self {
+			authority: u64::from_be_bytes(auth),
+			sub_authorities: sid.SubAuthority().iter().map(|rid| rid.raw()).collect(),
+		}
+	}
+}
+
+impl Sid {
+	/// Creates a new `Sid` from its identifier authority, like `5` for
+	/// [`SID_IDENTIFIER_AUTHORITY::NT`](crate::SID_IDENTIFIER_AUTHORITY::NT),
+	/// and its sub-authorities.
+	///
+	/// Fails with [`co::ERROR::INVALID_SID`](crate::co::ERROR::INVALID_SID) if
+	/// the authority doesn't fit in 48 bits, or if there are more than 15
+	/// sub-authorities.
+	#[must_use]
+	pub fn new(authority: u64, sub_authorities: &[u32]) -> SysResult<Self> {
+		if authority >> 48 != 0 || sub_authorities.len() > SID_MAX_SUB_AUTHORITIES {
+			return Err(co::ERROR::INVALID_SID);
+		}
+		Ok(Self { authority, sub_authorities: sub_authorities.to_vec() })
+	}
+
+	/// Parses a `Sid` from its string form, like `S-1-5-18`, or from one of
+	/// the SDDL aliases of the well-known SIDs, like `SY`.
+	///
+	/// Aliases relative to a domain, like `DA`, can't be resolved and fail with
+	/// [`co::ERROR::NONE_MAPPED`](crate::co::ERROR::NONE_MAPPED); any other
+	/// invalid string fails with
+	/// [`co::ERROR::INVALID_SID`](crate::co::ERROR::INVALID_SID).
+	#[must_use]
+	pub fn parse_str(s: &str) -> SysResult<Self> {
+		let s = s.trim();
+		if let Some((_, auth, subs)) = SID_ALIASES.iter()
+			.find(|(alias, _, _)| alias.eq_ignore_ascii_case(s))
+		{
+			return Self::new(*auth, subs);
+		}
+		if SID_DOMAIN_ALIASES.iter().any(|alias| alias.eq_ignore_ascii_case(s)) {
+			return Err(co::ERROR::NONE_MAPPED);
+		}
+
+		let mut parts = s.split('-');
+		if !parts.next().is_some_and(|p| p.eq_ignore_ascii_case("S"))
+			|| parts.next() != Some("1")
+		{
+			return Err(co::ERROR::INVALID_SID);
+		}
+		let authority = match parts.next() {
+			Some(p) if p.starts_with("0x") || p.starts_with("0X") =>
+				u64::from_str_radix(&p[2..], 16).ok(),
+			Some(p) if !p.is_empty() && p.bytes().all(|ch| ch.is_ascii_digit()) =>
+				p.parse::<u64>().ok(),
+			_ => None,
+		}.ok_or(co::ERROR::INVALID_SID)?;
+		let sub_authorities = parts
+			.map(|p| match p.bytes().all(|ch| ch.is_ascii_digit()) {
+				true => p.parse::<u32>().map_err(|_| co::ERROR::INVALID_SID),
+				false => Err(co::ERROR::INVALID_SID),
+			})
+			.collect::<SysResult<Vec<_>>>()?;
+		Self::new(authority, &sub_authorities)
+	}
+
+	/// Parses a `Sid` from the beginning of a binary [`SID`](crate::SID)
+	/// structure. Any bytes past the structure are ignored.
+	#[must_use]
+	pub fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		let head = slice_at(bytes, 0, 8).map_err(|_| co::ERROR::INVALID_SID)?;
+		let count = head[1] as usize;
+		if head[0] != SID_REVISION || count > SID_MAX_SUB_AUTHORITIES {
+			return Err(co::ERROR::INVALID_SID);
+		}
+		let mut auth = [0u8; 8];
+		auth[2..].copy_from_slice(&head[2..8]);
+		let sub_authorities = (0..count)
+			.map(|i| u32_at(bytes, 8 + i * 4).map_err(|_| co::ERROR::INVALID_SID))
+			.collect::<SysResult<Vec<_>>>()?;
+		Ok(Self { authority: u64::from_be_bytes(auth), sub_authorities })
+	}
+
+	/// Returns the identifier authority, like `5` for
+	/// [`SID_IDENTIFIER_AUTHORITY::NT`](crate::SID_IDENTIFIER_AUTHORITY::NT).
+	#[must_use]
+	pub const fn authority(&self) -> u64 {
+		self.authority
+	}
+
+	/// Returns the sub-authorities, the last one usually being the relative
+	/// identifier.
+	#[must_use]
+	pub fn sub_authorities(&self) -> &[u32] {
+		&self.sub_authorities
+	}
+
+	/// Returns the SDDL alias of this SID, if it's one of the well-known SIDs
+	/// which don't depend on a domain.
+	#[must_use]
+	pub fn alias(&self) -> Option<&'static str> {
+		SID_ALIASES.iter()
+			.find(|(_, auth, subs)| *auth == self.authority && *subs == &self.sub_authorities[..])
+			.map(|(alias, _, _)| *alias)
+	}
+
+	/// Returns the SDDL form of this SID: its alias, if any, otherwise its
+	/// string form.
+	#[must_use]
+	pub fn serialize_to_sddl(&self) -> String {
+		self.alias()
+			.map(|alias| alias.to_owned())
+			.unwrap_or_else(|| self.to_string())
+	}
+
+	/// Serializes this SID into a binary [`SID`](crate::SID) structure.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> Vec<u8> {
+		let mut buf = Vec::with_capacity(self.byte_len());
+		self.write_bytes(&mut buf);
+		buf
+	}
+
+	fn byte_len(&self) -> usize {
+		8 + self.sub_authorities.len() * 4
+	}
+
+	fn write_bytes(&self, buf: &mut Vec<u8>) {
+		buf.push(SID_REVISION);
+		buf.push(self.sub_authorities.len() as _);
+		buf.extend_from_slice(&self.authority.to_be_bytes()[2..]);
+		self.sub_authorities.iter()
+			.for_each(|sub| buf.extend_from_slice(&sub.to_le_bytes()));
+	}
+}
+
+/// An access control entry of an [`Acl`](crate::Acl).
+///
+/// The object types are only used by the object ACE types, like
+/// [`co::ACE_TYPE::ACCESS_ALLOWED_OBJECT`](crate::co::ACE_TYPE::ACCESS_ALLOWED_OBJECT);
+/// they're ignored by the other types.
+///
+/// # Examples
+///
+/// An ACE which grants read access to the built-in users, inherited by files
+/// and subdirectories:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let ace = w::Ace::allow(w::Sid::parse_str("BU")?, co::GENERIC::READ)
+///     .inherit(co::ACE_FLAG::OBJECT_INHERIT | co::ACE_FLAG::CONTAINER_INHERIT);
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Ace {
+	/// Type of the ACE.
+	pub ace_type: co::ACE_TYPE,
+	/// Inheritance and audit flags.
+	pub flags: co::ACE_FLAG,
+	/// Access mask, which combines constants like
+	/// [`co::GENERIC`](crate::co::GENERIC),
+	/// [`co::ACCESS_RIGHTS`](crate::co::ACCESS_RIGHTS) or
+	/// [`co::KEY`](crate::co::KEY).
+	pub mask: u32,
+	/// Type of object, property set or property protected by the ACE.
+	pub object_type: Option<GUID>,
+	/// Type of child object which can inherit the ACE.
+	pub inherited_object_type: Option<GUID>,
+	/// Trustee of the ACE.
+	pub sid: Sid,
+}
+
+impl Ace {
+	/// Creates a new `Ace` with no flags and no object types.
+	#[must_use]
+	pub fn new(ace_type: co::ACE_TYPE, sid: Sid, mask: impl Into<u32>) -> Self {
+		Self {
+			ace_type,
+			flags: co::ACE_FLAG::NoValue,
+			mask: mask.into(),
+			object_type: None,
+			inherited_object_type: None,
+			sid,
+		}
+	}
+
+	/// Creates a new
+	/// [`co::ACE_TYPE::ACCESS_ALLOWED`](crate::co::ACE_TYPE::ACCESS_ALLOWED)
+	/// `Ace`.
+	#[must_use]
+	pub fn allow(sid: Sid, mask: impl Into<u32>) -> Self {
+		Self::new(co::ACE_TYPE::ACCESS_ALLOWED, sid, mask)
+	}
+
+	/// Creates a new
+	/// [`co::ACE_TYPE::ACCESS_DENIED`](crate::co::ACE_TYPE::ACCESS_DENIED)
+	/// `Ace`.
+	#[must_use]
+	pub fn deny(sid: Sid, mask: impl Into<u32>) -> Self {
+		Self::new(co::ACE_TYPE::ACCESS_DENIED, sid, mask)
+	}
+
+	/// Creates a new
+	/// [`co::ACE_TYPE::SYSTEM_AUDIT`](crate::co::ACE_TYPE::SYSTEM_AUDIT)
+	/// `Ace`, to be placed in a SACL, which audits successful and/or failed
+	/// access attempts.
+	#[must_use]
+	pub fn audit(sid: Sid,
+		mask: impl Into<u32>,
+		on_success: bool,
+		on_failure: bool,
+	) -> Self
+	{
+		let mut new_self = Self::new(co::ACE_TYPE::SYSTEM_AUDIT, sid, mask);
+		if on_success {
+			new_self.flags |= co::ACE_FLAG::SUCCESSFUL_ACCESS;
+		}
+		if on_failure {
+			new_self.flags |= co::ACE_FLAG::FAILED_ACCESS;
+		}
+		new_self
+	}
+
+	/// Creates a new
+	/// [`co::ACE_TYPE::SYSTEM_MANDATORY_LABEL`](crate::co::ACE_TYPE::SYSTEM_MANDATORY_LABEL)
+	/// `Ace`, to be placed in a SACL, where the SID is an integrity level, like
+	/// `LW` or `HI`.
+	#[must_use]
+	pub fn mandatory_label(level: Sid,
+		policy: co::SYSTEM_MANDATORY_LABEL,
+	) -> Self
+	{
+		Self::new(co::ACE_TYPE::SYSTEM_MANDATORY_LABEL, level, policy)
+	}
+
+	/// Adds the given flags to the ACE, returning it.
+	#[must_use]
+	pub fn inherit(mut self, flags: co::ACE_FLAG) -> Self {
+		self.flags |= flags;
+		self
+	}
+
+	/// Tells whether the ACE type carries object types.
+	#[must_use]
+	pub fn is_object_ace(&self) -> bool {
+		matches!(self.ace_type,
+			co::ACE_TYPE::ACCESS_ALLOWED_OBJECT
+			| co::ACE_TYPE::ACCESS_DENIED_OBJECT
+			| co::ACE_TYPE::SYSTEM_AUDIT_OBJECT
+			| co::ACE_TYPE::SYSTEM_ALARM_OBJECT)
+	}
+
+	/// Parses an `Ace` from its SDDL form, like `(A;OICI;FA;;;SY)`, with or
+	/// without the parentheses.
+	#[must_use]
+	pub fn parse_sddl(sddl: &str) -> SysResult<Self> {
+		let sddl = sddl.trim();
+		let sddl = sddl.strip_prefix('(')
+			.and_then(|s| s.strip_suffix(')'))
+			.unwrap_or(sddl);
+
+		let fields = sddl.split(';').map(|f| f.trim()).collect::<Vec<_>>();
+		if fields.len() != 6 {
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+
+		let ace_type = ACE_TYPES.iter()
+			.find(|(alias, _)| alias.eq_ignore_ascii_case(fields[0]))
+			.map(|(_, ace_type)| *ace_type)
+			.ok_or(co::ERROR::INVALID_PARAMETER)?;
+		let mut flags = co::ACE_FLAG::NoValue;
+		for token in tokens(fields[1])? {
+			flags |= ACE_FLAGS.iter()
+				.find(|(alias, _)| alias.eq_ignore_ascii_case(token))
+				.map(|(_, flag)| *flag)
+				.ok_or(co::ERROR::INVALID_PARAMETER)?;
+		}
+		let mask = parse_rights(fields[2])?;
+		let object_type = parse_guid(fields[3])?;
+		let inherited_object_type = parse_guid(fields[4])?;
+		let sid = Sid::parse_str(fields[5])?;
+
+		let new_self = Self { ace_type, flags, mask, object_type, inherited_object_type, sid };
+		if !new_self.is_object_ace()
+			&& (new_self.object_type.is_some() || new_self.inherited_object_type.is_some())
+		{
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+		Ok(new_self)
+	}
+
+	/// Returns the SDDL form of this ACE, like `(A;OICI;FA;;;SY)`.
+	///
+	/// Fails with [`co::ERROR::NOT_SUPPORTED`](crate::co::ERROR::NOT_SUPPORTED)
+	/// if the ACE type has no SDDL form.
+	#[must_use]
+	pub fn serialize_to_sddl(&self) -> SysResult<String> {
+		let ace_type = ACE_TYPES.iter()
+			.find(|(_, ace_type)| *ace_type == self.ace_type)
+			.map(|(alias, _)| *alias)
+			.ok_or(co::ERROR::NOT_SUPPORTED)?;
+		let flags = ACE_FLAGS.iter()
+			.filter(|(_, flag)| self.flags.has(*flag))
+			.map(|(alias, _)| *alias)
+			.collect::<String>();
+		let rights = format_rights(self.mask,
+			self.ace_type == co::ACE_TYPE::SYSTEM_MANDATORY_LABEL);
+		let (obj, inh_obj) = match self.is_object_ace() {
+			true => (
+				self.object_type.map(|g| g.to_string()).unwrap_or_default(),
+				self.inherited_object_type.map(|g| g.to_string()).unwrap_or_default(),
+			),
+			false => (String::new(), String::new()),
+		};
+
+		Ok(format!("({};{};{};{};{};{})",
+			ace_type, flags, rights, obj, inh_obj, self.sid.serialize_to_sddl()))
+	}
+
+	fn byte_len(&self) -> usize {
+		let mut len = 8 + self.sid.byte_len(); // header and mask
+		if self.is_object_ace() {
+			len += 4
+				+ self.object_type.map_or(0, |_| 16)
+				+ self.inherited_object_type.map_or(0, |_| 16);
+		}
+		len
+	}
+
+	fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		let ace_type = unsafe { co::ACE_TYPE::from_raw(bytes[0]) };
+		if !ACE_TYPES.iter().any(|(_, t)| *t == ace_type) {
+			return Err(co::ERROR::NOT_SUPPORTED);
+		}
+		let mut new_self = Self {
+			ace_type,
+			flags: unsafe { co::ACE_FLAG::from_raw(bytes[1]) },
+			mask: u32_at(bytes, 4)?,
+			object_type: None,
+			inherited_object_type: None,
+			sid: Sid::new(0, &[])?,
+		};
+
+		let mut pos = 8;
+		if new_self.is_object_ace() {
+			let obj_flags = u32_at(bytes, pos)?;
+			pos += 4;
+			if obj_flags & ACE_OBJECT_TYPE_PRESENT != 0 {
+				new_self.object_type = Some(guid_at(bytes, pos)?);
+				pos += 16;
+			}
+			if obj_flags & ACE_INHERITED_OBJECT_TYPE_PRESENT != 0 {
+				new_self.inherited_object_type = Some(guid_at(bytes, pos)?);
+				pos += 16;
+			}
+		}
+		new_self.sid = Sid::parse_bytes(&bytes[pos.min(bytes.len())..])
+			.map_err(|_| co::ERROR::INVALID_ACL)?;
+		Ok(new_self)
+	}
+
+	fn write_bytes(&self, buf: &mut Vec<u8>) {
+		buf.push(self.ace_type.raw());
+		buf.push(self.flags.raw());
+		buf.extend_from_slice(&(self.byte_len() as u16).to_le_bytes());
+		buf.extend_from_slice(&self.mask.to_le_bytes());
+		if self.is_object_ace() {
+			let mut obj_flags = 0;
+			if self.object_type.is_some() {
+				obj_flags |= ACE_OBJECT_TYPE_PRESENT;
+			}
+			if self.inherited_object_type.is_some() {
+				obj_flags |= ACE_INHERITED_OBJECT_TYPE_PRESENT;
+			}
+			buf.extend_from_slice(&u32::to_le_bytes(obj_flags));
+			[self.object_type, self.inherited_object_type].iter()
+				.flatten()
+				.for_each(|guid| buf.extend_from_slice(&guid_bytes(guid)));
+		}
+		self.sid.write_bytes(buf);
+	}
+}
+
+/// An access control list, which is an ordered list of [`Ace`](crate::Ace)
+/// entries.
+///
+/// The ACEs are written in the given order; the system expects explicit deny
+/// ACEs to come before explicit allow ACEs, which come before the inherited
+/// ones.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let dacl = w::Acl::new()
+///     .ace(w::Ace::allow(w::Sid::parse_str("SY")?, co::GENERIC::ALL))
+///     .ace(w::Ace::allow(w::Sid::parse_str("BU")?, co::GENERIC::READ));
+///
+/// let acl_bytes = dacl.serialize_to_bytes()?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct Acl {
+	/// The ACEs of this list. They can be modified at will.
+	pub aces: Vec<Ace>,
+}
+
+impl Acl {
+	/// Creates a new, empty `Acl`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Appends an ACE to the list, returning it.
+	#[must_use]
+	pub fn ace(mut self, ace: Ace) -> Self {
+		self.aces.push(ace);
+		self
+	}
+
+	/// Parses an `Acl` from the beginning of a binary [`ACL`](crate::ACL)
+	/// structure.
+	///
+	/// Fails with [`co::ERROR::NOT_SUPPORTED`](crate::co::ERROR::NOT_SUPPORTED)
+	/// if an ACE type has no SDDL form, like the callback ACEs.
+	#[must_use]
+	pub fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		let head = slice_at(bytes, 0, 8).map_err(|_| co::ERROR::INVALID_ACL)?;
+		let acl_size = u16::from_le_bytes([head[2], head[3]]) as usize;
+		let ace_count = u16::from_le_bytes([head[4], head[5]]) as usize;
+		if !(ACL_REVISION..=ACL_REVISION_DS).contains(&head[0])
+			|| acl_size < 8 || acl_size > bytes.len()
+		{
+			return Err(co::ERROR::INVALID_ACL);
+		}
+
+		let bytes = &bytes[..acl_size];
+		let mut aces = Vec::with_capacity(ace_count);
+		let mut pos = 8;
+		for _ in 0..ace_count {
+			let ace_size = u16_at(bytes, pos + 2)? as usize;
+			if ace_size < 8 {
+				return Err(co::ERROR::INVALID_ACL);
+			}
+			aces.push(Ace::parse_bytes(slice_at(bytes, pos, ace_size)?)?);
+			pos += ace_size;
+		}
+		Ok(Self { aces })
+	}
+
+	/// Serializes this list into a binary [`ACL`](crate::ACL) structure. The
+	/// revision is `ACL_REVISION_DS` if there are object ACEs, otherwise
+	/// `ACL_REVISION`.
+	///
+	/// Fails with [`co::ERROR::INVALID_ACL`](crate::co::ERROR::INVALID_ACL) if
+	/// the list exceeds 64 KB.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> SysResult<Vec<u8>> {
+		let mut buf = Vec::with_capacity(self.byte_len());
+		self.write_bytes(&mut buf)?;
+		Ok(buf)
+	}
+
+	fn byte_len(&self) -> usize {
+		8 + self.aces.iter().map(|ace| ace.byte_len()).sum::<usize>()
+	}
+
+	fn write_bytes(&self, buf: &mut Vec<u8>) -> SysResult<()> {
+		let acl_size = u16::try_from(self.byte_len()).map_err(|_| co::ERROR::INVALID_ACL)?;
+		let revision = match self.aces.iter().any(|ace| ace.is_object_ace()) {
+			true => ACL_REVISION_DS,
+			false => ACL_REVISION,
+		};
+		buf.push(revision);
+		buf.push(0);
+		buf.extend_from_slice(&acl_size.to_le_bytes());
+		buf.extend_from_slice(&(self.aces.len() as u16).to_le_bytes());
+		buf.extend_from_slice(&[0, 0]);
+		self.aces.iter().for_each(|ace| ace.write_bytes(buf));
+		Ok(())
+	}
+
+	fn parse_sddl(flags_and_aces: &str) -> SysResult<(co::SE, Option<Self>)> {
+		let (mut flags_str, mut aces_str) = match flags_and_aces.find('(') {
+			Some(idx) => flags_and_aces.split_at(idx),
+			None => (flags_and_aces, ""),
+		};
+
+		let mut control = co::SE::NoValue;
+		let mut is_null = false;
+		loop {
+			flags_str = flags_str.trim_start();
+			if flags_str.is_empty() {
+				break;
+			} else if let Some(rest) = flags_str.strip_prefix("NO_ACCESS_CONTROL") {
+				is_null = true;
+				flags_str = rest;
+			} else if let Some(rest) = flags_str.strip_prefix('P') {
+				control |= co::SE::DACL_PROTECTED;
+				flags_str = rest;
+			} else if let Some(rest) = flags_str.strip_prefix("AR") {
+				control |= co::SE::DACL_AUTO_INHERIT_REQ;
+				flags_str = rest;
+			} else if let Some(rest) = flags_str.strip_prefix("AI") {
+				control |= co::SE::DACL_AUTO_INHERITED;
+				flags_str = rest;
+			} else {
+				return Err(co::ERROR::INVALID_PARAMETER);
+			}
+		}
+
+		let mut acl = Self::new();
+		loop {
+			aces_str = aces_str.trim_start();
+			if aces_str.is_empty() {
+				break;
+			}
+			let end = aces_str.find(')')
+				.filter(|_| aces_str.starts_with('('))
+				.ok_or(co::ERROR::INVALID_PARAMETER)?;
+			acl.aces.push(Ace::parse_sddl(&aces_str[..=end])?);
+			aces_str = &aces_str[end + 1..];
+		}
+
+		if is_null && !acl.aces.is_empty() {
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+		Ok((control, (!is_null).then_some(acl)))
+	}
+
+	fn serialize_to_sddl(acl: Option<&Self>, control: co::SE) -> SysResult<String> {
+		let mut sddl = String::new();
+		if control.has(co::SE::DACL_PROTECTED) {
+			sddl.push('P');
+		}
+		if control.has(co::SE::DACL_AUTO_INHERIT_REQ) {
+			sddl.push_str("AR");
+		}
+		if control.has(co::SE::DACL_AUTO_INHERITED) {
+			sddl.push_str("AI");
+		}
+		match acl {
+			Some(acl) => for ace in acl.aces.iter() {
+				sddl.push_str(&ace.serialize_to_sddl()?);
+			},
+			None => sddl.push_str("NO_ACCESS_CONTROL"),
+		}
+		Ok(sddl)
+	}
+}
+
+/// A security descriptor with owner, group, DACL and SACL, which can be parsed
+/// from and serialized to the
+/// [SDDL](https://learn.microsoft.com/en-us/windows/win32/secauthz/security-descriptor-string-format)
+/// string format, like `O:BAG:SYD:(A;;GA;;;SY)`, and the self-relative binary
+/// format.
+///
+/// Unlike
+/// [`ConvertStringSecurityDescriptorToSecurityDescriptor`](crate::ConvertStringSecurityDescriptorToSecurityDescriptor),
+/// the parsing and the serialization are pure, so they work on any platform.
+/// Because of that, SID aliases relative to a domain, like `DA`, are not
+/// supported.
+///
+/// A DACL which is present but null, which grants full access to everyone, is
+/// represented by `dacl` being `None` while `control` has
+/// [`co::SE::DACL_PRESENT`](crate::co::SE::DACL_PRESENT); in SDDL, it's written
+/// as `D:NO_ACCESS_CONTROL`. The same goes for the SACL.
+///
+/// # Examples
+///
+/// Creating a named pipe which can only be accessed by the local system and
+/// the administrators:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let sd = w::SecurityDescriptor::parse_sddl("D:P(A;;GA;;;SY)(A;;GA;;;BA)")?;
+/// let mut sd_buf = sd.to_self_relative()?;
+/// let mut sa = sd_buf.security_attributes(false);
+///
+/// let hpipe = w::HPIPE::CreateNamedPipe(
+///     "\\\\.\\pipe\\my_pipe",
+///     co::PIPE_ACCESS::DUPLEX,
+///     co::PIPE::TYPE_BYTE,
+///     Some(1),
+///     4096,
+///     4096,
+///     None,
+///     Some(&mut sa),
+/// )?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct SecurityDescriptor {
+	/// Control flags. When serializing,
+	/// [`co::SE::DACL_PRESENT`](crate::co::SE::DACL_PRESENT) and
+	/// [`co::SE::SACL_PRESENT`](crate::co::SE::SACL_PRESENT) are added if the
+	/// lists are present, and
+	/// [`co::SE::SELF_RELATIVE`](crate::co::SE::SELF_RELATIVE) is always added.
+	pub control: co::SE,
+	/// Owner of the object.
+	pub owner: Option<Sid>,
+	/// Primary group of the object.
+	pub group: Option<Sid>,
+	/// Discretionary access control list, which controls the access to the
+	/// object.
+	pub dacl: Option<Acl>,
+	/// System access control list, which controls auditing and integrity
+	/// labels.
+	pub sacl: Option<Acl>,
+}
+
+impl SecurityDescriptor {
+	/// Parses a `SecurityDescriptor` from an SDDL string, like
+	/// `O:BAG:SYD:(A;;GA;;;SY)`.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// the string is malformed.
+	#[must_use]
+	pub fn parse_sddl(sddl: &str) -> SysResult<Self> {
+		let mut new_self = Self::default();
+		let mut seen = Vec::<u8>::with_capacity(4);
+
+		for (key, val) in sddl_sections(sddl)? {
+			if seen.contains(&key) {
+				return Err(co::ERROR::INVALID_PARAMETER);
+			}
+			seen.push(key);
+
+			match key {
+				b'O' => new_self.owner = Some(Sid::parse_str(val)?),
+				b'G' => new_self.group = Some(Sid::parse_str(val)?),
+				b'D' => {
+					let (control, acl) = Acl::parse_sddl(val)?;
+					new_self.control |= control | co::SE::DACL_PRESENT;
+					new_self.dacl = acl;
+				},
+				_ => { // S
+					let (control, acl) = Acl::parse_sddl(val)?;
+					new_self.control |= dacl_to_sacl_control(control) | co::SE::SACL_PRESENT;
+					new_self.sacl = acl;
+				},
+			}
+		}
+		Ok(new_self)
+	}
+
+	/// Parses a `SecurityDescriptor` from a binary self-relative
+	/// [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR), like the ones
+	/// stored by the system.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_SECURITY_DESCR`](crate::co::ERROR::INVALID_SECURITY_DESCR)
+	/// if the descriptor is not self-relative or is malformed.
+	#[must_use]
+	pub fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		let head = slice_at(bytes, 0, SD_HEADER_SIZE)
+			.map_err(|_| co::ERROR::INVALID_SECURITY_DESCR)?;
+		let control = unsafe { co::SE::from_raw(u16::from_le_bytes([head[2], head[3]])) };
+		if head[0] != SD_REVISION || !control.has(co::SE::SELF_RELATIVE) {
+			return Err(co::ERROR::INVALID_SECURITY_DESCR);
+		}
+
+		let at = |idx: usize| -> SysResult<Option<&[u8]>> {
+			match u32_at(head, 4 + idx * 4)? as usize {
+				0 => Ok(None),
+				off => bytes.get(off..)
+					.filter(|rest| !rest.is_empty())
+					.map(Some)
+					.ok_or(co::ERROR::INVALID_SECURITY_DESCR),
+			}
+		};
+		let sid_at = |idx: usize| -> SysResult<Option<Sid>> {
+			at(idx)?.map(Sid::parse_bytes).transpose()
+		};
+		let acl_at = |idx: usize, present: co::SE| -> SysResult<Option<Acl>> {
+			match control.has(present) {
+				true => at(idx)?.map(Acl::parse_bytes).transpose(),
+				false => Ok(None),
+			}
+		};
+
+		Ok(Self {
+			control: control & !co::SE::SELF_RELATIVE,
+			owner: sid_at(0)?,
+			group: sid_at(1)?,
+			sacl: acl_at(2, co::SE::SACL_PRESENT)?,
+			dacl: acl_at(3, co::SE::DACL_PRESENT)?,
+		})
+	}
+
+	/// Returns the SDDL form of this security descriptor, with the well-known
+	/// SIDs written as aliases.
+	///
+	/// Fails with [`co::ERROR::NOT_SUPPORTED`](crate::co::ERROR::NOT_SUPPORTED)
+	/// if an ACE type has no SDDL form.
+	#[must_use]
+	pub fn serialize_to_sddl(&self) -> SysResult<String> {
+		let control = self.effective_control();
+		let mut sddl = String::new();
+		if let Some(owner) = &self.owner {
+			sddl.push_str("O:");
+			sddl.push_str(&owner.serialize_to_sddl());
+		}
+		if let Some(group) = &self.group {
+			sddl.push_str("G:");
+			sddl.push_str(&group.serialize_to_sddl());
+		}
+		if control.has(co::SE::DACL_PRESENT) {
+			sddl.push_str("D:");
+			sddl.push_str(&Acl::serialize_to_sddl(self.dacl.as_ref(), control)?);
+		}
+		if control.has(co::SE::SACL_PRESENT) {
+			sddl.push_str("S:");
+			sddl.push_str(&Acl::serialize_to_sddl(self.sacl.as_ref(),
+				sacl_to_dacl_control(control))?);
+		}
+		Ok(sddl)
+	}
+
+	/// Serializes this security descriptor into a binary self-relative
+	/// [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR). The SACL, the DACL,
+	/// the owner and the group are written in this order, right after the
+	/// header, just like the system does.
+	///
+	/// Fails with [`co::ERROR::INVALID_ACL`](crate::co::ERROR::INVALID_ACL) if
+	/// a list exceeds 64 KB.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> SysResult<Vec<u8>> {
+		let mut buf = vec![0u8; SD_HEADER_SIZE];
+		let mut offsets = [0u32; 4]; // owner, group, sacl, dacl
+
+		if let Some(sacl) = &self.sacl {
+			offsets[2] = buf.len() as _;
+			sacl.write_bytes(&mut buf)?;
+		}
+		if let Some(dacl) = &self.dacl {
+			offsets[3] = buf.len() as _;
+			dacl.write_bytes(&mut buf)?;
+		}
+		if let Some(owner) = &self.owner {
+			offsets[0] = buf.len() as _;
+			owner.write_bytes(&mut buf);
+		}
+		if let Some(group) = &self.group {
+			offsets[1] = buf.len() as _;
+			group.write_bytes(&mut buf);
+		}
+
+		let control = self.effective_control() | co::SE::SELF_RELATIVE;
+		buf[0] = SD_REVISION;
+		buf[2..4].copy_from_slice(&control.raw().to_le_bytes());
+		offsets.iter()
+			.enumerate()
+			.for_each(|(i, off)| buf[4 + i * 4..8 + i * 4].copy_from_slice(&off.to_le_bytes()));
+		Ok(buf)
+	}
+
+	/// Serializes this security descriptor into a
+	/// [`SelfRelativeSd`](crate::SelfRelativeSd) buffer, which can be passed to
+	/// the functions which take a
+	/// [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR) or a
+	/// [`SECURITY_ATTRIBUTES`](crate::SECURITY_ATTRIBUTES).
+	#[must_use]
+	pub fn to_self_relative(&self) -> SysResult<SelfRelativeSd> {
+		let bytes = self.serialize_to_bytes()?;
+		let mut buf = vec![0u64;
+			bytes.len().max(std::mem::size_of::<SECURITY_DESCRIPTOR>()).div_ceil(8)];
+		unsafe {
+			std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf.as_mut_ptr() as *mut u8, bytes.len());
+		}
+		Ok(SelfRelativeSd { buf, len: bytes.len() })
+	}
+
+	fn effective_control(&self) -> co::SE {
+		let mut control = self.control & !co::SE::SELF_RELATIVE;
+		if self.dacl.is_some() {
+			control |= co::SE::DACL_PRESENT;
+		}
+		if self.sacl.is_some() {
+			control |= co::SE::SACL_PRESENT;
+		}
+		control
+	}
+}
+
+/// A binary self-relative security descriptor, stored in a buffer aligned to
+/// be used as a [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR).
+///
+/// Returned by
+/// [`SecurityDescriptor::to_self_relative`](crate::SecurityDescriptor::to_self_relative).
+pub struct SelfRelativeSd {
+	buf: Vec<u64>,
+	len: usize,
+}
+
+impl SelfRelativeSd {
+	/// Returns the bytes of the descriptor.
+	#[must_use]
+	pub fn as_bytes(&self) -> &[u8] {
+		unsafe { std::slice::from_raw_parts(self.buf.as_ptr() as *const u8, self.len) }
+	}
+
+	/// Returns the descriptor as a
+	/// [`SECURITY_DESCRIPTOR`](crate::SECURITY_DESCRIPTOR), whose
+	/// [`co::SE::SELF_RELATIVE`](crate::co::SE::SELF_RELATIVE) flag tells the
+	/// system that the pointer fields are actually offsets.
+	#[must_use]
+	pub fn as_sd_mut(&mut self) -> &mut SECURITY_DESCRIPTOR {
+		unsafe { &mut *(self.buf.as_mut_ptr() as *mut SECURITY_DESCRIPTOR) }
+	}
+
+	/// Returns a [`SECURITY_ATTRIBUTES`](crate::SECURITY_ATTRIBUTES) pointing
+	/// to the descriptor.
+	#[must_use]
+	pub fn security_attributes(&mut self, inherit_handle: bool) -> SECURITY_ATTRIBUTES<'_> {
+		let mut sa = SECURITY_ATTRIBUTES::default();
+		sa.set_bInheritHandle(inherit_handle);
+		sa.set_lpSecurityDescriptor(Some(self.as_sd_mut()));
+		sa
+	}
+}
+
+/// Splits an SDDL string into its `O:`, `G:`, `D:` and `S:` sections, whose
+/// markers are only recognized outside the ACE parentheses.
+fn sddl_sections(sddl: &str) -> SysResult<Vec<(u8, &str)>> {
+	let raw = sddl.as_bytes();
+	let mut sections = Vec::<(u8, usize)>::with_capacity(4); // key, start of value
+	let mut depth = 0;
+
+	for (i, ch) in raw.iter().enumerate() {
+		match ch {
+			b'(' => depth += 1,
+			b')' if depth > 0 => depth -= 1,
+			b')' => return Err(co::ERROR::INVALID_PARAMETER),
+			b'O' | b'G' | b'D' | b'S' if depth == 0 && raw.get(i + 1) == Some(&b':') => {
+				if sections.is_empty() && !sddl[..i].trim().is_empty() {
+					return Err(co::ERROR::INVALID_PARAMETER); // garbage before first section
+				}
+				sections.push((*ch, i + 2));
+			},
+			_ => {},
+		}
+	}
+	if depth != 0 || (sections.is_empty() && !sddl.trim().is_empty()) {
+		return Err(co::ERROR::INVALID_PARAMETER);
+	}
+
+	Ok(sections.iter()
+		.enumerate()
+		.map(|(i, (key, start))| {
+			let end = sections.get(i + 1).map_or(sddl.len(), |(_, next)| next - 2);
+			(*key, sddl[*start..end].trim())
+		})
+		.collect())
+}
+
+/// Splits a string of 2-char SDDL tokens, like `OICI`.
+#[allow(unknown_lints, clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+fn tokens(s: &str) -> SysResult<Vec<&str>> {
+	if s.len() % 2 != 0 || !s.is_ascii() {
+		return Err(co::ERROR::INVALID_PARAMETER);
+	}
+	Ok((0..s.len()).step_by(2).map(|i| &s[i..i + 2]).collect())
+}
+
+fn parse_rights(s: &str) -> SysResult<u32> {
+	if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+		return u32::from_str_radix(hex, 16).map_err(|_| co::ERROR::INVALID_PARAMETER);
+	}
+	let mut mask = 0;
+	for token in tokens(s)? {
+		mask |= RIGHTS_EXACT.iter()
+			.chain(RIGHTS_BITS.iter())
+			.chain(RIGHTS_LABEL.iter())
+			.find(|(alias, _)| alias.eq_ignore_ascii_case(token))
+			.map(|(_, right)| *right)
+			.ok_or(co::ERROR::INVALID_PARAMETER)?;
+	}
+	Ok(mask)
+}
+
+fn format_rights(mask: u32, is_label: bool) -> String {
+	let (exact, bits) = match is_label {
+		true => (&[][..], RIGHTS_LABEL),
+		false => (RIGHTS_EXACT, RIGHTS_BITS),
+	};
+	if let Some((alias, _)) = exact.iter().find(|(_, right)| *right == mask) {
+		return (*alias).to_owned();
+	}
+	let covered = bits.iter().fold(0, |acc, (_, right)| acc | right);
+	if mask & !covered != 0 {
+		return format!("{:#x}", mask);
+	}
+	bits.iter()
+		.filter(|(_, right)| mask & right != 0)
+		.map(|(alias, _)| *alias)
+		.collect()
+}
+
+fn parse_guid(s: &str) -> SysResult<Option<GUID>> {
+	if s.is_empty() {
+		return Ok(None);
+	}
+	let is_valid = s.len() == 36 && s.bytes().enumerate().all(|(i, ch)| match i {
+		8 | 13 | 18 | 23 => ch == b'-',
+		_ => ch.is_ascii_hexdigit(),
+	});
+	match is_valid {
+		true => Ok(Some(GUID::new(&s.to_ascii_lowercase()))),
+		false => Err(co::ERROR::INVALID_PARAMETER),
+	}
+}
+
+fn guid_at(buf: &[u8], pos: usize) -> SysResult<GUID> {
+	let mut raw = [0u8; 16];
+	raw.copy_from_slice(slice_at(buf, pos, 16)?);
+	Ok(unsafe { std::mem::transmute::<[u8; 16], GUID>(raw) })
+}
+
+fn guid_bytes(guid: &GUID) -> [u8; 16] {
+	unsafe { std::mem::transmute::<GUID, [u8; 16]>(*guid) }
+}
+
+fn dacl_to_sacl_control(control: co::SE) -> co::SE {
+	let mut sacl_control = co::SE::NoValue;
+	if control.has(co::SE::DACL_PROTECTED) {
+		sacl_control |= co::SE::SACL_PROTECTED;
+	}
+	if control.has(co::SE::DACL_AUTO_INHERIT_REQ) {
+		sacl_control |= co::SE::SACL_AUTO_INHERIT_REQ;
+	}
+	if control.has(co::SE::DACL_AUTO_INHERITED) {
+		sacl_control |= co::SE::SACL_AUTO_INHERITED;
+	}
+	sacl_control
+}
+
+fn sacl_to_dacl_control(control: co::SE) -> co::SE {
+	let mut dacl_control = co::SE::NoValue;
+	if control.has(co::SE::SACL_PROTECTED) {
+		dacl_control |= co::SE::DACL_PROTECTED;
+	}
+	if control.has(co::SE::SACL_AUTO_INHERIT_REQ) {
+		dacl_control |= co::SE::DACL_AUTO_INHERIT_REQ;
+	}
+	if control.has(co::SE::SACL_AUTO_INHERITED) {
+		dacl_control |= co::SE::DACL_AUTO_INHERITED;
+	}
+	dacl_control
+}
+
+fn slice_at(buf: &[u8], pos: usize, len: usize) -> SysResult<&[u8]> {
+	pos.checked_add(len)
+		.and_then(|end| buf.get(pos..end))
+		.ok_or(co::ERROR::INVALID_ACL)
+}
+
+fn u16_at(buf: &[u8], pos: usize) -> SysResult<u16> {
+	slice_at(buf, pos, 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> SysResult<u32> {
+	slice_at(buf, pos, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const PROTECTED_SDDL: &str = "O:BAG:SYD:PAI(A;OICI;FA;;;SY)(A;OICI;FA;;;BA)";
+
+	/// Self-relative form of `PROTECTED_SDDL`, laid out as the system writes
+	/// it: header, DACL, owner, group.
+	const PROTECTED_BYTES: &[u8] = &[
+		0x01, 0x00, 0x04, 0x94, // revision, sbz1, control: DACL_PRESENT|DACL_AUTO_INHERITED|DACL_PROTECTED|SELF_RELATIVE
+		0x48, 0x00, 0x00, 0x00, // owner
+		0x58, 0x00, 0x00, 0x00, // group
+		0x00, 0x00, 0x00, 0x00, // sacl
+		0x14, 0x00, 0x00, 0x00, // dacl
+		0x02, 0x00, 0x34, 0x00, 0x02, 0x00, 0x00, 0x00, // ACL_REVISION, size 52, 2 ACEs
+		0x00, 0x03, 0x14, 0x00, 0xff, 0x01, 0x1f, 0x00, // allow, OICI, size 20, FA
+		0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x12, 0x00, 0x00, 0x00, // S-1-5-18
+		0x00, 0x03, 0x18, 0x00, 0xff, 0x01, 0x1f, 0x00, // allow, OICI, size 24, FA
+		0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x20, 0x00, 0x00, 0x00, 0x20, 0x02, 0x00, 0x00, // S-1-5-32-544
+		0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x20, 0x00, 0x00, 0x00, 0x20, 0x02, 0x00, 0x00, // owner BA
+		0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x12, 0x00, 0x00, 0x00, // group SY
+	];
+
+	fn round_trip(sddl: &str) -> SecurityDescriptor {
+		let sd = SecurityDescriptor::parse_sddl(sddl).unwrap();
+		assert_eq!(sd.serialize_to_sddl().unwrap(), sddl);
+		let bytes = sd.serialize_to_bytes().unwrap();
+		assert_eq!(SecurityDescriptor::parse_bytes(&bytes).unwrap(), sd);
+		sd
+	}
+
+	#[test]
+	fn sddl_protected() {
+		let sd = round_trip(PROTECTED_SDDL);
+		assert_eq!(sd.owner, Some(Sid::parse_str("S-1-5-32-544").unwrap()));
+		assert_eq!(sd.group, Some(Sid::parse_str("S-1-5-18").unwrap()));
+		assert_eq!(sd.control, co::SE::DACL_PRESENT | co::SE::DACL_PROTECTED | co::SE::DACL_AUTO_INHERITED);
+		assert!(sd.sacl.is_none());
+
+		let aces = &sd.dacl.as_ref().unwrap().aces;
+		assert_eq!(aces.len(), 2);
+		assert_eq!(aces[0].flags, co::ACE_FLAG::OBJECT_INHERIT | co::ACE_FLAG::CONTAINER_INHERIT);
+		assert_eq!(aces[0].mask, 0x001f_01ff);
+		assert_eq!(aces[1].sid.alias(), Some("BA"));
+	}
+
+	#[test]
+	fn sddl_whitespace_and_case() {
+		let sd = SecurityDescriptor::parse_sddl(" O:ba G:sy D:PAI (a;oici;fa;;;sy) (A;OICI;FA;;;S-1-5-32-544) ").unwrap();
+		assert_eq!(sd.serialize_to_sddl().unwrap(), PROTECTED_SDDL);
+	}
+
+	#[test]
+	fn sddl_no_access_control() {
+		let sd = round_trip("D:NO_ACCESS_CONTROL");
+		assert!(sd.dacl.is_none());
+		assert_eq!(sd.control, co::SE::DACL_PRESENT); // present, but null
+
+		let bytes = sd.serialize_to_bytes().unwrap();
+		assert_eq!(&bytes[16..20], &[0, 0, 0, 0]); // no DACL offset
+
+		let sd = round_trip("S:NO_ACCESS_CONTROL");
+		assert_eq!(sd.control, co::SE::SACL_PRESENT);
+
+		let sd = round_trip("D:"); // present and empty, which denies everything
+		assert_eq!(sd.dacl, Some(Acl::new()));
+		assert_eq!(round_trip("").control, co::SE::NoValue);
+	}
+
+	#[test]
+	fn sddl_sacl_flags() {
+		let sd = round_trip("D:AI(A;;GA;;;SY)S:PARAI(AU;SAFA;FA;;;WD)(ML;;NWNR;;;LW)");
+		assert_eq!(sd.control,
+			co::SE::DACL_PRESENT | co::SE::DACL_AUTO_INHERITED
+			| co::SE::SACL_PRESENT | co::SE::SACL_PROTECTED
+			| co::SE::SACL_AUTO_INHERIT_REQ | co::SE::SACL_AUTO_INHERITED);
+
+		let sacl = sd.sacl.as_ref().unwrap();
+		assert_eq!(sacl.aces[0], Ace::audit(Sid::parse_str("WD").unwrap(), 0x001f_01ffu32, true, true));
+		assert_eq!(sacl.aces[1].ace_type, co::ACE_TYPE::SYSTEM_MANDATORY_LABEL);
+		assert_eq!(sacl.aces[1].mask, 0x3); // no-write-up, no-read-up
+		assert_eq!(format_rights(0x1, true), "NW");
+		assert_eq!(format_rights(0x1, false), "CC"); // same bit, different alias
+	}
+
+	#[test]
+	fn object_aces() {
+		let sddl = "D:(OA;CI;RPWP;bf967a86-0de6-11d0-a285-00aa003049e2;;AU)\
+			(OD;;CR;;bf967aba-0de6-11d0-a285-00aa003049e2;PS)\
+			(OA;;RP;bf967a86-0de6-11d0-a285-00aa003049e2;bf967aba-0de6-11d0-a285-00aa003049e2;WD)";
+		let sd = round_trip(sddl);
+		let aces = &sd.dacl.as_ref().unwrap().aces;
+		assert!(aces.iter().all(|ace| ace.is_object_ace()));
+		assert_eq!(aces[0].object_type, Some(GUID::new("bf967a86-0de6-11d0-a285-00aa003049e2")));
+		assert!(aces[0].inherited_object_type.is_none());
+		assert!(aces[1].object_type.is_none());
+		assert!(aces[2].inherited_object_type.is_some());
+
+		let acl = sd.dacl.as_ref().unwrap().serialize_to_bytes().unwrap();
+		assert_eq!(acl[0], ACL_REVISION_DS);
+		let ace = &acl[8..];
+		assert_eq!(&ace[..4], &[0x05, 0x02, 0x28, 0x00]); // allow object, CI, size 40
+		assert_eq!(&ace[8..12], &[0x01, 0, 0, 0]); // object type present
+		assert_eq!(&ace[12..28], &[0x86, 0x7a, 0x96, 0xbf, 0xe6, 0x0d, 0xd0, 0x11,
+			0xa2, 0x85, 0x00, 0xaa, 0x00, 0x30, 0x49, 0xe2]);
+
+		let plain = Acl::new().ace(Ace::allow(Sid::parse_str("SY").unwrap(), 1u32));
+		assert_eq!(plain.serialize_to_bytes().unwrap()[0], ACL_REVISION);
+
+		assert_eq!(Ace::parse_sddl("(A;;RP;bf967a86-0de6-11d0-a285-00aa003049e2;;WD)").err(),
+			Some(co::ERROR::INVALID_PARAMETER)); // GUID in a non-object ACE
+		assert_eq!(Ace::parse_sddl("(OA;;RP;bf967a86-0de6-11d0-a285-00aa003049e;;WD)").err(),
+			Some(co::ERROR::INVALID_PARAMETER)); // short GUID
+	}
+
+	#[test]
+	fn sids() {
+		for (alias, s) in [("SY", "S-1-5-18"), ("BA", "S-1-5-32-544"), ("WD", "S-1-1-0"),
+			("LW", "S-1-16-4096"), ("UD", "S-1-5-84-0-0-0-0-0")]
+		{
+			let sid = Sid::parse_str(alias).unwrap();
+			assert_eq!(sid.to_string(), s);
+			assert_eq!(Sid::parse_str(s).unwrap().alias(), Some(alias));
+			assert_eq!(Sid::parse_bytes(&sid.serialize_to_bytes()).unwrap(), sid);
+		}
+
+		let user = Sid::parse_str("S-1-5-21-3623811015-3361044348-30300820-1013").unwrap();
+		assert_eq!(user.sub_authorities(), [21, 3623811015, 3361044348, 30300820, 1013]);
+		assert_eq!(user.alias(), None);
+		assert_eq!(user.serialize_to_sddl(), "S-1-5-21-3623811015-3361044348-30300820-1013");
+		assert_eq!(Sid::parse_str("s-1-0x5-18").unwrap().alias(), Some("SY"));
+		assert_eq!(Sid::parse_str("S-1-5").unwrap().sub_authorities(), [] as [u32; 0]);
+
+		assert_eq!(Sid::parse_str("DA").err(), Some(co::ERROR::NONE_MAPPED)); // domain-relative
+		for bad in ["", "S", "S-1", "S-2-5-18", "S-1--18", "S-1-5-x", "S-1-5-4294967296",
+			"S-1-0x1000000000000-1", "S-1-5-1-2-3-4-5-6-7-8-9-10-11-12-13-14-15-16", "XX"]
+		{
+			assert_eq!(Sid::parse_str(bad).err(), Some(co::ERROR::INVALID_SID), "{}", bad);
+		}
+	}
+
+	#[test]
+	fn sid_48_bit_authority() {
+		let sid = Sid::new(0x1234_5678_9abc, &[7]).unwrap();
+		assert_eq!(sid.to_string(), "S-1-0x123456789ABC-7");
+		assert_eq!(Sid::parse_str("S-1-0x123456789abc-7").unwrap(), sid);
+		assert_eq!(sid.serialize_to_bytes(),
+			[0x01, 0x01, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0x07, 0, 0, 0]); // big-endian authority
+		assert_eq!(Sid::parse_bytes(&sid.serialize_to_bytes()).unwrap(), sid);
+
+		let small = Sid::parse_str("S-1-0xffffffff-1").unwrap(); // fits in 32 bits, written in decimal
+		assert_eq!(small.to_string(), "S-1-4294967295-1");
+		assert_eq!(Sid::new(1 << 48, &[]).err(), Some(co::ERROR::INVALID_SID));
+	}
+
+	#[test]
+	fn self_relative_bytes() {
+		let sd = SecurityDescriptor::parse_sddl(PROTECTED_SDDL).unwrap();
+		assert_eq!(sd.serialize_to_bytes().unwrap(), PROTECTED_BYTES);
+		assert_eq!(sd.to_self_relative().unwrap().as_bytes(), PROTECTED_BYTES);
+		assert_eq!(SecurityDescriptor::parse_bytes(PROTECTED_BYTES).unwrap(), sd);
+	}
+
+	#[test]
+	fn self_relative_any_order() {
+		// owner and group before the DACL, as written by
+		// ConvertStringSecurityDescriptorToSecurityDescriptor
+		let mut bytes = PROTECTED_BYTES[..20].to_vec();
+		bytes.extend_from_slice(&PROTECTED_BYTES[0x48..0x64]); // owner, group
+		bytes.extend_from_slice(&PROTECTED_BYTES[0x14..0x48]); // DACL
+		bytes[4..8].copy_from_slice(&0x14u32.to_le_bytes());
+		bytes[8..12].copy_from_slice(&0x24u32.to_le_bytes());
+		bytes[16..20].copy_from_slice(&0x30u32.to_le_bytes());
+
+		let sd = SecurityDescriptor::parse_bytes(&bytes).unwrap();
+		assert_eq!(sd.serialize_to_sddl().unwrap(), PROTECTED_SDDL);
+		assert_eq!(sd.serialize_to_bytes().unwrap(), PROTECTED_BYTES); // normalized
+	}
+
+	#[test]
+	fn malformed_sddl() {
+		for bad in [
+			"D:(A;;GA;;;SY", // unbalanced
+			"D:A;;GA;;;SY)",
+			"D:(A;;GA;;;SY))",
+			"D:((A;;GA;;;SY))",
+			"O:SYO:BA", // duplicate sections
+			"D:(A;;GA;;;SY)D:(A;;GA;;;BA)",
+			"S:S:",
+			"X O:SY", // garbage before first section
+			"hello",
+			"D:PX(A;;GA;;;SY)", // unknown flag
+			"D:NO_ACCESS_CONTROL(A;;GA;;;SY)",
+			"D:(A;;GA;;SY)", // 5 fields
+			"D:(A;;GA;;;SY;)",
+			"D:(Z;;GA;;;SY)", // unknown type
+			"D:(A;O;GA;;;SY)", // odd token
+			"D:(A;XX;GA;;;SY)",
+			"D:(A;;GAX;;;SY)",
+			"D:(A;;0xZZ;;;SY)",
+			"D:(A;;GA;;;SY)x",
+		] {
+			assert_eq!(SecurityDescriptor::parse_sddl(bad).err(),
+				Some(co::ERROR::INVALID_PARAMETER), "{}", bad);
+		}
+		assert_eq!(SecurityDescriptor::parse_sddl("O:DA").err(), Some(co::ERROR::NONE_MAPPED));
+		assert_eq!(SecurityDescriptor::parse_sddl("O:S-1-x").err(), Some(co::ERROR::INVALID_SID));
+		assert_eq!(tokens("OIC").err(), Some(co::ERROR::INVALID_PARAMETER));
+		assert_eq!(tokens("\u{e9}I").err(), Some(co::ERROR::INVALID_PARAMETER));
+	}
+
+	#[test]
+	fn malformed_bytes() {
+		let parse = |bytes: &[u8]| SecurityDescriptor::parse_bytes(bytes).err();
+
+		assert_eq!(parse(&PROTECTED_BYTES[..19]), Some(co::ERROR::INVALID_SECURITY_DESCR));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[0] = 2; // revision
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_SECURITY_DESCR));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[3] &= 0x7f; // absolute
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_SECURITY_DESCR));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[4..8].copy_from_slice(&0x1000u32.to_le_bytes()); // owner out of range
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_SECURITY_DESCR));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[8..12].copy_from_slice(&0x64u32.to_le_bytes()); // group right at the end
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_SECURITY_DESCR));
+
+		let acl = 0x14;
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[acl + 2] = 0xff; // ACL size past the end
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_ACL));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[acl + 4] = 3; // one ACE too many
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_ACL));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[acl + 8 + 2] = 4; // ACE size below the header
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_ACL));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[acl + 8 + 2] = 0x40; // ACE size past the ACL
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_ACL));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[acl + 8 + 2] = 0x0c; // ACE size cuts its SID
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_ACL));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[acl + 8] = 0x09; // callback ACE, no SDDL form
+		assert_eq!(parse(&bad), Some(co::ERROR::NOT_SUPPORTED));
+		let mut bad = PROTECTED_BYTES.to_vec();
+		bad[0x48 + 1] = 16; // owner with too many sub-authorities
+		assert_eq!(parse(&bad), Some(co::ERROR::INVALID_SID));
+	}
+}
@@ -0,0 +1,966 @@
+use std::sync::{Arc, Mutex, mpsc};
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+use super::command::quote_arg;
+
+const DEFAULT_WAIT_HINT: u32 = 5000;
+
+/// Tracks the state of a service and builds the
+/// [`SERVICE_STATUS`](crate::SERVICE_STATUS) values to be reported to the
+/// Service Control Manager, validating the transitions and numbering the
+/// checkpoints of the pending states.
+///
+/// The controls listed in `controls_accepted` are only accepted while the
+/// service is running or paused.
+///
+/// This is the state machine used by
+/// [`ServiceDispatcher`](crate::ServiceDispatcher); it doesn't call any
+/// system function, so it can also be used on its own.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let mut sm = w::ServiceStateMachine::new(
+///     co::SERVICE_TYPE::WIN32_OWN_PROCESS, co::SERVICE_ACCEPT::STOP, 3000);
+///
+/// sm.set_state(co::SERVICE_STATE::START_PENDING, 3000)?; // check point 1
+/// sm.check_point(3000)?; // check point 2
+/// sm.set_state(co::SERVICE_STATE::RUNNING, 0)?;
+///
+/// let status = sm.control(co::SERVICE_CONTROL::STOP)?.unwrap();
+/// assert_eq!(status.dwCurrentState, co::SERVICE_STATE::STOP_PENDING);
+/// # Ok::<_, co::ERROR>(())
+/// ```
+#[derive(Clone)]
+pub struct ServiceStateMachine {
+	service_type: co::SERVICE_TYPE,
+	controls_accepted: co::SERVICE_ACCEPT,
+	control_wait_hint: u32,
+	state: co::SERVICE_STATE,
+	check_point: u32,
+	wait_hint: u32,
+	win32_exit_code: co::ERROR,
+	service_specific_exit_code: u32,
+}
+
+impl ServiceStateMachine {
+	/// Creates a new state machine in the
+	/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED) state.
+	///
+	/// `control_wait_hint` is the wait hint, in milliseconds, reported when a
+	/// control puts the service in a pending state.
+	#[must_use]
+	pub const fn new(
+		service_type: co::SERVICE_TYPE,
+		controls_accepted: co::SERVICE_ACCEPT,
+		control_wait_hint: u32,
+	) -> Self
+	{
+		Self {
+			service_type,
+			controls_accepted,
+			control_wait_hint,
+			state: co::SERVICE_STATE::STOPPED,
+			check_point: 0,
+			wait_hint: 0,
+			win32_exit_code: co::ERROR::SUCCESS,
+			service_specific_exit_code: 0,
+		}
+	}
+
+	/// Returns the current state.
+	#[must_use]
+	pub const fn state(&self) -> co::SERVICE_STATE {
+		self.state
+	}
+
+	/// Returns the status to be reported for the current state.
+	#[must_use]
+	pub fn status(&self) -> SERVICE_STATUS {
+		SERVICE_STATUS {
+			dwServiceType: self.service_type,
+			dwCurrentState: self.state,
+			dwControlsAccepted: match self.state {
+				co::SERVICE_STATE::RUNNING | co::SERVICE_STATE::PAUSED => self.controls_accepted,
+				_ => co::SERVICE_ACCEPT::default(), // no controls while pending
+			},
+			dwWin32ExitCode: self.win32_exit_code,
+			dwServiceSpecificExitCode: self.service_specific_exit_code,
+			dwCheckPoint: self.check_point,
+			dwWaitHint: self.wait_hint,
+		}
+	}
+
+	/// Moves to a new state, returning the status to be reported.
+	///
+	/// Entering a pending state starts its checkpoints at 1; setting the same
+	/// pending state again is the same as calling
+	/// [`ServiceStateMachine::check_point`](crate::ServiceStateMachine::check_point).
+	/// `wait_hint` is ignored for non-pending states.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_STATE`](crate::co::ERROR::INVALID_STATE) if the
+	/// transition is not allowed, like going from
+	/// [`SERVICE_STATE::STOP_PENDING`](crate::co::SERVICE_STATE::STOP_PENDING)
+	/// to [`SERVICE_STATE::RUNNING`](crate::co::SERVICE_STATE::RUNNING).
+	pub fn set_state(&mut self,
+		state: co::SERVICE_STATE,
+		wait_hint: u32,
+	) -> SysResult<SERVICE_STATUS>
+	{
+		if state == self.state {
+			return if is_pending(state) {
+				self.check_point(wait_hint)
+			} else {
+				Ok(self.status())
+			};
+		}
+		if !is_transition_allowed(self.state, state) {
+			return Err(co::ERROR::INVALID_STATE);
+		}
+
+		self.state = state;
+		if is_pending(state) {
+			self.check_point = 1;
+			self.wait_hint = wait_hint;
+		} else {
+			self.check_point = 0;
+			self.wait_hint = 0;
+		}
+		Ok(self.status())
+	}
+
+	/// Increments the checkpoint of the current pending state, returning the
+	/// status to be reported. Must be called before the previous wait hint
+	/// expires, otherwise the Service Control Manager assumes the service
+	/// hung.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_STATE`](crate::co::ERROR::INVALID_STATE) if the
+	/// current state is not pending.
+	pub fn check_point(&mut self, wait_hint: u32) -> SysResult<SERVICE_STATUS> {
+		if !is_pending(self.state) {
+			return Err(co::ERROR::INVALID_STATE);
+		}
+		self.check_point += 1;
+		self.wait_hint = wait_hint;
+		Ok(self.status())
+	}
+
+	/// Sets the exit code reported along with the status, usually before
+	/// moving to [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED).
+	pub fn set_exit_code(&mut self, exit_code: co::ERROR) {
+		self.win32_exit_code = exit_code;
+		self.service_specific_exit_code = 0;
+	}
+
+	/// Sets a service-specific exit code, reported along with
+	/// [`co::ERROR::SERVICE_SPECIFIC_ERROR`](crate::co::ERROR::SERVICE_SPECIFIC_ERROR).
+	pub fn set_service_specific_exit_code(&mut self, exit_code: u32) {
+		self.win32_exit_code = co::ERROR::SERVICE_SPECIFIC_ERROR;
+		self.service_specific_exit_code = exit_code;
+	}
+
+	/// Processes a control received by the service control handler.
+	///
+	/// Stop, pause and continue controls move the service to the matching
+	/// pending state, whose status is returned to be reported; other accepted
+	/// controls return `None`. The error, if any, is the value the handler
+	/// must return:
+	///
+	/// * [`co::ERROR::CALL_NOT_IMPLEMENTED`](crate::co::ERROR::CALL_NOT_IMPLEMENTED) if the control is not in `controls_accepted`;
+	/// * [`co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL`](crate::co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL) if the control is accepted, but not in the current state.
+	pub fn control(&mut self,
+		control: co::SERVICE_CONTROL,
+	) -> SysResult<Option<SERVICE_STATUS>>
+	{
+		let accept = match control {
+			co::SERVICE_CONTROL::INTERROGATE
+				| co::SERVICE_CONTROL::DEVICEEVENT => return Ok(None), // always accepted
+			co::SERVICE_CONTROL::STOP => co::SERVICE_ACCEPT::STOP,
+			co::SERVICE_CONTROL::PAUSE
+				| co::SERVICE_CONTROL::CONTINUE => co::SERVICE_ACCEPT::PAUSE_CONTINUE,
+			co::SERVICE_CONTROL::SHUTDOWN => co::SERVICE_ACCEPT::SHUTDOWN,
+			co::SERVICE_CONTROL::PRESHUTDOWN => co::SERVICE_ACCEPT::PRESHUTDOWN,
+			co::SERVICE_CONTROL::PARAMCHANGE => co::SERVICE_ACCEPT::PARAMCHANGE,
+			co::SERVICE_CONTROL::NETBINDADD
+				| co::SERVICE_CONTROL::NETBINDREMOVE
+				| co::SERVICE_CONTROL::NETBINDENABLE
+				| co::SERVICE_CONTROL::NETBINDDISABLE => co::SERVICE_ACCEPT::NETBINDCHANGE,
+			co::SERVICE_CONTROL::HARDWAREPROFILECHANGE => co::SERVICE_ACCEPT::HARDWAREPROFILECHANGE,
+			co::SERVICE_CONTROL::POWEREVENT => co::SERVICE_ACCEPT::POWEREVENT,
+			co::SERVICE_CONTROL::SESSIONCHANGE => co::SERVICE_ACCEPT::SESSIONCHANGE,
+			co::SERVICE_CONTROL::TIMECHANGE => co::SERVICE_ACCEPT::TIMECHANGE,
+			co::SERVICE_CONTROL::TRIGGEREVENT => co::SERVICE_ACCEPT::TRIGGEREVENT,
+			co::SERVICE_CONTROL::USERMODEREBOOT => co::SERVICE_ACCEPT::USERMODEREBOOT,
+			_ if (128..=255).contains(&control.raw()) => return Ok(None), // user-defined
+			_ => return Err(co::ERROR::CALL_NOT_IMPLEMENTED),
+		};
+
+		if !self.controls_accepted.has(accept) {
+			return Err(co::ERROR::CALL_NOT_IMPLEMENTED);
+		}
+		if !self.status().dwControlsAccepted.has(accept) {
+			return Err(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL); // pending or stopped
+		}
+
+		let next_state = match control {
+			co::SERVICE_CONTROL::STOP
+				| co::SERVICE_CONTROL::SHUTDOWN
+				| co::SERVICE_CONTROL::PRESHUTDOWN => co::SERVICE_STATE::STOP_PENDING,
+			co::SERVICE_CONTROL::PAUSE => co::SERVICE_STATE::PAUSE_PENDING,
+			co::SERVICE_CONTROL::CONTINUE => co::SERVICE_STATE::CONTINUE_PENDING,
+			_ => return Ok(None),
+		};
+		self.set_state(next_state, self.control_wait_hint)
+			.map(Some)
+			.map_err(|_| co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL) // pause while paused, etc.
+	}
+}
+
+const fn is_pending(state: co::SERVICE_STATE) -> bool {
+	matches!(state,
+		co::SERVICE_STATE::START_PENDING
+		| co::SERVICE_STATE::STOP_PENDING
+		| co::SERVICE_STATE::PAUSE_PENDING
+		| co::SERVICE_STATE::CONTINUE_PENDING)
+}
+
+fn is_transition_allowed(from: co::SERVICE_STATE, to: co::SERVICE_STATE) -> bool {
+	use co::SERVICE_STATE as S;
+	match from {
+		S::STOPPED => matches!(to, S::START_PENDING | S::RUNNING),
+		S::START_PENDING => matches!(to, S::RUNNING | S::STOP_PENDING | S::STOPPED),
+		S::RUNNING => matches!(to, S::PAUSE_PENDING | S::PAUSED | S::STOP_PENDING | S::STOPPED),
+		S::PAUSE_PENDING => matches!(to, S::PAUSED | S::RUNNING | S::STOP_PENDING | S::STOPPED),
+		S::PAUSED => matches!(to, S::CONTINUE_PENDING | S::RUNNING | S::STOP_PENDING | S::STOPPED),
+		S::CONTINUE_PENDING => matches!(to, S::RUNNING | S::PAUSED | S::STOP_PENDING | S::STOPPED),
+		S::STOP_PENDING => to == S::STOPPED,
+		_ => false,
+	}
+}
+
+//------------------------------------------------------------------------------
+
+type ServiceMain = dyn Fn(&ServiceContext) -> SysResult<()> + Send + Sync;
+
+/// Runs one or more services in the current process, with
+/// [`StartServiceCtrlDispatcher`](crate::StartServiceCtrlDispatcher).
+///
+/// Each service entry point receives a
+/// [`ServiceContext`](crate::ServiceContext), through which it reports its
+/// state and receives the controls sent by the Service Control Manager. The
+/// service is reported as
+/// [`SERVICE_STATE::START_PENDING`](crate::co::SERVICE_STATE::START_PENDING)
+/// before the entry point is called, and as
+/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED) after it
+/// returns, with the returned error as the exit code.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let res = w::ServiceDispatcher::new()
+///     .service("MyService", co::SERVICE_ACCEPT::STOP | co::SERVICE_ACCEPT::SHUTDOWN, |ctx| {
+///         // initialization...
+///         ctx.set_state(co::SERVICE_STATE::RUNNING)?;
+///
+///         while !ctx.is_stopping() {
+///             // do some work...
+///             ctx.next_control(Some(1000));
+///         }
+///
+///         // cleanup...
+///         Ok(())
+///     })
+///     .run();
+///
+/// match res {
+///     Err(co::ERROR::FAILED_SERVICE_CONTROLLER_CONNECT) => {
+///         println!("Not started as a service.");
+///     },
+///     res => res?,
+/// }
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct ServiceDispatcher {
+	services: Vec<(String, co::SERVICE_ACCEPT, Box<ServiceMain>)>,
+}
+
+impl Default for ServiceDispatcher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl ServiceDispatcher {
+	/// Creates a new dispatcher with no services.
+	#[must_use]
+	pub fn new() -> Self {
+		Self { services: Vec::new() }
+	}
+
+	/// Adds a service, which accepts the given controls while running.
+	///
+	/// If a single service is added, it runs as
+	/// [`SERVICE_TYPE::WIN32_OWN_PROCESS`](crate::co::SERVICE_TYPE::WIN32_OWN_PROCESS),
+	/// otherwise as
+	/// [`SERVICE_TYPE::WIN32_SHARE_PROCESS`](crate::co::SERVICE_TYPE::WIN32_SHARE_PROCESS).
+	#[must_use]
+	pub fn service<F>(mut self,
+		name: &str,
+		controls_accepted: co::SERVICE_ACCEPT,
+		service_main: F,
+	) -> Self
+		where F: Fn(&ServiceContext) -> SysResult<()> + Send + Sync + 'static,
+	{
+		self.services.push((name.to_owned(), controls_accepted, Box::new(service_main)));
+		self
+	}
+
+	/// Connects to the Service Control Manager and runs the services, blocking
+	/// until all of them have stopped.
+	///
+	/// When the process is not started by the Service Control Manager – like
+	/// when run from a console – it fails with
+	/// [`co::ERROR::FAILED_SERVICE_CONTROLLER_CONNECT`](crate::co::ERROR::FAILED_SERVICE_CONTROLLER_CONNECT).
+	pub fn run(self) -> SysResult<()> {
+		let service_type = if self.services.len() == 1 {
+			co::SERVICE_TYPE::WIN32_OWN_PROCESS
+		} else {
+			co::SERVICE_TYPE::WIN32_SHARE_PROCESS
+		};
+		let names = self.services.iter()
+			.map(|(name, _, _)| name.clone())
+			.collect::<Vec<_>>();
+		let services = self.services;
+
+		StartServiceCtrlDispatcher(&names, move |mut args: Vec<String>| {
+			let name = if args.is_empty() { String::default() } else { args.remove(0) };
+			let service = services.iter()
+				.find(|(svc_name, _, _)| svc_name.eq_ignore_ascii_case(&name))
+				.or_else(|| (services.len() == 1).then(|| &services[0]));
+			if let Some((svc_name, controls_accepted, service_main)) = service {
+				run_service(svc_name, service_type, *controls_accepted, service_main, args);
+			}
+		})
+	}
+}
+
+/// Runs the entry point of a service, in the thread created by the control
+/// dispatcher.
+fn run_service(
+	name: &str,
+	service_type: co::SERVICE_TYPE,
+	controls_accepted: co::SERVICE_ACCEPT,
+	service_main: &ServiceMain,
+	args: Vec<String>,
+) {
+	let reporter = Arc::new(Mutex::new(StatusReporter {
+		machine: ServiceStateMachine::new(service_type, controls_accepted, DEFAULT_WAIT_HINT),
+		hstatus: HSERVICESTATUS::NULL,
+	}));
+	let (tx, rx) = mpsc::channel();
+
+	let handler_reporter = reporter.clone();
+	let hstatus = match HSERVICESTATUS::RegisterServiceCtrlHandlerEx(name,
+		move |control: ServiceControl| -> u32 {
+			let code = control.code();
+			let mut reporter = handler_reporter.lock().unwrap();
+			match reporter.machine.control(code) {
+				Ok(status) => {
+					if let Some(status) = status {
+						let _ = reporter.hstatus.SetServiceStatus(&status);
+					}
+					if code != co::SERVICE_CONTROL::INTERROGATE {
+						let _ = tx.send(code); // service may have returned already
+					}
+					co::ERROR::SUCCESS.raw()
+				},
+				Err(e) => e.raw(),
+			}
+		},
+	) {
+		Ok(hstatus) => hstatus,
+		Err(_) => return, // no way to report anything
+	};
+
+	{
+		let mut reporter = reporter.lock().unwrap();
+		reporter.hstatus = hstatus;
+		if reporter.report(|m| m.set_state(co::SERVICE_STATE::START_PENDING, DEFAULT_WAIT_HINT)).is_err() {
+			return;
+		}
+	}
+
+	let ctx = ServiceContext {
+		name: name.to_owned(),
+		args,
+		reporter: reporter.clone(),
+		controls: rx,
+	};
+	let res = service_main(&ctx);
+
+	let mut reporter = reporter.lock().unwrap();
+	if let Err(e) = res {
+		reporter.machine.set_exit_code(e);
+	}
+	let _ = reporter.report(|m| m.set_state(co::SERVICE_STATE::STOPPED, 0)); // dispatcher may return now
+}
+
+/// The state machine of a service, along with the handle its status is
+/// reported to.
+struct StatusReporter {
+	machine: ServiceStateMachine,
+	hstatus: HSERVICESTATUS,
+}
+
+impl StatusReporter {
+	fn report<F>(&mut self, transition: F) -> SysResult<()>
+		where F: FnOnce(&mut ServiceStateMachine) -> SysResult<SERVICE_STATUS>,
+	{
+		let status = transition(&mut self.machine)?;
+		self.hstatus.SetServiceStatus(&status)
+	}
+}
+
+/// Passed to the entry point of each service run by a
+/// [`ServiceDispatcher`](crate::ServiceDispatcher), to report the service state
+/// and receive the controls.
+///
+/// When a stop, pause or continue control is accepted, the service is
+/// automatically moved to the matching pending state; the entry point must
+/// then finish the transition with
+/// [`ServiceContext::set_state`](crate::ServiceContext::set_state), or return
+/// in case of a stop.
+pub struct ServiceContext {
+	name: String,
+	args: Vec<String>,
+	reporter: Arc<Mutex<StatusReporter>>,
+	controls: mpsc::Receiver<co::SERVICE_CONTROL>,
+}
+
+impl ServiceContext {
+	/// Returns the name of the service.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the arguments passed to
+	/// [`HSERVICE::StartService`](crate::prelude::kernel_Hservice::StartService),
+	/// if any.
+	#[must_use]
+	pub fn args(&self) -> &[String] {
+		&self.args
+	}
+
+	/// Returns the current state of the service.
+	#[must_use]
+	pub fn state(&self) -> co::SERVICE_STATE {
+		self.reporter.lock().unwrap().machine.state()
+	}
+
+	/// Returns `true` if a stop or shutdown control was received, so the entry
+	/// point should return as soon as possible.
+	#[must_use]
+	pub fn is_stopping(&self) -> bool {
+		self.state() == co::SERVICE_STATE::STOP_PENDING
+	}
+
+	/// Moves the service to a new state, reporting it to the Service Control
+	/// Manager. A pending state is reported with a 5-second wait hint.
+	///
+	/// See
+	/// [`ServiceStateMachine::set_state`](crate::ServiceStateMachine::set_state)
+	/// for the allowed transitions.
+	pub fn set_state(&self, state: co::SERVICE_STATE) -> SysResult<()> {
+		self.reporter.lock().unwrap()
+			.report(|m| m.set_state(state, DEFAULT_WAIT_HINT))
+	}
+
+	/// Reports progress in the current pending state, asking the Service
+	/// Control Manager to wait `wait_hint` more milliseconds.
+	pub fn check_point(&self, wait_hint: u32) -> SysResult<()> {
+		self.reporter.lock().unwrap()
+			.report(|m| m.check_point(wait_hint))
+	}
+
+	/// Sets a service-specific exit code, to be reported when the service
+	/// stops.
+	pub fn set_service_specific_exit_code(&self, exit_code: u32) {
+		self.reporter.lock().unwrap()
+			.machine.set_service_specific_exit_code(exit_code);
+	}
+
+	/// Waits for the next accepted control, returning `None` if the timeout
+	/// expires.
+	///
+	/// [`SERVICE_CONTROL::INTERROGATE`](crate::co::SERVICE_CONTROL::INTERROGATE)
+	/// is answered automatically, and never returned.
+	#[must_use]
+	pub fn next_control(&self, milliseconds: Option<u32>) -> Option<co::SERVICE_CONTROL> {
+		match milliseconds {
+			Some(ms) => self.controls
+				.recv_timeout(std::time::Duration::from_millis(ms as _))
+				.ok(),
+			None => self.controls.recv().ok(),
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Installs a service in the Service Control Manager, with
+/// [`HSC::CreateService`](crate::prelude::kernel_Hsc::CreateService).
+///
+/// By default, the service runs the current executable as
+/// [`SERVICE_TYPE::WIN32_OWN_PROCESS`](crate::co::SERVICE_TYPE::WIN32_OWN_PROCESS)
+/// under the LocalSystem account, and is started on demand.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let svc = w::ServiceInstaller::new("MyService")
+///     .display_name("My Service")
+///     .description("Does important things.")
+///     .args(&["--service"])
+///     .start_type(co::SERVICE_START::AUTO_START)
+///     .install()?;
+///
+/// svc.start(&[] as &[&str], Some(10_000))?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct ServiceInstaller {
+	name: String,
+	display_name: Option<String>,
+	description: Option<String>,
+	binary_path: Option<String>,
+	args: Vec<String>,
+	service_type: co::SERVICE_TYPE,
+	start_type: co::SERVICE_START,
+	delayed_auto_start: bool,
+	dependencies: Vec<String>,
+	account: Option<(String, Option<String>)>,
+}
+
+impl ServiceInstaller {
+	/// Creates a new installer for the service with the given name.
+	#[must_use]
+	pub fn new(name: &str) -> Self {
+		Self {
+			name: name.to_owned(),
+			display_name: None,
+			description: None,
+			binary_path: None,
+			args: Vec::new(),
+			service_type: co::SERVICE_TYPE::WIN32_OWN_PROCESS,
+			start_type: co::SERVICE_START::DEMAND_START,
+			delayed_auto_start: false,
+			dependencies: Vec::new(),
+			account: None,
+		}
+	}
+
+	/// Sets the name shown in the Services console.
+	#[must_use]
+	pub fn display_name(mut self, display_name: &str) -> Self {
+		self.display_name = Some(display_name.to_owned());
+		self
+	}
+
+	/// Sets the description shown in the Services console.
+	#[must_use]
+	pub fn description(mut self, description: &str) -> Self {
+		self.description = Some(description.to_owned());
+		self
+	}
+
+	/// Sets the executable to be run, instead of the current one.
+	#[must_use]
+	pub fn binary_path(mut self, binary_path: &str) -> Self {
+		self.binary_path = Some(binary_path.to_owned());
+		self
+	}
+
+	/// Sets the arguments passed to the executable, which are quoted as
+	/// needed.
+	#[must_use]
+	pub fn args(mut self, args: &[impl AsRef<str>]) -> Self {
+		self.args = args.iter().map(|arg| arg.as_ref().to_owned()).collect();
+		self
+	}
+
+	/// Sets the service type, which must match how the executable runs its
+	/// services.
+	#[must_use]
+	pub fn service_type(mut self, service_type: co::SERVICE_TYPE) -> Self {
+		self.service_type = service_type;
+		self
+	}
+
+	/// Sets when the service is started.
+	#[must_use]
+	pub fn start_type(mut self, start_type: co::SERVICE_START) -> Self {
+		self.start_type = start_type;
+		self
+	}
+
+	/// If the start type is
+	/// [`SERVICE_START::AUTO_START`](crate::co::SERVICE_START::AUTO_START),
+	/// starts the service shortly after the other automatic services.
+	#[must_use]
+	pub fn delayed_auto_start(mut self, delayed: bool) -> Self {
+		self.delayed_auto_start = delayed;
+		self
+	}
+
+	/// Sets the services which must be running before this one starts.
+	#[must_use]
+	pub fn dependencies(mut self, dependencies: &[impl AsRef<str>]) -> Self {
+		self.dependencies = dependencies.iter().map(|dep| dep.as_ref().to_owned()).collect();
+		self
+	}
+
+	/// Sets the account the service runs under, like
+	/// `NT AUTHORITY\LocalService` or `.\username`.
+	#[must_use]
+	pub fn account(mut self, account: &str, password: Option<&str>) -> Self {
+		self.account = Some((account.to_owned(), password.map(|p| p.to_owned())));
+		self
+	}
+
+	/// Installs the service, returning a controller for it.
+	pub fn install(&self) -> SysResult<ServiceController> {
+		let binary_path = match &self.binary_path {
+			Some(binary_path) => binary_path.clone(),
+			None => HINSTANCE::NULL.GetModuleFileName()?,
+		};
+		let mut cmd_line = format!("\"{}\"", binary_path); // already quoted, kept as it is
+		for arg in self.args.iter() {
+			cmd_line.push(' ');
+			quote_arg(arg, &mut cmd_line);
+		}
+
+		let hsc = HSC::OpenSCManager(None, co::SC_MANAGER::CREATE_SERVICE)?;
+		let hservice = hsc.CreateService(
+			&self.name,
+			self.display_name.as_deref(),
+			co::SERVICE::ALL_ACCESS,
+			self.service_type,
+			self.start_type,
+			co::SERVICE_ERROR::NORMAL,
+			Some(&cmd_line),
+			None::<&[&str]>,
+			None,
+			(!self.dependencies.is_empty()).then_some(&self.dependencies[..]),
+			self.account.as_ref().map(|(account, _)| account.as_str()),
+			self.account.as_ref().and_then(|(_, password)| password.as_deref()),
+		)?;
+		let svc = ServiceController { hservice };
+
+		if let Err(e) = self.configure(&svc.hservice) {
+			let _ = svc.hservice.DeleteService(); // don't leave it half-configured
+			return Err(e);
+		}
+		Ok(svc)
+	}
+
+	fn configure(&self, hservice: &HSERVICE) -> SysResult<()> {
+		if let Some(description) = &self.description {
+			let mut wdescription = WString::from_str(description);
+			let mut info = SERVICE_DESCRIPTION::default();
+			info.set_lpDescription(Some(&mut wdescription));
+			unsafe { hservice.ChangeServiceConfig2(co::SERVICE_CONFIG::DESCRIPTION, &info)?; }
+		}
+		if self.delayed_auto_start {
+			let mut info = SERVICE_DELAYED_AUTO_START_INFO::default();
+			info.set_fDelayedAutostart(true);
+			unsafe { hservice.ChangeServiceConfig2(co::SERVICE_CONFIG::DELAYED_AUTO_START_INFO, &info)?; }
+		}
+		Ok(())
+	}
+}
+
+/// Controls an installed service: starts and stops it, queries its status and
+/// uninstalls it. The handle is closed automatically when the object goes out
+/// of scope.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let svc = w::ServiceController::open(
+///     "MyService", co::SERVICE::QUERY_STATUS | co::SERVICE::STOP | co::SERVICE::DELETE)?;
+/// svc.uninstall(Some(10_000))?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct ServiceController {
+	hservice: CloseServiceHandleSvcGuard,
+}
+
+impl ServiceController {
+	/// Opens an installed service with the given access rights.
+	pub fn open(name: &str, access: co::SERVICE) -> SysResult<Self> {
+		let hsc = HSC::OpenSCManager(None, co::SC_MANAGER::CONNECT)?;
+		let hservice = hsc.OpenService(name, access)?;
+		Ok(Self { hservice })
+	}
+
+	/// Returns the underlying handle.
+	#[must_use]
+	pub fn hservice(&self) -> &HSERVICE {
+		&self.hservice
+	}
+
+	/// Returns the current status of the service.
+	pub fn status(&self) -> SysResult<SERVICE_STATUS_PROCESS> {
+		self.hservice.QueryServiceStatusEx()
+	}
+
+	/// Starts the service with the given arguments, then waits until it's
+	/// running. If the service is already running, does nothing.
+	///
+	/// If the service stops while starting, fails with its exit code. If the
+	/// timeout expires, fails with
+	/// [`co::ERROR::SERVICE_REQUEST_TIMEOUT`](crate::co::ERROR::SERVICE_REQUEST_TIMEOUT).
+	pub fn start(&self,
+		args: &[impl AsRef<str>],
+		milliseconds: Option<u32>,
+	) -> SysResult<()>
+	{
+		match self.hservice.StartService(args) {
+			Ok(_) | Err(co::ERROR::SERVICE_ALREADY_RUNNING) => {},
+			Err(e) => return Err(e),
+		}
+		self.wait_state(co::SERVICE_STATE::RUNNING, milliseconds)
+	}
+
+	/// Sends a stop control to the service, then waits until it's stopped. If
+	/// the service is already stopped, does nothing.
+	///
+	/// If the timeout expires, fails with
+	/// [`co::ERROR::SERVICE_REQUEST_TIMEOUT`](crate::co::ERROR::SERVICE_REQUEST_TIMEOUT).
+	pub fn stop(&self, milliseconds: Option<u32>) -> SysResult<()> {
+		match self.hservice.ControlService(co::SERVICE_CONTROL::STOP) {
+			Ok(_) | Err(co::ERROR::SERVICE_NOT_ACTIVE) => {},
+			Err(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL) // already stopping
+				if self.status()?.dwCurrentState == co::SERVICE_STATE::STOP_PENDING => {},
+			Err(e) => return Err(e),
+		}
+		self.wait_state(co::SERVICE_STATE::STOPPED, milliseconds)
+	}
+
+	/// Stops the service if it's running, then marks it for deletion. The
+	/// service is removed once all handles to it are closed.
+	pub fn uninstall(self, milliseconds: Option<u32>) -> SysResult<()> {
+		if self.status()?.dwCurrentState != co::SERVICE_STATE::STOPPED {
+			self.stop(milliseconds)?;
+		}
+		self.hservice.DeleteService()
+	}
+
+	/// Polls the status until the target state is reached, at intervals based
+	/// on the wait hint, as recommended by the documentation.
+	fn wait_state(&self,
+		target: co::SERVICE_STATE,
+		milliseconds: Option<u32>,
+	) -> SysResult<()>
+	{
+		let deadline = milliseconds.map(|ms| GetTickCount64() + ms as u64);
+		loop {
+			let status = self.status()?;
+			if status.dwCurrentState == target {
+				return Ok(());
+			}
+			if status.dwCurrentState == co::SERVICE_STATE::STOPPED { // failed to start
+				return Err(match status.dwWin32ExitCode {
+					co::ERROR::SUCCESS => co::ERROR::SERVICE_NOT_ACTIVE,
+					exit_code => exit_code,
+				});
+			}
+			if deadline.is_some_and(|deadline| GetTickCount64() >= deadline) {
+				return Err(co::ERROR::SERVICE_REQUEST_TIMEOUT);
+			}
+			Sleep((status.dwWaitHint / 10).clamp(100, 1000));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use co::SERVICE_STATE as S;
+
+	fn machine(controls_accepted: co::SERVICE_ACCEPT) -> ServiceStateMachine {
+		ServiceStateMachine::new(co::SERVICE_TYPE::WIN32_OWN_PROCESS, controls_accepted, 3000)
+	}
+
+	fn running(controls_accepted: co::SERVICE_ACCEPT) -> ServiceStateMachine {
+		let mut sm = machine(controls_accepted);
+		sm.set_state(S::START_PENDING, 1000).unwrap();
+		sm.set_state(S::RUNNING, 0).unwrap();
+		sm
+	}
+
+	const ALL_STATES: [co::SERVICE_STATE; 7] = [
+		S::STOPPED, S::START_PENDING, S::STOP_PENDING, S::RUNNING,
+		S::CONTINUE_PENDING, S::PAUSE_PENDING, S::PAUSED,
+	];
+
+	#[test]
+	fn check_points() {
+		let mut sm = machine(co::SERVICE_ACCEPT::STOP);
+		assert_eq!(sm.state(), S::STOPPED);
+		assert_eq!(sm.check_point(100).err(), Some(co::ERROR::INVALID_STATE)); // not pending
+
+		let st = sm.set_state(S::START_PENDING, 1000).unwrap();
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (1, 1000));
+		let st = sm.check_point(2000).unwrap();
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (2, 2000));
+		let st = sm.set_state(S::START_PENDING, 500).unwrap(); // same as check_point
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (3, 500));
+
+		let st = sm.set_state(S::RUNNING, 9999).unwrap();
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (0, 0)); // hint ignored
+		assert!(sm.set_state(S::RUNNING, 0).unwrap() == st); // no-op
+		assert_eq!(sm.check_point(100).err(), Some(co::ERROR::INVALID_STATE));
+
+		let st = sm.set_state(S::STOP_PENDING, 700).unwrap(); // restarts at 1
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (1, 700));
+		let st = sm.set_state(S::STOPPED, 700).unwrap();
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (0, 0));
+	}
+
+	#[test]
+	fn transitions_allowed() {
+		let allowed = [
+			(S::STOPPED, S::START_PENDING), (S::STOPPED, S::RUNNING),
+			(S::START_PENDING, S::RUNNING), (S::START_PENDING, S::STOP_PENDING),
+			(S::START_PENDING, S::STOPPED),
+			(S::RUNNING, S::PAUSE_PENDING), (S::RUNNING, S::PAUSED),
+			(S::RUNNING, S::STOP_PENDING), (S::RUNNING, S::STOPPED),
+			(S::PAUSE_PENDING, S::PAUSED), (S::PAUSE_PENDING, S::RUNNING),
+			(S::PAUSE_PENDING, S::STOP_PENDING), (S::PAUSE_PENDING, S::STOPPED),
+			(S::PAUSED, S::CONTINUE_PENDING), (S::PAUSED, S::RUNNING),
+			(S::PAUSED, S::STOP_PENDING), (S::PAUSED, S::STOPPED),
+			(S::CONTINUE_PENDING, S::RUNNING), (S::CONTINUE_PENDING, S::PAUSED),
+			(S::CONTINUE_PENDING, S::STOP_PENDING), (S::CONTINUE_PENDING, S::STOPPED),
+			(S::STOP_PENDING, S::STOPPED),
+		];
+		for from in ALL_STATES {
+			for to in ALL_STATES {
+				if from != to {
+					assert_eq!(is_transition_allowed(from, to), allowed.contains(&(from, to)),
+						"{} -> {}", from.raw(), to.raw());
+				}
+			}
+		}
+		assert!(!is_transition_allowed(unsafe { co::SERVICE_STATE::from_raw(99) }, S::RUNNING));
+	}
+
+	#[test]
+	fn transitions_disallowed() {
+		let mut sm = machine(co::SERVICE_ACCEPT::STOP);
+		assert_eq!(sm.set_state(S::PAUSED, 0).err(), Some(co::ERROR::INVALID_STATE));
+		assert_eq!(sm.set_state(S::STOP_PENDING, 0).err(), Some(co::ERROR::INVALID_STATE));
+
+		sm.set_state(S::START_PENDING, 1000).unwrap();
+		sm.set_state(S::STOP_PENDING, 1000).unwrap();
+		assert_eq!(sm.set_state(S::RUNNING, 0).err(), Some(co::ERROR::INVALID_STATE));
+		assert_eq!(sm.state(), S::STOP_PENDING); // unchanged
+		assert_eq!(sm.status().dwCheckPoint, 1);
+	}
+
+	#[test]
+	fn control_not_implemented() {
+		let mut sm = running(co::SERVICE_ACCEPT::STOP);
+		assert_eq!(sm.control(co::SERVICE_CONTROL::PAUSE).err(), Some(co::ERROR::CALL_NOT_IMPLEMENTED));
+		assert_eq!(sm.control(co::SERVICE_CONTROL::SHUTDOWN).err(), Some(co::ERROR::CALL_NOT_IMPLEMENTED));
+		assert_eq!(sm.control(co::SERVICE_CONTROL::PARAMCHANGE).err(), Some(co::ERROR::CALL_NOT_IMPLEMENTED));
+		assert_eq!(sm.control(unsafe { co::SERVICE_CONTROL::from_raw(0x7f) }).err(),
+			Some(co::ERROR::CALL_NOT_IMPLEMENTED)); // reserved
+		assert_eq!(sm.state(), S::RUNNING);
+
+		// always accepted, even when not listed
+		assert!(sm.control(co::SERVICE_CONTROL::INTERROGATE).unwrap().is_none());
+		assert!(sm.control(unsafe { co::SERVICE_CONTROL::from_raw(200) }).unwrap().is_none());
+	}
+
+	#[test]
+	fn control_while_pending() {
+		let mut sm = machine(co::SERVICE_ACCEPT::STOP | co::SERVICE_ACCEPT::PARAMCHANGE);
+		assert_eq!(sm.control(co::SERVICE_CONTROL::STOP).err(),
+			Some(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL)); // stopped
+
+		let st = sm.set_state(S::START_PENDING, 1000).unwrap();
+		assert_eq!(st.dwControlsAccepted, co::SERVICE_ACCEPT::default()); // none while pending
+		assert_eq!(sm.control(co::SERVICE_CONTROL::STOP).err(),
+			Some(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL));
+		assert_eq!(sm.control(co::SERVICE_CONTROL::PARAMCHANGE).err(),
+			Some(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL));
+		assert_eq!(sm.control(co::SERVICE_CONTROL::PAUSE).err(),
+			Some(co::ERROR::CALL_NOT_IMPLEMENTED)); // not accepted at all
+		assert_eq!(sm.status().dwCheckPoint, 1); // untouched
+		assert!(sm.control(co::SERVICE_CONTROL::INTERROGATE).unwrap().is_none());
+	}
+
+	#[test]
+	fn control_stop() {
+		let mut sm = running(co::SERVICE_ACCEPT::STOP | co::SERVICE_ACCEPT::PARAMCHANGE);
+		assert_eq!(sm.status().dwControlsAccepted,
+			co::SERVICE_ACCEPT::STOP | co::SERVICE_ACCEPT::PARAMCHANGE);
+		assert!(sm.control(co::SERVICE_CONTROL::PARAMCHANGE).unwrap().is_none());
+		assert_eq!(sm.state(), S::RUNNING);
+
+		let st = sm.control(co::SERVICE_CONTROL::STOP).unwrap().unwrap();
+		assert_eq!(st.dwCurrentState, S::STOP_PENDING);
+		assert_eq!((st.dwCheckPoint, st.dwWaitHint), (1, 3000)); // control_wait_hint
+		assert_eq!(sm.control(co::SERVICE_CONTROL::STOP).err(),
+			Some(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL)); // already stopping
+	}
+
+	#[test]
+	fn control_pause_continue() {
+		let mut sm = running(co::SERVICE_ACCEPT::PAUSE_CONTINUE);
+		assert_eq!(sm.control(co::SERVICE_CONTROL::CONTINUE).err(),
+			Some(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL)); // continue while running
+
+		let st = sm.control(co::SERVICE_CONTROL::PAUSE).unwrap().unwrap();
+		assert_eq!(st.dwCurrentState, S::PAUSE_PENDING);
+		sm.set_state(S::PAUSED, 0).unwrap();
+
+		assert_eq!(sm.control(co::SERVICE_CONTROL::PAUSE).err(),
+			Some(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL)); // pause while paused
+		assert_eq!(sm.state(), S::PAUSED);
+
+		let st = sm.control(co::SERVICE_CONTROL::CONTINUE).unwrap().unwrap();
+		assert_eq!(st.dwCurrentState, S::CONTINUE_PENDING);
+		assert_eq!(st.dwCheckPoint, 1);
+	}
+
+	#[test]
+	fn exit_codes() {
+		let mut sm = running(co::SERVICE_ACCEPT::STOP);
+		sm.set_service_specific_exit_code(42);
+		let st = sm.set_state(S::STOPPED, 0).unwrap();
+		assert_eq!(st.dwWin32ExitCode, co::ERROR::SERVICE_SPECIFIC_ERROR);
+		assert_eq!(st.dwServiceSpecificExitCode, 42);
+
+		sm.set_exit_code(co::ERROR::ACCESS_DENIED);
+		let st = sm.status();
+		assert_eq!(st.dwWin32ExitCode, co::ERROR::ACCESS_DENIED);
+		assert_eq!(st.dwServiceSpecificExitCode, 0);
+		assert_eq!(st.dwServiceType, co::SERVICE_TYPE::WIN32_OWN_PROCESS);
+	}
+}
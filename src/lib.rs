@@ -187,6 +187,15 @@ pub mod msg {
 		pub use super::super::comctl::messages::pbm::*;
 	}
 
+	#[cfg(feature = "comctl")]
+	pub mod psm {
+		//! Property sheet
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-property-sheets-reference-messages),
+		//! whose constants have [`PSM`](crate::co::PSM) prefix.
+
+		pub use super::super::comctl::messages::psm::*;
+	}
+
 	#[cfg(feature = "comctl")]
 	pub mod sb {
 		//! Status bar control
@@ -223,6 +232,15 @@ pub mod msg {
 		pub use super::super::comctl::messages::tcm::*;
 	}
 
+	#[cfg(feature = "comctl")]
+	pub mod tdm {
+		//! Task dialog
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-task-dialogs-reference-messages),
+		//! whose constants have [`TDM`](crate::co::TDM) prefix.
+
+		pub use super::super::comctl::messages::tdm::*;
+	}
+
 	#[cfg(feature = "comctl")]
 	pub mod trbm {
 		//! Trackbar control
@@ -51,6 +51,52 @@ macro_rules! pub_struct_msg_empty_handleable {
 	};
 }
 
+/// Struct for WM_MOUSEWHEEL-based handleable messages.
+macro_rules! pub_struct_msg_wheel {
+	(
+		$name:ident : $wmconst:expr;
+		$( #[$doc:meta] )*
+	) => {
+		$( #[$doc] )*
+		/// message parameters.
+		///
+		/// Return type: `()`.
+		pub struct $name {
+			pub keys: co::MK,
+			pub wheel_delta: i16,
+			/// Mouse pointer position, in screen coordinates.
+			pub coords: POINT,
+		}
+
+		unsafe impl MsgSend for $name {
+			type RetType = ();
+
+			fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+			fn as_generic_wm(&mut self) -> WndMsg {
+				WndMsg {
+					msg_id: $wmconst,
+					wparam: MAKEDWORD(self.keys.raw(), self.wheel_delta as _) as _,
+					lparam: u32::from(self.coords) as _,
+				}
+			}
+		}
+
+		unsafe impl MsgSendRecv for $name {
+			fn from_generic_wm(p: WndMsg) -> Self {
+				Self {
+					keys: unsafe { co::MK::from_raw(LOWORD(p.wparam as _)) },
+					wheel_delta: HIWORD(p.wparam as _) as _,
+					coords: POINT {
+						x: LOWORD(p.lparam as _) as i16 as _,
+						y: HIWORD(p.lparam as _) as i16 as _,
+					},
+				}
+			}
+		}
+	};
+}
+
 /// Struct for WM_CHAR-based handleable messages.
 macro_rules! pub_struct_msg_char_code {
 	(
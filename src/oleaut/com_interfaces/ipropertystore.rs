@@ -122,4 +122,37 @@ pub trait oleaut_IPropertyStore: ole_IUnknown {
 			hr => Err(hr),
 		}
 	}
+
+	/// [`IPropertyStore::SetValue`](https://learn.microsoft.com/en-us/windows/win32/api/propsys/nf-propsys-ipropertystore-setvalue)
+	/// method.
+	///
+	/// Changes are only persisted after
+	/// [`IPropertyStore::Commit`](crate::prelude::oleaut_IPropertyStore::Commit).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let pstore: w::IPropertyStore; // initialized somewhere
+	/// # let pstore = unsafe { w::IPropertyStore::null() };
+	///
+	/// pstore.SetValue(
+	///     &w::PROPERTYKEY::Title,
+	///     &w::PROPVARIANT::new_str("My title")?,
+	/// )?;
+	/// pstore.Commit()?;
+	/// # Ok::<_, winsafe::co::HRESULT>(())
+	/// ```
+	fn SetValue(&self, key: &PROPERTYKEY, value: &PROPVARIANT) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<IPropertyStoreVT>(self).SetValue)(
+					self.ptr(),
+					key as *const _ as _,
+					value as *const _ as _,
+				)
+			},
+		)
+	}
 }
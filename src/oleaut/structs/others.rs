@@ -19,3 +19,27 @@ impl PROPERTYKEY {
 		Self { fmtid, pid: PID_FIRST_USABLE }
 	}
 }
+
+#[allow(non_upper_case_globals)]
+impl PROPERTYKEY {
+	/// [`PKEY_Title`](https://learn.microsoft.com/en-us/windows/win32/properties/props-system-title)
+	/// property key, a [`co::VT::LPWSTR`](crate::co::VT::LPWSTR) string.
+	pub const Title: Self = Self {
+		fmtid: GUID::new("f29f85e0-4ff9-1068-ab91-08002b27b3d9"),
+		pid: 2,
+	};
+
+	/// [`PKEY_AppUserModel_ID`](https://learn.microsoft.com/en-us/windows/win32/properties/props-system-appusermodel-id)
+	/// property key, a [`co::VT::LPWSTR`](crate::co::VT::LPWSTR) string.
+	pub const AppUserModel_ID: Self = Self {
+		fmtid: GUID::new("9f4c2855-9f79-4b39-a8d0-e1d42de1d5f3"),
+		pid: 5,
+	};
+
+	/// [`PKEY_AppUserModel_IsDestListSeparator`](https://learn.microsoft.com/en-us/windows/win32/properties/props-system-appusermodel-isdestlistseparator)
+	/// property key, a [`co::VT::BOOL`](crate::co::VT::BOOL) value.
+	pub const AppUserModel_IsDestListSeparator: Self = Self {
+		fmtid: GUID::new("9f4c2855-9f79-4b39-a8d0-e1d42de1d5f3"),
+		pid: 6,
+	};
+}
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use crate::co;
+use crate::decl::*;
 use crate::oleaut::ffi;
 use crate::prelude::*;
 
@@ -87,4 +88,36 @@ impl PROPVARIANT {
 			None
 		}
 	}
+
+	/// Creates a new object holding a [`co::VT::LPWSTR`](crate::co::VT::LPWSTR)
+	/// string, allocated with
+	/// [`CoTaskMemAlloc`](crate::CoTaskMemAlloc).
+	///
+	/// This is the string type expected by most property keys, like
+	/// [`PROPERTYKEY::Title`](crate::PROPERTYKEY::Title).
+	#[must_use]
+	pub fn new_str(val: &str) -> HrResult<Self> {
+		let wstr = WString::from_str(val);
+		let num_bytes = (wstr.str_len() + 1) * std::mem::size_of::<u16>(); // including terminating null
+		let mut pmem = CoTaskMemAlloc(num_bytes)?;
+		pmem.as_mut_slice()
+			.chunks_exact_mut(2)
+			.zip(wstr.as_slice().iter().chain(std::iter::repeat(&0)))
+			.for_each(|(dest, ch)| dest.copy_from_slice(&ch.to_ne_bytes()));
+		let ptr = pmem.leak().0 as usize; // will be freed by PropVariantClear
+		Ok(unsafe { Self::from_raw(co::VT::LPWSTR, &ptr.to_ne_bytes()) })
+	}
+
+	/// If the object holds a [`co::VT::LPWSTR`](crate::co::VT::LPWSTR)
+	/// string, returns it, otherwise `None`.
+	#[must_use]
+	pub fn str(&self) -> Option<String> {
+		if self.vt() == co::VT::LPWSTR {
+			let ptr = usize::from_ne_bytes(
+				self.raw()[..std::mem::size_of::<usize>()].try_into().unwrap());
+			Some(unsafe { WString::from_wchars_nullt(ptr as _) }.to_string())
+		} else {
+			None
+		}
+	}
 }
@@ -168,6 +168,15 @@ const_bitflag! { GPS: u32;
 	MASK_VALID 0x1fff
 }
 
+const_ordinary! { KDC: u32;
+	/// [`KNOWNDESTCATEGORY`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/ne-shobjidl_core-knowndestcategory)
+	/// enumeration (`u32`).
+	=>
+	=>
+	FREQUENT 1
+	RECENT 2
+}
+
 const_bitflag! { KF: u32;
 	/// [`KNOWN_FOLDER_FLAG`](https://learn.microsoft.com/en-us/windows/win32/api/shlobj_core/ne-shlobj_core-known_folder_flag)
 	/// enumeration (`u32`).
@@ -524,6 +533,41 @@ const_bitflag! { SLGP: u32;
 	RELATIVEPRIORITY 0x8
 }
 
+const_bitflag! { SLDF: u32;
+	/// [`SHELL_LINK_DATA_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/shlobj_core/ne-shlobj_core-shell_link_data_flags)
+	/// enumeration (`u32`).
+	=>
+	=>
+	DEFAULT 0x0000_0000
+	HAS_ID_LIST 0x0000_0001
+	HAS_LINK_INFO 0x0000_0002
+	HAS_NAME 0x0000_0004
+	HAS_RELPATH 0x0000_0008
+	HAS_WORKINGDIR 0x0000_0010
+	HAS_ARGS 0x0000_0020
+	HAS_ICONLOCATION 0x0000_0040
+	UNICODE 0x0000_0080
+	FORCE_NO_LINKINFO 0x0000_0100
+	HAS_EXP_SZ 0x0000_0200
+	RUN_IN_SEPARATE 0x0000_0400
+	HAS_DARWINID 0x0000_1000
+	RUNAS_USER 0x0000_2000
+	HAS_EXP_ICON_SZ 0x0000_4000
+	NO_PIDL_ALIAS 0x0000_8000
+	FORCE_UNCNAME 0x0001_0000
+	RUN_WITH_SHIMLAYER 0x0002_0000
+	FORCE_NO_LINKTRACK 0x0004_0000
+	ENABLE_TARGET_METADATA 0x0008_0000
+	DISABLE_LINK_PATH_TRACKING 0x0010_0000
+	DISABLE_KNOWNFOLDER_RELATIVE_TRACKING 0x0020_0000
+	NO_KF_ALIAS 0x0040_0000
+	ALLOW_LINK_TO_LINK 0x0080_0000
+	UNALIAS_ON_SAVE 0x0100_0000
+	PREFER_ENVIRONMENT_PATH 0x0200_0000
+	KEEP_LOCAL_IDLIST_FOR_UNC_TARGET 0x0400_0000
+	PERSIST_VOLUME_ID_RELATIVE 0x0800_0000
+}
+
 const_bitflag! { SLR: u32;
 	/// [`IShellLink::Resolve`](crate::prelude::shell_IShellLink::GetPath)
 	/// `flags` (`u32`).
@@ -592,3 +636,36 @@ const_ordinary! { TBPF: u32;
 	/// generic percentage not indicative of actual progress.
 	PAUSED 0x8
 }
+
+const_bitflag! { THB: u32;
+	/// [`THUMBBUTTONMASK`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/ne-shobjidl_core-thumbbuttonmask)
+	/// enumeration (`u32`).
+	=>
+	=>
+	BITMAP 0x0000_0001
+	ICON 0x0000_0002
+	TOOLTIP 0x0000_0004
+	FLAGS 0x0000_0008
+}
+
+const_bitflag! { THBF: u32;
+	/// [`THUMBBUTTONFLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/ne-shobjidl_core-thumbbuttonflags)
+	/// enumeration (`u32`).
+	=>
+	=>
+	ENABLED 0x0000_0000
+	DISABLED 0x0000_0001
+	DISMISSONCLICK 0x0000_0002
+	NOBACKGROUND 0x0000_0004
+	HIDDEN 0x0000_0008
+	NONINTERACTIVE 0x0000_0010
+}
+
+const_cmd! { THBN;
+	/// Thumbnail toolbar button
+	/// [notifications](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-itaskbarlist3-thumbbaraddbuttons#remarks)
+	/// (`u16`).
+	=>
+	=>
+	CLICKED 0x1800
+}
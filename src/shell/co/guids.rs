@@ -3,6 +3,8 @@
 use crate::co::*;
 
 const_guid_values! { CLSID;
+	DestinationList "77f10cf0-3db5-4966-b520-b7c54fd35ed6"
+	EnumerableObjectCollection "2d3468c1-36a7-43b6-ac24-d3f02fd9607a"
 	FileOpenDialog "dc1c5a9c-e88a-4dde-a5a1-60f82a20aef7"
 	FileSaveDialog "c0b4e2f3-ba21-4773-8dba-335ec946eb8b"
 	ShellLink "00021401-0000-0000-c000-000000000046"
@@ -0,0 +1,177 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
+use crate::decl::*;
+use crate::kernel::ffi_types::*;
+use crate::ole::privs::*;
+use crate::prelude::*;
+use crate::vt::*;
+
+/// [`ICustomDestinationList`](crate::ICustomDestinationList) virtual table.
+#[repr(C)]
+pub struct ICustomDestinationListVT {
+	pub IUnknownVT: IUnknownVT,
+	pub SetAppID: fn(COMPTR, PCSTR) -> HRES,
+	pub BeginList: fn(COMPTR, *mut u32, PCVOID, *mut COMPTR) -> HRES,
+	pub AppendCategory: fn(COMPTR, PCSTR, COMPTR) -> HRES,
+	pub AppendKnownCategory: fn(COMPTR, u32) -> HRES,
+	pub AddUserTasks: fn(COMPTR, COMPTR) -> HRES,
+	pub CommitList: fn(COMPTR) -> HRES,
+	pub GetRemovedDestinations: fn(COMPTR, PCVOID, *mut COMPTR) -> HRES,
+	pub DeleteList: fn(COMPTR, PCSTR) -> HRES,
+	pub AbortList: fn(COMPTR) -> HRES,
+}
+
+com_interface! { ICustomDestinationList: "6332debf-87b5-4670-90c0-5e57b408a49e";
+	/// [`ICustomDestinationList`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nn-shobjidl_core-icustomdestinationlist)
+	/// COM interface over
+	/// [`ICustomDestinationListVT`](crate::vt::ICustomDestinationListVT).
+	///
+	/// Automatically calls
+	/// [`IUnknown::Release`](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release)
+	/// when the object goes out of scope.
+	///
+	/// For a higher-level approach, see [`JumpList`](crate::JumpList).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let obj = w::CoCreateInstance::<w::ICustomDestinationList>(
+	///     &co::CLSID::DestinationList,
+	///     None,
+	///     co::CLSCTX::INPROC_SERVER,
+	/// )?;
+	/// # Ok::<_, co::HRESULT>(())
+	/// ```
+}
+
+impl shell_ICustomDestinationList for ICustomDestinationList {}
+
+/// This trait is enabled with the `shell` feature, and provides methods for
+/// [`ICustomDestinationList`](crate::ICustomDestinationList).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait shell_ICustomDestinationList: ole_IUnknown {
+	fn_com_noparm! { AbortList: ICustomDestinationListVT;
+		/// [`ICustomDestinationList::AbortList`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-abortlist)
+		/// method.
+	}
+
+	/// [`ICustomDestinationList::AddUserTasks`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-addusertasks)
+	/// method.
+	fn AddUserTasks(&self, tasks: &impl shell_IObjectArray) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).AddUserTasks)(
+					self.ptr(),
+					tasks.ptr(),
+				)
+			},
+		)
+	}
+
+	/// [`ICustomDestinationList::AppendCategory`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-appendcategory)
+	/// method.
+	fn AppendCategory(&self,
+		category: &str,
+		items: &impl shell_IObjectArray,
+	) -> HrResult<()>
+	{
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).AppendCategory)(
+					self.ptr(),
+					WString::from_str(category).as_ptr(),
+					items.ptr(),
+				)
+			},
+		)
+	}
+
+	/// [`ICustomDestinationList::AppendKnownCategory`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-appendknowncategory)
+	/// method.
+	fn AppendKnownCategory(&self, category: co::KDC) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).AppendKnownCategory)(
+					self.ptr(),
+					category.raw(),
+				)
+			},
+		)
+	}
+
+	/// [`ICustomDestinationList::BeginList`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-beginlist)
+	/// method.
+	///
+	/// Returns the maximum number of items that fit in the Jump List, and the
+	/// items removed by the user since the last list was committed.
+	#[must_use]
+	fn BeginList(&self) -> HrResult<(u32, IObjectArray)> {
+		let mut min_slots = u32::default();
+		let mut queried = unsafe { IObjectArray::null() };
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).BeginList)(
+					self.ptr(),
+					&mut min_slots,
+					&IObjectArray::IID as *const _ as _,
+					queried.as_mut(),
+				)
+			},
+		).map(|_| (min_slots, queried))
+	}
+
+	fn_com_noparm! { CommitList: ICustomDestinationListVT;
+		/// [`ICustomDestinationList::CommitList`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-commitlist)
+		/// method.
+	}
+
+	/// [`ICustomDestinationList::DeleteList`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-deletelist)
+	/// method.
+	fn DeleteList(&self, app_id: Option<&str>) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).DeleteList)(
+					self.ptr(),
+					WString::from_opt_str(app_id).as_ptr(),
+				)
+			},
+		)
+	}
+
+	/// [`ICustomDestinationList::GetRemovedDestinations`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-getremoveddestinations)
+	/// method.
+	#[must_use]
+	fn GetRemovedDestinations(&self) -> HrResult<IObjectArray> {
+		let mut queried = unsafe { IObjectArray::null() };
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).GetRemovedDestinations)(
+					self.ptr(),
+					&IObjectArray::IID as *const _ as _,
+					queried.as_mut(),
+				)
+			},
+		).map(|_| queried)
+	}
+
+	/// [`ICustomDestinationList::SetAppID`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-icustomdestinationlist-setappid)
+	/// method.
+	fn SetAppID(&self, app_id: &str) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ICustomDestinationListVT>(self).SetAppID)(
+					self.ptr(),
+					WString::from_str(app_id).as_ptr(),
+				)
+			},
+		)
+	}
+}
@@ -0,0 +1,82 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::decl::*;
+use crate::kernel::ffi_types::*;
+use crate::ole::privs::*;
+use crate::prelude::*;
+use crate::vt::*;
+
+/// [`IObjectArray`](crate::IObjectArray) virtual table.
+#[repr(C)]
+pub struct IObjectArrayVT {
+	pub IUnknownVT: IUnknownVT,
+	pub GetCount: fn(COMPTR, *mut u32) -> HRES,
+	pub GetAt: fn(COMPTR, u32, PCVOID, *mut COMPTR) -> HRES,
+}
+
+com_interface! { IObjectArray: "92ca9dcd-5622-4bba-a805-5e9f541bd8c9";
+	/// [`IObjectArray`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nn-objectarray-iobjectarray)
+	/// COM interface over [`IObjectArrayVT`](crate::vt::IObjectArrayVT).
+	///
+	/// Automatically calls
+	/// [`IUnknown::Release`](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release)
+	/// when the object goes out of scope.
+}
+
+impl shell_IObjectArray for IObjectArray {}
+
+/// This trait is enabled with the `shell` feature, and provides methods for
+/// [`IObjectArray`](crate::IObjectArray).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait shell_IObjectArray: ole_IUnknown {
+	/// [`IObjectArray::GetAt`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nf-objectarray-iobjectarray-getat)
+	/// method.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let arr: w::IObjectArray; // initialized somewhere
+	/// # let arr = unsafe { w::IObjectArray::null() };
+	///
+	/// for i in 0..arr.GetCount()? {
+	///     let link = arr.GetAt::<w::IShellLink>(i)?;
+	///     println!("{}", link.GetArguments()?);
+	/// }
+	/// # Ok::<_, winsafe::co::HRESULT>(())
+	/// ```
+	#[must_use]
+	fn GetAt<T>(&self, index: u32) -> HrResult<T>
+		where T: ole_IUnknown,
+	{
+		let mut queried = unsafe { T::null() };
+		ok_to_hrresult(
+			unsafe {
+				(vt::<IObjectArrayVT>(self).GetAt)(
+					self.ptr(),
+					index,
+					&T::IID as *const _ as _,
+					queried.as_mut(),
+				)
+			},
+		).map(|_| queried)
+	}
+
+	/// [`IObjectArray::GetCount`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nf-objectarray-iobjectarray-getcount)
+	/// method.
+	#[must_use]
+	fn GetCount(&self) -> HrResult<u32> {
+		let mut count = u32::default();
+		ok_to_hrresult(
+			unsafe {
+				(vt::<IObjectArrayVT>(self).GetCount)(self.ptr(), &mut count)
+			},
+		).map(|_| count)
+	}
+}
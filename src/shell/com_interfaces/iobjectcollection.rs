@@ -0,0 +1,91 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::decl::*;
+use crate::kernel::ffi_types::*;
+use crate::ole::privs::*;
+use crate::prelude::*;
+use crate::vt::*;
+
+/// [`IObjectCollection`](crate::IObjectCollection) virtual table.
+#[repr(C)]
+pub struct IObjectCollectionVT {
+	pub IObjectArrayVT: IObjectArrayVT,
+	pub AddObject: fn(COMPTR, COMPTR) -> HRES,
+	pub AddFromArray: fn(COMPTR, COMPTR) -> HRES,
+	pub RemoveObjectAt: fn(COMPTR, u32) -> HRES,
+	pub Clear: fn(COMPTR) -> HRES,
+}
+
+com_interface! { IObjectCollection: "5632b1a4-e38a-400a-928a-d4cd63230295";
+	/// [`IObjectCollection`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nn-objectarray-iobjectcollection)
+	/// COM interface over
+	/// [`IObjectCollectionVT`](crate::vt::IObjectCollectionVT).
+	///
+	/// Automatically calls
+	/// [`IUnknown::Release`](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release)
+	/// when the object goes out of scope.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let obj = w::CoCreateInstance::<w::IObjectCollection>(
+	///     &co::CLSID::EnumerableObjectCollection,
+	///     None,
+	///     co::CLSCTX::INPROC_SERVER,
+	/// )?;
+	/// # Ok::<_, co::HRESULT>(())
+	/// ```
+}
+
+impl shell_IObjectArray for IObjectCollection {}
+impl shell_IObjectCollection for IObjectCollection {}
+
+/// This trait is enabled with the `shell` feature, and provides methods for
+/// [`IObjectCollection`](crate::IObjectCollection).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait shell_IObjectCollection: shell_IObjectArray {
+	/// [`IObjectCollection::AddFromArray`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nf-objectarray-iobjectcollection-addfromarray)
+	/// method.
+	fn AddFromArray(&self, source: &impl shell_IObjectArray) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<IObjectCollectionVT>(self).AddFromArray)(
+					self.ptr(),
+					source.ptr(),
+				)
+			},
+		)
+	}
+
+	/// [`IObjectCollection::AddObject`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nf-objectarray-iobjectcollection-addobject)
+	/// method.
+	fn AddObject(&self, obj: &impl ole_IUnknown) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<IObjectCollectionVT>(self).AddObject)(self.ptr(), obj.ptr())
+			},
+		)
+	}
+
+	fn_com_noparm! { Clear: IObjectCollectionVT;
+		/// [`IObjectCollection::Clear`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nf-objectarray-iobjectcollection-clear)
+		/// method.
+	}
+
+	/// [`IObjectCollection::RemoveObjectAt`](https://learn.microsoft.com/en-us/windows/win32/api/objectarray/nf-objectarray-iobjectcollection-removeobjectat)
+	/// method.
+	fn RemoveObjectAt(&self, index: u32) -> HrResult<()> {
+		ok_to_hrresult(
+			unsafe {
+				(vt::<IObjectCollectionVT>(self).RemoveObjectAt)(self.ptr(), index)
+			},
+		)
+	}
+}
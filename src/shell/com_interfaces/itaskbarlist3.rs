@@ -189,7 +189,7 @@ pub trait shell_ITaskbarList3: shell_ITaskbarList2 {
 				(vt::<ITaskbarList3VT>(self).SetThumbnailClip)(
 					self.ptr(),
 					hwnd.ptr(),
-					&clip as *const _ as _,
+					clip.as_ref().map_or(std::ptr::null_mut(), |rc| rc as *const _ as _),
 				)
 			},
 		)
@@ -202,12 +202,55 @@ pub trait shell_ITaskbarList3: shell_ITaskbarList2 {
 		tip: Option<&str>,
 	) -> HrResult<()>
 	{
+		let tip_w = WString::from_opt_str(tip);
 		ok_to_hrresult(
 			unsafe {
 				(vt::<ITaskbarList3VT>(self).SetThumbnailTooltip)(
 					self.ptr(),
 					hwnd.ptr(),
-					tip.map_or(std::ptr::null_mut(), |s| WString::from_str(s).as_ptr()),
+					tip_w.as_ptr(),
+				)
+			},
+		)
+	}
+
+	/// [`ITaskbarList3::ThumbBarAddButtons`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-itaskbarlist3-thumbbaraddbuttons)
+	/// method.
+	///
+	/// Up to 7 buttons can be added, and only once per window. Button clicks
+	/// are notified through [`wm::Command`](crate::msg::wm::Command) messages
+	/// with [`co::THBN::CLICKED`](crate::co::THBN::CLICKED) code.
+	fn ThumbBarAddButtons(&self,
+		hwnd: &HWND,
+		buttons: &[THUMBBUTTON],
+	) -> HrResult<()>
+	{
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ITaskbarList3VT>(self).ThumbBarAddButtons)(
+					self.ptr(),
+					hwnd.ptr(),
+					buttons.len() as _,
+					buttons.as_ptr() as _,
+				)
+			},
+		)
+	}
+
+	/// [`ITaskbarList3::ThumbBarUpdateButtons`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-itaskbarlist3-thumbbarupdatebuttons)
+	/// method.
+	fn ThumbBarUpdateButtons(&self,
+		hwnd: &HWND,
+		buttons: &[THUMBBUTTON],
+	) -> HrResult<()>
+	{
+		ok_to_hrresult(
+			unsafe {
+				(vt::<ITaskbarList3VT>(self).ThumbBarUpdateButtons)(
+					self.ptr(),
+					hwnd.ptr(),
+					buttons.len() as _,
+					buttons.as_ptr() as _,
 				)
 			},
 		)
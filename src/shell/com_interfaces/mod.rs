@@ -1,9 +1,12 @@
+mod icustomdestinationlist;
 mod ienumshellitems;
 mod ifiledialog;
 mod ifiledialogevents;
 mod ifileopendialog;
 mod ifilesavedialog;
 mod imodalwindow;
+mod iobjectarray;
+mod iobjectcollection;
 mod ishellitem;
 mod ishellitem2;
 mod ishellitemarray;
@@ -14,12 +17,15 @@ mod itaskbarlist3;
 mod itaskbarlist4;
 
 pub mod decl {
+	pub use super::icustomdestinationlist::ICustomDestinationList;
 	pub use super::ienumshellitems::IEnumShellItems;
 	pub use super::ifiledialog::IFileDialog;
 	pub use super::ifiledialogevents::IFileDialogEvents;
 	pub use super::ifileopendialog::IFileOpenDialog;
 	pub use super::ifilesavedialog::IFileSaveDialog;
 	pub use super::imodalwindow::IModalWindow;
+	pub use super::iobjectarray::IObjectArray;
+	pub use super::iobjectcollection::IObjectCollection;
 	pub use super::ishellitem::IShellItem;
 	pub use super::ishellitem2::IShellItem2;
 	pub use super::ishellitemarray::IShellItemArray;
@@ -31,12 +37,15 @@ pub mod decl {
 }
 
 pub mod traits {
+	pub use super::icustomdestinationlist::shell_ICustomDestinationList;
 	pub use super::ienumshellitems::shell_IEnumShellItems;
 	pub use super::ifiledialog::shell_IFileDialog;
 	pub use super::ifiledialogevents::shell_IFileDialogEvents;
 	pub use super::ifileopendialog::shell_IFileOpenDialog;
 	pub use super::ifilesavedialog::shell_IFileSaveDialog;
 	pub use super::imodalwindow::shell_IModalWindow;
+	pub use super::iobjectarray::shell_IObjectArray;
+	pub use super::iobjectcollection::shell_IObjectCollection;
 	pub use super::ishellitem::shell_IShellItem;
 	pub use super::ishellitem2::shell_IShellItem2;
 	pub use super::ishellitemarray::shell_IShellItemArray;
@@ -48,12 +57,15 @@ pub mod traits {
 }
 
 pub mod vt {
+	pub use super::icustomdestinationlist::ICustomDestinationListVT;
 	pub use super::ienumshellitems::IEnumShellItemsVT;
 	pub use super::ifiledialog::IFileDialogVT;
 	pub use super::ifiledialogevents::IFileDialogEventsVT;
 	pub use super::ifileopendialog::IFileOpenDialogVT;
 	pub use super::ifilesavedialog::IFileSaveDialogVT;
 	pub use super::imodalwindow::IModalWindowVT;
+	pub use super::iobjectarray::IObjectArrayVT;
+	pub use super::iobjectcollection::IObjectCollectionVT;
 	pub use super::ishellitem::IShellItemVT;
 	pub use super::ishellitem2::IShellItem2VT;
 	pub use super::ishellitemarray::IShellItemArrayVT;
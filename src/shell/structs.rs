@@ -143,3 +143,21 @@ impl_default_with_size!(SHSTOCKICONINFO, cbSize);
 impl SHSTOCKICONINFO {
 	pub_fn_string_arr_get_set!(szPath, get_szPath);
 }
+
+/// [`THUMBBUTTON`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/ns-shobjidl_core-thumbbutton)
+/// struct.
+#[repr(C)]
+pub struct THUMBBUTTON {
+	pub dwMask: co::THB,
+	pub iId: u32,
+	pub iBitmap: u32,
+	pub hIcon: HICON,
+	szTip: [u16; 260],
+	pub dwFlags: co::THBF,
+}
+
+impl_default!(THUMBBUTTON);
+
+impl THUMBBUTTON {
+	pub_fn_string_arr_get_set!(szTip, set_szTip);
+}
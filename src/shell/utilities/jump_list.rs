@@ -0,0 +1,287 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// A task or an item of a custom category of a [`JumpList`](crate::JumpList),
+/// created as an [`IShellLink`](crate::IShellLink).
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct JumpListLink {
+	/// Text displayed in the Jump List.
+	pub title: String,
+	/// Path of the program to be launched. If empty, the current executable
+	/// is used.
+	pub path: String,
+	/// Command line arguments passed to the program.
+	pub arguments: String,
+	/// Tooltip displayed when the mouse hovers the item.
+	pub description: String,
+	/// Path of the file containing the icon, and its index within the file.
+	/// If `None`, the icon of the program is used.
+	pub icon: Option<(String, i32)>,
+	/// Working directory of the launched program.
+	pub working_dir: String,
+}
+
+enum JumpListEntry {
+	Link(JumpListLink),
+	Separator,
+}
+
+enum JumpListCategory {
+	Custom(String, Vec<JumpListLink>),
+	Known(co::KDC),
+}
+
+/// High-level builder of the taskbar
+/// [Jump List](https://learn.microsoft.com/en-us/windows/win32/shell/taskbar-extensions#jump-lists)
+/// of the application, over an
+/// [`ICustomDestinationList`](crate::ICustomDestinationList).
+///
+/// Items which were removed by the user from the Jump List are reported by
+/// [`commit`](crate::JumpList::commit), and they are automatically left out
+/// of the custom categories, since the shell refuses to display them again.
+///
+/// Note that the known categories are populated by the shell only with files
+/// whose type is registered to the application, and which were added with
+/// [`SHAddToRecentDocs`](crate::SHAddToRecentDocs) or opened with the common
+/// file dialogs.
+///
+/// COM must have been initialized in the current thread.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let _com_guard = w::CoInitializeEx(
+///     co::COINIT::APARTMENTTHREADED | co::COINIT::DISABLE_OLE1DDE)?;
+///
+/// let removed = w::JumpList::new()
+///     .add_task(w::JumpListLink {
+///         title: "New window".to_owned(),
+///         arguments: "--new-window".to_owned(),
+///         ..Default::default()
+///     })
+///     .add_separator()
+///     .add_task(w::JumpListLink {
+///         title: "Settings".to_owned(),
+///         arguments: "--settings".to_owned(),
+///         icon: Some(("shell32.dll".to_owned(), 316)),
+///         ..Default::default()
+///     })
+///     .add_known_category(co::KDC::RECENT)
+///     .add_category("Projects", vec![
+///         w::JumpListLink {
+///             title: "Project A".to_owned(),
+///             arguments: "C:\\Projects\\a.proj".to_owned(),
+///             ..Default::default()
+///         },
+///     ])
+///     .commit()?;
+///
+/// for link in removed.iter() {
+///     println!("Removed by the user: {}", link.title);
+/// }
+/// # Ok::<_, co::HRESULT>(())
+/// ```
+#[derive(Default)]
+pub struct JumpList {
+	app_id: Option<String>,
+	tasks: Vec<JumpListEntry>,
+	categories: Vec<JumpListCategory>,
+}
+
+impl JumpList {
+	/// Creates a new, empty Jump List.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the explicit
+	/// [AppUserModelID](https://learn.microsoft.com/en-us/windows/win32/shell/appids)
+	/// of the application. Only needed if the process has an explicit ID.
+	#[must_use]
+	pub fn app_id(mut self, app_id: &str) -> Self {
+		self.app_id = Some(app_id.to_owned());
+		self
+	}
+
+	/// Adds a task to the "Tasks" category.
+	#[must_use]
+	pub fn add_task(mut self, link: JumpListLink) -> Self {
+		self.tasks.push(JumpListEntry::Link(link));
+		self
+	}
+
+	/// Adds a separator line to the "Tasks" category.
+	#[must_use]
+	pub fn add_separator(mut self) -> Self {
+		self.tasks.push(JumpListEntry::Separator);
+		self
+	}
+
+	/// Adds a custom category with the given items.
+	#[must_use]
+	pub fn add_category(mut self, name: &str, links: Vec<JumpListLink>) -> Self {
+		self.categories.push(JumpListCategory::Custom(name.to_owned(), links));
+		self
+	}
+
+	/// Adds a known category, maintained by the shell.
+	#[must_use]
+	pub fn add_known_category(mut self, category: co::KDC) -> Self {
+		self.categories.push(JumpListCategory::Known(category));
+		self
+	}
+
+	/// Builds the Jump List and replaces the current one. Returns the items
+	/// which were removed by the user since the last commit.
+	///
+	/// If an error occurs, the current Jump List is left untouched.
+	pub fn commit(&self) -> HrResult<Vec<JumpListLink>> {
+		let cdl = CoCreateInstance::<ICustomDestinationList>(
+			&co::CLSID::DestinationList, None, co::CLSCTX::INPROC_SERVER)?;
+		if let Some(app_id) = &self.app_id {
+			cdl.SetAppID(app_id)?;
+		}
+
+		let (_, removed_arr) = cdl.BeginList()?;
+		match self.build(&cdl, &removed_arr) {
+			Ok(removed) => {
+				cdl.CommitList()?;
+				Ok(removed)
+			},
+			Err(e) => {
+				cdl.AbortList().unwrap_or_default();
+				Err(e)
+			},
+		}
+	}
+
+	/// Removes the Jump List of the application.
+	pub fn delete(app_id: Option<&str>) -> HrResult<()> {
+		let cdl = CoCreateInstance::<ICustomDestinationList>(
+			&co::CLSID::DestinationList, None, co::CLSCTX::INPROC_SERVER)?;
+		cdl.DeleteList(app_id)
+	}
+
+	fn build(&self,
+		cdl: &ICustomDestinationList,
+		removed_arr: &IObjectArray,
+	) -> HrResult<Vec<JumpListLink>>
+	{
+		let removed = Self::read_removed(removed_arr)?;
+		let exe_path = HINSTANCE::NULL.GetModuleFileName()
+			.map_err(|e| e.to_hresult())?;
+
+		// Known categories are appended in the order they were added, among
+		// the custom ones, so the user controls their placement.
+		for category in self.categories.iter() {
+			match category {
+				JumpListCategory::Custom(name, links) => {
+					let coll = Self::new_collection()?;
+					for link in links.iter() {
+						if !removed.iter().any(|r| Self::same_link(r, link, &exe_path)) {
+							coll.AddObject(&Self::create_link(link, &exe_path)?)?;
+						}
+					}
+					if coll.GetCount()? > 0 {
+						cdl.AppendCategory(name, &coll)?;
+					}
+				},
+				JumpListCategory::Known(kdc) => cdl.AppendKnownCategory(*kdc)?,
+			}
+		}
+
+		if !self.tasks.is_empty() {
+			let coll = Self::new_collection()?;
+			for entry in self.tasks.iter() {
+				coll.AddObject(&match entry {
+					JumpListEntry::Link(link) => Self::create_link(link, &exe_path)?,
+					JumpListEntry::Separator => Self::create_separator()?,
+				})?;
+			}
+			cdl.AddUserTasks(&coll)?;
+		}
+
+		Ok(removed)
+	}
+
+	fn new_collection() -> HrResult<IObjectCollection> {
+		CoCreateInstance::<IObjectCollection>(
+			&co::CLSID::EnumerableObjectCollection,
+			None,
+			co::CLSCTX::INPROC_SERVER,
+		)
+	}
+
+	fn create_link(link: &JumpListLink, exe_path: &str) -> HrResult<IShellLink> {
+		let sl = CoCreateInstance::<IShellLink>(
+			&co::CLSID::ShellLink, None, co::CLSCTX::INPROC_SERVER)?;
+		sl.SetPath(if link.path.is_empty() { exe_path } else { &link.path })?;
+		sl.SetArguments(&link.arguments)?;
+		if !link.description.is_empty() {
+			sl.SetDescription(&link.description)?;
+		}
+		if let Some((icon_path, icon_index)) = &link.icon {
+			sl.SetIconLocation(icon_path, *icon_index)?;
+		}
+		if !link.working_dir.is_empty() {
+			sl.SetWorkingDirectory(&link.working_dir)?;
+		}
+
+		let pstore = sl.QueryInterface::<IPropertyStore>()?;
+		pstore.SetValue(&PROPERTYKEY::Title, &PROPVARIANT::new_str(&link.title)?)?;
+		pstore.Commit()?;
+		Ok(sl)
+	}
+
+	fn create_separator() -> HrResult<IShellLink> {
+		let sl = CoCreateInstance::<IShellLink>(
+			&co::CLSID::ShellLink, None, co::CLSCTX::INPROC_SERVER)?;
+		let pstore = sl.QueryInterface::<IPropertyStore>()?;
+		pstore.SetValue(
+			&PROPERTYKEY::AppUserModel_IsDestListSeparator,
+			&PROPVARIANT::new_bool(true),
+		)?;
+		pstore.Commit()?;
+		Ok(sl)
+	}
+
+	/// Reads the removed shell links; other removed objects, like shell items
+	/// of the known categories, are ignored.
+	fn read_removed(removed_arr: &IObjectArray) -> HrResult<Vec<JumpListLink>> {
+		let mut removed = Vec::<JumpListLink>::default();
+		for i in 0..removed_arr.GetCount()? {
+			let sl = match removed_arr.GetAt::<IShellLink>(i) {
+				Ok(sl) => sl,
+				Err(co::HRESULT::E_NOINTERFACE) => continue,
+				Err(e) => return Err(e),
+			};
+
+			let title = sl.QueryInterface::<IPropertyStore>()
+				.and_then(|pstore| pstore.GetValue(&PROPERTYKEY::Title))
+				.ok()
+				.and_then(|pv| pv.str())
+				.unwrap_or_default();
+			let (icon_path, icon_index) = sl.GetIconLocation()?;
+
+			removed.push(JumpListLink {
+				title,
+				path: sl.GetPath(None, co::SLGP::RAWPATH)?,
+				arguments: sl.GetArguments()?,
+				description: sl.GetDescription()?,
+				icon: if icon_path.is_empty() { None } else { Some((icon_path, icon_index)) },
+				working_dir: sl.GetWorkingDirectory()?,
+			});
+		}
+		Ok(removed)
+	}
+
+	fn same_link(removed: &JumpListLink, link: &JumpListLink, exe_path: &str) -> bool {
+		let link_path = if link.path.is_empty() { exe_path } else { &link.path };
+		removed.arguments == link.arguments
+			&& removed.path.eq_ignore_ascii_case(link_path)
+	}
+}
@@ -0,0 +1,1184 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+const HEADER_SIZE: u32 = 0x4c;
+const LINK_CLSID: GUID = GUID::new("00021401-0000-0000-c000-000000000046");
+
+const SIG_ENVIRONMENT: u32 = 0xa000_0001;
+const SIG_TRACKER: u32 = 0xa000_0003;
+const SIG_SPECIAL_FOLDER: u32 = 0xa000_0005;
+const SIG_ICON_ENVIRONMENT: u32 = 0xa000_0007;
+const SIG_KNOWN_FOLDER: u32 = 0xa000_000b;
+
+/// Pure-Rust reader and writer of the
+/// [Shell Link](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-shllink/16cb4ca1-9339-4d0c-a68d-bf1d6cc0f943)
+/// binary format, used by `.lnk` shortcut files.
+///
+/// Unlike [`IShellLink`](crate::IShellLink), parsing and serializing don't
+/// need COM nor a Windows host, so shortcuts can be processed offline.
+/// Serialized strings are always written as UTF-16; ANSI strings are decoded
+/// as Windows-1252.
+///
+/// When running on Windows, the data can be exchanged with `IShellLink` with
+/// [`from_shell_link`](crate::LnkFile::from_shell_link) and
+/// [`to_shell_link`](crate::LnkFile::to_shell_link).
+///
+/// # Examples
+///
+/// Reading a shortcut:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let bytes = std::fs::read("C:\\Temp\\foo.lnk").unwrap();
+/// let lnk = w::LnkFile::parse(&bytes)?;
+///
+/// println!("Target: {}", lnk.target_path().unwrap_or_default());
+/// println!("Arguments: {}", lnk.arguments.as_deref().unwrap_or_default());
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+///
+/// Creating a shortcut:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let lnk = w::LnkFile {
+///     link_info: Some(w::LnkLinkInfo {
+///         volume: Some(w::LnkVolumeId {
+///             drive_type: co::DRIVE::FIXED,
+///             ..Default::default()
+///         }),
+///         local_base_path: Some("C:\\Windows\\notepad.exe".to_owned()),
+///         ..Default::default()
+///     }),
+///     arguments: Some("C:\\Temp\\foo.txt".to_owned()),
+///     working_dir: Some("C:\\Temp".to_owned()),
+///     ..Default::default()
+/// };
+///
+/// std::fs::write("C:\\Temp\\notepad.lnk", lnk.serialize()).unwrap();
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct LnkFile {
+	/// Flags of the link. When serializing, the flags which indicate the
+	/// presence of the fields are computed automatically.
+	pub flags: co::SLDF,
+	/// Attributes of the link target.
+	pub file_attributes: co::FILE_ATTRIBUTE,
+	/// Creation time of the link target.
+	pub creation_time: FILETIME,
+	/// Last access time of the link target.
+	pub access_time: FILETIME,
+	/// Last write time of the link target.
+	pub write_time: FILETIME,
+	/// Size of the link target, in bytes; only the lower 32 bits.
+	pub file_size: u32,
+	/// Index of the icon within the icon location.
+	pub icon_index: i32,
+	/// Expected window state of the launched application.
+	pub show_cmd: co::SW,
+	/// Hotkey of the link: low byte is the virtual key code, high byte is
+	/// the modifiers.
+	pub hotkey: u16,
+	/// Raw items of the `LinkTargetIDList`, which is an `ITEMIDLIST` of the
+	/// link target; each element doesn't include its size field.
+	pub id_list: Option<Vec<Vec<u8>>>,
+	/// Information to resolve the link target when the `id_list` can't be
+	/// used.
+	pub link_info: Option<LnkLinkInfo>,
+	/// Description, also known as comment.
+	pub description: Option<String>,
+	/// Path of the link target, relative to the `.lnk` file.
+	pub relative_path: Option<String>,
+	/// Working directory of the launched application.
+	pub working_dir: Option<String>,
+	/// Command line arguments of the launched application.
+	pub arguments: Option<String>,
+	/// Path of the file which contains the icon.
+	pub icon_location: Option<String>,
+	/// Extra data blocks, in the order they appear in the file.
+	pub extra_data: Vec<LnkExtraData>,
+}
+
+impl Default for LnkFile {
+	fn default() -> Self {
+		Self {
+			flags: co::SLDF::DEFAULT,
+			file_attributes: co::FILE_ATTRIBUTE::NORMAL,
+			creation_time: FILETIME::default(),
+			access_time: FILETIME::default(),
+			write_time: FILETIME::default(),
+			file_size: 0,
+			icon_index: 0,
+			show_cmd: co::SW::SHOWNORMAL,
+			hotkey: 0,
+			id_list: None,
+			link_info: None,
+			description: None,
+			relative_path: None,
+			working_dir: None,
+			arguments: None,
+			icon_location: None,
+			extra_data: Vec::default(),
+		}
+	}
+}
+
+impl LnkFile {
+	/// Parses the contents of a `.lnk` file.
+	///
+	/// Fails with [`co::ERROR::BAD_FORMAT`](crate::co::ERROR::BAD_FORMAT) if
+	/// the data is not a shell link, or
+	/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if it's
+	/// truncated or malformed.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		let mut rd = Reader::new(data);
+		if rd.u32()? != HEADER_SIZE || rd.guid()? != LINK_CLSID {
+			return Err(co::ERROR::BAD_FORMAT);
+		}
+
+		let flags = unsafe { co::SLDF::from_raw(rd.u32()?) };
+		let mut lnk = Self {
+			flags,
+			file_attributes: unsafe { co::FILE_ATTRIBUTE::from_raw(rd.u32()?) },
+			creation_time: rd.filetime()?,
+			access_time: rd.filetime()?,
+			write_time: rd.filetime()?,
+			file_size: rd.u32()?,
+			icon_index: rd.u32()? as _,
+			show_cmd: unsafe { co::SW::from_raw(rd.u32()? as _) },
+			hotkey: rd.u16()?,
+			..Default::default()
+		};
+		rd.bytes(10)?; // reserved
+
+		if flags.has(co::SLDF::HAS_ID_LIST) {
+			let list_size = rd.u16()? as usize;
+			let mut list_rd = Reader::new(rd.bytes(list_size)?);
+			let mut items = Vec::<Vec<u8>>::default();
+			loop {
+				let item_size = list_rd.u16()? as usize;
+				match item_size {
+					0 => break, // TerminalID
+					1 => return Err(co::ERROR::INVALID_DATA),
+					_ => items.push(list_rd.bytes(item_size - 2)?.to_vec()),
+				}
+			}
+			lnk.id_list = Some(items);
+		}
+
+		if flags.has(co::SLDF::HAS_LINK_INFO) {
+			let info_size = Reader::new(rd.peek(4)?).u32()? as usize;
+			lnk.link_info = Some(LnkLinkInfo::parse(rd.bytes(info_size)?)?);
+		}
+
+		let unicode = flags.has(co::SLDF::UNICODE);
+		for (flag, field) in [
+			(co::SLDF::HAS_NAME, &mut lnk.description),
+			(co::SLDF::HAS_RELPATH, &mut lnk.relative_path),
+			(co::SLDF::HAS_WORKINGDIR, &mut lnk.working_dir),
+			(co::SLDF::HAS_ARGS, &mut lnk.arguments),
+			(co::SLDF::HAS_ICONLOCATION, &mut lnk.icon_location),
+		] {
+			if flags.has(flag) {
+				let num_chars = rd.u16()? as usize;
+				*field = Some(if unicode {
+					utf16_decode(rd.bytes(num_chars * 2)?)
+				} else {
+					ansi_decode(rd.bytes(num_chars)?)
+				});
+			}
+		}
+
+		while rd.remaining() >= 4 {
+			let block_size = rd.u32()? as usize;
+			if block_size < 4 {
+				break; // TerminalBlock
+			} else if block_size < 8 {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			let signature = rd.u32()?;
+			lnk.extra_data.push(
+				LnkExtraData::parse(signature, rd.bytes(block_size - 8)?)?,
+			);
+		}
+
+		Ok(lnk)
+	}
+
+	/// Reads and parses a `.lnk` file.
+	#[must_use]
+	pub fn read_from_file(file_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(file_path, FileAccess::ExistingReadOnly)?;
+		Self::parse(fin.as_slice())
+	}
+
+	/// Serializes the link into the bytes of a `.lnk` file.
+	#[must_use]
+	pub fn serialize(&self) -> Vec<u8> {
+		let mut flags = self.flags | co::SLDF::UNICODE;
+		for (flag, present) in [ // flags computed from the fields
+			(co::SLDF::HAS_ID_LIST, self.id_list.is_some()),
+			(co::SLDF::HAS_LINK_INFO, self.link_info.is_some()),
+			(co::SLDF::HAS_NAME, self.description.is_some()),
+			(co::SLDF::HAS_RELPATH, self.relative_path.is_some()),
+			(co::SLDF::HAS_WORKINGDIR, self.working_dir.is_some()),
+			(co::SLDF::HAS_ARGS, self.arguments.is_some()),
+			(co::SLDF::HAS_ICONLOCATION, self.icon_location.is_some()),
+			(co::SLDF::HAS_EXP_SZ, self.extra_data.iter()
+				.any(|b| matches!(b, LnkExtraData::Environment(_)))),
+			(co::SLDF::HAS_EXP_ICON_SZ, self.extra_data.iter()
+				.any(|b| matches!(b, LnkExtraData::IconEnvironment(_)))),
+		] {
+			if present {
+				flags |= flag;
+			} else {
+				flags &= !flag;
+			}
+		}
+
+		let mut w = Writer::default();
+		w.u32(HEADER_SIZE);
+		w.guid(&LINK_CLSID);
+		w.u32(flags.raw());
+		w.u32(self.file_attributes.raw());
+		w.filetime(&self.creation_time);
+		w.filetime(&self.access_time);
+		w.filetime(&self.write_time);
+		w.u32(self.file_size);
+		w.u32(self.icon_index as _);
+		w.u32(self.show_cmd.raw() as _);
+		w.u16(self.hotkey);
+		w.bytes(&[0; 10]); // reserved
+
+		if let Some(items) = &self.id_list {
+			let list_size = items.iter().map(|item| item.len() + 2).sum::<usize>() + 2;
+			w.u16(list_size as _);
+			for item in items.iter() {
+				w.u16((item.len() + 2) as _);
+				w.bytes(item);
+			}
+			w.u16(0); // TerminalID
+		}
+
+		if let Some(link_info) = &self.link_info {
+			link_info.serialize(&mut w);
+		}
+
+		for s in [&self.description, &self.relative_path, &self.working_dir,
+			&self.arguments, &self.icon_location].into_iter().flatten()
+		{
+			w.u16(s.encode_utf16().count() as _);
+			s.encode_utf16().for_each(|ch| w.u16(ch));
+		}
+
+		self.extra_data.iter().for_each(|block| block.serialize(&mut w));
+		w.u32(0); // TerminalBlock
+		w.buf
+	}
+
+	/// Serializes the link and writes it to a `.lnk` file, which is created
+	/// if it doesn't exist.
+	pub fn write_to_file(&self, file_path: &str) -> SysResult<()> {
+		let fout = File::open(file_path, FileAccess::OpenOrCreateRW)?;
+		fout.erase_and_write(&self.serialize())
+	}
+
+	/// Returns the path of the link target, composed from the
+	/// [`link_info`](crate::LnkFile::link_info) local or network path, or
+	/// taken from the environment variables block, if any.
+	#[must_use]
+	pub fn target_path(&self) -> Option<String> {
+		if let Some(link_info) = &self.link_info {
+			let suffix = &link_info.common_path_suffix;
+			if let Some(base_path) = &link_info.local_base_path {
+				return Some(format!("{}{}", base_path, suffix));
+			} else if let Some(network) = &link_info.network {
+				return Some(if suffix.is_empty() {
+					network.net_name.clone()
+				} else {
+					format!("{}\\{}", network.net_name, suffix)
+				});
+			}
+		}
+
+		self.extra_data.iter().find_map(|block| match block {
+			LnkExtraData::Environment(target) => Some(target.clone()),
+			_ => None,
+		})
+	}
+
+	/// Serializes the data of an [`IShellLink`](crate::IShellLink) with
+	/// [`IPersistStream::Save`](crate::prelude::ole_IPersistStream::Save),
+	/// and parses it.
+	#[must_use]
+	pub fn from_shell_link(link: &impl shell_IShellLink) -> HrResult<Self> {
+		let stream = SHCreateMemStream(&[])?;
+		link.QueryInterface::<IPersistStream>()?.Save(&stream, false)?;
+		stream.Seek(0, co::STREAM_SEEK::SET)?;
+
+		let mut data = Vec::<u8>::default();
+		let mut buf = [0u8; 4096];
+		loop {
+			let num_read = stream.Read(&mut buf)? as usize;
+			if num_read == 0 {
+				break;
+			}
+			data.extend_from_slice(&buf[..num_read]);
+		}
+		Self::parse(&data).map_err(|e| e.to_hresult())
+	}
+
+	/// Creates a new [`IShellLink`](crate::IShellLink) and loads the
+	/// serialized link into it with
+	/// [`IPersistStream::Load`](crate::prelude::ole_IPersistStream::Load).
+	///
+	/// COM must have been initialized in the current thread.
+	#[must_use]
+	pub fn to_shell_link(&self) -> HrResult<IShellLink> {
+		let link = CoCreateInstance::<IShellLink>(
+			&co::CLSID::ShellLink, None, co::CLSCTX::INPROC_SERVER)?;
+		let stream = SHCreateMemStream(&self.serialize())?;
+		link.QueryInterface::<IPersistStream>()?.Load(&stream)?;
+		Ok(link)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// The `LinkInfo` structure of a [`LnkFile`](crate::LnkFile), with the
+/// information to resolve the link target when its `IDList` can't be used.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct LnkLinkInfo {
+	/// Volume of the link target, when it's on a local drive. Must be present
+	/// along with `local_base_path`; if `None` on serialization, an empty
+	/// volume is written.
+	pub volume: Option<LnkVolumeId>,
+	/// Local path of the link target, to be joined with
+	/// `common_path_suffix`.
+	pub local_base_path: Option<String>,
+	/// Network location of the link target, when it's on a network share.
+	pub network: Option<LnkNetworkLink>,
+	/// Path suffix, to be joined with `local_base_path` or the network name.
+	pub common_path_suffix: String,
+}
+
+impl LnkLinkInfo {
+	const FLAG_LOCAL: u32 = 0x1;
+	const FLAG_NETWORK: u32 = 0x2;
+	const HEADER_SIZE_UNICODE: u32 = 0x24;
+
+	fn parse(block: &[u8]) -> SysResult<Self> {
+		let mut rd = Reader::new(block);
+		rd.u32()?; // LinkInfoSize
+		let header_size = rd.u32()?;
+		let flags = rd.u32()?;
+		let volume_off = rd.u32()?;
+		let local_off = rd.u32()?;
+		let network_off = rd.u32()?;
+		let suffix_off = rd.u32()?;
+		let (local_off_u, suffix_off_u) = if header_size >= Self::HEADER_SIZE_UNICODE {
+			(rd.u32()?, rd.u32()?)
+		} else {
+			(0, 0)
+		};
+
+		let mut info = Self::default();
+		if flags & Self::FLAG_LOCAL != 0 {
+			info.volume = Some(LnkVolumeId::parse(tail_at(block, volume_off)?)?);
+			info.local_base_path = Some(if local_off_u != 0 {
+				utf16z_at(block, local_off_u)?
+			} else {
+				ansiz_at(block, local_off)?
+			});
+		}
+		if flags & Self::FLAG_NETWORK != 0 {
+			info.network = Some(LnkNetworkLink::parse(tail_at(block, network_off)?)?);
+		}
+		info.common_path_suffix = if suffix_off_u != 0 {
+			utf16z_at(block, suffix_off_u)?
+		} else {
+			ansiz_at(block, suffix_off)?
+		};
+		Ok(info)
+	}
+
+	fn serialize(&self, w: &mut Writer) {
+		let start = w.len();
+		let mut flags = 0;
+		if self.local_base_path.is_some() {
+			flags |= Self::FLAG_LOCAL;
+		}
+		if self.network.is_some() {
+			flags |= Self::FLAG_NETWORK;
+		}
+
+		w.u32(0); // LinkInfoSize, patched below
+		w.u32(Self::HEADER_SIZE_UNICODE);
+		w.u32(flags);
+		w.bytes(&[0; 6 * 4]); // offsets, patched below
+		let patch_off = |w: &mut Writer, field_idx: usize| {
+			let off = (w.len() - start) as u32;
+			w.patch_u32(start + 12 + field_idx * 4, off);
+		};
+
+		if let Some(base_path) = &self.local_base_path {
+			patch_off(w, 0);
+			self.volume.clone().unwrap_or_default().serialize(w);
+			patch_off(w, 1);
+			w.ansiz(base_path);
+		}
+		if let Some(network) = &self.network {
+			patch_off(w, 2);
+			network.serialize(w);
+		}
+		patch_off(w, 3);
+		w.ansiz(&self.common_path_suffix);
+		if let Some(base_path) = &self.local_base_path {
+			patch_off(w, 4);
+			w.utf16z(base_path);
+		}
+		patch_off(w, 5);
+		w.utf16z(&self.common_path_suffix);
+
+		w.patch_u32(start, (w.len() - start) as _);
+	}
+}
+
+/// The `VolumeID` structure of a [`LnkLinkInfo`](crate::LnkLinkInfo).
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct LnkVolumeId {
+	/// Type of the drive.
+	pub drive_type: co::DRIVE,
+	/// Serial number of the volume.
+	pub serial_number: u32,
+	/// Label of the volume.
+	pub label: String,
+}
+
+impl LnkVolumeId {
+	const LABEL_OFF_UNICODE: u32 = 0x14;
+
+	fn parse(block: &[u8]) -> SysResult<Self> {
+		let mut rd = Reader::new(block);
+		let size = rd.u32()? as usize;
+		let block = block.get(..size).ok_or(co::ERROR::INVALID_DATA)?;
+		let drive_type = unsafe { co::DRIVE::from_raw(rd.u32()?) };
+		let serial_number = rd.u32()?;
+		let label_off = rd.u32()?;
+		let label = if label_off == Self::LABEL_OFF_UNICODE {
+			utf16z_at(block, rd.u32()?)?
+		} else {
+			ansiz_at(block, label_off)?
+		};
+		Ok(Self { drive_type, serial_number, label })
+	}
+
+	fn serialize(&self, w: &mut Writer) {
+		let start = w.len();
+		w.u32(0); // VolumeIDSize, patched below
+		w.u32(self.drive_type.raw());
+		w.u32(self.serial_number);
+		match ansi_encode(&self.label) {
+			Some(ansi) => {
+				w.u32(0x10);
+				w.bytes(&ansi);
+				w.bytes(&[0]);
+			},
+			None => {
+				w.u32(Self::LABEL_OFF_UNICODE);
+				w.u32(Self::LABEL_OFF_UNICODE); // 5 fields, label right after them
+				w.utf16z(&self.label);
+			},
+		}
+		w.patch_u32(start, (w.len() - start) as _);
+	}
+}
+
+/// The `CommonNetworkRelativeLink` structure of a
+/// [`LnkLinkInfo`](crate::LnkLinkInfo).
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct LnkNetworkLink {
+	/// Server share path, like `\\server\share`.
+	pub net_name: String,
+	/// Device name, like a mapped drive letter `Z:`.
+	pub device_name: Option<String>,
+	/// Network provider type, one of the `WNNC_NET_*` values.
+	pub provider_type: Option<u32>,
+}
+
+impl LnkNetworkLink {
+	const FLAG_VALID_DEVICE: u32 = 0x1;
+	const FLAG_VALID_NET_TYPE: u32 = 0x2;
+	const HEADER_SIZE_UNICODE: u32 = 0x1c;
+
+	fn parse(block: &[u8]) -> SysResult<Self> {
+		let mut rd = Reader::new(block);
+		let size = rd.u32()? as usize;
+		let block = block.get(..size).ok_or(co::ERROR::INVALID_DATA)?;
+		let flags = rd.u32()?;
+		let net_off = rd.u32()?;
+		let device_off = rd.u32()?;
+		let provider_type = rd.u32()?;
+		let (net_off_u, device_off_u) = if net_off > 0x14 {
+			(rd.u32()?, rd.u32()?)
+		} else {
+			(0, 0)
+		};
+
+		Ok(Self {
+			net_name: if net_off_u != 0 {
+				utf16z_at(block, net_off_u)?
+			} else {
+				ansiz_at(block, net_off)?
+			},
+			device_name: if flags & Self::FLAG_VALID_DEVICE == 0 {
+				None
+			} else if device_off_u != 0 {
+				Some(utf16z_at(block, device_off_u)?)
+			} else {
+				Some(ansiz_at(block, device_off)?)
+			},
+			provider_type: (flags & Self::FLAG_VALID_NET_TYPE != 0)
+				.then_some(provider_type),
+		})
+	}
+
+	fn serialize(&self, w: &mut Writer) {
+		let start = w.len();
+		let mut flags = 0;
+		if self.device_name.is_some() {
+			flags |= Self::FLAG_VALID_DEVICE;
+		}
+		if self.provider_type.is_some() {
+			flags |= Self::FLAG_VALID_NET_TYPE;
+		}
+
+		w.u32(0); // CommonNetworkRelativeLinkSize, patched below
+		w.u32(flags);
+		w.u32(Self::HEADER_SIZE_UNICODE); // NetNameOffset
+		w.u32(0); // DeviceNameOffset, patched below
+		w.u32(self.provider_type.unwrap_or_default());
+		w.u32(0); // NetNameOffsetUnicode, patched below
+		w.u32(0); // DeviceNameOffsetUnicode, patched below
+
+		w.ansiz(&self.net_name);
+		if let Some(device_name) = &self.device_name {
+			w.patch_u32(start + 12, (w.len() - start) as _);
+			w.ansiz(device_name);
+		}
+		w.patch_u32(start + 20, (w.len() - start) as _);
+		w.utf16z(&self.net_name);
+		if let Some(device_name) = &self.device_name {
+			w.patch_u32(start + 24, (w.len() - start) as _);
+			w.utf16z(device_name);
+		}
+		w.patch_u32(start, (w.len() - start) as _);
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// An extra data block of a [`LnkFile`](crate::LnkFile).
+#[derive(Clone, PartialEq, Eq)]
+pub enum LnkExtraData {
+	/// `EnvironmentVariablesDataBlock`: path of the link target, with
+	/// environment variables.
+	Environment(String),
+	/// `IconEnvironmentDataBlock`: path of the icon, with environment
+	/// variables.
+	IconEnvironment(String),
+	/// `KnownFolderDataBlock`: known folder of the link target, and the
+	/// offset of its item within the `id_list`.
+	KnownFolder {
+		/// The known folder.
+		id: co::KNOWNFOLDERID,
+		/// Offset, in bytes, of the item within the `IDList`.
+		offset: u32,
+	},
+	/// `SpecialFolderDataBlock`: special folder of the link target, and the
+	/// offset of its item within the `id_list`.
+	SpecialFolder {
+		/// The `CSIDL` value of the folder.
+		id: u32,
+		/// Offset, in bytes, of the item within the `IDList`.
+		offset: u32,
+	},
+	/// `TrackerDataBlock`: data used by the Distributed Link Tracking service
+	/// to find the link target.
+	Tracker {
+		/// NetBIOS name of the machine where the target was last known to
+		/// reside.
+		machine_id: String,
+		/// Volume and object identifiers.
+		droid: [GUID; 2],
+		/// Volume and object identifiers, at the creation of the link.
+		droid_birth: [GUID; 2],
+	},
+	/// Any other block, kept as raw data.
+	Other {
+		/// Signature of the block.
+		signature: u32,
+		/// Block contents, after the signature.
+		data: Vec<u8>,
+	},
+}
+
+impl LnkExtraData {
+	const ENV_BODY_SIZE: usize = 260 + 260 * 2;
+	const TRACKER_BODY_SIZE: usize = 0x58;
+
+	fn parse(signature: u32, body: &[u8]) -> SysResult<Self> {
+		let mut rd = Reader::new(body);
+		Ok(match (signature, body.len()) {
+			(SIG_ENVIRONMENT | SIG_ICON_ENVIRONMENT, Self::ENV_BODY_SIZE) => {
+				let ansi = rd.bytes(260)?;
+				let unicode = utf16_decode(rd.bytes(520)?);
+				let target = if unicode.is_empty() {
+					ansi_decode(ansi.split(|b| *b == 0).next().unwrap_or_default())
+				} else {
+					unicode
+				};
+				if signature == SIG_ENVIRONMENT {
+					Self::Environment(target)
+				} else {
+					Self::IconEnvironment(target)
+				}
+			},
+			(SIG_KNOWN_FOLDER, 20) => Self::KnownFolder {
+				id: co::KNOWNFOLDERID::from(rd.guid()?),
+				offset: rd.u32()?,
+			},
+			(SIG_SPECIAL_FOLDER, 8) => Self::SpecialFolder {
+				id: rd.u32()?,
+				offset: rd.u32()?,
+			},
+			(SIG_TRACKER, Self::TRACKER_BODY_SIZE) => {
+				rd.bytes(8)?; // Length and Version
+				let machine_id = rd.bytes(16)?;
+				Self::Tracker {
+					machine_id: ansi_decode(
+						machine_id.split(|b| *b == 0).next().unwrap_or_default()),
+					droid: [rd.guid()?, rd.guid()?],
+					droid_birth: [rd.guid()?, rd.guid()?],
+				}
+			},
+			_ => Self::Other { signature, data: body.to_vec() },
+		})
+	}
+
+	fn serialize(&self, w: &mut Writer) {
+		match self {
+			Self::Environment(target) | Self::IconEnvironment(target) => {
+				w.u32((8 + Self::ENV_BODY_SIZE) as _);
+				w.u32(if let Self::Environment(_) = self {
+					SIG_ENVIRONMENT
+				} else {
+					SIG_ICON_ENVIRONMENT
+				});
+				let mut ansi = ansi_encode_lossy(target);
+				ansi.resize(259, 0); // always null-terminated
+				w.bytes(&ansi);
+				w.bytes(&[0]);
+				let mut unicode = target.encode_utf16().collect::<Vec<_>>();
+				unicode.resize(259, 0);
+				unicode.iter().for_each(|ch| w.u16(*ch));
+				w.u16(0);
+			},
+			Self::KnownFolder { id, offset } => {
+				w.u32(8 + 20);
+				w.u32(SIG_KNOWN_FOLDER);
+				w.guid(id.as_ref());
+				w.u32(*offset);
+			},
+			Self::SpecialFolder { id, offset } => {
+				w.u32(8 + 8);
+				w.u32(SIG_SPECIAL_FOLDER);
+				w.u32(*id);
+				w.u32(*offset);
+			},
+			Self::Tracker { machine_id, droid, droid_birth } => {
+				w.u32((8 + Self::TRACKER_BODY_SIZE) as _);
+				w.u32(SIG_TRACKER);
+				w.u32(Self::TRACKER_BODY_SIZE as _); // Length
+				w.u32(0); // Version
+				let mut machine = ansi_encode_lossy(machine_id);
+				machine.resize(15, 0);
+				w.bytes(&machine);
+				w.bytes(&[0]);
+				droid.iter().chain(droid_birth.iter()).for_each(|g| w.guid(g));
+			},
+			Self::Other { signature, data } => {
+				w.u32((8 + data.len()) as _);
+				w.u32(*signature);
+				w.bytes(data);
+			},
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Reads little-endian values from a byte slice, failing with
+/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) past the end.
+struct Reader<'a> {
+	data: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Reader<'a> {
+	const fn new(data: &'a [u8]) -> Self {
+		Self { data, pos: 0 }
+	}
+
+	const fn remaining(&self) -> usize {
+		self.data.len() - self.pos
+	}
+
+	fn peek(&self, num_bytes: usize) -> SysResult<&'a [u8]> {
+		self.pos.checked_add(num_bytes)
+			.and_then(|end| self.data.get(self.pos..end))
+			.ok_or(co::ERROR::INVALID_DATA)
+	}
+
+	fn bytes(&mut self, num_bytes: usize) -> SysResult<&'a [u8]> {
+		let bytes = self.peek(num_bytes)?;
+		self.pos += num_bytes;
+		Ok(bytes)
+	}
+
+	fn u16(&mut self) -> SysResult<u16> {
+		Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+	}
+
+	fn u32(&mut self) -> SysResult<u32> {
+		Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+	}
+
+	fn filetime(&mut self) -> SysResult<FILETIME> {
+		Ok(FILETIME { dwLowDateTime: self.u32()?, dwHighDateTime: self.u32()? })
+	}
+
+	fn guid(&mut self) -> SysResult<GUID> {
+		let bytes = self.bytes(16)?;
+		Ok(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const GUID) })
+	}
+}
+
+/// Writes little-endian values to a growing buffer.
+#[derive(Default)]
+struct Writer {
+	buf: Vec<u8>,
+}
+
+impl Writer {
+	fn len(&self) -> usize {
+		self.buf.len()
+	}
+
+	fn bytes(&mut self, data: &[u8]) {
+		self.buf.extend_from_slice(data);
+	}
+
+	fn u16(&mut self, val: u16) {
+		self.bytes(&val.to_le_bytes());
+	}
+
+	fn u32(&mut self, val: u32) {
+		self.bytes(&val.to_le_bytes());
+	}
+
+	fn patch_u32(&mut self, pos: usize, val: u32) {
+		self.buf[pos..pos + 4].copy_from_slice(&val.to_le_bytes());
+	}
+
+	fn filetime(&mut self, ft: &FILETIME) {
+		self.u32(ft.dwLowDateTime);
+		self.u32(ft.dwHighDateTime);
+	}
+
+	fn guid(&mut self, guid: &GUID) {
+		self.bytes(unsafe {
+			std::slice::from_raw_parts(guid as *const _ as *const u8, 16)
+		});
+	}
+
+	fn ansiz(&mut self, s: &str) {
+		self.bytes(&ansi_encode_lossy(s));
+		self.bytes(&[0]);
+	}
+
+	fn utf16z(&mut self, s: &str) {
+		s.encode_utf16().for_each(|ch| self.u16(ch));
+		self.u16(0);
+	}
+}
+
+fn tail_at(block: &[u8], off: u32) -> SysResult<&[u8]> {
+	block.get(off as usize..).ok_or(co::ERROR::INVALID_DATA)
+}
+
+fn ansiz_at(block: &[u8], off: u32) -> SysResult<String> {
+	let tail = tail_at(block, off)?;
+	let len = tail.iter().position(|b| *b == 0).ok_or(co::ERROR::INVALID_DATA)?;
+	Ok(ansi_decode(&tail[..len]))
+}
+
+fn utf16z_at(block: &[u8], off: u32) -> SysResult<String> {
+	let tail = tail_at(block, off)?;
+	let len = tail.chunks_exact(2)
+		.position(|ch| ch == [0, 0])
+		.ok_or(co::ERROR::INVALID_DATA)?;
+	Ok(utf16_decode(&tail[..len * 2]))
+}
+
+/// Decodes UTF-16 little-endian bytes, up to the first null, if any.
+fn utf16_decode(bytes: &[u8]) -> String {
+	let chars = bytes.chunks_exact(2)
+		.map(|ch| u16::from_le_bytes([ch[0], ch[1]]))
+		.take_while(|ch| *ch != 0)
+		.collect::<Vec<_>>();
+	String::from_utf16_lossy(&chars)
+}
+
+/// Windows-1252 characters in the `0x80..=0x9f` range; the undefined ones are
+/// mapped to the C1 control characters, like `MultiByteToWideChar` does.
+const CP1252_HIGH: [u16; 32] = [
+	0x20ac, 0x0081, 0x201a, 0x0192, 0x201e, 0x2026, 0x2020, 0x2021,
+	0x02c6, 0x2030, 0x0160, 0x2039, 0x0152, 0x008d, 0x017d, 0x008f,
+	0x0090, 0x2018, 0x2019, 0x201c, 0x201d, 0x2022, 0x2013, 0x2014,
+	0x02dc, 0x2122, 0x0161, 0x203a, 0x0153, 0x009d, 0x017e, 0x0178,
+];
+
+fn ansi_decode(bytes: &[u8]) -> String {
+	bytes.iter()
+		.map(|b| match b {
+			0x80..=0x9f => char::from_u32(CP1252_HIGH[(b - 0x80) as usize] as _).unwrap(),
+			b => *b as char,
+		})
+		.collect()
+}
+
+/// Encodes to Windows-1252, returning `None` if a character can't be
+/// represented.
+fn ansi_encode(s: &str) -> Option<Vec<u8>> {
+	s.chars()
+		.map(|ch| match ch as u32 {
+			c @ (0..=0x7f | 0xa0..=0xff) => Some(c as u8),
+			c => CP1252_HIGH.iter()
+				.position(|high| *high as u32 == c)
+				.map(|idx| 0x80 + idx as u8),
+		})
+		.collect()
+}
+
+/// Encodes to Windows-1252, replacing unrepresentable characters with `?`.
+fn ansi_encode_lossy(s: &str) -> Vec<u8> {
+	s.chars()
+		.map(|ch| ansi_encode(ch.encode_utf8(&mut [0; 4])).map_or(b'?', |b| b[0]))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn utf16z(s: &str) -> Vec<u8> {
+		s.encode_utf16().chain([0]).flat_map(|ch| ch.to_le_bytes()).collect()
+	}
+
+	fn counted_utf16(s: &str) -> Vec<u8> {
+		let mut buf = (s.encode_utf16().count() as u16).to_le_bytes().to_vec();
+		buf.extend(s.encode_utf16().flat_map(|ch| ch.to_le_bytes()));
+		buf
+	}
+
+	const DOCUMENTS: [u8; 16] = [ // FOLDERID_Documents, as stored in memory
+		0xd0, 0x9a, 0xd3, 0xfd, 0x8f, 0x23, 0xaf, 0x46,
+		0xad, 0xb4, 0x6c, 0x85, 0x48, 0x03, 0x69, 0xc7,
+	];
+
+	/// A shortcut to `C:\test\a.txt`, assembled after the MS-SHLLINK sample
+	/// and laid out as Windows writes it; all blocks, except the network link,
+	/// are present.
+	fn fixture() -> Vec<u8> {
+		let mut d = Vec::<u8>::new();
+
+		// ShellLinkHeader
+		d.extend([0x4c, 0, 0, 0]);
+		d.extend([0x01, 0x14, 0x02, 0, 0, 0, 0, 0, 0xc0, 0, 0, 0, 0, 0, 0, 0x46]);
+		d.extend(0x0000_02b7u32.to_le_bytes()); // ID list, link info, name, working dir, args, unicode, env
+		d.extend(0x0000_0020u32.to_le_bytes()); // FILE_ATTRIBUTE_ARCHIVE
+		for _ in 0..3 {
+			d.extend([0xd0, 0xe9, 0xee, 0xf2, 0x15, 0x15, 0xc9, 0x01]);
+		}
+		d.extend(0x1234u32.to_le_bytes()); // file size
+		d.extend(2u32.to_le_bytes()); // icon index
+		d.extend(1u32.to_le_bytes()); // SW_SHOWNORMAL
+		d.extend([0x41, 0x06]); // Ctrl+Shift+A
+		d.extend([0; 10]);
+
+		// LinkTargetIDList
+		d.extend(0x1du16.to_le_bytes());
+		d.extend(0x14u16.to_le_bytes());
+		d.extend([0x1f, 0x50, 0xe0, 0x4f, 0xd0, 0x20, 0xea, 0x3a, 0x69, 0x10,
+			0xa2, 0xd8, 0x08, 0x00, 0x2b, 0x30, 0x30, 0x9d]); // My Computer
+		d.extend(0x07u16.to_le_bytes());
+		d.extend([0x2f, b'C', b':', b'\\', 0]); // drive
+		d.extend([0, 0]); // TerminalID
+
+		// LinkInfo
+		d.extend(0x62u32.to_le_bytes()); // LinkInfoSize
+		d.extend(0x24u32.to_le_bytes()); // LinkInfoHeaderSize
+		d.extend(1u32.to_le_bytes()); // VolumeIDAndLocalBasePath
+		d.extend(0x24u32.to_le_bytes()); // VolumeIDOffset
+		d.extend(0x35u32.to_le_bytes()); // LocalBasePathOffset
+		d.extend(0u32.to_le_bytes()); // CommonNetworkRelativeLinkOffset
+		d.extend(0x43u32.to_le_bytes()); // CommonPathSuffixOffset
+		d.extend(0x44u32.to_le_bytes()); // LocalBasePathOffsetUnicode
+		d.extend(0x60u32.to_le_bytes()); // CommonPathSuffixOffsetUnicode
+		d.extend(0x11u32.to_le_bytes()); // VolumeIDSize
+		d.extend(3u32.to_le_bytes()); // DRIVE_FIXED
+		d.extend(0x307a_8a81u32.to_le_bytes()); // serial number
+		d.extend(0x10u32.to_le_bytes()); // VolumeLabelOffset
+		d.push(0); // empty label
+		d.extend(b"C:\\test\\a.txt\0");
+		d.push(0); // empty suffix
+		d.extend(utf16z("C:\\test\\a.txt"));
+		d.extend(utf16z(""));
+
+		// StringData
+		d.extend(counted_utf16("A text file"));
+		d.extend(counted_utf16("C:\\test"));
+		d.extend(counted_utf16("/x \u{1f600}"));
+
+		// EnvironmentVariablesDataBlock
+		d.extend(0x314u32.to_le_bytes());
+		d.extend(0xa000_0001u32.to_le_bytes());
+		let mut ansi = b"%SystemDrive%\\test\\a.txt".to_vec();
+		ansi.resize(260, 0);
+		d.extend(ansi);
+		let mut unicode = utf16z("%SystemDrive%\\test\\a.txt");
+		unicode.resize(520, 0);
+		d.extend(unicode);
+
+		// KnownFolderDataBlock
+		d.extend(0x1cu32.to_le_bytes());
+		d.extend(0xa000_000bu32.to_le_bytes());
+		d.extend(DOCUMENTS);
+		d.extend(0x14u32.to_le_bytes());
+
+		// SpecialFolderDataBlock
+		d.extend(0x10u32.to_le_bytes());
+		d.extend(0xa000_0005u32.to_le_bytes());
+		d.extend(0x24u32.to_le_bytes()); // CSIDL_WINDOWS
+		d.extend(0x14u32.to_le_bytes());
+
+		// TrackerDataBlock
+		d.extend(0x60u32.to_le_bytes());
+		d.extend(0xa000_0003u32.to_le_bytes());
+		d.extend(0x58u32.to_le_bytes());
+		d.extend(0u32.to_le_bytes());
+		d.extend(b"desktop-01\0\0\0\0\0\0");
+		(0..4u8).for_each(|i| d.extend([i + 1; 16]));
+
+		d.extend([0; 4]); // TerminalBlock
+		d
+	}
+
+	#[test]
+	fn header() {
+		let lnk = LnkFile::parse(&fixture()).unwrap();
+		assert_eq!(lnk.flags.raw(), 0x0000_02b7);
+		assert_eq!(lnk.file_attributes, co::FILE_ATTRIBUTE::ARCHIVE);
+		let ft = FILETIME { dwLowDateTime: 0xf2ee_e9d0, dwHighDateTime: 0x01c9_1515 };
+		assert!(lnk.creation_time == ft);
+		assert!(lnk.access_time == ft);
+		assert!(lnk.write_time == ft);
+		assert_eq!(lnk.file_size, 0x1234);
+		assert_eq!(lnk.icon_index, 2);
+		assert_eq!(lnk.show_cmd, co::SW::SHOWNORMAL);
+		assert_eq!(lnk.hotkey, 0x0641);
+	}
+
+	#[test]
+	fn id_list() {
+		let lnk = LnkFile::parse(&fixture()).unwrap();
+		let items = lnk.id_list.unwrap();
+		assert_eq!(items.len(), 2);
+		assert_eq!(items[0].len(), 18);
+		assert_eq!(items[0][..2], [0x1f, 0x50]);
+		assert_eq!(items[1], [0x2f, b'C', b':', b'\\', 0]);
+	}
+
+	#[test]
+	fn link_info() {
+		let lnk = LnkFile::parse(&fixture()).unwrap();
+		let info = lnk.link_info.as_ref().unwrap();
+		let volume = info.volume.as_ref().unwrap();
+		assert_eq!(volume.drive_type, co::DRIVE::FIXED);
+		assert_eq!(volume.serial_number, 0x307a_8a81);
+		assert_eq!(volume.label, "");
+		assert_eq!(info.local_base_path.as_deref(), Some("C:\\test\\a.txt"));
+		assert!(info.network.is_none());
+		assert_eq!(info.common_path_suffix, "");
+		assert_eq!(lnk.target_path().as_deref(), Some("C:\\test\\a.txt"));
+	}
+
+	#[test]
+	fn link_info_network() {
+		let info = LnkLinkInfo {
+			network: Some(LnkNetworkLink {
+				net_name: "\\\\server\\share".to_owned(),
+				device_name: Some("Z:".to_owned()),
+				provider_type: Some(0x0002_0000), // WNNC_NET_LANMAN
+			}),
+			common_path_suffix: "docs\\a.txt".to_owned(),
+			..Default::default()
+		};
+		let mut w = Writer::default();
+		info.serialize(&mut w);
+		assert!(LnkLinkInfo::parse(&w.buf).unwrap() == info);
+
+		let lnk = LnkFile { link_info: Some(info), ..Default::default() };
+		assert_eq!(lnk.target_path().as_deref(), Some("\\\\server\\share\\docs\\a.txt"));
+	}
+
+	#[test]
+	fn string_data() {
+		let lnk = LnkFile::parse(&fixture()).unwrap();
+		assert_eq!(lnk.description.as_deref(), Some("A text file"));
+		assert_eq!(lnk.relative_path, None);
+		assert_eq!(lnk.working_dir.as_deref(), Some("C:\\test"));
+		assert_eq!(lnk.arguments.as_deref(), Some("/x \u{1f600}")); // surrogate pair
+		assert_eq!(lnk.icon_location, None);
+	}
+
+	#[test]
+	fn string_data_ansi() {
+		let mut d = fixture()[..0x4c].to_vec();
+		d[0x14..0x18].copy_from_slice(&0x0000_0004u32.to_le_bytes()); // HAS_NAME only
+		d.extend(6u16.to_le_bytes());
+		d.extend([b'c', b'a', b'f', 0xe9, b' ', 0x80]);
+		d.extend([0; 4]);
+
+		let lnk = LnkFile::parse(&d).unwrap();
+		assert_eq!(lnk.description.as_deref(), Some("caf\u{e9} \u{20ac}"));
+		assert!(lnk.id_list.is_none());
+		assert!(lnk.link_info.is_none());
+		assert!(lnk.extra_data.is_empty());
+	}
+
+	#[test]
+	fn extra_data() {
+		let lnk = LnkFile::parse(&fixture()).unwrap();
+		assert_eq!(lnk.extra_data.len(), 4);
+		assert!(lnk.extra_data[0] == LnkExtraData::Environment("%SystemDrive%\\test\\a.txt".to_owned()));
+		match &lnk.extra_data[1] {
+			LnkExtraData::KnownFolder { id, offset } => {
+				assert_eq!(*id, co::KNOWNFOLDERID::Documents);
+				assert_eq!(*offset, 0x14);
+			},
+			_ => panic!("Expected a known folder block."),
+		}
+		assert!(lnk.extra_data[2] == LnkExtraData::SpecialFolder { id: 0x24, offset: 0x14 });
+		match &lnk.extra_data[3] {
+			LnkExtraData::Tracker { machine_id, droid, droid_birth } => {
+				assert_eq!(machine_id, "desktop-01");
+				assert_eq!(droid[0], Reader::new(&[1; 16]).guid().unwrap());
+				assert_eq!(droid_birth[1], Reader::new(&[4; 16]).guid().unwrap());
+			},
+			_ => panic!("Expected a tracker block."),
+		}
+	}
+
+	#[test]
+	fn extra_data_unknown() {
+		let mut d = fixture();
+		let term = d.len() - 4;
+		d.splice(term..term, [0x0c, 0, 0, 0, 0x09, 0, 0, 0xa0, 1, 2, 3, 4]);
+		let lnk = LnkFile::parse(&d).unwrap();
+		assert!(lnk.extra_data[4] == LnkExtraData::Other { signature: 0xa000_0009, data: vec![1, 2, 3, 4] });
+		assert_eq!(lnk.serialize(), d);
+	}
+
+	#[test]
+	fn serialize_same_bytes() {
+		let d = fixture();
+		assert_eq!(LnkFile::parse(&d).unwrap().serialize(), d);
+	}
+
+	#[test]
+	fn serialize_round_trip() {
+		let lnk = LnkFile {
+			link_info: Some(LnkLinkInfo {
+				volume: Some(LnkVolumeId {
+					drive_type: co::DRIVE::REMOVABLE,
+					serial_number: 7,
+					label: "\u{3b1}\u{3b2}".to_owned(), // not representable in ANSI
+				}),
+				local_base_path: Some("E:\\".to_owned()),
+				common_path_suffix: "x.txt".to_owned(),
+				..Default::default()
+			}),
+			icon_location: Some("%SystemRoot%\\a.ico".to_owned()),
+			extra_data: vec![LnkExtraData::IconEnvironment("%SystemRoot%\\a.ico".to_owned())],
+			..Default::default()
+		};
+		let parsed = LnkFile::parse(&lnk.serialize()).unwrap();
+		assert!(parsed.flags.has(co::SLDF::HAS_LINK_INFO | co::SLDF::HAS_ICONLOCATION
+			| co::SLDF::HAS_EXP_ICON_SZ | co::SLDF::UNICODE));
+		assert!(parsed.link_info == lnk.link_info);
+		assert_eq!(parsed.icon_location, lnk.icon_location);
+		assert!(parsed.extra_data == lnk.extra_data);
+	}
+
+	#[test]
+	fn truncated() {
+		let d = fixture();
+		let extra_start = d.len() - 4 - 0x60 - 0x10 - 0x1c - 0x314;
+		for len in 0..extra_start {
+			assert!(LnkFile::parse(&d[..len]).is_err(), "Truncated at {}.", len);
+		}
+		for len in extra_start..d.len() - 4 {
+			// At block boundaries, the missing blocks are simply absent.
+			assert!(LnkFile::parse(&d[..len]).map_or(true, |lnk| lnk.extra_data.len() < 4),
+				"Truncated at {}.", len);
+		}
+		assert_eq!(LnkFile::parse(&d[..extra_start + 0x100]).err(), Some(co::ERROR::INVALID_DATA));
+	}
+
+	#[test]
+	fn malformed() {
+		let mut d = fixture();
+		d[4] = 0x02; // wrong CLSID
+		assert_eq!(LnkFile::parse(&d).err(), Some(co::ERROR::BAD_FORMAT));
+
+		let mut d = fixture();
+		d[0x4e..0x50].copy_from_slice(&1u16.to_le_bytes()); // ItemIDSize of 1
+		assert_eq!(LnkFile::parse(&d).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut d = fixture();
+		let link_info = 0x4c + 2 + 0x1d;
+		d[link_info + 16..link_info + 20].copy_from_slice(&0x1000u32.to_le_bytes()); // LocalBasePathOffsetUnicode out of range
+		d[link_info + 28..link_info + 32].copy_from_slice(&0x1000u32.to_le_bytes());
+		assert_eq!(LnkFile::parse(&d).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut d = fixture();
+		d[link_info..link_info + 4].copy_from_slice(&0xffff_fff0u32.to_le_bytes()); // LinkInfoSize past the end
+		assert_eq!(LnkFile::parse(&d).err(), Some(co::ERROR::INVALID_DATA));
+	}
+
+	#[test]
+	fn reader_overflow() {
+		let mut r = Reader::new(&[1, 2, 3, 4]);
+		r.bytes(2).unwrap();
+		assert_eq!(r.peek(usize::MAX).err(), Some(co::ERROR::INVALID_DATA)); // end would wrap around
+		assert_eq!(r.bytes(usize::MAX - 1).err(), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(r.bytes(2).unwrap(), [3, 4]);
+		assert_eq!(r.peek(1).err(), Some(co::ERROR::INVALID_DATA));
+	}
+}
@@ -0,0 +1,5 @@
+mod jump_list;
+mod lnk_file;
+
+pub use jump_list::{JumpList, JumpListLink};
+pub use lnk_file::{LnkExtraData, LnkFile, LnkLinkInfo, LnkNetworkLink, LnkVolumeId};
@@ -1075,6 +1075,29 @@ const_ordinary! { DLGID: u16;
 	CONTINUE 11
 }
 
+const_ws! { DS: u32;
+	/// Dialog box
+	/// [styles](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dialog-box-styles)
+	/// (`u32`).
+	=>
+	=>
+	ABSALIGN 0x0001
+	SYSMODAL 0x0002
+	LOOK3D 0x0004
+	FIXEDSYS 0x0008
+	NOFAILCREATE 0x0010
+	LOCALEDIT 0x0020
+	SETFONT 0x0040
+	MODALFRAME 0x0080
+	NOIDLEMSG 0x0100
+	SETFOREGROUND 0x0200
+	CONTROL 0x0400
+	CENTER 0x0800
+	CENTERMOUSE 0x1000
+	CONTEXTHELP 0x2000
+	SHELLFONT Self::SETFONT.0 | Self::FIXEDSYS.0
+}
+
 const_bitflag! { DT: u32;
 	/// [`HDC::DrawText`](crate::prelude::user_Hdc::DrawText) `format` (`u32`).
 	=>
@@ -1325,6 +1348,36 @@ const_ordinary! { FAPPCOMMAND: u16;
 	OEM 0x1000
 }
 
+const_bitflag! { FR: u32;
+	/// [`FINDREPLACE`](crate::FINDREPLACE) `Flags` (`u32`).
+	=>
+	=>
+	DOWN 0x0000_0001
+	WHOLEWORD 0x0000_0002
+	MATCHCASE 0x0000_0004
+	FINDNEXT 0x0000_0008
+	REPLACE 0x0000_0010
+	REPLACEALL 0x0000_0020
+	DIALOGTERM 0x0000_0040
+	SHOWHELP 0x0000_0080
+	ENABLEHOOK 0x0000_0100
+	ENABLETEMPLATE 0x0000_0200
+	NOUPDOWN 0x0000_0400
+	NOMATCHCASE 0x0000_0800
+	NOWHOLEWORD 0x0000_1000
+	ENABLETEMPLATEHANDLE 0x0000_2000
+	HIDEUPDOWN 0x0000_4000
+	HIDEMATCHCASE 0x0000_8000
+	HIDEWHOLEWORD 0x0001_0000
+	RAW 0x0002_0000
+	SHOWWRAPAROUND 0x0004_0000
+	NOWRAPAROUND 0x0008_0000
+	WRAPAROUND 0x0010_0000
+	MATCHDIAC 0x2000_0000
+	MATCHKASHIDA 0x4000_0000
+	MATCHALEFHAMZA 0x8000_0000
+}
+
 const_ordinary! { GA: u32;
 	/// [`HWND::GetAncestor`](crate::prelude::user_Hwnd::GetAncestor) `flags`
 	/// (`u32`).
@@ -1710,6 +1763,29 @@ const_ws! { LBS: u32;
 	STANDARD Self::NOTIFY.0 | Self::SORT.0 | WS::VSCROLL.0 | WS::BORDER.0
 }
 
+const_bitflag! { LLKHF: u32;
+	/// [`KBDLLHOOKSTRUCT`](crate::KBDLLHOOKSTRUCT) `flags` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	EXTENDED 0x01
+	LOWER_IL_INJECTED 0x02
+	INJECTED 0x10
+	ALTDOWN 0x20
+	UP 0x80
+}
+
+const_bitflag! { LLMHF: u32;
+	/// [`MSLLHOOKSTRUCT`](crate::MSLLHOOKSTRUCT) `flags` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	INJECTED 0x01
+	LOWER_IL_INJECTED 0x02
+}
+
 const_ordinary! { LSFW: u32;
 	/// [`LockSetForegroundWindow`](crate::LockSetForegroundWindow) `lock_code`
 	/// (`u32`).
@@ -2000,6 +2076,20 @@ const_bitflag! { MNS: u32;
 	CHECKORBMP 0x0400_0000
 }
 
+const_bitflag! { MOD: u16;
+	/// [`HWND::RegisterHotKey`](crate::prelude::user_Hwnd::RegisterHotKey)
+	/// `modifiers` (`u16`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	ALT 0x0001
+	CONTROL 0x0002
+	SHIFT 0x0004
+	WIN 0x0008
+	NOREPEAT 0x4000
+}
+
 const_ordinary! { MONITOR: u32;
 	/// [`HMONITOR::MonitorFromPoint`](crate::prelude::user_Hmonitor::MonitorFromPoint),
 	/// [`HMONITOR::MonitorFromRect`](crate::prelude::user_Hmonitor::MonitorFromRect),
@@ -2042,6 +2132,19 @@ const_bitflag! { MOUSEEVENTF: u32;
 	ABSOLUTE 0x8000
 }
 
+const_bitflag! { MOUSE_STATE: u16;
+	/// [`RAWMOUSE`](crate::RAWMOUSE) `usFlags` (`u16`).
+	///
+	/// Originally has `MOUSE` prefix.
+	=>
+	=>
+	MOVE_RELATIVE 0
+	MOVE_ABSOLUTE 1
+	VIRTUAL_DESKTOP 0x02
+	ATTRIBUTES_CHANGED 0x04
+	MOVE_NOCOALESCE 0x08
+}
+
 const_ordinary! { MSGF: u8;
 	/// [`wm::EnterIdle`](crate::msg::wm::EnterIdle) reason (`u8`).
 	=>
@@ -2207,6 +2310,31 @@ const_bitflag! { PM: u32;
 	QS_SENDMESSAGE QS::SENDMESSAGE.0 << 16
 }
 
+const_bitflag! { PSD: u32;
+	/// [`PAGESETUPDLG`](crate::PAGESETUPDLG) `Flags` (`u32`).
+	=>
+	=>
+	DEFAULTMINMARGINS 0x0000_0000
+	INWININIINTLMEASURE 0x0000_0000
+	MINMARGINS 0x0000_0001
+	MARGINS 0x0000_0002
+	INTHOUSANDTHSOFINCHES 0x0000_0004
+	INHUNDREDTHSOFMILLIMETERS 0x0000_0008
+	DISABLEMARGINS 0x0000_0010
+	DISABLEPRINTER 0x0000_0020
+	NOWARNING 0x0000_0080
+	DISABLEORIENTATION 0x0000_0100
+	DISABLEPAPER 0x0000_0200
+	RETURNDEFAULT 0x0000_0400
+	SHOWHELP 0x0000_0800
+	ENABLEPAGESETUPHOOK 0x0000_2000
+	ENABLEPAGESETUPTEMPLATE 0x0000_8000
+	ENABLEPAGESETUPTEMPLATEHANDLE 0x0002_0000
+	ENABLEPAGEPAINTHOOK 0x0004_0000
+	DISABLEPAGEPAINTING 0x0008_0000
+	NONETWORKBUTTON 0x0020_0000
+}
+
 const_bitflag! { QS: u32;
 	/// [`GetQueueStatus`](crate::GetQueueStatus) `flags` (`u32`).
 	=>
@@ -2259,6 +2387,80 @@ const_ordinary! { REGION: i32;
 	COMPLEX 3
 }
 
+const_bitflag! { RI_KEY: u16;
+	/// [`RAWKEYBOARD`](crate::RAWKEYBOARD) `Flags` (`u16`).
+	=>
+	=>
+	MAKE 0
+	BREAK 1
+	E0 2
+	E1 4
+}
+
+const_bitflag! { RI_MOUSE: u16;
+	/// [`RAWMOUSE`](crate::RAWMOUSE) `usButtonFlags` (`u16`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	LEFT_BUTTON_DOWN 0x0001
+	LEFT_BUTTON_UP 0x0002
+	RIGHT_BUTTON_DOWN 0x0004
+	RIGHT_BUTTON_UP 0x0008
+	MIDDLE_BUTTON_DOWN 0x0010
+	MIDDLE_BUTTON_UP 0x0020
+	BUTTON_1_DOWN Self::LEFT_BUTTON_DOWN.0
+	BUTTON_1_UP Self::LEFT_BUTTON_UP.0
+	BUTTON_2_DOWN Self::RIGHT_BUTTON_DOWN.0
+	BUTTON_2_UP Self::RIGHT_BUTTON_UP.0
+	BUTTON_3_DOWN Self::MIDDLE_BUTTON_DOWN.0
+	BUTTON_3_UP Self::MIDDLE_BUTTON_UP.0
+	BUTTON_4_DOWN 0x0040
+	BUTTON_4_UP 0x0080
+	BUTTON_5_DOWN 0x0100
+	BUTTON_5_UP 0x0200
+	WHEEL 0x0400
+	HWHEEL 0x0800
+}
+
+const_bitflag! { RIDEV: u32;
+	/// [`RAWINPUTDEVICE`](crate::RAWINPUTDEVICE) `dwFlags` (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	REMOVE 0x0000_0001
+	EXCLUDE 0x0000_0010
+	PAGEONLY 0x0000_0020
+	NOLEGACY 0x0000_0030
+	INPUTSINK 0x0000_0100
+	CAPTUREMOUSE 0x0000_0200
+	NOHOTKEYS 0x0000_0200
+	APPKEYS 0x0000_0400
+	EXINPUTSINK 0x0000_1000
+	DEVNOTIFY 0x0000_2000
+}
+
+const_ordinary! { RIM: u8;
+	/// [`wm::Input`](crate::msg::wm::Input) `code` (`u8`).
+	=>
+	=>
+	/// Input occurred while the application was in the foreground.
+	INPUT 0
+	/// Input occurred while the application was not in the foreground.
+	INPUTSINK 1
+}
+
+const_ordinary! { RIM_TYPE: u32;
+	/// [`RAWINPUTHEADER`](crate::RAWINPUTHEADER) `dwType` and
+	/// [`RAWINPUTDEVICELIST`](crate::RAWINPUTDEVICELIST) `dwType` (`u32`).
+	=>
+	=>
+	MOUSE 0
+	KEYBOARD 1
+	HID 2
+}
+
 const_ordinary! { SB_EM: u16;
 	/// [`em::Scroll`](crate::msg::em::Scroll) action.
 	///
@@ -3370,6 +3572,7 @@ const_ordinary! { WM: u32;
 	MBUTTONDOWN 0x0207
 	MBUTTONUP 0x0208
 	MBUTTONDBLCLK 0x0209
+	MOUSEWHEEL 0x020a
 	MOUSEHWHEEL 0x020e
 	XBUTTONDOWN 0x020b
 	XBUTTONUP 0x020c
@@ -430,6 +430,19 @@ pub enum MenuItemInfo {
 	Submenu(HMENU, String),
 }
 
+/// Variant parameter for:
+///
+/// * [`RID_DEVICE_INFO`](crate::RID_DEVICE_INFO).
+#[derive(Clone, Copy)]
+pub enum MouseKbHid {
+	/// Mouse device.
+	Mouse(RID_DEVICE_INFO_MOUSE),
+	/// Keyboard device.
+	Kb(RID_DEVICE_INFO_KEYBOARD),
+	/// Human interface device.
+	Hid(RID_DEVICE_INFO_HID),
+}
+
 /// Variant parameter for:
 ///
 /// * [`wm::NcCalcSize`](crate::msg::wm::NcCalcSize).
@@ -3,6 +3,9 @@ use crate::kernel::ffi_types::*;
 extern_sys! { "comdlg32";
 	ChooseColorW(PVOID) -> BOOL
 	CommDlgExtendedError() -> u32
+	FindTextW(PVOID) -> HANDLE
+	PageSetupDlgW(PVOID) -> BOOL
+	ReplaceTextW(PVOID) -> HANDLE
 }
 
 #[cfg(target_pointer_width = "32")]
@@ -127,6 +130,9 @@ extern_sys! { "user32";
 	GetNextDlgTabItem(HANDLE, HANDLE, BOOL) -> HANDLE
 	GetParent(HANDLE) -> HANDLE
 	GetQueueStatus(u32) -> u32
+	GetRawInputData(HANDLE, u32, PVOID, *mut u32, u32) -> u32
+	GetRawInputDeviceInfoW(HANDLE, u32, PVOID, *mut u32) -> u32
+	GetRawInputDeviceList(PVOID, *mut u32, u32) -> u32
 	GetScrollInfo(HANDLE, i32, PVOID) -> BOOL
 	GetScrollPos(HANDLE, i32) -> i32
 	GetShellWindow() -> HANDLE
@@ -201,6 +207,8 @@ extern_sys! { "user32";
 	RealGetWindowClassW(HANDLE, PSTR, i32) -> u32
 	RedrawWindow(HANDLE, PCVOID, HANDLE, u32) -> BOOL
 	RegisterClassExW(PCVOID) -> u16
+	RegisterHotKey(HANDLE, i32, u32, u32) -> BOOL
+	RegisterRawInputDevices(PCVOID, u32, u32) -> BOOL
 	RegisterWindowMessageW(PCSTR) -> u32
 	ReleaseCapture() -> BOOL
 	ReleaseDC(HANDLE, HANDLE) -> i32
@@ -258,6 +266,7 @@ extern_sys! { "user32";
 	UnhookWindowsHookEx(HANDLE) -> BOOL
 	UnionRect(PVOID, PCVOID, PCVOID) -> BOOL
 	UnregisterClassW(PCSTR, HANDLE) -> BOOL
+	UnregisterHotKey(HANDLE, i32) -> BOOL
 	UpdateWindow(HANDLE) -> BOOL
 	ValidateRect(HANDLE, PCVOID) -> BOOL
 	ValidateRgn(HANDLE, HANDLE) -> BOOL
@@ -465,6 +465,31 @@ pub fn ExitWindowsEx(
 	bool_to_sysresult(unsafe { ffi::ExitWindowsEx(flags.raw(), reason.raw()) })
 }
 
+/// [`FindText`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/nf-commdlg-findtextw)
+/// function.
+///
+/// Creates a modeless Find dialog, returning its handle. The dialog notifies
+/// its owner window with the registered
+/// [`FINDMSGSTRING`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/findmsgstring)
+/// message.
+///
+/// Unless you need something specific, consider using the
+/// [`comm_dlg::find_text`](crate::comm_dlg::find_text) high-level
+/// abstraction, which manages the buffers.
+///
+/// # Safety
+///
+/// The `lpstrFindWhat` buffer must have at least 80 chars.
+///
+/// The [`FINDREPLACE`](crate::FINDREPLACE) struct and its buffers must remain
+/// valid, at the same memory location, until the dialog is destroyed.
+pub unsafe fn FindText(fr: &mut FINDREPLACE) -> Result<HWND, co::CDERR> {
+	match ffi::FindTextW(fr as *mut _ as _) {
+		p if p.is_null() => Err(CommDlgExtendedError()),
+		p => Ok(HWND::from_ptr(p)),
+	}
+}
+
 /// [`GetAsyncKeyState`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getasynckeystate)
 /// function.
 ///
@@ -604,6 +629,50 @@ pub fn GetQueueStatus(flags: co::QS) -> u32 {
 	unsafe { ffi::GetQueueStatus(flags.raw()) }
 }
 
+/// [`GetRawInputDeviceList`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdevicelist)
+/// function.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// for dev in w::GetRawInputDeviceList()?.iter() {
+///     println!("{} {}",
+///         dev.hDevice.GetRawInputDeviceInfoName()?, dev.dwType);
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[must_use]
+pub fn GetRawInputDeviceList() -> SysResult<Vec<RAWINPUTDEVICELIST>> {
+	let cb_item = std::mem::size_of::<RAWINPUTDEVICELIST>() as u32;
+	loop {
+		let mut num_devices = u32::default();
+		if unsafe {
+			ffi::GetRawInputDeviceList(std::ptr::null_mut(), &mut num_devices, cb_item)
+		} == u32::MAX {
+			return Err(GetLastError());
+		}
+
+		let mut devices = (0..num_devices)
+			.map(|_| RAWINPUTDEVICELIST::default())
+			.collect::<Vec<_>>();
+		match unsafe {
+			ffi::GetRawInputDeviceList(
+				devices.as_mut_ptr() as _, &mut num_devices, cb_item)
+		} {
+			u32::MAX => match GetLastError() {
+				co::ERROR::INSUFFICIENT_BUFFER => continue, // a device was attached meanwhile
+				e => return Err(e),
+			},
+			n => {
+				devices.truncate(n as _);
+				return Ok(devices);
+			},
+		}
+	}
+}
+
 /// [`GetSysColor`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getsyscolor)
 /// function.
 #[must_use]
@@ -714,6 +783,40 @@ pub fn OffsetRect(rc: &mut RECT, dx: i32, dy: i32) -> SysResult<()> {
 	bool_to_sysresult(unsafe { ffi::OffsetRect(rc as *mut _ as _, dx, dy) })
 }
 
+/// [`PageSetupDlg`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/nf-commdlg-pagesetupdlgw)
+/// function.
+///
+/// Returns `false` if the user cancelled the dialog.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hparent: w::HWND; // initialized somewhere
+/// # let hparent = w::HWND::NULL;
+///
+/// let mut psd = w::PAGESETUPDLG::default();
+/// psd.hwndOwner = unsafe { hparent.raw_copy() };
+/// psd.Flags = co::PSD::INTHOUSANDTHSOFINCHES;
+///
+/// if w::PageSetupDlg(&mut psd)? {
+///     let _devmode = unsafe { w::guard::GlobalFreeGuard::new(psd.hDevMode.raw_copy()) };
+///     let _devnames = unsafe { w::guard::GlobalFreeGuard::new(psd.hDevNames.raw_copy()) };
+///     println!("Left margin: {}", psd.rtMargin.left);
+/// }
+/// # Ok::<_, co::CDERR>(())
+/// ```
+pub fn PageSetupDlg(psd: &mut PAGESETUPDLG) -> Result<bool, co::CDERR> {
+	match unsafe { ffi::PageSetupDlgW(psd as *mut _ as _) } {
+		0 => match CommDlgExtendedError() {
+			co::CDERR::NoValue => Ok(false),
+			err => Err(err),
+		},
+		_ => Ok(true),
+	}
+}
+
 /// [`PeekMessage`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-peekmessagew)
 /// function.
 pub fn PeekMessage(
@@ -776,6 +879,48 @@ pub unsafe fn RegisterClassEx(wcx: &WNDCLASSEX) -> SysResult<ATOM> {
 	}
 }
 
+/// [`RegisterRawInputDevices`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerrawinputdevices)
+/// function.
+///
+/// # Examples
+///
+/// Receiving [`wm::Input`](crate::msg::wm::Input) for the mouse and the
+/// keyboard, even when the window is not in the foreground:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hwnd: w::HWND; // initialized somewhere
+/// # let hwnd = w::HWND::NULL;
+///
+/// w::RegisterRawInputDevices(&[
+///     w::RAWINPUTDEVICE {
+///         usUsagePage: 0x01, // generic desktop controls
+///         usUsage: 0x02, // mouse
+///         dwFlags: co::RIDEV::INPUTSINK,
+///         hwndTarget: unsafe { hwnd.raw_copy() },
+///     },
+///     w::RAWINPUTDEVICE {
+///         usUsagePage: 0x01,
+///         usUsage: 0x06, // keyboard
+///         dwFlags: co::RIDEV::INPUTSINK,
+///         hwndTarget: unsafe { hwnd.raw_copy() },
+///     },
+/// ])?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub fn RegisterRawInputDevices(devices: &[RAWINPUTDEVICE]) -> SysResult<()> {
+	bool_to_sysresult(
+		unsafe {
+			ffi::RegisterRawInputDevices(
+				vec_ptr(devices) as _,
+				devices.len() as _,
+				std::mem::size_of::<RAWINPUTDEVICE>() as _,
+			)
+		},
+	)
+}
+
 /// [`RegisterWindowMessage`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerwindowmessagew)
 /// function.
 #[must_use]
@@ -788,6 +933,42 @@ pub fn RegisterWindowMessage(s: &str) -> SysResult<u32> {
 	}
 }
 
+/// [`ReleaseCapture`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-releasecapture)
+/// function.
+///
+/// Usually the mouse capture is released by the
+/// [`ReleaseCaptureGuard`](crate::guard::ReleaseCaptureGuard) returned by
+/// [`HWND::SetCapture`](crate::prelude::user_Hwnd::SetCapture).
+pub fn ReleaseCapture() -> SysResult<()> {
+	bool_to_sysresult(unsafe { ffi::ReleaseCapture() })
+}
+
+/// [`ReplaceText`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/nf-commdlg-replacetextw)
+/// function.
+///
+/// Creates a modeless Replace dialog, returning its handle. The dialog
+/// notifies its owner window with the registered
+/// [`FINDMSGSTRING`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/findmsgstring)
+/// message.
+///
+/// Unless you need something specific, consider using the
+/// [`comm_dlg::replace_text`](crate::comm_dlg::replace_text) high-level
+/// abstraction, which manages the buffers.
+///
+/// # Safety
+///
+/// The `lpstrFindWhat` and `lpstrReplaceWith` buffers must have at least 80
+/// chars.
+///
+/// The [`FINDREPLACE`](crate::FINDREPLACE) struct and its buffers must remain
+/// valid, at the same memory location, until the dialog is destroyed.
+pub unsafe fn ReplaceText(fr: &mut FINDREPLACE) -> Result<HWND, co::CDERR> {
+	match ffi::ReplaceTextW(fr as *mut _ as _) {
+		p if p.is_null() => Err(CommDlgExtendedError()),
+		p => Ok(HWND::from_ptr(p)),
+	}
+}
+
 /// [`SendInput`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-sendinput)
 /// function.
 ///
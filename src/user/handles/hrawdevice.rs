@@ -0,0 +1,112 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+use crate::user::{ffi, privs::*};
+
+impl_handle! { HRAWDEVICE;
+	/// Handle to a
+	/// [raw input](https://learn.microsoft.com/en-us/windows/win32/inputdev/raw-input)
+	/// device.
+	///
+	/// Originally just a `HANDLE`.
+}
+
+impl user_Hrawdevice for HRAWDEVICE {}
+
+/// This trait is enabled with the `user` feature, and provides methods for
+/// [`HRAWDEVICE`](crate::HRAWDEVICE).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait user_Hrawdevice: Handle {
+	/// [`GetRawInputDeviceInfo`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdeviceinfow)
+	/// function, with `RIDI_DEVICEINFO`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hdev: w::HRAWDEVICE; // initialized somewhere
+	/// # let hdev = w::HRAWDEVICE::NULL;
+	///
+	/// if let w::MouseKbHid::Hid(hid) = hdev.GetRawInputDeviceInfoDevInfo()?.info() {
+	///     println!("VID {:04x} PID {:04x}", hid.dwVendorId, hid.dwProductId);
+	/// }
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn GetRawInputDeviceInfoDevInfo(&self) -> SysResult<RID_DEVICE_INFO> {
+		let mut info = RID_DEVICE_INFO::default();
+		let mut sz = std::mem::size_of::<RID_DEVICE_INFO>() as u32;
+		match unsafe {
+			ffi::GetRawInputDeviceInfoW(self.ptr(), RIDI_DEVICEINFO,
+				&mut info as *mut _ as _, &mut sz)
+		} {
+			u32::MAX => Err(GetLastError()),
+			_ => Ok(info),
+		}
+	}
+
+	/// [`GetRawInputDeviceInfo`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdeviceinfow)
+	/// function, with `RIDI_DEVICENAME`.
+	///
+	/// Returns the device interface path, which can be opened with
+	/// [`HFILE::CreateFile`](crate::prelude::kernel_Hfile::CreateFile).
+	#[must_use]
+	fn GetRawInputDeviceInfoName(&self) -> SysResult<String> {
+		let mut num_chars = u32::default();
+		if unsafe {
+			ffi::GetRawInputDeviceInfoW(self.ptr(), RIDI_DEVICENAME,
+				std::ptr::null_mut(), &mut num_chars)
+		} == u32::MAX {
+			return Err(GetLastError());
+		}
+
+		let mut buf = WString::new_alloc_buf(num_chars as usize + 1);
+		match unsafe {
+			ffi::GetRawInputDeviceInfoW(self.ptr(), RIDI_DEVICENAME,
+				buf.as_mut_ptr() as _, &mut num_chars)
+		} {
+			u32::MAX => Err(GetLastError()),
+			_ => Ok(buf.to_string()),
+		}
+	}
+
+	/// [`GetRawInputDeviceInfo`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdeviceinfow)
+	/// function, with `RIDI_PREPARSEDDATA`.
+	///
+	/// Returns the opaque HID preparsed data, used to interpret the reports of
+	/// [`RawInput::Hid`](crate::RawInput::Hid). Fails if the device is not a
+	/// HID.
+	#[must_use]
+	fn GetRawInputDeviceInfoPreparsedData(&self) -> SysResult<Vec<u8>> {
+		let mut sz = u32::default();
+		if unsafe {
+			ffi::GetRawInputDeviceInfoW(self.ptr(), RIDI_PREPARSEDDATA,
+				std::ptr::null_mut(), &mut sz)
+		} == u32::MAX {
+			return Err(GetLastError());
+		}
+		if sz == 0 {
+			return Err(co::ERROR::NOT_SUPPORTED); // not a HID
+		}
+
+		let mut buf = vec![0u8; sz as _];
+		match unsafe {
+			ffi::GetRawInputDeviceInfoW(self.ptr(), RIDI_PREPARSEDDATA,
+				buf.as_mut_ptr() as _, &mut sz)
+		} {
+			u32::MAX => Err(GetLastError()),
+			n => {
+				buf.truncate(n as _);
+				Ok(buf)
+			},
+		}
+	}
+}
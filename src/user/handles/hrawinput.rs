@@ -0,0 +1,52 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::decl::*;
+use crate::prelude::*;
+use crate::user::{ffi, privs::*};
+
+impl_handle! { HRAWINPUT;
+	/// Handle to a
+	/// [raw input](https://learn.microsoft.com/en-us/windows/win32/inputdev/raw-input)
+	/// data block, received by [`wm::Input`](crate::msg::wm::Input).
+}
+
+impl user_Hrawinput for HRAWINPUT {}
+
+/// This trait is enabled with the `user` feature, and provides methods for
+/// [`HRAWINPUT`](crate::HRAWINPUT).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait user_Hrawinput: Handle {
+	/// [`GetRawInputData`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdata)
+	/// function, with `RID_INPUT`.
+	///
+	/// Returns the whole `RAWINPUT` block, which can be parsed with
+	/// [`RawInput::parse`](crate::RawInput::parse).
+	#[must_use]
+	fn GetRawInputData(&self) -> SysResult<Vec<u8>> {
+		let cb_header = std::mem::size_of::<RAWINPUTHEADER>() as u32;
+		let mut sz = u32::default();
+		if unsafe {
+			ffi::GetRawInputData(self.ptr(), RID_INPUT,
+				std::ptr::null_mut(), &mut sz, cb_header)
+		} == u32::MAX {
+			return Err(GetLastError());
+		}
+
+		let mut buf = vec![0u8; sz as _];
+		match unsafe {
+			ffi::GetRawInputData(self.ptr(), RID_INPUT,
+				buf.as_mut_ptr() as _, &mut sz, cb_header)
+		} {
+			u32::MAX => Err(GetLastError()),
+			n => {
+				buf.truncate(n as _);
+				Ok(buf)
+			},
+		}
+	}
+}
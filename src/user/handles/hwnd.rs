@@ -1292,6 +1292,56 @@ pub trait user_Hwnd: Handle {
 		)
 	}
 
+	/// [`RegisterHotKey`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerhotkey)
+	/// function.
+	///
+	/// The window will receive a [`WM_HOTKEY`](crate::msg::wm::Hotkey)
+	/// message when the hotkey is pressed. If the handle is
+	/// [`HWND::NULL`](crate::prelude::Handle::NULL), the message is posted to
+	/// the message queue of the calling thread.
+	///
+	/// The hotkey must be unregistered with
+	/// [`HWND::UnregisterHotKey`](crate::prelude::user_Hwnd::UnregisterHotKey).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hwnd: w::HWND; // initialized somewhere
+	/// # let hwnd = w::HWND::NULL;
+	///
+	/// const HOTKEY_ID: i32 = 1;
+	///
+	/// hwnd.RegisterHotKey(
+	///     HOTKEY_ID,
+	///     co::MOD::CONTROL | co::MOD::ALT | co::MOD::NOREPEAT,
+	///     co::VK::CHAR_K,
+	/// )?;
+	///
+	/// // ...
+	///
+	/// hwnd.UnregisterHotKey(HOTKEY_ID)?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	fn RegisterHotKey(&self,
+		id: i32,
+		modifiers: co::MOD,
+		vkey_code: co::VK,
+	) -> SysResult<()>
+	{
+		bool_to_sysresult(
+			unsafe {
+				ffi::RegisterHotKey(
+					self.ptr(),
+					id,
+					modifiers.raw() as _,
+					vkey_code.raw() as _,
+				)
+			},
+		)
+	}
+
 	/// [`ScreenToClient`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-screentoclient)
 	/// function.
 	///
@@ -1762,6 +1812,12 @@ pub trait user_Hwnd: Handle {
 		)
 	}
 
+	/// [`UnregisterHotKey`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-unregisterhotkey)
+	/// function.
+	fn UnregisterHotKey(&self, id: i32) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::UnregisterHotKey(self.ptr(), id) })
+	}
+
 	/// [`UpdateWindow`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-updatewindow)
 	/// function.
 	fn UpdateWindow(&self) -> SysResult<()> {
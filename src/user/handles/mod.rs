@@ -9,6 +9,8 @@ mod hinstance;
 mod hmenu;
 mod hmonitor;
 mod hprocess;
+mod hrawdevice;
+mod hrawinput;
 mod hwnd;
 
 pub mod decl {
@@ -21,6 +23,8 @@ pub mod decl {
 	pub use super::hicon::HICON;
 	pub use super::hmenu::HMENU;
 	pub use super::hmonitor::HMONITOR;
+	pub use super::hrawdevice::HRAWDEVICE;
+	pub use super::hrawinput::HRAWINPUT;
 	pub use super::hwnd::HWND;
 
 	impl_handle! { HBITMAP;
@@ -52,5 +56,7 @@ pub mod traits {
 	pub use super::hmenu::user_Hmenu;
 	pub use super::hmonitor::user_Hmonitor;
 	pub use super::hprocess::user_Hprocess;
+	pub use super::hrawdevice::user_Hrawdevice;
+	pub use super::hrawinput::user_Hrawinput;
 	pub use super::hwnd::user_Hwnd;
 }
@@ -805,6 +805,40 @@ unsafe impl<'a> MsgSendRecv for Help<'a> {
 	}
 }
 
+/// [`WM_HOTKEY`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-hotkey)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct Hotkey {
+	pub id: i32,
+	pub modifiers: co::MOD,
+	pub vkey_code: co::VK,
+}
+
+unsafe impl MsgSend for Hotkey {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::WM::HOTKEY,
+			wparam: self.id as _,
+			lparam: MAKEDWORD(self.modifiers.raw(), self.vkey_code.raw()) as _,
+		}
+	}
+}
+
+unsafe impl MsgSendRecv for Hotkey {
+	fn from_generic_wm(p: WndMsg) -> Self {
+		Self {
+			id: p.wparam as _,
+			modifiers: unsafe { co::MOD::from_raw(LOWORD(p.lparam as _)) },
+			vkey_code: unsafe { co::VK::from_raw(HIWORD(p.lparam as _)) },
+		}
+	}
+}
+
 /// [`WM_HSCROLL`](https://learn.microsoft.com/en-us/windows/win32/controls/wm-hscroll)
 /// message parameters.
 ///
@@ -914,6 +948,42 @@ unsafe impl MsgSendRecv for InitMenuPopup {
 	}
 }
 
+/// [`WM_INPUT`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-input)
+/// message parameters.
+///
+/// Return type: `()`.
+///
+/// After processing this message, the window procedure must still call
+/// [`HWND::DefWindowProc`](crate::prelude::user_Hwnd::DefWindowProc), so the
+/// system can release the raw input data.
+pub struct Input {
+	pub code: co::RIM,
+	pub hraw_input: HRAWINPUT,
+}
+
+unsafe impl MsgSend for Input {
+	type RetType = ();
+
+	fn convert_ret(&self, _: isize) -> Self::RetType {}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::WM::INPUT,
+			wparam: self.code.raw() as _,
+			lparam: self.hraw_input.ptr() as _,
+		}
+	}
+}
+
+unsafe impl MsgSendRecv for Input {
+	fn from_generic_wm(p: WndMsg) -> Self {
+		Self {
+			code: unsafe { co::RIM::from_raw((p.wparam & 0xff) as _) }, // GET_RAWINPUT_CODE_WPARAM
+			hraw_input: unsafe { HRAWINPUT::from_ptr(p.lparam as _) },
+		}
+	}
+}
+
 pub_struct_msg_char_key! { KeyDown: co::WM::KEYDOWN;
 	/// [`WM_KEYDOWN`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-keydown)
 }
@@ -1087,6 +1157,10 @@ pub_struct_msg_button! { MouseHover: co::WM::MOUSEHOVER;
 	/// [`WM_MOUSEHOVER`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousehover)
 }
 
+pub_struct_msg_wheel! { MouseHWheel: co::WM::MOUSEHWHEEL;
+	/// [`WM_MOUSEHWHEEL`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousehwheel)
+}
+
 pub_struct_msg_empty_handleable! { MouseLeave: co::WM::MOUSELEAVE;
 	/// [`WM_MOUSELEAVE`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mouseleave)
 }
@@ -1095,6 +1169,10 @@ pub_struct_msg_button! { MouseMove: co::WM::MOUSEMOVE;
 	/// [`WM_MOUSEMOVE`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousemove)
 }
 
+pub_struct_msg_wheel! { MouseWheel: co::WM::MOUSEWHEEL;
+	/// [`WM_MOUSEWHEEL`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousewheel)
+}
+
 /// [`WM_MOVE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-move)
 /// message parameters.
 ///
@@ -6,6 +6,7 @@ mod funcs;
 mod handles;
 mod structs;
 mod msg_traits;
+mod utilities;
 
 pub(in crate::user) mod ffi;
 pub(in crate::user) mod iterators;
@@ -20,6 +21,7 @@ pub mod decl {
 	pub use super::funcs::*;
 	pub use super::handles::decl::*;
 	pub use super::structs::*;
+	pub use super::utilities::*;
 }
 
 pub mod traits {
@@ -14,6 +14,10 @@ pub(crate) const FAPPCOMMAND_MASK: u16 = 0xf000;
 pub(crate) const HWND_MESSAGE: isize = -3;
 pub(crate) const LB_ERR: i32 = -1;
 pub(crate) const LB_ERRSPACE: i32 = -2;
+pub(crate) const RID_INPUT: u32 = 0x1000_0003;
+pub(crate) const RIDI_DEVICEINFO: u32 = 0x2000_000b;
+pub(crate) const RIDI_DEVICENAME: u32 = 0x2000_0007;
+pub(crate) const RIDI_PREPARSEDDATA: u32 = 0x2000_0005;
 pub(crate) const WC_DIALOG: u16 = 0x8002;
 
 /// Takes an `isize` and returns `Err` if `-1`.
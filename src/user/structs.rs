@@ -521,6 +521,38 @@ pub struct MSG {
 
 impl_default!(MSG);
 
+/// [`FINDREPLACE`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/ns-commdlg-findreplacew)
+/// struct.
+///
+/// Used by the modeless [`FindText`](crate::FindText) and
+/// [`ReplaceText`](crate::ReplaceText) dialogs, therefore it must outlive the
+/// dialog window.
+#[repr(C)]
+pub struct FINDREPLACE<'a, 'b> {
+	lStructSize: u32,
+	pub hwndOwner: HWND,
+	pub hInstance: HINSTANCE,
+	pub Flags: co::FR,
+	lpstrFindWhat: *mut u16,
+	lpstrReplaceWith: *mut u16,
+	wFindWhatLen: u16,
+	wReplaceWithLen: u16,
+	pub lCustData: isize,
+	pub lpfnHook: Option<CCHOOKPROC>,
+	lpTemplateName: *mut u16, // u16 resource ID
+
+	_lpstrFindWhat: PhantomData<&'a mut u16>,
+	_lpstrReplaceWith: PhantomData<&'b mut u16>,
+}
+
+impl_default_with_size!(FINDREPLACE, lStructSize, 'a, 'b);
+
+impl<'a, 'b> FINDREPLACE<'a, 'b> {
+	pub_fn_string_buf_get_set!('a, lpstrFindWhat, set_lpstrFindWhat, raw_lpstrFindWhat, wFindWhatLen);
+	pub_fn_string_buf_get_set!('b, lpstrReplaceWith, set_lpstrReplaceWith, raw_lpstrReplaceWith, wReplaceWithLen);
+	pub_fn_resource_id_get_set!(lpTemplateName, set_lpTemplateName);
+}
+
 /// [`GUITHREADINFO`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-guithreadinfo)
 /// struct.
 #[repr(C)]
@@ -618,13 +650,35 @@ impl INPUT {
 	/// Sets the event tagged union field.
 	pub fn set_event(&mut self, event: HwKbMouse) {
 		match event {
-			HwKbMouse::Hw(hi) => self.union0.hi = hi,
-			HwKbMouse::Kb(ki) => self.union0.ki = ki,
-			HwKbMouse::Mouse(mi) => self.union0.mi = mi,
+			HwKbMouse::Hw(hi) => {
+				self.dwType = co::INPUT::HARDWARE;
+				self.union0.hi = hi;
+			},
+			HwKbMouse::Kb(ki) => {
+				self.dwType = co::INPUT::KEYBOARD;
+				self.union0.ki = ki;
+			},
+			HwKbMouse::Mouse(mi) => {
+				self.dwType = co::INPUT::MOUSE;
+				self.union0.mi = mi;
+			},
 		}
 	}
 }
 
+/// [`KBDLLHOOKSTRUCT`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-kbdllhookstruct)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct KBDLLHOOKSTRUCT {
+	pub vkCode: co::VK,
+	___MISSING_ALIGNMENT__: u16,
+	pub scanCode: u32,
+	pub flags: co::LLKHF,
+	pub time: u32,
+	pub dwExtraInfo: usize,
+}
+
 /// [`KEYBDINPUT`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-keybdinput)
 /// struct.
 #[repr(C)]
@@ -750,6 +804,36 @@ pub struct MOUSEINPUT {
 	pub dwExtraInfo: usize,
 }
 
+/// [`MSLLHOOKSTRUCT`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-msllhookstruct)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct MSLLHOOKSTRUCT {
+	pub pt: POINT,
+	pub mouseData: u32,
+	pub flags: co::LLMHF,
+	pub time: u32,
+	pub dwExtraInfo: usize,
+}
+
+impl MSLLHOOKSTRUCT {
+	/// Returns the wheel delta from `mouseData`, for
+	/// [`WM::MOUSEWHEEL`](crate::co::WM::MOUSEWHEEL) and
+	/// [`WM::MOUSEHWHEEL`](crate::co::WM::MOUSEHWHEEL) events.
+	#[must_use]
+	pub const fn wheel_delta(&self) -> i16 {
+		HIWORD(self.mouseData) as _
+	}
+
+	/// Returns which X button was pressed or released, `1` or `2`, from
+	/// `mouseData`, for [`WM::XBUTTONDOWN`](crate::co::WM::XBUTTONDOWN) and
+	/// [`WM::XBUTTONUP`](crate::co::WM::XBUTTONUP) events.
+	#[must_use]
+	pub const fn xbutton(&self) -> u16 {
+		HIWORD(self.mouseData)
+	}
+}
+
 /// [`NCCALCSIZE_PARAMS`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-nccalcsize_params)
 /// struct.
 #[repr(C)]
@@ -766,6 +850,36 @@ impl<'a> NCCALCSIZE_PARAMS<'a> {
 	pub_fn_ptr_get_set!('a, lppos, set_lppos, WINDOWPOS);
 }
 
+/// [`PAGESETUPDLG`](https://learn.microsoft.com/en-us/windows/win32/api/commdlg/ns-commdlg-pagesetupdlgw)
+/// struct.
+///
+/// The `hDevMode` and `hDevNames` handles returned by
+/// [`PageSetupDlg`](crate::PageSetupDlg) must be freed with
+/// [`GlobalFree`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-globalfree).
+#[repr(C)]
+pub struct PAGESETUPDLG {
+	lStructSize: u32,
+	pub hwndOwner: HWND,
+	pub hDevMode: HGLOBAL,
+	pub hDevNames: HGLOBAL,
+	pub Flags: co::PSD,
+	pub ptPaperSize: POINT,
+	pub rtMinMargin: RECT,
+	pub rtMargin: RECT,
+	pub hInstance: HINSTANCE,
+	pub lCustData: isize,
+	lpfnPageSetupHook: *mut std::ffi::c_void,
+	lpfnPagePaintHook: *mut std::ffi::c_void,
+	lpPageSetupTemplateName: *mut u16, // u16 resource ID
+	pub hPageSetupTemplate: HGLOBAL,
+}
+
+impl_default_with_size!(PAGESETUPDLG, lStructSize);
+
+impl PAGESETUPDLG {
+	pub_fn_resource_id_get_set!(lpPageSetupTemplateName, set_lpPageSetupTemplateName);
+}
+
 /// [`PAINTSTRUCT`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-paintstruct)
 /// struct.
 #[repr(C)]
@@ -819,6 +933,78 @@ impl POINT {
 	}
 }
 
+/// [`RAWINPUTDEVICE`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawinputdevice)
+/// struct.
+#[repr(C)]
+pub struct RAWINPUTDEVICE {
+	pub usUsagePage: u16,
+	pub usUsage: u16,
+	pub dwFlags: co::RIDEV,
+	pub hwndTarget: HWND,
+}
+
+impl_default!(RAWINPUTDEVICE);
+
+/// [`RAWINPUTDEVICELIST`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawinputdevicelist)
+/// struct.
+#[repr(C)]
+pub struct RAWINPUTDEVICELIST {
+	pub hDevice: HRAWDEVICE,
+	pub dwType: co::RIM_TYPE,
+}
+
+impl_default!(RAWINPUTDEVICELIST);
+
+/// [`RAWINPUTHEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawinputheader)
+/// struct.
+#[repr(C)]
+pub struct RAWINPUTHEADER {
+	pub dwType: co::RIM_TYPE,
+	pub dwSize: u32,
+	pub hDevice: HRAWDEVICE,
+	pub wParam: usize,
+}
+
+impl_default!(RAWINPUTHEADER);
+
+/// [`RAWKEYBOARD`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawkeyboard)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct RAWKEYBOARD {
+	pub MakeCode: u16,
+	pub Flags: co::RI_KEY,
+	pub Reserved: u16,
+	pub VKey: co::VK,
+	pub Message: co::WM,
+	pub ExtraInformation: u32,
+}
+
+/// [`RAWMOUSE`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawmouse)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct RAWMOUSE {
+	pub usFlags: co::MOUSE_STATE,
+	___MISSING_ALIGNMENT__: u16,
+	pub usButtonFlags: co::RI_MOUSE,
+	pub usButtonData: u16,
+	pub ulRawButtons: u32,
+	pub lLastX: i32,
+	pub lLastY: i32,
+	pub ulExtraInformation: u32,
+}
+
+impl RAWMOUSE {
+	/// Returns the wheel delta from `usButtonData`, if `usButtonFlags` has
+	/// [`RI_MOUSE::WHEEL`](crate::co::RI_MOUSE::WHEEL) or
+	/// [`RI_MOUSE::HWHEEL`](crate::co::RI_MOUSE::HWHEEL).
+	#[must_use]
+	pub const fn wheel_delta(&self) -> i16 {
+		self.usButtonData as _
+	}
+}
+
 /// [`RECT`](https://learn.microsoft.com/en-us/windows/win32/api/windef/ns-windef-rect)
 /// struct.
 #[repr(C)]
@@ -837,6 +1023,76 @@ impl std::fmt::Display for RECT {
 	}
 }
 
+/// [`RID_DEVICE_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rid_device_info)
+/// struct.
+#[repr(C)]
+pub struct RID_DEVICE_INFO {
+	cbSize: u32,
+	dwType: co::RIM_TYPE,
+	union0: RID_DEVICE_INFO_union0,
+}
+
+#[repr(C)]
+union RID_DEVICE_INFO_union0 {
+	mouse: RID_DEVICE_INFO_MOUSE,
+	keyboard: RID_DEVICE_INFO_KEYBOARD,
+	hid: RID_DEVICE_INFO_HID,
+}
+
+impl_default_with_size!(RID_DEVICE_INFO, cbSize);
+
+impl RID_DEVICE_INFO {
+	/// Returns the device info tagged union field.
+	#[must_use]
+	pub const fn info(&self) -> MouseKbHid {
+		match self.dwType {
+			co::RIM_TYPE::MOUSE => MouseKbHid::Mouse(unsafe { self.union0.mouse }),
+			co::RIM_TYPE::KEYBOARD => MouseKbHid::Kb(unsafe { self.union0.keyboard }),
+			_ => MouseKbHid::Hid(unsafe { self.union0.hid }),
+		}
+	}
+}
+
+/// [`RID_DEVICE_INFO_HID`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rid_device_info_hid)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct RID_DEVICE_INFO_HID {
+	pub dwVendorId: u32,
+	pub dwProductId: u32,
+	pub dwVersionNumber: u32,
+	pub usUsagePage: u16,
+	pub usUsage: u16,
+}
+
+/// [`RID_DEVICE_INFO_KEYBOARD`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rid_device_info_keyboard)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct RID_DEVICE_INFO_KEYBOARD {
+	pub dwType: u32,
+	pub dwSubType: u32,
+	pub dwKeyboardMode: u32,
+	pub dwNumberOfFunctionKeys: u32,
+	pub dwNumberOfIndicators: u32,
+	pub dwNumberOfKeysTotal: u32,
+}
+
+/// [`RID_DEVICE_INFO_MOUSE`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rid_device_info_mouse)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct RID_DEVICE_INFO_MOUSE {
+	pub dwId: u32,
+	pub dwNumberOfButtons: u32,
+	pub dwSampleRate: u32,
+	fHasHorizontalWheel: BOOL,
+}
+
+impl RID_DEVICE_INFO_MOUSE {
+	pub_fn_bool_get_set!(fHasHorizontalWheel, set_fHasHorizontalWheel);
+}
+
 /// [`SCROLLINFO`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-scrollinfo)
 /// struct.
 #[repr(C)]
@@ -0,0 +1,536 @@
+use crate::co;
+use crate::decl::*;
+
+/// Builds a sequence of synthesized keyboard and mouse events, to be sent with
+/// [`SendInput`](crate::SendInput), typically for UI automation.
+///
+/// Mouse coordinates are absolute screen coordinates, which are normalized to
+/// the virtual screen – the bounding rectangle of all monitors.
+///
+/// Building the sequence doesn't send anything, so the generated events can
+/// be inspected with [`inputs`](crate::InputSequence::inputs).
+///
+/// # Examples
+///
+/// Opening a new browser tab, typing an address and scrolling down:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// w::InputSequence::new()
+///     .chord(&[co::VK::CONTROL, co::VK::CHAR_T])
+///     .type_text("example.com\n")
+///     .click(w::POINT::new(400, 300), co::VK::LBUTTON)
+///     .wheel(-3 * 120)
+///     .send()?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+#[derive(Clone)]
+pub struct InputSequence {
+	virtual_screen: RECT,
+	inputs: Vec<HwKbMouse>,
+}
+
+impl Default for InputSequence {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl InputSequence {
+	/// Creates an empty sequence, retrieving the virtual screen bounds with
+	/// [`GetSystemMetrics`](crate::GetSystemMetrics).
+	#[must_use]
+	pub fn new() -> Self {
+		let left = GetSystemMetrics(co::SM::XVIRTUALSCREEN);
+		let top = GetSystemMetrics(co::SM::YVIRTUALSCREEN);
+		Self::with_virtual_screen(RECT {
+			left,
+			top,
+			right: left + GetSystemMetrics(co::SM::CXVIRTUALSCREEN),
+			bottom: top + GetSystemMetrics(co::SM::CYVIRTUALSCREEN),
+		})
+	}
+
+	/// Creates an empty sequence with the given virtual screen bounds, used to
+	/// normalize the mouse coordinates.
+	#[must_use]
+	pub fn with_virtual_screen(virtual_screen: RECT) -> Self {
+		Self { virtual_screen, inputs: Vec::default() }
+	}
+
+	/// Returns the events generated so far.
+	#[must_use]
+	pub fn inputs(&self) -> &[HwKbMouse] {
+		&self.inputs
+	}
+
+	/// Sends the events with [`SendInput`](crate::SendInput), returning the
+	/// number of events successfully inserted into the input stream.
+	///
+	/// An empty sequence sends nothing.
+	pub fn send(&self) -> SysResult<u32> {
+		if self.inputs.is_empty() {
+			Ok(0)
+		} else {
+			SendInput(&self.inputs)
+		}
+	}
+
+	/// Presses a key, without releasing it.
+	#[must_use]
+	pub fn key_down(mut self, vkey_code: co::VK) -> Self {
+		self.push_key(vkey_code, co::KEYEVENTF::NoValue);
+		self
+	}
+
+	/// Releases a key.
+	#[must_use]
+	pub fn key_up(mut self, vkey_code: co::VK) -> Self {
+		self.push_key(vkey_code, co::KEYEVENTF::KEYUP);
+		self
+	}
+
+	/// Presses and releases a key.
+	#[must_use]
+	pub fn key(self, vkey_code: co::VK) -> Self {
+		self.key_down(vkey_code).key_up(vkey_code)
+	}
+
+	/// Presses the keys in the given order, then releases them in the reverse
+	/// order, like Ctrl+Shift+T:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// w::InputSequence::new()
+	///     .chord(&[co::VK::CONTROL, co::VK::SHIFT, co::VK::CHAR_T])
+	///     .send()?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	pub fn chord(mut self, vkey_codes: &[co::VK]) -> Self {
+		for vk in vkey_codes.iter() {
+			self.push_key(*vk, co::KEYEVENTF::NoValue);
+		}
+		for vk in vkey_codes.iter().rev() {
+			self.push_key(*vk, co::KEYEVENTF::KEYUP);
+		}
+		self
+	}
+
+	/// Types the given text, regardless of the keyboard layout, by sending
+	/// each UTF-16 code unit with
+	/// [`KEYEVENTF::UNICODE`](crate::co::KEYEVENTF::UNICODE). Characters
+	/// outside the Basic Multilingual Plane are sent as surrogate pairs.
+	///
+	/// Line breaks – `\n`, `\r` or `\r\n` – are sent as the Enter key, and tabs
+	/// as the Tab key, since most controls ignore them as characters.
+	#[must_use]
+	pub fn type_text(mut self, text: &str) -> Self {
+		let mut chars = text.chars().peekable();
+		while let Some(ch) = chars.next() {
+			match ch {
+				'\r' | '\n' => {
+					if ch == '\r' && chars.peek() == Some(&'\n') {
+						chars.next(); // CRLF is a single line break
+					}
+					self = self.key(co::VK::RETURN);
+				},
+				'\t' => self = self.key(co::VK::TAB),
+				_ => {
+					let mut buf = [0u16; 2];
+					for unit in ch.encode_utf16(&mut buf).iter() {
+						self.push_unicode(*unit, co::KEYEVENTF::NoValue);
+						self.push_unicode(*unit, co::KEYEVENTF::KEYUP);
+					}
+				},
+			}
+		}
+		self
+	}
+
+	/// Moves the cursor to the given screen position.
+	#[must_use]
+	pub fn move_to(mut self, pt: POINT) -> Self {
+		self.push_mouse(Some(pt), co::MOUSEEVENTF::MOVE, 0);
+		self
+	}
+
+	/// Moves the cursor through each one of the given screen positions.
+	#[must_use]
+	pub fn move_along(mut self, path: &[POINT]) -> Self {
+		for pt in path.iter() {
+			self = self.move_to(*pt);
+		}
+		self
+	}
+
+	/// Presses a mouse button at the given screen position, without releasing
+	/// it.
+	///
+	/// # Panics
+	///
+	/// Panics if `button` is not one of
+	/// [`VK::LBUTTON`](crate::co::VK::LBUTTON),
+	/// [`VK::RBUTTON`](crate::co::VK::RBUTTON),
+	/// [`VK::MBUTTON`](crate::co::VK::MBUTTON),
+	/// [`VK::XBUTTON1`](crate::co::VK::XBUTTON1) or
+	/// [`VK::XBUTTON2`](crate::co::VK::XBUTTON2).
+	#[must_use]
+	pub fn button_down(mut self, pt: POINT, button: co::VK) -> Self {
+		let (down, _, data) = button_flags(button);
+		self.push_mouse(Some(pt), co::MOUSEEVENTF::MOVE | down, data);
+		self
+	}
+
+	/// Releases a mouse button at the given screen position.
+	///
+	/// # Panics
+	///
+	/// Panics if `button` is not a mouse button, like in
+	/// [`button_down`](crate::InputSequence::button_down).
+	#[must_use]
+	pub fn button_up(mut self, pt: POINT, button: co::VK) -> Self {
+		let (_, up, data) = button_flags(button);
+		self.push_mouse(Some(pt), co::MOUSEEVENTF::MOVE | up, data);
+		self
+	}
+
+	/// Clicks a mouse button at the given screen position.
+	///
+	/// # Panics
+	///
+	/// Panics if `button` is not a mouse button, like in
+	/// [`button_down`](crate::InputSequence::button_down).
+	#[must_use]
+	pub fn click(self, pt: POINT, button: co::VK) -> Self {
+		self.button_down(pt, button).button_up(pt, button)
+	}
+
+	/// Double-clicks a mouse button at the given screen position.
+	///
+	/// # Panics
+	///
+	/// Panics if `button` is not a mouse button, like in
+	/// [`button_down`](crate::InputSequence::button_down).
+	#[must_use]
+	pub fn double_click(self, pt: POINT, button: co::VK) -> Self {
+		self.click(pt, button).click(pt, button)
+	}
+
+	/// Drags with a mouse button pressed: the button is pressed at the first
+	/// position of the path, the cursor moves through the remaining ones, and
+	/// the button is released at the last one. An empty path generates no
+	/// events.
+	///
+	/// # Panics
+	///
+	/// Panics if `button` is not a mouse button, like in
+	/// [`button_down`](crate::InputSequence::button_down).
+	///
+	/// # Examples
+	///
+	/// Dragging a window by its caption, in 4 steps:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let from = w::POINT::new(100, 10);
+	/// let to = w::POINT::new(500, 300);
+	///
+	/// let path = (0..=4)
+	///     .map(|i| w::POINT::new(
+	///         from.x + (to.x - from.x) * i / 4,
+	///         from.y + (to.y - from.y) * i / 4,
+	///     ))
+	///     .collect::<Vec<_>>();
+	///
+	/// w::InputSequence::new()
+	///     .drag(&path, co::VK::LBUTTON)
+	///     .send()?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	pub fn drag(mut self, path: &[POINT], button: co::VK) -> Self {
+		if let (Some(first), Some(last)) = (path.first(), path.last()) {
+			self = self.button_down(*first, button);
+			if path.len() > 2 {
+				self = self.move_along(&path[1..path.len() - 1]);
+			}
+			self = self.button_up(*last, button);
+		}
+		self
+	}
+
+	/// Rotates the vertical mouse wheel at the current cursor position.
+	/// Positive values scroll up, negative scroll down; one notch is
+	/// [`WHEEL_DELTA`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-mousewheel),
+	/// which is 120.
+	#[must_use]
+	pub fn wheel(mut self, delta: i32) -> Self {
+		self.push_mouse(None, co::MOUSEEVENTF::WHEEL, delta as _);
+		self
+	}
+
+	/// Rotates the horizontal mouse wheel at the current cursor position.
+	/// Positive values scroll right, negative scroll left; one notch is 120.
+	#[must_use]
+	pub fn hwheel(mut self, delta: i32) -> Self {
+		self.push_mouse(None, co::MOUSEEVENTF::HWHEEL, delta as _);
+		self
+	}
+
+	fn push_key(&mut self, vkey_code: co::VK, flags: co::KEYEVENTF) {
+		let extended = if is_extended_key(vkey_code) {
+			co::KEYEVENTF::EXTENDEDKEY
+		} else {
+			co::KEYEVENTF::NoValue
+		};
+		self.inputs.push(HwKbMouse::Kb(KEYBDINPUT {
+			wVk: vkey_code,
+			dwFlags: flags | extended,
+			..Default::default()
+		}));
+	}
+
+	fn push_unicode(&mut self, code_unit: u16, flags: co::KEYEVENTF) {
+		self.inputs.push(HwKbMouse::Kb(KEYBDINPUT {
+			wScan: code_unit,
+			dwFlags: co::KEYEVENTF::UNICODE | flags,
+			..Default::default()
+		}));
+	}
+
+	fn push_mouse(&mut self, pt: Option<POINT>, flags: co::MOUSEEVENTF, data: u32) {
+		let (dx, dy, abs) = match pt {
+			Some(pt) => (
+				normalize(pt.x, self.virtual_screen.left, self.virtual_screen.right),
+				normalize(pt.y, self.virtual_screen.top, self.virtual_screen.bottom),
+				co::MOUSEEVENTF::ABSOLUTE | co::MOUSEEVENTF::VIRTUALDESK,
+			),
+			None => (0, 0, co::MOUSEEVENTF::default()),
+		};
+		self.inputs.push(HwKbMouse::Mouse(MOUSEINPUT {
+			dx,
+			dy,
+			mouseData: data,
+			dwFlags: flags | abs,
+			..Default::default()
+		}));
+	}
+}
+
+/// Returns the down and up flags of a mouse button, and its `mouseData`.
+fn button_flags(button: co::VK) -> (co::MOUSEEVENTF, co::MOUSEEVENTF, u32) {
+	const XBUTTON1: u32 = 0x0001;
+	const XBUTTON2: u32 = 0x0002;
+
+	match button {
+		co::VK::LBUTTON => (co::MOUSEEVENTF::LEFTDOWN, co::MOUSEEVENTF::LEFTUP, 0),
+		co::VK::RBUTTON => (co::MOUSEEVENTF::RIGHTDOWN, co::MOUSEEVENTF::RIGHTUP, 0),
+		co::VK::MBUTTON => (co::MOUSEEVENTF::MIDDLEDOWN, co::MOUSEEVENTF::MIDDLEUP, 0),
+		co::VK::XBUTTON1 => (co::MOUSEEVENTF::XDOWN, co::MOUSEEVENTF::XUP, XBUTTON1),
+		co::VK::XBUTTON2 => (co::MOUSEEVENTF::XDOWN, co::MOUSEEVENTF::XUP, XBUTTON2),
+		_ => panic!("Not a mouse button: {}.", button),
+	}
+}
+
+/// Keys which, on an enhanced keyboard, don't belong to the original 83-key
+/// set, and must be flagged with `KEYEVENTF_EXTENDEDKEY`.
+fn is_extended_key(vkey_code: co::VK) -> bool {
+	matches!(vkey_code,
+		co::VK::PRIOR | co::VK::NEXT | co::VK::END | co::VK::HOME
+		| co::VK::LEFT | co::VK::UP | co::VK::RIGHT | co::VK::DOWN
+		| co::VK::SNAPSHOT | co::VK::INSERT | co::VK::DELETE
+		| co::VK::LWIN | co::VK::RWIN | co::VK::APPS
+		| co::VK::DIVIDE | co::VK::NUMLOCK
+		| co::VK::RCONTROL | co::VK::RMENU)
+}
+
+/// Maps a screen coordinate within `[min, max)` to the `0..=65535` range of
+/// absolute mouse coordinates, rounding to the nearest value. Coordinates
+/// outside the range are clamped.
+fn normalize(coord: i32, min: i32, max: i32) -> i32 {
+	let span = max as i64 - min as i64 - 1;
+	if span <= 0 {
+		return 0;
+	}
+	let offset = (coord as i64 - min as i64).clamp(0, span);
+	((offset * 65535 + span / 2) / span) as _
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SCREEN: RECT = RECT { left: 0, top: 0, right: 1921, bottom: 1081 };
+
+	/// Virtual key code and flags of each keyboard event.
+	fn keys(seq: &InputSequence) -> Vec<(co::VK, u16, co::KEYEVENTF)> {
+		seq.inputs().iter()
+			.map(|input| match input {
+				HwKbMouse::Kb(ki) => (ki.wVk, ki.wScan, ki.dwFlags),
+				_ => panic!("Expected a keyboard event."),
+			})
+			.collect()
+	}
+
+	/// Position, data and flags of each mouse event.
+	fn mouse(seq: &InputSequence) -> Vec<(i32, i32, u32, co::MOUSEEVENTF)> {
+		seq.inputs().iter()
+			.map(|input| match input {
+				HwKbMouse::Mouse(mi) => (mi.dx, mi.dy, mi.mouseData, mi.dwFlags),
+				_ => panic!("Expected a mouse event."),
+			})
+			.collect()
+	}
+
+	const DOWN: co::KEYEVENTF = co::KEYEVENTF::NoValue;
+	const UP: co::KEYEVENTF = co::KEYEVENTF::KEYUP;
+	const ABS: co::MOUSEEVENTF = unsafe {
+		co::MOUSEEVENTF::from_raw(co::MOUSEEVENTF::MOVE.raw()
+			| co::MOUSEEVENTF::ABSOLUTE.raw() | co::MOUSEEVENTF::VIRTUALDESK.raw())
+	};
+
+	#[test]
+	fn chord_order() {
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.chord(&[co::VK::CONTROL, co::VK::SHIFT, co::VK::CHAR_T]);
+		assert_eq!(keys(&seq), [
+			(co::VK::CONTROL, 0, DOWN),
+			(co::VK::SHIFT, 0, DOWN),
+			(co::VK::CHAR_T, 0, DOWN),
+			(co::VK::CHAR_T, 0, UP),
+			(co::VK::SHIFT, 0, UP),
+			(co::VK::CONTROL, 0, UP),
+		]);
+	}
+
+	#[test]
+	fn extended_keys() {
+		let ext = co::KEYEVENTF::EXTENDEDKEY;
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.key_down(co::VK::RCONTROL)
+			.key(co::VK::LEFT)
+			.key_up(co::VK::RCONTROL)
+			.key(co::VK::NUMPAD4); // same physical key as Left, but not extended
+		assert_eq!(keys(&seq), [
+			(co::VK::RCONTROL, 0, DOWN | ext),
+			(co::VK::LEFT, 0, DOWN | ext),
+			(co::VK::LEFT, 0, UP | ext),
+			(co::VK::RCONTROL, 0, UP | ext),
+			(co::VK::NUMPAD4, 0, DOWN),
+			(co::VK::NUMPAD4, 0, UP),
+		]);
+	}
+
+	#[test]
+	fn type_text_unicode() {
+		let uni = co::KEYEVENTF::UNICODE;
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.type_text("a\u{e9}\u{1f600}");
+		assert_eq!(keys(&seq), [
+			(co::VK::default(), 0x0061, uni | DOWN),
+			(co::VK::default(), 0x0061, uni | UP),
+			(co::VK::default(), 0x00e9, uni | DOWN),
+			(co::VK::default(), 0x00e9, uni | UP),
+			(co::VK::default(), 0xd83d, uni | DOWN), // high surrogate
+			(co::VK::default(), 0xd83d, uni | UP),
+			(co::VK::default(), 0xde00, uni | DOWN), // low surrogate
+			(co::VK::default(), 0xde00, uni | UP),
+		]);
+	}
+
+	#[test]
+	fn type_text_line_breaks() {
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.type_text("\r\n\n\r\t");
+		let vks = keys(&seq).iter().map(|(vk, _, _)| *vk).collect::<Vec<_>>();
+		assert_eq!(vks, [
+			co::VK::RETURN, co::VK::RETURN, // CRLF is a single break
+			co::VK::RETURN, co::VK::RETURN,
+			co::VK::RETURN, co::VK::RETURN,
+			co::VK::TAB, co::VK::TAB,
+		]);
+	}
+
+	#[test]
+	fn move_to() {
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.move_along(&[POINT::new(0, 0), POINT::new(960, 540), POINT::new(1920, 1080)]);
+		assert_eq!(mouse(&seq), [
+			(0, 0, 0, ABS),
+			(32768, 32768, 0, ABS),
+			(65535, 65535, 0, ABS),
+		]);
+	}
+
+	#[test]
+	fn move_to_multi_monitor() {
+		let screen = RECT { left: -1280, top: -200, right: 1920, bottom: 1080 };
+		let seq = InputSequence::with_virtual_screen(screen)
+			.move_to(POINT::new(-1280, -200))
+			.move_to(POINT::new(-5000, 5000)) // clamped
+			.move_to(POINT::new(1919, 1079));
+		assert_eq!(mouse(&seq), [
+			(0, 0, 0, ABS),
+			(0, 65535, 0, ABS),
+			(65535, 65535, 0, ABS),
+		]);
+	}
+
+	#[test]
+	fn click_and_drag() {
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.click(POINT::new(0, 0), co::VK::RBUTTON)
+			.drag(&[POINT::new(0, 0), POINT::new(960, 540), POINT::new(1920, 0)], co::VK::XBUTTON2);
+		assert_eq!(mouse(&seq), [
+			(0, 0, 0, ABS | co::MOUSEEVENTF::RIGHTDOWN),
+			(0, 0, 0, ABS | co::MOUSEEVENTF::RIGHTUP),
+			(0, 0, 2, ABS | co::MOUSEEVENTF::XDOWN),
+			(32768, 32768, 0, ABS),
+			(65535, 0, 2, ABS | co::MOUSEEVENTF::XUP),
+		]);
+
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.drag(&[], co::VK::LBUTTON);
+		assert!(seq.inputs().is_empty());
+	}
+
+	#[test]
+	fn wheel() {
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.wheel(-240)
+			.hwheel(120);
+		assert_eq!(mouse(&seq), [
+			(0, 0, -240i32 as u32, co::MOUSEEVENTF::WHEEL), // relative to the cursor
+			(0, 0, 120, co::MOUSEEVENTF::HWHEEL),
+		]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn not_a_button() {
+		let _ = InputSequence::with_virtual_screen(SCREEN)
+			.click(POINT::new(0, 0), co::VK::CHAR_A);
+	}
+
+	#[test]
+	fn input_structs() {
+		let seq = InputSequence::with_virtual_screen(SCREEN)
+			.key(co::VK::CHAR_A)
+			.move_to(POINT::new(960, 540));
+		let inputs = seq.inputs().iter()
+			.map(|event| INPUT::new(*event))
+			.collect::<Vec<_>>();
+		match inputs[1].event() {
+			HwKbMouse::Kb(ki) => assert_eq!(ki.dwFlags, UP),
+			_ => panic!("Expected a keyboard event."),
+		}
+		match inputs[2].event() {
+			HwKbMouse::Mouse(mi) => assert_eq!((mi.dx, mi.dy), (32768, 32768)),
+			_ => panic!("Expected a mouse event."),
+		}
+	}
+}
@@ -0,0 +1,230 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::thread::LocalKey;
+
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+type HookFunc<T> = Rc<RefCell<dyn FnMut(co::WM, &T) -> bool>>;
+
+/// Closures of a low-level hook type, all served by a single `HHOOK` installed
+/// in the current thread.
+struct HookChain<T> {
+	hhook: HHOOK,
+	next_id: u64,
+	funcs: Vec<(u64, HookFunc<T>)>,
+}
+
+impl<T> HookChain<T> {
+	const fn new() -> Self {
+		Self { hhook: HHOOK::NULL, next_id: 0, funcs: Vec::new() }
+	}
+}
+
+thread_local! {
+	static KEYBOARD_CHAIN: RefCell<HookChain<KBDLLHOOKSTRUCT>> = const { RefCell::new(HookChain::new()) };
+	static MOUSE_CHAIN: RefCell<HookChain<MSLLHOOKSTRUCT>> = const { RefCell::new(HookChain::new()) };
+}
+
+//------------------------------------------------------------------------------
+
+/// A low-level keyboard hook, which calls a closure for each keyboard input
+/// event in the system, before it reaches any window.
+///
+/// Internally, a [`WH::KEYBOARD_LL`](crate::co::WH::KEYBOARD_LL) hook is
+/// installed with
+/// [`HHOOK::SetWindowsHookEx`](crate::prelude::user_Hhook::SetWindowsHookEx),
+/// and it's automatically removed when the last `KeyboardHook` of the thread
+/// is dropped.
+///
+/// The closure is called in the thread which created the hook, so this thread
+/// must run a message loop. Since the system waits for the closure to return,
+/// it must be fast.
+///
+/// # Examples
+///
+/// Swallowing the Windows keys while the hook is alive:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let _hook = w::KeyboardHook::new(|msg, kb| {
+///     let is_win_key = kb.vkCode == co::VK::LWIN || kb.vkCode == co::VK::RWIN;
+///     if is_win_key && msg == co::WM::KEYDOWN {
+///         println!("Windows key blocked");
+///     }
+///     is_win_key
+/// })?;
+///
+/// // run the message loop...
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct KeyboardHook {
+	id: u64,
+	_not_send: PhantomData<*const ()>, // the hook belongs to the creating thread
+}
+
+impl Drop for KeyboardHook {
+	fn drop(&mut self) {
+		remove_func(&KEYBOARD_CHAIN, self.id);
+	}
+}
+
+impl KeyboardHook {
+	/// Installs the hook, which will call the closure with the keyboard
+	/// message – [`WM::KEYDOWN`](crate::co::WM::KEYDOWN),
+	/// [`WM::KEYUP`](crate::co::WM::KEYUP),
+	/// [`WM::SYSKEYDOWN`](crate::co::WM::SYSKEYDOWN) or
+	/// [`WM::SYSKEYUP`](crate::co::WM::SYSKEYUP) – and the event data.
+	///
+	/// If the closure returns `true`, the event is swallowed: it won't be
+	/// passed to the remaining hooks, nor to the target window.
+	pub fn new<F>(func: F) -> SysResult<Self>
+		where F: FnMut(co::WM, &KBDLLHOOKSTRUCT) -> bool + 'static,
+	{
+		add_func(&KEYBOARD_CHAIN, co::WH::KEYBOARD_LL, keyboard_proc,
+			Rc::new(RefCell::new(func)))
+			.map(|id| Self { id, _not_send: PhantomData })
+	}
+}
+
+extern "system" fn keyboard_proc(code: i32, wparam: usize, lparam: isize) -> isize {
+	hook_proc(&KEYBOARD_CHAIN, code, wparam, lparam)
+}
+
+//------------------------------------------------------------------------------
+
+/// A low-level mouse hook, which calls a closure for each mouse input event in
+/// the system, before it reaches any window.
+///
+/// Internally, a [`WH::MOUSE_LL`](crate::co::WH::MOUSE_LL) hook is installed
+/// with
+/// [`HHOOK::SetWindowsHookEx`](crate::prelude::user_Hhook::SetWindowsHookEx),
+/// and it's automatically removed when the last `MouseHook` of the thread is
+/// dropped.
+///
+/// The closure is called in the thread which created the hook, so this thread
+/// must run a message loop. Since the system waits for the closure to return,
+/// it must be fast.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let _hook = w::MouseHook::new(|msg, ms| {
+///     if msg == co::WM::MOUSEWHEEL {
+///         println!("Wheel {} at {}", ms.wheel_delta(), ms.pt);
+///     }
+///     false // don't swallow
+/// })?;
+///
+/// // run the message loop...
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub struct MouseHook {
+	id: u64,
+	_not_send: PhantomData<*const ()>, // the hook belongs to the creating thread
+}
+
+impl Drop for MouseHook {
+	fn drop(&mut self) {
+		remove_func(&MOUSE_CHAIN, self.id);
+	}
+}
+
+impl MouseHook {
+	/// Installs the hook, which will call the closure with the mouse message –
+	/// like [`WM::MOUSEMOVE`](crate::co::WM::MOUSEMOVE) or
+	/// [`WM::LBUTTONDOWN`](crate::co::WM::LBUTTONDOWN) – and the event data.
+	///
+	/// If the closure returns `true`, the event is swallowed: it won't be
+	/// passed to the remaining hooks, nor to the target window.
+	pub fn new<F>(func: F) -> SysResult<Self>
+		where F: FnMut(co::WM, &MSLLHOOKSTRUCT) -> bool + 'static,
+	{
+		add_func(&MOUSE_CHAIN, co::WH::MOUSE_LL, mouse_proc,
+			Rc::new(RefCell::new(func)))
+			.map(|id| Self { id, _not_send: PhantomData })
+	}
+}
+
+extern "system" fn mouse_proc(code: i32, wparam: usize, lparam: isize) -> isize {
+	hook_proc(&MOUSE_CHAIN, code, wparam, lparam)
+}
+
+//------------------------------------------------------------------------------
+
+/// Adds the closure to the chain, installing the hook if it's the first one.
+fn add_func<T: 'static>(
+	chain: &'static LocalKey<RefCell<HookChain<T>>>,
+	hook_id: co::WH,
+	proc: HOOKPROC,
+	func: HookFunc<T>,
+) -> SysResult<u64>
+{
+	chain.with(|chain| {
+		let mut chain = chain.borrow_mut();
+		if chain.hhook == HHOOK::NULL {
+			let hinst = HINSTANCE::GetModuleHandle(None)?;
+			chain.hhook = HHOOK::SetWindowsHookEx(hook_id, proc, Some(&hinst), None)?;
+		}
+
+		let id = chain.next_id;
+		chain.next_id += 1;
+		chain.funcs.push((id, func));
+		Ok(id)
+	})
+}
+
+/// Removes the closure from the chain, uninstalling the hook if it was the
+/// last one.
+fn remove_func<T: 'static>(
+	chain: &'static LocalKey<RefCell<HookChain<T>>>,
+	id: u64,
+)
+{
+	chain.with(|chain| {
+		let mut chain = chain.borrow_mut();
+		chain.funcs.retain(|(func_id, _)| *func_id != id);
+		if chain.funcs.is_empty() && chain.hhook != HHOOK::NULL {
+			chain.hhook.UnhookWindowsHookEx().ok(); // ignore errors; handle is invalidated
+			chain.hhook = HHOOK::NULL;
+		}
+	});
+}
+
+/// Dispatches the hook event to the closures, until one swallows it.
+fn hook_proc<T: 'static>(
+	chain: &'static LocalKey<RefCell<HookChain<T>>>,
+	code: i32,
+	wparam: usize,
+	lparam: isize,
+) -> isize
+{
+	const HC_ACTION: i32 = 0;
+
+	let (hhook, funcs) = chain.with(|chain| {
+		let chain = chain.borrow();
+		let funcs = if code == HC_ACTION {
+			chain.funcs.iter().map(|(_, func)| func.clone()).collect()
+		} else {
+			Vec::default()
+		};
+		(unsafe { chain.hhook.raw_copy() }, funcs) // chain is released before calling closures
+	});
+
+	let msg = unsafe { co::WM::from_raw(wparam as _) };
+	let data = unsafe { &*(lparam as *const T) };
+	for func in funcs.iter() {
+		if let Ok(mut func) = func.try_borrow_mut() { // skip closures being reentered
+			if func(msg, data) {
+				return 1; // swallow the event
+			}
+		}
+	}
+
+	hhook.CallNextHookEx(unsafe { co::WH::from_raw(code) }, wparam, lparam)
+}
@@ -0,0 +1,7 @@
+mod input_sequence;
+mod low_level_hook;
+mod raw_input;
+
+pub use input_sequence::InputSequence;
+pub use low_level_hook::{KeyboardHook, MouseHook};
+pub use raw_input::RawInput;
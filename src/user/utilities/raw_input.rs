@@ -0,0 +1,249 @@
+use crate::co;
+use crate::decl::*;
+
+/// A parsed `RAWINPUT` block, as returned by
+/// [`HRAWINPUT::GetRawInputData`](crate::prelude::user_Hrawinput::GetRawInputData).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hraw: w::HRAWINPUT; // initialized somewhere
+/// # let hraw = w::HRAWINPUT::NULL;
+///
+/// match w::RawInput::parse(&hraw.GetRawInputData()?)? {
+///     w::RawInput::Mouse(_, ms) => {
+///         println!("Moved {}, {}", ms.lLastX, ms.lLastY);
+///     },
+///     w::RawInput::Keyboard(_, kb) => {
+///         let up = kb.Flags.has(co::RI_KEY::BREAK);
+///         println!("Scan code {:#04x} {}", kb.MakeCode, if up { "up" } else { "down" });
+///     },
+///     w::RawInput::Hid(hdr, reports) => {
+///         println!("{} reports from {}", reports.len(), hdr.hDevice);
+///     },
+/// }
+/// # Ok::<_, co::ERROR>(())
+/// ```
+pub enum RawInput {
+	/// Mouse input.
+	Mouse(RAWINPUTHEADER, RAWMOUSE),
+	/// Keyboard input.
+	Keyboard(RAWINPUTHEADER, RAWKEYBOARD),
+	/// Input from a human interface device, with the raw reports, which all
+	/// have the same size.
+	Hid(RAWINPUTHEADER, Vec<Vec<u8>>),
+}
+
+impl RawInput {
+	/// Parses a `RAWINPUT` block, in the memory layout of the current
+	/// process.
+	///
+	/// This function doesn't call any system function, so it can also parse
+	/// previously captured blocks. Malformed or truncated blocks return
+	/// [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA).
+	pub fn parse(buf: &[u8]) -> SysResult<Self> {
+		let cb_header = std::mem::size_of::<RAWINPUTHEADER>();
+		if buf.len() < cb_header {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+
+		let header = unsafe { read_at::<RAWINPUTHEADER>(buf, 0) };
+		let total = header.dwSize as usize;
+		if total < cb_header || total > buf.len() {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let body = &buf[cb_header..total];
+
+		match header.dwType {
+			co::RIM_TYPE::MOUSE => {
+				if body.len() < std::mem::size_of::<RAWMOUSE>() {
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				let ms = unsafe { read_at::<RAWMOUSE>(body, 0) };
+				Ok(Self::Mouse(header, ms))
+			},
+			co::RIM_TYPE::KEYBOARD => {
+				if body.len() < std::mem::size_of::<RAWKEYBOARD>() {
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				let kb = unsafe { read_at::<RAWKEYBOARD>(body, 0) };
+				Ok(Self::Keyboard(header, kb))
+			},
+			co::RIM_TYPE::HID => {
+				const CB_COUNTS: usize = 2 * std::mem::size_of::<u32>(); // dwSizeHid and dwCount
+				if body.len() < CB_COUNTS {
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				let cb_report = unsafe { read_at::<u32>(body, 0) } as usize;
+				let count = unsafe { read_at::<u32>(body, 4) } as usize;
+				let data = &body[CB_COUNTS..];
+
+				match cb_report.checked_mul(count) {
+					Some(cb_data) if cb_data <= data.len() => {},
+					_ => return Err(co::ERROR::INVALID_DATA),
+				}
+				let reports = if cb_report == 0 {
+					Vec::default()
+				} else {
+					data[..cb_report * count]
+						.chunks_exact(cb_report)
+						.map(|report| report.to_vec())
+						.collect()
+				};
+				Ok(Self::Hid(header, reports))
+			},
+			_ => Err(co::ERROR::INVALID_DATA),
+		}
+	}
+
+	/// Returns the header of the block.
+	#[must_use]
+	pub const fn header(&self) -> &RAWINPUTHEADER {
+		match self {
+			Self::Mouse(header, _)
+				| Self::Keyboard(header, _)
+				| Self::Hid(header, _) => header,
+		}
+	}
+}
+
+/// Reads a struct from an unaligned position of the buffer, whose bounds must
+/// have already been checked.
+unsafe fn read_at<T>(buf: &[u8], offset: usize) -> T {
+	std::ptr::read_unaligned(buf.as_ptr().add(offset) as *const T)
+}
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod tests { // blocks in the 64-bit layout
+	use super::*;
+	use crate::prelude::*;
+
+	const MOUSE_WHEEL: [u8; 48] = [
+		0x00, 0x00, 0x00, 0x00, 0x30, 0x00, 0x00, 0x00, // dwType, dwSize
+		0x45, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // hDevice
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // wParam: RIM_INPUT
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x88, 0xff, // usFlags, usButtonFlags: WHEEL, usButtonData: -120
+		0x00, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, // ulRawButtons, lLastX
+		0xfd, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, // lLastY, ulExtraInformation
+	];
+
+	const KEYBOARD_A_UP: [u8; 40] = [
+		0x01, 0x00, 0x00, 0x00, 0x28, 0x00, 0x00, 0x00, // dwType, dwSize
+		0x3f, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // hDevice
+		0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // wParam: RIM_INPUTSINK
+		0x1e, 0x00, 0x01, 0x00, 0x00, 0x00, 0x41, 0x00, // MakeCode, Flags: BREAK, Reserved, VKey
+		0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Message: WM_KEYUP, ExtraInformation
+	];
+
+	const HID_GAMEPAD: [u8; 40] = [
+		0x02, 0x00, 0x00, 0x00, 0x28, 0x00, 0x00, 0x00, // dwType, dwSize
+		0x51, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, // hDevice
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // wParam
+		0x04, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, // dwSizeHid, dwCount
+		0x01, 0x80, 0x7f, 0x00, 0x01, 0x81, 0x7f, 0x00, // bRawData
+	];
+
+	fn with_u32(buf: &[u8], offset: usize, val: u32) -> Vec<u8> {
+		let mut buf = buf.to_vec();
+		buf[offset..offset + 4].copy_from_slice(&val.to_le_bytes());
+		buf
+	}
+
+	#[test]
+	fn mouse() {
+		match RawInput::parse(&MOUSE_WHEEL).unwrap() {
+			RawInput::Mouse(hdr, ms) => {
+				assert_eq!(hdr.dwType, co::RIM_TYPE::MOUSE);
+				assert_eq!(hdr.dwSize, 48);
+				assert_eq!(hdr.hDevice.ptr() as usize, 0x1_0045);
+				assert_eq!(hdr.wParam, 0);
+				assert_eq!(ms.usFlags, co::MOUSE_STATE::MOVE_RELATIVE);
+				assert_eq!(ms.usButtonFlags, co::RI_MOUSE::WHEEL);
+				assert_eq!(ms.wheel_delta(), -120);
+				assert_eq!((ms.lLastX, ms.lLastY), (5, -3));
+			},
+			_ => panic!("Expected mouse input."),
+		}
+	}
+
+	#[test]
+	fn keyboard() {
+		match RawInput::parse(&KEYBOARD_A_UP).unwrap() {
+			RawInput::Keyboard(hdr, kb) => {
+				assert_eq!(hdr.dwType, co::RIM_TYPE::KEYBOARD);
+				assert_eq!(hdr.wParam, 1);
+				assert_eq!(kb.MakeCode, 0x1e);
+				assert!(kb.Flags.has(co::RI_KEY::BREAK));
+				assert_eq!(kb.VKey, co::VK::CHAR_A);
+				assert_eq!(kb.Message, co::WM::KEYUP);
+			},
+			_ => panic!("Expected keyboard input."),
+		}
+	}
+
+	#[test]
+	fn hid() {
+		let input = RawInput::parse(&HID_GAMEPAD).unwrap();
+		assert_eq!(input.header().dwType, co::RIM_TYPE::HID);
+		match input {
+			RawInput::Hid(_, reports) => {
+				assert_eq!(reports, [[0x01, 0x80, 0x7f, 0x00], [0x01, 0x81, 0x7f, 0x00]]);
+			},
+			_ => panic!("Expected HID input."),
+		}
+	}
+
+	#[test]
+	fn hid_no_reports() {
+		for (size_hid, count) in [(0, 0), (0, 5), (4, 0)] {
+			let buf = with_u32(&with_u32(&HID_GAMEPAD, 24, size_hid), 28, count);
+			match RawInput::parse(&buf).unwrap() {
+				RawInput::Hid(_, reports) => assert!(reports.is_empty()),
+				_ => panic!("Expected HID input."),
+			}
+		}
+	}
+
+	#[test]
+	fn padded_buffer() {
+		let mut buf = KEYBOARD_A_UP.to_vec();
+		buf.extend([0xcc; 8]); // bytes past dwSize are ignored
+		assert!(matches!(RawInput::parse(&buf), Ok(RawInput::Keyboard(..))));
+	}
+
+	#[test]
+	fn truncated() {
+		for blob in [&MOUSE_WHEEL[..], &KEYBOARD_A_UP, &HID_GAMEPAD] {
+			for len in 0..blob.len() {
+				assert_eq!(RawInput::parse(&blob[..len]).err(), Some(co::ERROR::INVALID_DATA));
+			}
+		}
+	}
+
+	#[test]
+	fn bad_size() {
+		for size in [0, 23, 49, u32::MAX] {
+			let buf = with_u32(&MOUSE_WHEEL, 4, size);
+			assert_eq!(RawInput::parse(&buf).err(), Some(co::ERROR::INVALID_DATA));
+		}
+
+		let buf = with_u32(&MOUSE_WHEEL, 4, 40); // header fits, RAWMOUSE doesn't
+		assert_eq!(RawInput::parse(&buf).err(), Some(co::ERROR::INVALID_DATA));
+	}
+
+	#[test]
+	fn hid_oversized() {
+		for (size_hid, count) in [(4, 3), (9, 1), (0x8000_0000, 2), (u32::MAX, u32::MAX)] {
+			let buf = with_u32(&with_u32(&HID_GAMEPAD, 24, size_hid), 28, count);
+			assert_eq!(RawInput::parse(&buf).err(), Some(co::ERROR::INVALID_DATA));
+		}
+	}
+
+	#[test]
+	fn unknown_type() {
+		let buf = with_u32(&MOUSE_WHEEL, 0, 3);
+		assert_eq!(RawInput::parse(&buf).err(), Some(co::ERROR::INVALID_DATA));
+	}
+}
@@ -2,6 +2,7 @@
 
 use crate::decl::*;
 use crate::guard::*;
+use crate::ole::privs::*;
 use crate::prelude::*;
 use crate::uxtheme::ffi;
 
@@ -28,4 +29,46 @@ pub trait uxtheme_Hwnd: ole_Hwnd {
 				.map(|ptr| CloseThemeDataGuard::new(HTHEME::from_ptr(ptr)))
 		}
 	}
+
+	/// [`SetWindowTheme`](https://learn.microsoft.com/en-us/windows/win32/api/uxtheme/nf-uxtheme-setwindowtheme)
+	/// function.
+	///
+	/// Passing `None` to both arguments restores the default theme, while
+	/// passing `Some("")` to both disables visual styles for the window.
+	///
+	/// # Examples
+	///
+	/// Applying the dark Explorer theme to a list view:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hlist: w::HWND; // initialized somewhere
+	/// # let hlist = w::HWND::NULL;
+	///
+	/// hlist.SetWindowTheme(Some("DarkMode_Explorer"), None)?;
+	/// # w::HrResult::Ok(())
+	/// ```
+	fn SetWindowTheme(&self,
+		sub_app_name: Option<&str>,
+		sub_id_list: Option<&str>,
+	) -> HrResult<()>
+	{
+		let sub_app_name_w = WString::from_opt_str(sub_app_name);
+		let sub_id_list_w = WString::from_opt_str(sub_id_list);
+		let empty = [0u16]; // an empty WString is a null pointer, which has another meaning
+		let as_ptr = |s: Option<&str>, w: &WString| {
+			if s == Some("") { empty.as_ptr() } else { w.as_ptr() }
+		};
+
+		ok_to_hrresult(
+			unsafe {
+				ffi::SetWindowTheme(
+					self.ptr(),
+					as_ptr(sub_app_name, &sub_app_name_w),
+					as_ptr(sub_id_list, &sub_id_list_w),
+				)
+			},
+		)
+	}
 }